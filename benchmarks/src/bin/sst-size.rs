@@ -0,0 +1,224 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares the size of SST files written with per-column encodings and each compression codec
+//! to the size of files written with the properties SSTs used before, which compresses all
+//! columns with zstd and plain encoding. The data set mimics metrics from many hosts, so the
+//! tag columns contain lots of repeated strings.
+
+#![allow(clippy::print_stdout)]
+
+use std::sync::Arc;
+
+use clap::Parser;
+use common_recordbatch::{RecordBatch, RecordBatches};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::vectors::{
+    Float64Vector, StringVector, TimestampMillisecondVector, UInt64Vector, UInt8Vector, VectorRef,
+};
+use object_store::services::Fs;
+use object_store::ObjectStore;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, Encoding, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use storage::metadata::RegionMetadata;
+use storage::sst::WriteOptions;
+use storage::{ParquetWriter, Source};
+use store_api::storage::{
+    ColumnDescriptorBuilder, ColumnFamilyDescriptorBuilder, OpType, RegionDescriptorBuilder,
+    RowKeyDescriptorBuilder, SstCompression,
+};
+
+/// Same as the max row group size of SSTs.
+const ROW_GROUP_SIZE: usize = 4096;
+
+#[derive(Parser)]
+#[command(name = "SST size benchmark")]
+struct Args {
+    /// Number of distinct hosts.
+    #[arg(long = "hosts", default_value_t = 1000)]
+    hosts: usize,
+
+    /// Number of rows of each host.
+    #[arg(long = "rows-per-host", default_value_t = 360)]
+    rows_per_host: usize,
+
+    /// Directory to write SST files.
+    #[arg(short, long, default_value_t = String::from("/tmp/greptime-sst-size"))]
+    dir: String,
+}
+
+fn new_region_metadata() -> RegionMetadata {
+    let column = |id, name: &str, data_type| {
+        ColumnDescriptorBuilder::new(id, name, data_type)
+            .build()
+            .unwrap()
+    };
+    let ts =
+        ColumnDescriptorBuilder::new(1, "ts", ConcreteDataType::timestamp_millisecond_datatype())
+            .is_nullable(false)
+            .is_time_index(true)
+            .build()
+            .unwrap();
+    let row_key = RowKeyDescriptorBuilder::new(ts)
+        .push_column(column(2, "host", ConcreteDataType::string_datatype()))
+        .push_column(column(3, "region", ConcreteDataType::string_datatype()))
+        .build()
+        .unwrap();
+    let default_cf = ColumnFamilyDescriptorBuilder::default()
+        .push_column(column(4, "cpu", ConcreteDataType::float64_datatype()))
+        .push_column(column(5, "memory", ConcreteDataType::float64_datatype()))
+        .build()
+        .unwrap();
+    let desc = RegionDescriptorBuilder::default()
+        .id(0)
+        .name("sst_size")
+        .row_key(row_key)
+        .default_cf(default_cf)
+        .compaction_time_window(None)
+        .build()
+        .unwrap();
+
+    RegionMetadata::try_from(desc).unwrap()
+}
+
+/// Generates rows sorted by `(host, region, ts)` in the column order of the store schema
+/// `host, region, ts, cpu, memory, __sequence, __op_type`.
+fn new_record_batches(metadata: &RegionMetadata, args: &Args) -> RecordBatches {
+    let schema = metadata.schema().store_schema().schema().clone();
+    let rows = (0..args.hosts * args.rows_per_host)
+        .map(|row| (row / args.rows_per_host, row % args.rows_per_host))
+        .collect::<Vec<_>>();
+
+    let mut batches = Vec::new();
+    for (chunk_idx, chunk) in rows.chunks(ROW_GROUP_SIZE).enumerate() {
+        let hosts = chunk
+            .iter()
+            .map(|(host, _)| format!("host-{host:06}"))
+            .collect::<Vec<_>>();
+        let regions = chunk
+            .iter()
+            .map(|(host, _)| format!("region-{}", host % 16))
+            .collect::<Vec<_>>();
+        let ts = chunk
+            .iter()
+            .map(|(_, i)| 1_680_000_000_000 + *i as i64 * 10_000)
+            .collect::<Vec<_>>();
+        let cpu = chunk
+            .iter()
+            .map(|(host, i)| ((host * 31 + i * 17) % 1000) as f64 / 10.0)
+            .collect::<Vec<_>>();
+        let memory = chunk
+            .iter()
+            .map(|(host, i)| ((host * 13 + i * 7) % 4096) as f64 * 1024.0)
+            .collect::<Vec<_>>();
+        let start = (chunk_idx * ROW_GROUP_SIZE) as u64;
+        let sequence = (start..start + chunk.len() as u64).collect::<Vec<_>>();
+
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(hosts)),
+            Arc::new(StringVector::from(regions)),
+            Arc::new(TimestampMillisecondVector::from_vec(ts)),
+            Arc::new(Float64Vector::from_vec(cpu)),
+            Arc::new(Float64Vector::from_vec(memory)),
+            Arc::new(UInt64Vector::from_vec(sequence)),
+            Arc::new(UInt8Vector::from_vec(vec![
+                OpType::Put.as_u8();
+                chunk.len()
+            ])),
+        ];
+        batches.push(RecordBatch::new(schema.clone(), columns).unwrap());
+    }
+
+    RecordBatches::try_new(schema, batches).unwrap()
+}
+
+/// Returns the size of the file written with the properties SSTs used before.
+fn legacy_file_size(batches: &RecordBatches) -> usize {
+    let props = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .set_encoding(Encoding::PLAIN)
+        .set_max_row_group_size(ROW_GROUP_SIZE)
+        .build();
+    let mut buf = Vec::new();
+    let mut writer = ArrowWriter::try_new(
+        &mut buf,
+        batches.schema().arrow_schema().clone(),
+        Some(props),
+    )
+    .unwrap();
+    for batch in batches.iter() {
+        writer.write(batch.df_record_batch()).unwrap();
+    }
+    writer.close().unwrap();
+    buf.len()
+}
+
+async fn sst_file_size(
+    object_store: &ObjectStore,
+    batches: &RecordBatches,
+    compression: SstCompression,
+) -> u64 {
+    let file_name = format!("{compression:?}.parquet").to_lowercase();
+    let writer = ParquetWriter::new(
+        &file_name,
+        Source::Stream(batches.as_stream()),
+        object_store.clone(),
+    );
+    let opts = WriteOptions {
+        compression,
+        ..Default::default()
+    };
+    writer.write_sst(&opts).await.unwrap().unwrap().file_size
+}
+
+fn main() {
+    let args = Args::parse();
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut builder = Fs::default();
+            builder.root(&args.dir);
+            let object_store = ObjectStore::new(builder).unwrap().finish();
+
+            let metadata = new_region_metadata();
+            let batches = new_record_batches(&metadata, &args);
+            println!(
+                "Writing {} rows of {} hosts",
+                args.hosts * args.rows_per_host,
+                args.hosts
+            );
+
+            let legacy_size = legacy_file_size(&batches);
+            println!("{:<24}{:>12} bytes", "zstd, plain (before)", legacy_size);
+            for compression in [
+                SstCompression::Uncompressed,
+                SstCompression::Snappy,
+                SstCompression::Gzip,
+                SstCompression::Lz4,
+                SstCompression::Zstd,
+            ] {
+                let size = sst_file_size(&object_store, &batches, compression).await;
+                println!(
+                    "{:<24}{:>12} bytes ({:.1}%)",
+                    format!("{compression:?}").to_lowercase(),
+                    size,
+                    size as f64 * 100.0 / legacy_size as f64
+                );
+            }
+        })
+}
@@ -21,6 +21,7 @@ use datafusion::datasource::provider_as_source;
 use datafusion::logical_expr::TableSource;
 use session::context::QueryContext;
 use snafu::{ensure, OptionExt};
+use table::requests::AsOf;
 use table::table::adapter::DfTableProviderAdapter;
 
 use crate::error::{
@@ -34,6 +35,8 @@ pub struct DfTableSourceProvider {
     disallow_cross_schema_query: bool,
     default_catalog: String,
     default_schema: String,
+    /// Version of tables to read, `None` to read the latest version.
+    as_of: Option<AsOf>,
 }
 
 impl DfTableSourceProvider {
@@ -48,9 +51,16 @@ impl DfTableSourceProvider {
             resolved_tables: HashMap::new(),
             default_catalog: query_ctx.current_catalog(),
             default_schema: query_ctx.current_schema(),
+            as_of: None,
         }
     }
 
+    /// Resolves tables to read them at `as_of`.
+    pub fn with_as_of(mut self, as_of: Option<AsOf>) -> Self {
+        self.as_of = as_of;
+        self
+    }
+
     pub fn resolve_table_ref<'a>(
         &'a self,
         table_ref: TableReference<'a>,
@@ -115,7 +125,7 @@ impl DfTableSourceProvider {
                 table: format_full_table_name(catalog_name, schema_name, table_name),
             })?;
 
-        let table = DfTableProviderAdapter::new(table);
+        let table = DfTableProviderAdapter::new(table).with_as_of(self.as_of);
         let table = provider_as_source(Arc::new(table));
         self.resolved_tables.insert(resolved_name, table.clone());
         Ok(table)
@@ -29,7 +29,10 @@ use datatypes::vectors::StringVector;
 use serde::Serializer;
 use table::engine::{EngineContext, TableEngine, TableReference};
 use table::metadata::TableId;
-use table::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
+use table::requests::{
+    AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest, RestoreTableRequest,
+    TruncateTableRequest,
+};
 use table::test_util::MemTable;
 use table::TableRef;
 use tokio::sync::RwLock;
@@ -222,6 +225,22 @@ impl TableEngine for MockTableEngine {
         unimplemented!()
     }
 
+    async fn truncate_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: TruncateTableRequest,
+    ) -> table::Result<bool> {
+        unimplemented!()
+    }
+
+    async fn restore_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: RestoreTableRequest,
+    ) -> table::Result<TableRef> {
+        unimplemented!()
+    }
+
     async fn close(&self) -> table::Result<()> {
         Ok(())
     }
@@ -180,7 +180,10 @@ mod tests {
 
     use common_base::readable_size::ReadableSize;
     use common_test_util::temp_dir::create_named_temp_file;
-    use datanode::datanode::{CompactionConfig, ObjectStoreConfig, RegionManifestConfig};
+    use datanode::datanode::{
+        CompactionConfig, MemtableConfig, MemtableType, ObjectStoreConfig, RegionManifestConfig,
+        WriteBufferConfig,
+    };
     use servers::Mode;
 
     use super::*;
@@ -225,6 +228,13 @@ mod tests {
             checkpoint_margin = 9
             gc_duration = '7s'
             checkpoint_on_startup = true
+
+            [storage.write_buffer]
+            global_write_buffer_size = "512MB"
+            write_stall_timeout = "5s"
+
+            [storage.memtable]
+            type = "time_series"
         "#;
         write!(file, "{}", toml_str).unwrap();
 
@@ -280,6 +290,19 @@ mod tests {
             },
             options.storage.manifest,
         );
+        assert_eq!(
+            WriteBufferConfig {
+                global_write_buffer_size: Some(ReadableSize::mb(512)),
+                write_stall_timeout: Duration::from_secs(5),
+            },
+            options.storage.write_buffer,
+        );
+        assert_eq!(
+            MemtableConfig {
+                memtable_type: MemtableType::TimeSeries,
+            },
+            options.storage.memtable,
+        );
     }
 
     #[test]
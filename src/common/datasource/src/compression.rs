@@ -0,0 +1,234 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use async_compression::tokio::bufread::{
+    BzDecoder, BzEncoder, GzipDecoder, GzipEncoder, XzDecoder, XzEncoder, ZstdDecoder, ZstdEncoder,
+};
+use async_compression::tokio::write;
+use snafu::ResultExt;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use crate::error::{self, Error, Result};
+use crate::file_format::BoxedReader;
+
+/// Compression type of text files (csv, json), parquet files have their own compression.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionType {
+    #[default]
+    Uncompressed,
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+}
+
+impl FromStr for CompressionType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "" | "UNCOMPRESSED" => Ok(Self::Uncompressed),
+            "GZIP" | "GZ" => Ok(Self::Gzip),
+            "BZIP2" | "BZ2" => Ok(Self::Bzip2),
+            "XZ" => Ok(Self::Xz),
+            "ZSTD" | "ZST" => Ok(Self::Zstd),
+            _ => error::UnsupportedCompressionTypeSnafu {
+                compression_type: s,
+            }
+            .fail(),
+        }
+    }
+}
+
+impl Display for CompressionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Uncompressed => "UNCOMPRESSED",
+            Self::Gzip => "GZIP",
+            Self::Bzip2 => "BZIP2",
+            Self::Xz => "XZ",
+            Self::Zstd => "ZSTD",
+        })
+    }
+}
+
+impl CompressionType {
+    pub const fn is_compressed(&self) -> bool {
+        !matches!(self, Self::Uncompressed)
+    }
+
+    /// Decompresses `data` read from the file at `path`.
+    pub async fn decode(&self, data: Vec<u8>, path: &str) -> Result<Vec<u8>> {
+        let input = data.as_slice();
+        match self {
+            Self::Uncompressed => return Ok(data),
+            Self::Gzip => read_all(GzipDecoder::new(input)).await,
+            Self::Bzip2 => read_all(BzDecoder::new(input)).await,
+            Self::Xz => read_all(XzDecoder::new(input)).await,
+            Self::Zstd => read_all(ZstdDecoder::new(input)).await,
+        }
+        .context(error::DecompressSnafu {
+            compression_type: *self,
+            path,
+        })
+    }
+
+    /// Wraps `reader` to decompress the data read from it.
+    pub(crate) fn convert_reader<R>(&self, reader: R) -> BoxedReader
+    where
+        R: AsyncBufRead + Send + Unpin + 'static,
+    {
+        match self {
+            Self::Uncompressed => Box::new(reader),
+            Self::Gzip => Box::new(BufReader::new(GzipDecoder::new(reader))),
+            Self::Bzip2 => Box::new(BufReader::new(BzDecoder::new(reader))),
+            Self::Xz => Box::new(BufReader::new(XzDecoder::new(reader))),
+            Self::Zstd => Box::new(BufReader::new(ZstdDecoder::new(reader))),
+        }
+    }
+
+    /// Compresses `data` to be written to the file at `path`.
+    pub async fn encode(&self, data: Vec<u8>, path: &str) -> Result<Vec<u8>> {
+        let input = data.as_slice();
+        match self {
+            Self::Uncompressed => return Ok(data),
+            Self::Gzip => read_all(GzipEncoder::new(input)).await,
+            Self::Bzip2 => read_all(BzEncoder::new(input)).await,
+            Self::Xz => read_all(XzEncoder::new(input)).await,
+            Self::Zstd => read_all(ZstdEncoder::new(input)).await,
+        }
+        .context(error::CompressSnafu {
+            compression_type: *self,
+            path,
+        })
+    }
+
+    /// Creates an [Encoder] to compress data incrementally.
+    pub fn encoder(&self) -> Encoder {
+        let inner = match self {
+            Self::Uncompressed => EncoderInner::Uncompressed(Vec::new()),
+            Self::Gzip => EncoderInner::Gzip(write::GzipEncoder::new(Vec::new())),
+            Self::Bzip2 => EncoderInner::Bzip2(write::BzEncoder::new(Vec::new())),
+            Self::Xz => EncoderInner::Xz(write::XzEncoder::new(Vec::new())),
+            Self::Zstd => EncoderInner::Zstd(write::ZstdEncoder::new(Vec::new())),
+        };
+        Encoder {
+            compression_type: *self,
+            inner,
+        }
+    }
+}
+
+enum EncoderInner {
+    Uncompressed(Vec<u8>),
+    Gzip(write::GzipEncoder<Vec<u8>>),
+    Bzip2(write::BzEncoder<Vec<u8>>),
+    Xz(write::XzEncoder<Vec<u8>>),
+    Zstd(write::ZstdEncoder<Vec<u8>>),
+}
+
+/// Compresses data incrementally. Compressed bytes are buffered in memory until they are
+/// taken by [Encoder::take].
+pub struct Encoder {
+    compression_type: CompressionType,
+    inner: EncoderInner,
+}
+
+impl Encoder {
+    /// Compresses `data` to be written to the file at `path`.
+    pub async fn write(&mut self, data: &[u8], path: &str) -> Result<()> {
+        match &mut self.inner {
+            EncoderInner::Uncompressed(buf) => {
+                buf.extend_from_slice(data);
+                Ok(())
+            }
+            EncoderInner::Gzip(encoder) => encoder.write_all(data).await,
+            EncoderInner::Bzip2(encoder) => encoder.write_all(data).await,
+            EncoderInner::Xz(encoder) => encoder.write_all(data).await,
+            EncoderInner::Zstd(encoder) => encoder.write_all(data).await,
+        }
+        .context(error::CompressSnafu {
+            compression_type: self.compression_type,
+            path,
+        })
+    }
+
+    /// Flushes all pending data and writes the trailer of the compressed stream. No data
+    /// can be written after finishing.
+    pub async fn finish(&mut self, path: &str) -> Result<()> {
+        match &mut self.inner {
+            EncoderInner::Uncompressed(_) => Ok(()),
+            EncoderInner::Gzip(encoder) => encoder.shutdown().await,
+            EncoderInner::Bzip2(encoder) => encoder.shutdown().await,
+            EncoderInner::Xz(encoder) => encoder.shutdown().await,
+            EncoderInner::Zstd(encoder) => encoder.shutdown().await,
+        }
+        .context(error::CompressSnafu {
+            compression_type: self.compression_type,
+            path,
+        })
+    }
+
+    /// Takes the compressed bytes produced so far.
+    pub fn take(&mut self) -> Vec<u8> {
+        let buf = match &mut self.inner {
+            EncoderInner::Uncompressed(buf) => buf,
+            EncoderInner::Gzip(encoder) => encoder.get_mut(),
+            EncoderInner::Bzip2(encoder) => encoder.get_mut(),
+            EncoderInner::Xz(encoder) => encoder.get_mut(),
+            EncoderInner::Zstd(encoder) => encoder.get_mut(),
+        };
+        std::mem::take(buf)
+    }
+}
+
+async fn read_all<R: AsyncRead + Unpin>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_compression_round_trip() {
+        let data = b"host,ts,cpu\na,1,0.5\nb,2,1.5\n".to_vec();
+        for compression_type in [
+            CompressionType::Uncompressed,
+            CompressionType::Gzip,
+            CompressionType::Bzip2,
+            CompressionType::Xz,
+            CompressionType::Zstd,
+        ] {
+            let encoded = compression_type.encode(data.clone(), "test").await.unwrap();
+            assert_eq!(compression_type.is_compressed(), encoded != data);
+            let decoded = compression_type.decode(encoded, "test").await.unwrap();
+            assert_eq!(data, decoded);
+
+            let parsed = CompressionType::from_str(&compression_type.to_string()).unwrap();
+            assert_eq!(compression_type, parsed);
+        }
+
+        assert_eq!(
+            CompressionType::Gzip,
+            CompressionType::from_str("gz").unwrap()
+        );
+        assert!(CompressionType::from_str("lz4").is_err());
+    }
+}
@@ -14,10 +14,14 @@
 
 use std::any::Any;
 
+use arrow_schema::ArrowError;
 use common_error::prelude::*;
+use parquet::errors::ParquetError;
 use snafu::Location;
 use url::ParseError;
 
+use crate::compression::CompressionType;
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 pub enum Error {
@@ -48,6 +52,104 @@ pub enum Error {
 
     #[snafu(display("Invalid connection: {}", msg))]
     InvalidConnection { msg: String },
+
+    #[snafu(display("Unsupported file format: {}", format))]
+    UnsupportedFormat { format: String, location: Location },
+
+    #[snafu(display("Invalid format option, key: {}, value: {}", key, value))]
+    InvalidFormatOption {
+        key: String,
+        value: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to read object from path: {}, source: {}", path, source))]
+    ReadObject {
+        path: String,
+        location: Location,
+        source: object_store::Error,
+    },
+
+    #[snafu(display("Failed to read file: {}, source: {}", path, source))]
+    ReadFile {
+        path: String,
+        location: Location,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to infer schema from file: {}, source: {}", path, source))]
+    InferSchema {
+        path: String,
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display("Failed to read record batch from file: {}, source: {}", path, source))]
+    ReadRecordBatch {
+        path: String,
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display("Failed to read parquet file: {}, source: {}", path, source))]
+    ReadParquet {
+        path: String,
+        location: Location,
+        source: ParquetError,
+    },
+
+    #[snafu(display("Failed to write parquet file: {}, source: {}", path, source))]
+    WriteParquet {
+        path: String,
+        location: Location,
+        source: ParquetError,
+    },
+
+    #[snafu(display("Unsupported compression type: {}", compression_type))]
+    UnsupportedCompressionType {
+        compression_type: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to decompress file: {} with {}, source: {}",
+        path,
+        compression_type,
+        source
+    ))]
+    Decompress {
+        compression_type: CompressionType,
+        path: String,
+        location: Location,
+        source: std::io::Error,
+    },
+
+    #[snafu(display(
+        "Failed to compress file: {} with {}, source: {}",
+        path,
+        compression_type,
+        source
+    ))]
+    Compress {
+        compression_type: CompressionType,
+        path: String,
+        location: Location,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Failed to encode record batch to file: {}, source: {}", path, source))]
+    WriteRecordBatch {
+        path: String,
+        location: Location,
+        source: ArrowError,
+    },
+
+    #[snafu(display("Failed to write object to path: {}, source: {}", path, source))]
+    WriteObject {
+        path: String,
+        location: Location,
+        source: object_store::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -56,13 +158,28 @@ impl ErrorExt for Error {
     fn status_code(&self) -> StatusCode {
         use Error::*;
         match self {
-            BuildBackend { .. } | ListObjects { .. } => StatusCode::StorageUnavailable,
+            BuildBackend { .. }
+            | ListObjects { .. }
+            | ReadObject { .. }
+            | ReadFile { .. }
+            | WriteObject { .. } => StatusCode::StorageUnavailable,
 
             UnsupportedBackendProtocol { .. }
             | InvalidConnection { .. }
             | InvalidUrl { .. }
             | EmptyHostPath { .. }
-            | InvalidPath { .. } => StatusCode::InvalidArguments,
+            | InvalidPath { .. }
+            | InvalidFormatOption { .. }
+            | InferSchema { .. }
+            | ReadRecordBatch { .. }
+            | ReadParquet { .. }
+            | Decompress { .. } => StatusCode::InvalidArguments,
+
+            UnsupportedFormat { .. } | UnsupportedCompressionType { .. } => StatusCode::Unsupported,
+
+            Compress { .. } | WriteRecordBatch { .. } | WriteParquet { .. } => {
+                StatusCode::Unexpected
+            }
         }
     }
 
@@ -74,6 +191,19 @@ impl ErrorExt for Error {
         match self {
             Error::BuildBackend { location, .. } => Some(*location),
             Error::ListObjects { location, .. } => Some(*location),
+            Error::UnsupportedFormat { location, .. } => Some(*location),
+            Error::InvalidFormatOption { location, .. } => Some(*location),
+            Error::ReadObject { location, .. } => Some(*location),
+            Error::ReadFile { location, .. } => Some(*location),
+            Error::InferSchema { location, .. } => Some(*location),
+            Error::ReadRecordBatch { location, .. } => Some(*location),
+            Error::ReadParquet { location, .. } => Some(*location),
+            Error::UnsupportedCompressionType { location, .. } => Some(*location),
+            Error::Decompress { location, .. } => Some(*location),
+            Error::Compress { location, .. } => Some(*location),
+            Error::WriteRecordBatch { location, .. } => Some(*location),
+            Error::WriteObject { location, .. } => Some(*location),
+            Error::WriteParquet { location, .. } => Some(*location),
             Error::UnsupportedBackendProtocol { .. }
             | Error::EmptyHostPath { .. }
             | Error::InvalidPath { .. }
@@ -0,0 +1,557 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File formats that can be read from an object store.
+
+pub mod csv;
+pub mod json;
+pub mod parquet;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use async_compat::CompatExt;
+use datafusion::error::DataFusionError;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::TryStreamExt;
+use object_store::{ObjectStore, Writer};
+use snafu::ResultExt;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+
+use self::csv::CsvFormat;
+use self::json::JsonFormat;
+use self::parquet::ParquetFormat;
+use crate::compression::{CompressionType, Encoder};
+use crate::error::{self, Result};
+
+pub const FORMAT_TYPE: &str = "FORMAT";
+pub const FORMAT_HAS_HEADER: &str = "HAS_HEADER";
+pub const FORMAT_DELIMITER: &str = "DELIMITER";
+pub const FORMAT_QUOTE: &str = "QUOTE";
+pub const FORMAT_COMPRESSION_TYPE: &str = "COMPRESSION_TYPE";
+pub const FORMAT_SCHEMA_INFER_MAX_RECORD: &str = "SCHEMA_INFER_MAX_RECORD";
+
+/// Default max records to read when inferring the schema of text formats.
+pub const DEFAULT_SCHEMA_INFER_MAX_RECORD: usize = 1000;
+
+/// Size of chunks uploaded by [TextWriter], which is larger than the minimal part size of
+/// multipart uploads of object stores such as S3.
+const TEXT_WRITER_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    Csv(CsvFormat),
+    Json(JsonFormat),
+    Parquet(ParquetFormat),
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::Parquet(ParquetFormat::default())
+    }
+}
+
+impl TryFrom<&HashMap<String, String>> for Format {
+    type Error = error::Error;
+
+    /// Builds the format from options whose keys are in upper case, the format
+    /// defaults to parquet if `FORMAT` is absent.
+    fn try_from(options: &HashMap<String, String>) -> Result<Self> {
+        let Some(format) = options.get(FORMAT_TYPE) else { return Ok(Format::default()) };
+
+        match format.to_uppercase().as_str() {
+            "CSV" => Ok(Format::Csv(CsvFormat::try_from(options)?)),
+            "JSON" | "NDJSON" => Ok(Format::Json(JsonFormat::try_from(options)?)),
+            "PARQUET" => Ok(Format::Parquet(ParquetFormat::default())),
+            _ => error::UnsupportedFormatSnafu { format }.fail(),
+        }
+    }
+}
+
+impl Format {
+    /// Infers the arrow schema of the file at `path`.
+    pub async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        match self {
+            Format::Csv(format) => format.infer_schema(store, path).await,
+            Format::Json(format) => format.infer_schema(store, path).await,
+            Format::Parquet(format) => format.infer_schema(store, path).await,
+        }
+    }
+
+    /// Writes `batches` to the file at `path`, returns the number of rows written.
+    pub async fn write(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        batches: &[RecordBatch],
+    ) -> Result<usize> {
+        match self {
+            Format::Csv(format) => format.write(store, path, batches).await,
+            Format::Json(format) => format.write(store, path, batches).await,
+            Format::Parquet(format) => format.write(store, path, batches).await,
+        }
+    }
+
+    /// Returns a stream of record batches read from the file at `path`, the file is read
+    /// incrementally as the stream is polled.
+    ///
+    /// `schema` is the expected schema of the file, `projection` are indices of the fields
+    /// in `schema` to read. Columns are located by name and fields missing in the file are
+    /// skipped. The data types in the file (or inferred from it) are kept, so the caller
+    /// should cast the columns if necessary.
+    pub async fn read(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        schema: SchemaRef,
+        projection: Option<&[usize]>,
+    ) -> Result<SendableRecordBatchStream> {
+        match self {
+            Format::Csv(format) => format.read(store, path, schema, projection).await,
+            Format::Json(format) => format.read(store, path, schema, projection).await,
+            Format::Parquet(format) => format.read(store, path, schema, projection).await,
+        }
+    }
+}
+
+/// Writes record batches to a csv or json file one by one. Encoded bytes are uploaded to
+/// the object store in chunks, so the whole file is never held in memory.
+pub struct TextWriter {
+    path: String,
+    format: Format,
+    writer: Writer,
+    encoder: Encoder,
+    buf: Vec<u8>,
+    num_rows: usize,
+    is_first: bool,
+}
+
+impl TextWriter {
+    /// Creates a writer of the file at `path`, the file is overwritten if it exists.
+    pub async fn try_new(store: &ObjectStore, path: &str, format: Format) -> Result<Self> {
+        let compression_type = match &format {
+            Format::Csv(format) => format.compression_type,
+            Format::Json(format) => format.compression_type,
+            Format::Parquet(_) => {
+                return error::UnsupportedFormatSnafu { format: "PARQUET" }.fail();
+            }
+        };
+        let writer = store
+            .writer(path)
+            .await
+            .context(error::WriteObjectSnafu { path })?;
+
+        Ok(Self {
+            path: path.to_string(),
+            format,
+            writer,
+            encoder: compression_type.encoder(),
+            buf: Vec::new(),
+            num_rows: 0,
+            is_first: true,
+        })
+    }
+
+    /// Encodes `batch` and uploads the buffered data once it exceeds the chunk size.
+    pub async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let data = match &self.format {
+            Format::Csv(format) => format.encode(batch, self.is_first, &self.path)?,
+            Format::Json(format) => format.encode(batch, &self.path)?,
+            Format::Parquet(_) => unreachable!("checked in `TextWriter::try_new`"),
+        };
+        self.is_first = false;
+        self.num_rows += batch.num_rows();
+
+        self.encoder.write(&data, &self.path).await?;
+        self.buf.extend(self.encoder.take());
+        if self.buf.len() >= TEXT_WRITER_CHUNK_SIZE {
+            let chunk = std::mem::take(&mut self.buf);
+            self.append(chunk).await?;
+        }
+        Ok(())
+    }
+
+    /// Uploads all remaining data and closes the file, returns the number of rows written.
+    pub async fn close(mut self) -> Result<usize> {
+        self.encoder.finish(&self.path).await?;
+        self.buf.extend(self.encoder.take());
+        let chunk = std::mem::take(&mut self.buf);
+        self.append(chunk).await?;
+        self.writer
+            .close()
+            .await
+            .context(error::WriteObjectSnafu { path: &self.path })?;
+        Ok(self.num_rows)
+    }
+
+    async fn append(&mut self, chunk: Vec<u8>) -> Result<()> {
+        self.writer
+            .append(chunk)
+            .await
+            .context(error::WriteObjectSnafu { path: &self.path })
+    }
+}
+
+/// Infers and merges the schemas of all `paths`.
+pub async fn infer_schemas(
+    store: &ObjectStore,
+    paths: &[String],
+    format: &Format,
+) -> Result<Schema> {
+    let mut schemas = Vec::with_capacity(paths.len());
+    for path in paths {
+        schemas.push(format.infer_schema(store, path).await?);
+    }
+    Schema::try_merge(schemas).context(error::InferSchemaSnafu {
+        path: paths.join(","),
+    })
+}
+
+/// Buffered reader of the decompressed content of a file.
+pub(crate) type BoxedReader = Box<dyn AsyncBufRead + Send + Unpin>;
+
+/// Decodes record batches from the content of a text file incrementally.
+pub(crate) trait ArrowDecoder: Send + 'static {
+    /// Decodes records from `buf`, returns the number of bytes consumed. Returns 0 if
+    /// `buf` is empty or the batch being decoded is full.
+    fn decode(&mut self, buf: &[u8]) -> std::result::Result<usize, ArrowError>;
+
+    /// Flushes decoded records to a batch, returns `None` if there is no record.
+    fn flush(&mut self) -> std::result::Result<Option<RecordBatch>, ArrowError>;
+}
+
+impl ArrowDecoder for arrow::csv::reader::Decoder {
+    fn decode(&mut self, buf: &[u8]) -> std::result::Result<usize, ArrowError> {
+        self.decode(buf)
+    }
+
+    fn flush(&mut self) -> std::result::Result<Option<RecordBatch>, ArrowError> {
+        self.flush()
+    }
+}
+
+impl ArrowDecoder for arrow::json::RawDecoder {
+    fn decode(&mut self, buf: &[u8]) -> std::result::Result<usize, ArrowError> {
+        self.decode(buf)
+    }
+
+    fn flush(&mut self) -> std::result::Result<Option<RecordBatch>, ArrowError> {
+        self.flush()
+    }
+}
+
+/// Opens a reader of the file at `path` that decompresses the content by `compression_type`.
+pub(crate) async fn open_reader(
+    store: &ObjectStore,
+    path: &str,
+    compression_type: CompressionType,
+) -> Result<BoxedReader> {
+    let reader = store
+        .reader(path)
+        .await
+        .context(error::ReadObjectSnafu { path })?;
+    Ok(compression_type.convert_reader(BufReader::new(reader.compat())))
+}
+
+/// Reads at most `max_lines` leading lines of the decompressed file at `path`, the whole
+/// file is read if `max_lines` is `None`.
+pub(crate) async fn read_lines(
+    store: &ObjectStore,
+    path: &str,
+    compression_type: CompressionType,
+    max_lines: Option<usize>,
+) -> Result<Vec<u8>> {
+    let mut reader = open_reader(store, path, compression_type).await?;
+    let mut buf = Vec::new();
+    let mut num_lines = 0;
+    while max_lines.map_or(true, |max_lines| num_lines < max_lines) {
+        let read = reader
+            .read_until(b'\n', &mut buf)
+            .await
+            .context(error::ReadFileSnafu { path })?;
+        if read == 0 {
+            break;
+        }
+        num_lines += 1;
+    }
+    Ok(buf)
+}
+
+/// Returns a stream of batches of `schema` decoded by `decoder` from the content read
+/// by `reader`.
+pub(crate) fn decode_stream<D: ArrowDecoder>(
+    path: &str,
+    schema: SchemaRef,
+    reader: BoxedReader,
+    decoder: D,
+) -> SendableRecordBatchStream {
+    let stream = futures::stream::try_unfold(
+        (reader, decoder, path.to_string()),
+        |(mut reader, mut decoder, path)| async move {
+            // Feeds the decoder until the batch is full or the file ends.
+            loop {
+                let buf = reader
+                    .fill_buf()
+                    .await
+                    .context(error::ReadFileSnafu { path: &path })?;
+                let decoded = decoder
+                    .decode(buf)
+                    .context(error::ReadRecordBatchSnafu { path: &path })?;
+                if decoded == 0 {
+                    break;
+                }
+                reader.consume(decoded);
+            }
+            let batch = decoder
+                .flush()
+                .context(error::ReadRecordBatchSnafu { path: &path })?;
+            Ok::<_, error::Error>(batch.map(|batch| (batch, (reader, decoder, path))))
+        },
+    )
+    .map_err(|e| DataFusionError::External(Box::new(e)));
+
+    Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+}
+
+/// Writes `data` to the object at `path`, the object is overwritten if it exists.
+pub(crate) async fn write_object(store: &ObjectStore, path: &str, data: Vec<u8>) -> Result<()> {
+    store
+        .write(path, data)
+        .await
+        .context(error::WriteObjectSnafu { path })
+}
+
+/// Returns the number of rows in `batches`.
+pub(crate) fn num_rows(batches: &[RecordBatch]) -> usize {
+    batches.iter().map(|batch| batch.num_rows()).sum()
+}
+
+/// Returns indices of fields in `file_schema` that are requested by `schema` and optional
+/// `projection`, in the requested order. Fields are located by name, and fields missing in
+/// the file are skipped.
+pub(crate) fn file_projection(
+    file_schema: &Schema,
+    schema: &Schema,
+    projection: Option<&[usize]>,
+) -> Vec<usize> {
+    let find = |idx: usize| file_schema.index_of(schema.field(idx).name()).ok();
+    match projection {
+        Some(projection) => projection.iter().filter_map(|idx| find(*idx)).collect(),
+        None => (0..schema.fields().len()).filter_map(find).collect(),
+    }
+}
+
+fn parse_bool_option(options: &HashMap<String, String>, key: &str) -> Result<Option<bool>> {
+    options
+        .get(key)
+        .map(|value| {
+            value
+                .to_lowercase()
+                .parse::<bool>()
+                .map_err(|_| error::InvalidFormatOptionSnafu { key, value }.build())
+        })
+        .transpose()
+}
+
+fn parse_usize_option(options: &HashMap<String, String>, key: &str) -> Result<Option<usize>> {
+    options
+        .get(key)
+        .map(|value| {
+            value
+                .parse::<usize>()
+                .map_err(|_| error::InvalidFormatOptionSnafu { key, value }.build())
+        })
+        .transpose()
+}
+
+/// Parses an option that must be a single ascii character, such as a delimiter.
+fn parse_char_option(options: &HashMap<String, String>, key: &str) -> Result<Option<u8>> {
+    options
+        .get(key)
+        .map(|value| match value.as_bytes() {
+            [c] => Ok(*c),
+            _ => error::InvalidFormatOptionSnafu { key, value }.fail(),
+        })
+        .transpose()
+}
+
+fn parse_compression_option(
+    options: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<CompressionType>> {
+    options
+        .get(key)
+        .map(|value| CompressionType::from_str(value))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use common_test_util::temp_dir::create_temp_dir;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_text_writer() {
+        let dir = create_temp_dir("test_text_writer");
+        let mut builder = Fs::default();
+        builder.root(dir.path().to_str().unwrap());
+        let store = ObjectStore::new(builder).unwrap().finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("cpu", DataType::Float64, true),
+        ]));
+        let batches = [
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(vec!["a", "b"])),
+                    Arc::new(Float64Array::from(vec![0.5, 1.5])),
+                ],
+            )
+            .unwrap(),
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(vec!["c"])),
+                    Arc::new(Float64Array::from(vec![2.5])),
+                ],
+            )
+            .unwrap(),
+        ];
+
+        let formats = [
+            Format::Csv(CsvFormat {
+                compression_type: CompressionType::Gzip,
+                ..Default::default()
+            }),
+            Format::Json(JsonFormat {
+                compression_type: CompressionType::Zstd,
+                ..Default::default()
+            }),
+            Format::Csv(CsvFormat::default()),
+        ];
+        for format in formats {
+            let mut writer = TextWriter::try_new(&store, "cpu", format.clone())
+                .await
+                .unwrap();
+            for batch in &batches {
+                writer.write(batch).await.unwrap();
+            }
+            assert_eq!(3, writer.close().await.unwrap());
+
+            let read: Vec<_> = format
+                .read(&store, "cpu", schema.clone(), None)
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(3, num_rows(&read), "{format:?}");
+        }
+
+        assert!(
+            TextWriter::try_new(&store, "cpu", Format::Parquet(ParquetFormat::default()))
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_file_projection() {
+        let file_schema = Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("ts", DataType::Int64, true),
+            Field::new("cpu", DataType::Float64, true),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Int64, true),
+            Field::new("memory", DataType::Float64, true),
+            Field::new("host", DataType::Utf8, true),
+        ]);
+
+        assert_eq!(vec![1, 0], file_projection(&file_schema, &schema, None));
+        assert_eq!(
+            vec![0, 1],
+            file_projection(&file_schema, &schema, Some(&[2, 1, 0]))
+        );
+        assert!(file_projection(&file_schema, &schema, Some(&[1])).is_empty());
+    }
+
+    #[test]
+    fn test_format_from_options() {
+        let format = Format::try_from(&HashMap::new()).unwrap();
+        assert_eq!(Format::Parquet(ParquetFormat::default()), format);
+
+        let options = HashMap::from([(FORMAT_TYPE.to_string(), "json".to_string())]);
+        let format = Format::try_from(&options).unwrap();
+        assert_eq!(Format::Json(JsonFormat::default()), format);
+
+        let options = HashMap::from([
+            (FORMAT_TYPE.to_string(), "CSV".to_string()),
+            (FORMAT_HAS_HEADER.to_string(), "false".to_string()),
+            (FORMAT_DELIMITER.to_string(), "|".to_string()),
+            (FORMAT_QUOTE.to_string(), "'".to_string()),
+            (FORMAT_COMPRESSION_TYPE.to_string(), "gzip".to_string()),
+        ]);
+        let format = Format::try_from(&options).unwrap();
+        assert_eq!(
+            Format::Csv(CsvFormat {
+                has_header: false,
+                delimiter: b'|',
+                quote: b'\'',
+                compression_type: CompressionType::Gzip,
+                ..Default::default()
+            }),
+            format
+        );
+
+        let options = HashMap::from([
+            (FORMAT_TYPE.to_string(), "json".to_string()),
+            (FORMAT_COMPRESSION_TYPE.to_string(), "zstd".to_string()),
+        ]);
+        let format = Format::try_from(&options).unwrap();
+        assert_eq!(
+            Format::Json(JsonFormat {
+                compression_type: CompressionType::Zstd,
+                ..Default::default()
+            }),
+            format
+        );
+
+        let options = HashMap::from([
+            (FORMAT_TYPE.to_string(), "json".to_string()),
+            (FORMAT_COMPRESSION_TYPE.to_string(), "lz4".to_string()),
+        ]);
+        assert!(Format::try_from(&options).is_err());
+
+        let options = HashMap::from([
+            (FORMAT_TYPE.to_string(), "csv".to_string()),
+            (FORMAT_DELIMITER.to_string(), "||".to_string()),
+        ]);
+        assert!(Format::try_from(&options).is_err());
+
+        let options = HashMap::from([(FORMAT_TYPE.to_string(), "orc".to_string())]);
+        assert!(Format::try_from(&options).is_err());
+    }
+}
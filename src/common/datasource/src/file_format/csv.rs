@@ -0,0 +1,358 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow::csv::{ReaderBuilder, WriterBuilder};
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{ArrowError, Schema, SchemaRef};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use object_store::ObjectStore;
+use snafu::ResultExt;
+
+use crate::compression::CompressionType;
+use crate::error::{self, Result};
+use crate::file_format::{self, DEFAULT_SCHEMA_INFER_MAX_RECORD};
+
+/// Quote character used by the csv writer of arrow.
+const DEFAULT_QUOTE: u8 = b'"';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvFormat {
+    pub has_header: bool,
+    pub delimiter: u8,
+    /// Quote character of fields.
+    pub quote: u8,
+    pub schema_infer_max_record: Option<usize>,
+    pub compression_type: CompressionType,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        CsvFormat {
+            has_header: true,
+            delimiter: b',',
+            quote: DEFAULT_QUOTE,
+            schema_infer_max_record: Some(DEFAULT_SCHEMA_INFER_MAX_RECORD),
+            compression_type: CompressionType::Uncompressed,
+        }
+    }
+}
+
+impl TryFrom<&HashMap<String, String>> for CsvFormat {
+    type Error = error::Error;
+
+    fn try_from(options: &HashMap<String, String>) -> Result<Self> {
+        let mut format = CsvFormat::default();
+        if let Some(has_header) =
+            file_format::parse_bool_option(options, file_format::FORMAT_HAS_HEADER)?
+        {
+            format.has_header = has_header;
+        }
+        if let Some(delimiter) =
+            file_format::parse_char_option(options, file_format::FORMAT_DELIMITER)?
+        {
+            format.delimiter = delimiter;
+        }
+        if let Some(quote) = file_format::parse_char_option(options, file_format::FORMAT_QUOTE)? {
+            format.quote = quote;
+        }
+        if let Some(max_record) =
+            file_format::parse_usize_option(options, file_format::FORMAT_SCHEMA_INFER_MAX_RECORD)?
+        {
+            format.schema_infer_max_record = Some(max_record);
+        }
+        if let Some(compression_type) =
+            file_format::parse_compression_option(options, file_format::FORMAT_COMPRESSION_TYPE)?
+        {
+            format.compression_type = compression_type;
+        }
+        Ok(format)
+    }
+}
+
+impl CsvFormat {
+    /// Infers the schema from the leading records of the file at `path`.
+    pub async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        // The header takes one more line.
+        let max_lines = self
+            .schema_infer_max_record
+            .map(|max_record| max_record + usize::from(self.has_header));
+        let data = file_format::read_lines(store, path, self.compression_type, max_lines).await?;
+        self.infer_schema_from(&data, path)
+    }
+
+    fn infer_schema_from(&self, data: &[u8], path: &str) -> Result<Schema> {
+        // The reader infers the schema with the same options used to read records.
+        let reader = ReaderBuilder::new()
+            .has_header(self.has_header)
+            .with_delimiter(self.delimiter)
+            .with_quote(self.quote)
+            .infer_schema(self.schema_infer_max_record)
+            .build(Cursor::new(data))
+            .context(error::InferSchemaSnafu { path })?;
+        Ok(reader.schema().as_ref().clone())
+    }
+
+    /// Reads the csv file at `path` with the types inferred from the file.
+    ///
+    /// Columns are matched by the header, files without a header are assumed to have
+    /// the same columns as `schema`.
+    pub async fn read(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        schema: SchemaRef,
+        projection: Option<&[usize]>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut file_schema = self.infer_schema(store, path).await?;
+        if !self.has_header {
+            file_schema = Schema::new(
+                file_schema
+                    .fields()
+                    .iter()
+                    .zip(schema.fields())
+                    .map(|(file_field, field)| file_field.clone().with_name(field.name()))
+                    .collect(),
+            );
+        }
+        let indices = file_format::file_projection(&file_schema, &schema, projection);
+        let projected_schema = file_schema
+            .project(&indices)
+            .context(error::InferSchemaSnafu { path })?;
+
+        let decoder = ReaderBuilder::new()
+            .has_header(self.has_header)
+            .with_delimiter(self.delimiter)
+            .with_quote(self.quote)
+            .with_schema(Arc::new(file_schema))
+            .with_projection(indices)
+            .build_decoder();
+        let reader = file_format::open_reader(store, path, self.compression_type).await?;
+
+        Ok(file_format::decode_stream(
+            path,
+            Arc::new(projected_schema),
+            reader,
+            decoder,
+        ))
+    }
+
+    /// Writes `batches` to the csv file at `path`, the header is written only if
+    /// `has_header` is set.
+    pub async fn write(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        batches: &[RecordBatch],
+    ) -> Result<usize> {
+        let mut buf = Vec::new();
+        for (i, batch) in batches.iter().enumerate() {
+            buf.extend(self.encode(batch, i == 0, path)?);
+        }
+
+        let data = self.compression_type.encode(buf, path).await?;
+        file_format::write_object(store, path, data).await?;
+        Ok(file_format::num_rows(batches))
+    }
+
+    /// Encodes `batch` in csv, the header is written only if both `has_header` and
+    /// `is_first` are set.
+    pub(crate) fn encode(
+        &self,
+        batch: &RecordBatch,
+        is_first: bool,
+        path: &str,
+    ) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        {
+            let mut writer = WriterBuilder::new()
+                .has_headers(self.has_header && is_first)
+                .with_delimiter(self.delimiter)
+                .build(&mut buf);
+            writer
+                .write(batch)
+                .context(error::WriteRecordBatchSnafu { path })?;
+        }
+        if self.quote == DEFAULT_QUOTE {
+            return Ok(buf);
+        }
+        self.requote(&buf, path)
+    }
+
+    /// Quotes fields of csv `data` by `quote` instead of the double quote, as the csv
+    /// writer of arrow always quotes fields by double quotes.
+    fn requote(&self, data: &[u8], path: &str) -> Result<Vec<u8>> {
+        let to_arrow_error = |e: csv::Error| ArrowError::CsvError(e.to_string());
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .from_reader(data);
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .from_writer(Vec::new());
+
+        let mut record = csv::ByteRecord::new();
+        while reader
+            .read_byte_record(&mut record)
+            .map_err(to_arrow_error)
+            .context(error::WriteRecordBatchSnafu { path })?
+        {
+            writer
+                .write_byte_record(&record)
+                .map_err(to_arrow_error)
+                .context(error::WriteRecordBatchSnafu { path })?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| ArrowError::CsvError(e.to_string()))
+            .context(error::WriteRecordBatchSnafu { path })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use common_test_util::temp_dir::create_temp_dir;
+    use futures::TryStreamExt;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    fn new_test_store(dir: &str) -> ObjectStore {
+        let mut builder = Fs::default();
+        builder.root(dir);
+        ObjectStore::new(builder).unwrap().finish()
+    }
+
+    #[tokio::test]
+    async fn test_infer_and_read_csv() {
+        let dir = create_temp_dir("test_infer_and_read_csv");
+        let store = new_test_store(dir.path().to_str().unwrap());
+        store
+            .write("cpu.csv", "host,ts,cpu\na,1,0.5\nb,2,1.5\n")
+            .await
+            .unwrap();
+
+        let format = CsvFormat::default();
+        let schema = format.infer_schema(&store, "cpu.csv").await.unwrap();
+        assert_eq!(
+            Schema::new(vec![
+                Field::new("host", DataType::Utf8, true),
+                Field::new("ts", DataType::Int64, true),
+                Field::new("cpu", DataType::Float64, true),
+            ]),
+            schema
+        );
+
+        let batches = format
+            .read(&store, "cpu.csv", Arc::new(schema), Some(&[2, 0]))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(2, batches[0].num_rows());
+        assert_eq!("cpu", batches[0].schema().field(0).name());
+        assert_eq!("host", batches[0].schema().field(1).name());
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_quoted_csv() {
+        let dir = create_temp_dir("test_write_and_read_quoted_csv");
+        let store = new_test_store(dir.path().to_str().unwrap());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("cpu", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a,b", "it's \"c\""])),
+                Arc::new(Float64Array::from(vec![0.5, 1.5])),
+            ],
+        )
+        .unwrap();
+
+        let format = CsvFormat {
+            quote: b'\'',
+            ..Default::default()
+        };
+        format
+            .write(&store, "cpu.csv", &[batch.clone()])
+            .await
+            .unwrap();
+        let data = store.read("cpu.csv").await.unwrap();
+        assert_eq!(
+            "host,cpu\n'a,b',0.5\n'it''s \"c\"',1.5\n",
+            String::from_utf8(data).unwrap()
+        );
+
+        let file_schema = format.infer_schema(&store, "cpu.csv").await.unwrap();
+        assert_eq!(schema.as_ref(), &file_schema);
+        let batches = format
+            .read(&store, "cpu.csv", schema, None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(vec![batch], batches);
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_compressed_csv() {
+        let dir = create_temp_dir("test_write_and_read_compressed_csv");
+        let store = new_test_store(dir.path().to_str().unwrap());
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("cpu", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a|b", "c"])),
+                Arc::new(Float64Array::from(vec![0.5, 1.5])),
+            ],
+        )
+        .unwrap();
+
+        let format = CsvFormat {
+            delimiter: b'|',
+            compression_type: CompressionType::Gzip,
+            ..Default::default()
+        };
+        let rows = format
+            .write(&store, "cpu.csv.gz", &[batch.clone()])
+            .await
+            .unwrap();
+        assert_eq!(2, rows);
+
+        let batches = format
+            .read(&store, "cpu.csv.gz", schema, None)
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(vec![batch], batches);
+    }
+}
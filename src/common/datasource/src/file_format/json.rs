@@ -0,0 +1,210 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::{BufReader, Cursor};
+use std::sync::Arc;
+
+use arrow::json::reader::infer_json_schema;
+use arrow::json::{LineDelimitedWriter, RawReaderBuilder};
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{Schema, SchemaRef};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use object_store::ObjectStore;
+use snafu::ResultExt;
+
+use crate::compression::CompressionType;
+use crate::error::{self, Result};
+use crate::file_format::{self, DEFAULT_SCHEMA_INFER_MAX_RECORD};
+
+/// Newline delimited json format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonFormat {
+    pub schema_infer_max_record: Option<usize>,
+    pub compression_type: CompressionType,
+}
+
+impl Default for JsonFormat {
+    fn default() -> Self {
+        JsonFormat {
+            schema_infer_max_record: Some(DEFAULT_SCHEMA_INFER_MAX_RECORD),
+            compression_type: CompressionType::Uncompressed,
+        }
+    }
+}
+
+impl TryFrom<&HashMap<String, String>> for JsonFormat {
+    type Error = error::Error;
+
+    fn try_from(options: &HashMap<String, String>) -> Result<Self> {
+        let mut format = JsonFormat::default();
+        if let Some(max_record) =
+            file_format::parse_usize_option(options, file_format::FORMAT_SCHEMA_INFER_MAX_RECORD)?
+        {
+            format.schema_infer_max_record = Some(max_record);
+        }
+        if let Some(compression_type) =
+            file_format::parse_compression_option(options, file_format::FORMAT_COMPRESSION_TYPE)?
+        {
+            format.compression_type = compression_type;
+        }
+        Ok(format)
+    }
+}
+
+impl JsonFormat {
+    /// Infers the schema from the leading records of the file at `path`.
+    pub async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        let data = file_format::read_lines(
+            store,
+            path,
+            self.compression_type,
+            self.schema_infer_max_record,
+        )
+        .await?;
+        self.infer_schema_from(&data, path)
+    }
+
+    fn infer_schema_from(&self, data: &[u8], path: &str) -> Result<Schema> {
+        let mut reader = BufReader::new(Cursor::new(data));
+        infer_json_schema(&mut reader, self.schema_infer_max_record)
+            .context(error::InferSchemaSnafu { path })
+    }
+
+    /// Reads the json file at `path` with the types inferred from the file.
+    pub async fn read(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        schema: SchemaRef,
+        projection: Option<&[usize]>,
+    ) -> Result<SendableRecordBatchStream> {
+        let file_schema = self.infer_schema(store, path).await?;
+        let indices = file_format::file_projection(&file_schema, &schema, projection);
+        // Json decoder decodes objects by field names, so a projected schema is enough.
+        let file_schema = Arc::new(
+            file_schema
+                .project(&indices)
+                .context(error::InferSchemaSnafu { path })?,
+        );
+
+        let decoder = RawReaderBuilder::new(file_schema.clone())
+            .build_decoder()
+            .context(error::ReadRecordBatchSnafu { path })?;
+        let reader = file_format::open_reader(store, path, self.compression_type).await?;
+
+        Ok(file_format::decode_stream(
+            path,
+            file_schema,
+            reader,
+            decoder,
+        ))
+    }
+
+    /// Writes `batches` to the file at `path`, one json object per row.
+    pub async fn write(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        batches: &[RecordBatch],
+    ) -> Result<usize> {
+        let mut buf = Vec::new();
+        for batch in batches {
+            buf.extend(self.encode(batch, path)?);
+        }
+
+        let data = self.compression_type.encode(buf, path).await?;
+        file_format::write_object(store, path, data).await?;
+        Ok(file_format::num_rows(batches))
+    }
+
+    /// Encodes `batch` in json, one object per row.
+    pub(crate) fn encode(&self, batch: &RecordBatch, path: &str) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        {
+            let mut writer = LineDelimitedWriter::new(&mut buf);
+            writer
+                .write_batches(std::slice::from_ref(batch))
+                .context(error::WriteRecordBatchSnafu { path })?;
+            writer
+                .finish()
+                .context(error::WriteRecordBatchSnafu { path })?;
+        }
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use common_test_util::temp_dir::create_temp_dir;
+    use futures::TryStreamExt;
+    use object_store::services::Fs;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_and_read_json() {
+        let dir = create_temp_dir("test_write_and_read_json");
+        let mut builder = Fs::default();
+        builder.root(dir.path().to_str().unwrap());
+        let store = ObjectStore::new(builder).unwrap().finish();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("ts", DataType::Int64, true),
+            Field::new("cpu", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(Float64Array::from(vec![0.5, 1.5])),
+            ],
+        )
+        .unwrap();
+
+        let format = JsonFormat {
+            compression_type: CompressionType::Zstd,
+            ..Default::default()
+        };
+        let rows = format
+            .write(&store, "cpu.json.zst", &[batch])
+            .await
+            .unwrap();
+        assert_eq!(2, rows);
+
+        let file_schema = format.infer_schema(&store, "cpu.json.zst").await.unwrap();
+        for field in schema.fields() {
+            let file_field = file_schema.field_with_name(field.name()).unwrap();
+            assert_eq!(field.data_type(), file_field.data_type());
+        }
+
+        let batches = format
+            .read(&store, "cpu.json.zst", schema, Some(&[2, 0]))
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(2, batches[0].num_rows());
+        assert_eq!("cpu", batches[0].schema().field(0).name());
+        assert_eq!("host", batches[0].schema().field(1).name());
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+use arrow_schema::{Schema, SchemaRef};
+use async_compat::{Compat, CompatExt};
+use datafusion::error::DataFusionError;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::SendableRecordBatchStream;
+use futures::TryStreamExt;
+use object_store::ObjectStore;
+use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask};
+use snafu::ResultExt;
+use tokio::io::BufReader;
+
+use crate::error::{self, Result};
+use crate::file_format;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParquetFormat {}
+
+impl ParquetFormat {
+    async fn stream_builder(
+        store: &ObjectStore,
+        path: &str,
+    ) -> Result<ParquetRecordBatchStreamBuilder<BufReader<Compat<object_store::Reader>>>> {
+        let reader = store
+            .reader(path)
+            .await
+            .context(error::ReadObjectSnafu { path })?;
+        ParquetRecordBatchStreamBuilder::new(BufReader::new(reader.compat()))
+            .await
+            .context(error::ReadParquetSnafu { path })
+    }
+
+    pub async fn infer_schema(&self, store: &ObjectStore, path: &str) -> Result<Schema> {
+        let builder = Self::stream_builder(store, path).await?;
+        Ok(builder.schema().as_ref().clone())
+    }
+
+    /// Reads the parquet file at `path`, only columns requested by `schema` and
+    /// `projection` that also exist in the file are read.
+    ///
+    /// Columns are returned in the order of the file.
+    pub async fn read(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        schema: SchemaRef,
+        projection: Option<&[usize]>,
+    ) -> Result<SendableRecordBatchStream> {
+        let mut builder = Self::stream_builder(store, path).await?;
+
+        let mut indices = file_format::file_projection(builder.schema(), &schema, projection);
+        indices.sort_unstable();
+        let projected_schema = builder
+            .schema()
+            .project(&indices)
+            .context(error::InferSchemaSnafu { path })?;
+        let schema_desc = builder.metadata().file_metadata().schema_descr_ptr();
+        builder = builder.with_projection(ProjectionMask::roots(&schema_desc, indices));
+
+        let stream = builder
+            .build()
+            .context(error::ReadParquetSnafu { path })?
+            .map_err(DataFusionError::ParquetError);
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            Arc::new(projected_schema),
+            stream,
+        )))
+    }
+
+    /// Writes `batches` to the parquet file at `path`, all batches should have the same schema.
+    pub async fn write(
+        &self,
+        store: &ObjectStore,
+        path: &str,
+        batches: &[RecordBatch],
+    ) -> Result<usize> {
+        let Some(first) = batches.first() else {
+            return Ok(0);
+        };
+
+        let mut buf = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut buf, first.schema(), None)
+            .context(error::WriteParquetSnafu { path })?;
+        for batch in batches {
+            writer
+                .write(batch)
+                .context(error::WriteParquetSnafu { path })?;
+        }
+        writer.close().context(error::WriteParquetSnafu { path })?;
+
+        file_format::write_object(store, path, buf).await?;
+        Ok(file_format::num_rows(batches))
+    }
+}
@@ -59,6 +59,8 @@ pub enum StatusCode {
     // ====== Begin of storage related status code =====
     /// Storage is temporarily unable to handle the request
     StorageUnavailable = 5000,
+    /// Storage is too busy to accept writes, e.g. its write buffer is full
+    StorageBusy = 5001,
     // ====== End of storage related status code =======
 
     // ====== Begin of server related status code =====
@@ -90,6 +92,7 @@ impl StatusCode {
     pub fn is_retryable(&self) -> bool {
         match self {
             StatusCode::StorageUnavailable
+            | StatusCode::StorageBusy
             | StatusCode::RuntimeResourcesExhausted
             | StatusCode::Internal => true,
 
@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 use servers::http::HttpOptions;
 use servers::Mode;
 use storage::config::EngineConfig as StorageEngineConfig;
+pub use storage::config::MemtableType;
 use storage::scheduler::SchedulerConfig;
 
 use crate::error::Result;
@@ -47,6 +48,8 @@ pub struct StorageConfig {
     pub store: ObjectStoreConfig,
     pub compaction: CompactionConfig,
     pub manifest: RegionManifestConfig,
+    pub write_buffer: WriteBufferConfig,
+    pub memtable: MemtableConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Default, Deserialize)]
@@ -168,6 +171,36 @@ impl Default for CompactionConfig {
     }
 }
 
+/// Options for the write buffer shared by all regions
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct WriteBufferConfig {
+    /// Max memory size of memtables of all regions, flushes are triggered once the usage
+    /// is close to this size. `None` means unlimited.
+    pub global_write_buffer_size: Option<ReadableSize>,
+    /// Max duration a write waits for the write buffer, the write is rejected after timeout.
+    #[serde(with = "humantime_serde")]
+    pub write_stall_timeout: Duration,
+}
+
+impl Default for WriteBufferConfig {
+    fn default() -> Self {
+        Self {
+            global_write_buffer_size: Some(ReadableSize::gb(1)),
+            write_stall_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Options for memtables of regions
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
+#[serde(default)]
+pub struct MemtableConfig {
+    /// Type of memtables, `btree` or `time_series`.
+    #[serde(rename = "type")]
+    pub memtable_type: MemtableType,
+}
+
 impl From<&DatanodeOptions> for SchedulerConfig {
     fn from(value: &DatanodeOptions) -> Self {
         Self {
@@ -185,6 +218,9 @@ impl From<&DatanodeOptions> for StorageEngineConfig {
             max_files_in_l0: value.storage.compaction.max_files_in_level0,
             max_purge_tasks: value.storage.compaction.max_purge_tasks,
             sst_write_buffer_size: value.storage.compaction.sst_write_buffer_size,
+            global_write_buffer_size: value.storage.write_buffer.global_write_buffer_size,
+            write_stall_timeout: value.storage.write_buffer.write_stall_timeout,
+            memtable_type: value.storage.memtable.memtable_type,
         }
     }
 }
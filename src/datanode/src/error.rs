@@ -17,7 +17,6 @@ use std::any::Any;
 use common_datasource::error::Error as DataSourceError;
 use common_error::prelude::*;
 use common_procedure::ProcedureId;
-use datafusion::parquet;
 use snafu::Location;
 use storage::error::Error as StorageError;
 use table::error::Error as TableError;
@@ -165,6 +164,53 @@ pub enum Error {
         source: TableError,
     },
 
+    #[snafu(display("Failed to compact table: {}, source: {}", table_name, source))]
+    CompactTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display("Failed to truncate table: {}, source: {}", table_name, source))]
+    TruncateTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display(
+        "Failed to create snapshot {} of table: {}, source: {}",
+        snapshot,
+        table_name,
+        source
+    ))]
+    CreateSnapshot {
+        table_name: String,
+        snapshot: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display(
+        "Failed to drop snapshot {} of table: {}, source: {}",
+        snapshot,
+        table_name,
+        source
+    ))]
+    DropSnapshot {
+        table_name: String,
+        snapshot: String,
+        #[snafu(backtrace)]
+        source: TableError,
+    },
+
+    #[snafu(display("Failed to restore table: {}, source: {}", table_name, source))]
+    RestoreTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
     #[snafu(display("Failed to start server, source: {}", source))]
     StartServer {
         #[snafu(backtrace)]
@@ -422,32 +468,60 @@ pub enum Error {
         source: common_query::error::Error,
     },
 
-    #[snafu(display(
-        "File Schema mismatch, expected table schema: {} but found :{}",
-        table_schema,
-        file_schema
-    ))]
-    InvalidSchema {
-        table_schema: String,
-        file_schema: String,
+    #[snafu(display("Failed to poll stream, source: {}", source))]
+    PollStream {
+        source: datafusion_common::DataFusionError,
+        location: Location,
     },
 
-    #[snafu(display("Failed to read parquet file, source: {}", source))]
-    ReadParquet {
-        source: parquet::errors::ParquetError,
-        location: Location,
+    #[snafu(display("Invalid file format options, source: {}", source))]
+    ParseFileFormat {
+        #[snafu(backtrace)]
+        source: DataSourceError,
     },
 
-    #[snafu(display("Failed to poll stream, source: {}", source))]
-    PollStream {
-        source: datafusion_common::DataFusionError,
+    #[snafu(display("Failed to read file: {}, source: {}", path, source))]
+    ReadFile {
+        path: String,
+        #[snafu(backtrace)]
+        source: DataSourceError,
+    },
+
+    #[snafu(display("Failed to write file: {}, source: {}", path, source))]
+    WriteFile {
+        path: String,
+        #[snafu(backtrace)]
+        source: DataSourceError,
+    },
+
+    #[snafu(display(
+        "Failed to coerce column {} in file {} to the type of table column, source: {}",
+        column,
+        path,
+        source
+    ))]
+    CoerceColumn {
+        column: String,
+        path: String,
+        source: datatypes::arrow::error::ArrowError,
         location: Location,
     },
 
-    #[snafu(display("Failed to build parquet record batch stream, source: {}", source))]
-    BuildParquetRecordBatchStream {
+    #[snafu(display("Failed to collect record batches, source: {}", source))]
+    CollectRecordBatches {
+        #[snafu(backtrace)]
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to build the plan to copy table: {}, source: {}",
+        table_name,
+        source
+    ))]
+    BuildCopyPlan {
+        table_name: String,
+        source: datafusion_common::DataFusionError,
         location: Location,
-        source: parquet::errors::ParquetError,
     },
 
     #[snafu(display("Failed to read object in path: {}, source: {}", path, source))]
@@ -535,6 +609,10 @@ impl ErrorExt for Error {
             }
             DropTable { source, .. } => source.status_code(),
             FlushTable { source, .. } => source.status_code(),
+            CompactTable { source, .. } => source.status_code(),
+            TruncateTable { source, .. } => source.status_code(),
+            CreateSnapshot { source, .. } | DropSnapshot { source, .. } => source.status_code(),
+            RestoreTable { source, .. } => source.status_code(),
 
             Insert { source, .. } => source.status_code(),
             Delete { source, .. } => source.status_code(),
@@ -571,7 +649,8 @@ impl ErrorExt for Error {
             | MissingNodeId { .. }
             | MissingMetasrvOpts { .. }
             | ColumnNoneDefaultValue { .. }
-            | ParseUrl { .. } => StatusCode::InvalidArguments,
+            | ParseUrl { .. }
+            | CoerceColumn { .. } => StatusCode::InvalidArguments,
 
             // TODO(yingwen): Further categorize http error.
             StartServer { .. }
@@ -584,17 +663,15 @@ impl ErrorExt for Error {
             | RenameTable { .. }
             | Catalog { .. }
             | MissingRequiredField { .. }
-            | BuildParquetRecordBatchStream { .. }
-            | InvalidSchema { .. }
             | ParseDataTypes { .. }
             | IncorrectInternalState { .. }
             | ShutdownServer { .. }
             | ShutdownInstance { .. }
-            | CloseTableEngine { .. } => StatusCode::Internal,
+            | CloseTableEngine { .. }
+            | BuildCopyPlan { .. } => StatusCode::Internal,
 
             BuildBackend { .. }
             | InitBackend { .. }
-            | ReadParquet { .. }
             | WriteParquet { .. }
             | PollStream { .. }
             | ReadObject { .. }
@@ -608,6 +685,10 @@ impl ErrorExt for Error {
             BumpTableId { source, .. } => source.status_code(),
             ColumnDefaultValue { source, .. } => source.status_code(),
             CopyTable { source, .. } => source.status_code(),
+            ParseFileFormat { source } | ReadFile { source, .. } | WriteFile { source, .. } => {
+                source.status_code()
+            }
+            CollectRecordBatches { source } => source.status_code(),
             TableScanExec { source, .. } => source.status_code(),
             UnrecognizedTableOption { .. } => StatusCode::InvalidArguments,
             RecoverProcedure { source, .. } | SubmitProcedure { source, .. } => {
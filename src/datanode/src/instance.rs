@@ -26,6 +26,7 @@ use common_procedure::local::{LocalManager, ManagerConfig};
 use common_procedure::store::state_store::ObjectStateStore;
 use common_procedure::ProcedureManagerRef;
 use common_telemetry::logging::info;
+use file_table_engine::engine::FileTableEngine;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use log_store::LogConfig;
 use meta_client::client::{MetaClient, MetaClientBuilder};
@@ -61,6 +62,8 @@ use crate::error::{
 use crate::heartbeat::HeartbeatTask;
 use crate::sql::{SqlHandler, SqlRequest};
 
+mod copy_database;
+mod copy_query_to;
 mod grpc;
 pub mod sql;
 
@@ -115,10 +118,14 @@ impl Instance {
                 object_store.clone(),
                 compaction_scheduler,
             ),
-            object_store,
+            object_store.clone(),
         ));
 
-        let engine_manager = Arc::new(MemoryTableEngineManager::new(table_engine.clone()));
+        let file_table_engine = Arc::new(FileTableEngine::new(object_store));
+        let engine_manager = Arc::new(MemoryTableEngineManager::with(vec![
+            table_engine.clone(),
+            file_table_engine,
+        ]));
 
         // create remote catalog manager
         let (catalog_manager, table_id_provider) = match opts.mode {
@@ -144,7 +151,7 @@ impl Instance {
                     )
                 } else {
                     let catalog = Arc::new(
-                        catalog::local::LocalCatalogManager::try_new(engine_manager)
+                        catalog::local::LocalCatalogManager::try_new(engine_manager.clone())
                             .await
                             .context(CatalogSnafu)?,
                     );
@@ -158,7 +165,7 @@ impl Instance {
 
             Mode::Distributed => {
                 let catalog = Arc::new(catalog::remote::RemoteCatalogManager::new(
-                    engine_manager,
+                    engine_manager.clone(),
                     opts.node_id.context(MissingNodeIdSnafu)?,
                     Arc::new(MetaKvBackend {
                         client: meta_client.as_ref().unwrap().clone(),
@@ -197,7 +204,7 @@ impl Instance {
         Ok(Self {
             query_engine: query_engine.clone(),
             sql_handler: SqlHandler::new(
-                Arc::new(MemoryTableEngineManager::new(table_engine.clone())),
+                engine_manager,
                 catalog_manager.clone(),
                 table_engine,
                 procedure_manager.clone(),
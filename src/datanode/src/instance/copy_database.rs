@@ -0,0 +1,268 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Copies all tables in a database to or from a directory. Each table has its own sub
+//! directory named by the table, which contains the SQL to create the table and the data file.
+
+use std::sync::Arc;
+
+use catalog::SchemaProviderRef;
+use common_catalog::consts::{FILE_ENGINE, MITO_ENGINE};
+use common_datasource::file_format::Format;
+use common_datasource::lister::{Lister, Source};
+use common_datasource::object_store::{build_backend, parse_url};
+use common_query::Output;
+use common_telemetry::logging::info;
+use query::sql::create_table_sql;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
+use sql::statements::statement::Statement;
+use table::metadata::TableType;
+use table::requests::{CopyDatabaseRequest, CopyDirection, CopyTableRequest};
+
+use crate::error::{self, Result};
+use crate::instance::copy_query_to::has_time_range;
+use crate::instance::Instance;
+
+/// Name of the file that stores the SQL to create the table.
+const CREATE_TABLE_FILE: &str = "create_table.sql";
+
+impl Instance {
+    pub(crate) async fn copy_database_to(&self, req: CopyDatabaseRequest) -> Result<Output> {
+        let schema = self.find_schema(&req)?;
+        let format = Format::try_from(&req.with).context(error::ParseFileFormatSnafu)?;
+        let location = dir_location(&req.location);
+        let (_schema, _host, path) = parse_url(&location).context(error::ParseUrlSnafu)?;
+        let object_store =
+            build_backend(&location, req.connection.clone()).context(error::BuildBackendSnafu)?;
+
+        let mut table_names = schema.table_names().context(error::CatalogSnafu)?;
+        table_names.sort_unstable();
+
+        let mut rows_copied = 0;
+        for table_name in table_names {
+            let Some(table) = schema.table(&table_name).await.context(error::CatalogSnafu)?
+                else { continue };
+            // Only mito tables and external tables are exported. Others, like the `numbers`
+            // table for tests or tables restored from snapshots, are not owned by the database.
+            let table_info = table.table_info();
+            let engine = &table_info.meta.engine;
+            let exportable = match engine.as_str() {
+                MITO_ENGINE => table_info.table_type == TableType::Base,
+                FILE_ENGINE => true,
+                _ => false,
+            };
+            if !exportable {
+                continue;
+            }
+
+            let sql = create_table_sql(&table, None).context(error::ExecuteSqlSnafu)?;
+            let sql_path = format!("{path}{table_name}/{CREATE_TABLE_FILE}");
+            object_store
+                .write(&sql_path, sql)
+                .await
+                .context(error::WriteObjectSnafu { path: &sql_path })?;
+
+            // Files of external tables are not managed by us, so only the SQL is exported.
+            if engine == FILE_ENGINE {
+                continue;
+            }
+
+            info!(
+                "Copying table {}.{}.{} to {}",
+                req.catalog_name, req.schema_name, table_name, location
+            );
+            let copy_req = CopyTableRequest {
+                catalog_name: req.catalog_name.clone(),
+                schema_name: req.schema_name.clone(),
+                location: format!("{location}{table_name}/{}", data_filename(&format)),
+                table_name,
+                with: req.with.clone(),
+                connection: req.connection.clone(),
+                pattern: None,
+                direction: CopyDirection::Export,
+            };
+            let output = if has_time_range(&req.with) {
+                let ctx = Arc::new(QueryContext::with(&req.catalog_name, &req.schema_name));
+                self.copy_table_range_to(copy_req, ctx).await?
+            } else {
+                self.sql_handler.copy_table_to(copy_req).await?
+            };
+            if let Output::AffectedRows(rows) = output {
+                rows_copied += rows;
+            }
+        }
+
+        Ok(Output::AffectedRows(rows_copied))
+    }
+
+    pub(crate) async fn copy_database_from(&self, req: CopyDatabaseRequest) -> Result<Output> {
+        let schema = self.find_schema(&req)?;
+        let format = Format::try_from(&req.with).context(error::ParseFileFormatSnafu)?;
+        let location = dir_location(&req.location);
+        let (_schema, _host, path) = parse_url(&location).context(error::ParseUrlSnafu)?;
+        let object_store =
+            build_backend(&location, req.connection.clone()).context(error::BuildBackendSnafu)?;
+
+        let lister = Lister::new(object_store.clone(), Source::Dir, path, None);
+        let entries = lister.list().await.context(error::ListObjectsSnafu)?;
+        let mut table_dirs = entries
+            .iter()
+            .map(|entry| entry.path())
+            .filter(|path| path.ends_with('/'))
+            .collect::<Vec<_>>();
+        table_dirs.sort_unstable();
+
+        // Tables are created in the target database, regardless of the database they
+        // are exported from.
+        let target_ctx = Arc::new(QueryContext::with(&req.catalog_name, &req.schema_name));
+        let mut rows_copied = 0;
+        for dir in table_dirs {
+            let sql_path = format!("{dir}{CREATE_TABLE_FILE}");
+            if !object_store
+                .is_exist(&sql_path)
+                .await
+                .context(error::ReadObjectSnafu { path: &sql_path })?
+            {
+                continue;
+            }
+            let sql = object_store
+                .read(&sql_path)
+                .await
+                .context(error::ReadObjectSnafu { path: &sql_path })?;
+            let sql = String::from_utf8_lossy(&sql);
+
+            let mut stmts = ParserContext::create_with_dialect(&sql, &GenericDialect {})
+                .context(error::ParseSqlSnafu)?;
+            ensure!(
+                stmts.len() == 1,
+                error::InvalidSqlSnafu {
+                    msg: format!("expect one statement in {sql_path}, actual: {sql}"),
+                }
+            );
+
+            let (table_name, copy_data) = match stmts.remove(0) {
+                Statement::CreateTable(create_table) => {
+                    let table_name = last_ident(&create_table.name, &sql_path)?;
+                    if !table_exists(&schema, &table_name)? {
+                        self.create_table_by_stmt(create_table, target_ctx.clone())
+                            .await?;
+                    }
+                    (table_name, true)
+                }
+                Statement::CreateExternalTable(create_table) => {
+                    let table_name = last_ident(&create_table.name, &sql_path)?;
+                    if !table_exists(&schema, &table_name)? {
+                        self.create_external_table_by_stmt(create_table, target_ctx.clone())
+                            .await?;
+                    }
+                    (table_name, false)
+                }
+                _ => {
+                    return error::InvalidSqlSnafu {
+                        msg: format!(
+                            "expect a CREATE TABLE statement in {sql_path}, actual: {sql}"
+                        ),
+                    }
+                    .fail()
+                }
+            };
+
+            // Empty tables have no data file.
+            let data_filename = data_filename(&format);
+            let data_path = format!("{dir}{data_filename}");
+            if !copy_data
+                || !object_store
+                    .is_exist(&data_path)
+                    .await
+                    .context(error::ReadObjectSnafu { path: &data_path })?
+            {
+                continue;
+            }
+
+            info!(
+                "Copying table {}.{}.{} from {}",
+                req.catalog_name, req.schema_name, table_name, location
+            );
+            let output = self
+                .sql_handler
+                .copy_table_from(CopyTableRequest {
+                    catalog_name: req.catalog_name.clone(),
+                    schema_name: req.schema_name.clone(),
+                    location: format!("{location}{}/{data_filename}", dir_name(dir)),
+                    table_name,
+                    with: req.with.clone(),
+                    connection: req.connection.clone(),
+                    pattern: None,
+                    direction: CopyDirection::Import,
+                })
+                .await?;
+            if let Output::AffectedRows(rows) = output {
+                rows_copied += rows;
+            }
+        }
+
+        Ok(Output::AffectedRows(rows_copied))
+    }
+
+    fn find_schema(&self, req: &CopyDatabaseRequest) -> Result<SchemaProviderRef> {
+        self.catalog_manager
+            .schema(&req.catalog_name, &req.schema_name)
+            .context(error::CatalogSnafu)?
+            .context(error::DatabaseNotFoundSnafu {
+                catalog: &req.catalog_name,
+                schema: &req.schema_name,
+            })
+    }
+}
+
+/// Appends a `/` to the location if absent, as the location of a database is a directory.
+fn dir_location(location: &str) -> String {
+    if location.ends_with('/') {
+        location.to_string()
+    } else {
+        format!("{location}/")
+    }
+}
+
+/// Returns the last component of the directory `path`, which ends with `/`.
+fn dir_name(path: &str) -> &str {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+}
+
+fn data_filename(format: &Format) -> &'static str {
+    match format {
+        Format::Csv(_) => "data.csv",
+        Format::Json(_) => "data.json",
+        Format::Parquet(_) => "data.parquet",
+    }
+}
+
+fn last_ident(name: &sql::ast::ObjectName, sql_path: &str) -> Result<String> {
+    name.0
+        .last()
+        .map(|ident| ident.value.clone())
+        .with_context(|| error::InvalidSqlSnafu {
+            msg: format!("empty table name in {sql_path}"),
+        })
+}
+
+fn table_exists(schema: &SchemaProviderRef, table_name: &str) -> Result<bool> {
+    schema.table_exist(table_name).context(error::CatalogSnafu)
+}
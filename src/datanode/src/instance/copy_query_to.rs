@@ -0,0 +1,215 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exports the result of a query, or rows of a table in a time range, to a file.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common_query::Output;
+use common_recordbatch::SendableRecordBatchStream;
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datafusion::datasource::provider_as_source;
+use datafusion::prelude::{Column, Expr as DfExpr};
+use datafusion::scalar::ScalarValue;
+use datafusion::sql::TableReference as DfTableReference;
+use datafusion_expr::LogicalPlanBuilder;
+use datatypes::prelude::ConcreteDataType;
+use query::parser::QueryStatement;
+use query::plan::LogicalPlan;
+use session::context::QueryContextRef;
+use snafu::{OptionExt, ResultExt};
+use sql::statements::copy::{CopyQueryTo, COPY_END_TIME_KEY, COPY_START_TIME_KEY};
+use sql::statements::statement::Statement;
+use table::engine::TableReference;
+use table::requests::CopyTableRequest;
+use table::table::adapter::DfTableProviderAdapter;
+
+use crate::error::{self, Result};
+use crate::instance::Instance;
+use crate::sql::copy_table_to::copy_stream_to;
+
+impl Instance {
+    pub(crate) async fn copy_query_to(
+        &self,
+        copy_query_to: CopyQueryTo,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let CopyQueryTo {
+            query,
+            with,
+            connection,
+            location,
+            ..
+        } = copy_query_to;
+
+        let plan = self
+            .query_engine
+            .planner()
+            .plan(
+                QueryStatement::Sql(Statement::Query(query)),
+                query_ctx.clone(),
+            )
+            .await
+            .context(error::PlanStatementSnafu)?;
+        let stream = self.execute_to_stream(plan, query_ctx).await?;
+
+        let rows_copied = copy_stream_to(stream, &location, &with, connection).await?;
+        Ok(Output::AffectedRows(rows_copied))
+    }
+
+    /// Exports rows of the table whose time index is in `[START_TIME, END_TIME)`.
+    pub(crate) async fn copy_table_range_to(
+        &self,
+        req: CopyTableRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let table_name = TableReference::full(&req.catalog_name, &req.schema_name, &req.table_name);
+        let table = self.sql_handler.get_table(&table_name).await?;
+
+        let schema = table.schema();
+        let ts_column = schema
+            .timestamp_column()
+            .context(error::MissingTimestampColumnSnafu)?;
+        let ConcreteDataType::Timestamp(ts_type) = &ts_column.data_type else {
+            return error::InvalidSqlSnafu {
+                msg: format!("time index {} is not a timestamp", ts_column.name),
+            }
+            .fail();
+        };
+        let unit = ts_type.unit();
+
+        let ts_expr = DfExpr::Column(Column::from_name(&ts_column.name));
+        let start = parse_time_option(&req.with, COPY_START_TIME_KEY, unit)?
+            .map(|start| ts_expr.clone().gt_eq(DfExpr::Literal(start)));
+        let end = parse_time_option(&req.with, COPY_END_TIME_KEY, unit)?
+            .map(|end| ts_expr.lt(DfExpr::Literal(end)));
+        let filter = match (start, end) {
+            (Some(start), Some(end)) => Some(start.and(end)),
+            (start, end) => start.or(end),
+        };
+
+        let build_plan = || {
+            let table_ref = DfTableReference::full(
+                req.catalog_name.clone(),
+                req.schema_name.clone(),
+                req.table_name.clone(),
+            );
+            let source = provider_as_source(Arc::new(DfTableProviderAdapter::new(table.clone())));
+            let builder = LogicalPlanBuilder::scan(table_ref, source, None)?;
+            match filter {
+                Some(filter) => builder.filter(filter)?.build(),
+                None => builder.build(),
+            }
+        };
+        let plan = build_plan().context(error::BuildCopyPlanSnafu {
+            table_name: table_name.to_string(),
+        })?;
+        let stream = self
+            .execute_to_stream(LogicalPlan::DfPlan(plan), query_ctx)
+            .await?;
+
+        let rows_copied = copy_stream_to(stream, &req.location, &req.with, req.connection).await?;
+        Ok(Output::AffectedRows(rows_copied))
+    }
+
+    async fn execute_to_stream(
+        &self,
+        plan: LogicalPlan,
+        query_ctx: QueryContextRef,
+    ) -> Result<SendableRecordBatchStream> {
+        let output = self
+            .query_engine
+            .execute(plan, query_ctx)
+            .await
+            .context(error::ExecuteLogicalPlanSnafu)?;
+        match output {
+            Output::Stream(stream) => Ok(stream),
+            Output::RecordBatches(batches) => Ok(batches.as_stream()),
+            Output::AffectedRows(_) => error::InvalidSqlSnafu {
+                msg: "expect a query that returns rows to copy",
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Returns true if the request only exports rows in a time range.
+pub(crate) fn has_time_range(with: &HashMap<String, String>) -> bool {
+    with.contains_key(COPY_START_TIME_KEY) || with.contains_key(COPY_END_TIME_KEY)
+}
+
+/// Parses the time option `key` as a literal in time `unit`. The time is either a
+/// timestamp string, or an integer in milliseconds.
+fn parse_time_option(
+    with: &HashMap<String, String>,
+    key: &str,
+    unit: TimeUnit,
+) -> Result<Option<ScalarValue>> {
+    let Some(value) = with.get(key) else { return Ok(None) };
+
+    let ts = match value.parse::<i64>() {
+        Ok(millis) => Timestamp::new_millisecond(millis),
+        Err(_) => Timestamp::from_str(value).context(error::ParseTimestampSnafu { raw: value })?,
+    };
+    let ts = ts
+        .convert_to(unit)
+        .with_context(|| error::InvalidSqlSnafu {
+            msg: format!("{key} {value} overflows the time index"),
+        })?;
+
+    let value = Some(ts.value());
+    Ok(Some(match unit {
+        TimeUnit::Second => ScalarValue::TimestampSecond(value, None),
+        TimeUnit::Millisecond => ScalarValue::TimestampMillisecond(value, None),
+        TimeUnit::Microsecond => ScalarValue::TimestampMicrosecond(value, None),
+        TimeUnit::Nanosecond => ScalarValue::TimestampNanosecond(value, None),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_option() {
+        let with = HashMap::from([
+            (COPY_START_TIME_KEY.to_string(), "1680307200000".to_string()),
+            (
+                COPY_END_TIME_KEY.to_string(),
+                "2023-04-02T00:00:00Z".to_string(),
+            ),
+        ]);
+        assert!(has_time_range(&with));
+        assert!(!has_time_range(&HashMap::new()));
+
+        assert_eq!(
+            Some(ScalarValue::TimestampSecond(Some(1680307200), None)),
+            parse_time_option(&with, COPY_START_TIME_KEY, TimeUnit::Second).unwrap()
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(1680393600000), None)),
+            parse_time_option(&with, COPY_END_TIME_KEY, TimeUnit::Millisecond).unwrap()
+        );
+        assert_eq!(
+            None,
+            parse_time_option(&HashMap::new(), COPY_END_TIME_KEY, TimeUnit::Millisecond).unwrap()
+        );
+
+        let with = HashMap::from([(COPY_START_TIME_KEY.to_string(), "yesterday".to_string())]);
+        assert!(parse_time_option(&with, COPY_START_TIME_KEY, TimeUnit::Millisecond).is_err());
+    }
+}
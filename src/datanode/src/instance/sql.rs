@@ -25,15 +25,21 @@ use query::query_engine::StatementHandler;
 use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::ast::ObjectName;
-use sql::statements::copy::{CopyTable, CopyTableArgument};
+use sql::statements::copy::{CopyDatabase, CopyTable, CopyTableArgument};
+use sql::statements::create::{CreateExternalTable, CreateTable};
 use sql::statements::statement::Statement;
 use table::engine::TableReference;
-use table::requests::{CopyDirection, CopyTableRequest, CreateDatabaseRequest, DropTableRequest};
+use table::requests::{
+    CompactTableRequest, CopyDatabaseRequest, CopyDirection, CopyTableRequest,
+    CreateDatabaseRequest, CreateSnapshotRequest, DropSnapshotRequest, DropTableRequest,
+    RestoreTableRequest, TruncateTableRequest,
+};
 
 use crate::error::{
     self, BumpTableIdSnafu, ExecuteSqlSnafu, ExecuteStatementSnafu, PlanStatementSnafu, Result,
     TableIdProviderNotFoundSnafu,
 };
+use crate::instance::copy_query_to::has_time_range;
 use crate::instance::Instance;
 use crate::metrics;
 use crate::sql::{SqlHandler, SqlRequest};
@@ -65,31 +71,11 @@ impl Instance {
             }
 
             QueryStatement::Sql(Statement::CreateTable(create_table)) => {
-                let table_id = self
-                    .table_id_provider
-                    .as_ref()
-                    .context(TableIdProviderNotFoundSnafu)?
-                    .next_table_id()
-                    .await
-                    .context(BumpTableIdSnafu)?;
-                let _engine_name = create_table.engine.clone();
-                // TODO(hl): Select table engine by engine_name
-
-                let name = create_table.name.clone();
-                let (catalog, schema, table) = table_idents_to_full_name(&name, query_ctx.clone())?;
-                let table_ref = TableReference::full(&catalog, &schema, &table);
-                let request =
-                    self.sql_handler
-                        .create_to_request(table_id, create_table, &table_ref)?;
-                let table_id = request.id;
-                info!("Creating table: {table_ref}, table id = {table_id}",);
-
-                self.sql_handler
-                    .execute(SqlRequest::CreateTable(request), query_ctx)
-                    .await
+                self.create_table_by_stmt(create_table, query_ctx).await
             }
-            QueryStatement::Sql(Statement::CreateExternalTable(_create_external_table)) => {
-                unimplemented!()
+            QueryStatement::Sql(Statement::CreateExternalTable(create_external_table)) => {
+                self.create_external_table_by_stmt(create_external_table, query_ctx)
+                    .await
             }
             QueryStatement::Sql(Statement::Alter(alter_table)) => {
                 let name = alter_table.table_name().clone();
@@ -122,14 +108,17 @@ impl Instance {
                     .execute(SqlRequest::ShowTables(show_tables), query_ctx)
                     .await
             }
-            QueryStatement::Sql(Statement::ShowCreateTable(_show_create_table)) => {
-                unimplemented!("SHOW CREATE TABLE is unimplemented yet");
+            QueryStatement::Sql(Statement::ShowCreateTable(show_create_table)) => {
+                self.sql_handler
+                    .execute(SqlRequest::ShowCreateTable(show_create_table), query_ctx)
+                    .await
             }
             QueryStatement::Sql(Statement::Copy(copy_table)) => {
                 let req = match copy_table {
                     CopyTable::To(copy_table) => {
                         let CopyTableArgument {
                             location,
+                            with,
                             connection,
                             pattern,
                             table_name,
@@ -142,6 +131,7 @@ impl Instance {
                             schema_name,
                             table_name,
                             location,
+                            with,
                             connection,
                             pattern,
                             direction: CopyDirection::Export,
@@ -150,6 +140,7 @@ impl Instance {
                     CopyTable::From(copy_table) => {
                         let CopyTableArgument {
                             location,
+                            with,
                             connection,
                             pattern,
                             table_name,
@@ -162,6 +153,7 @@ impl Instance {
                             schema_name,
                             table_name,
                             location,
+                            with,
                             connection,
                             pattern,
                             direction: CopyDirection::Import,
@@ -169,10 +161,112 @@ impl Instance {
                     }
                 };
 
+                // Exporting rows in a time range requires filtering by the query engine.
+                if matches!(req.direction, CopyDirection::Export) && has_time_range(&req.with) {
+                    return self.copy_table_range_to(req, query_ctx).await;
+                }
                 self.sql_handler
                     .execute(SqlRequest::CopyTable(req), query_ctx)
                     .await
             }
+            QueryStatement::Sql(Statement::CompactTable(compact_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(&compact_table.table_name, query_ctx.clone())?;
+                let req = CompactTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    region_number: compact_table.region_number,
+                    wait: Some(compact_table.wait),
+                };
+                self.sql_handler
+                    .execute(SqlRequest::CompactTable(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::TruncateTable(truncate_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(&truncate_table.table_name, query_ctx.clone())?;
+                let req = TruncateTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::TruncateTable(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::CreateSnapshot(create_snapshot)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(&create_snapshot.table_name, query_ctx.clone())?;
+                let req = CreateSnapshotRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    snapshot_name: create_snapshot.snapshot_name.value,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::CreateSnapshot(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::DropSnapshot(drop_snapshot)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(&drop_snapshot.table_name, query_ctx.clone())?;
+                let req = DropSnapshotRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    snapshot_name: drop_snapshot.snapshot_name.value,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::DropSnapshot(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::RestoreTable(restore_table)) => {
+                let (catalog_name, schema_name, table_name) =
+                    table_idents_to_full_name(&restore_table.table_name, query_ctx.clone())?;
+                let (source_catalog, source_schema, source_table_name) =
+                    table_idents_to_full_name(&restore_table.source_table_name, query_ctx.clone())?;
+                ensure!(
+                    catalog_name == source_catalog && schema_name == source_schema,
+                    error::InvalidSqlSnafu {
+                        msg: "The restored table must be in the schema of the source table",
+                    }
+                );
+                let req = RestoreTableRequest {
+                    catalog_name,
+                    schema_name,
+                    table_name,
+                    source_table_name,
+                    snapshot_name: restore_table.snapshot_name.value,
+                };
+                self.sql_handler
+                    .execute(SqlRequest::RestoreTable(req), query_ctx)
+                    .await
+            }
+            QueryStatement::Sql(Statement::CopyQueryTo(copy_query_to)) => {
+                self.copy_query_to(copy_query_to, query_ctx).await
+            }
+            QueryStatement::Sql(Statement::CopyDatabase(copy_database)) => {
+                let (argument, direction) = match copy_database {
+                    CopyDatabase::To(argument) => (argument, CopyDirection::Export),
+                    CopyDatabase::From(argument) => (argument, CopyDirection::Import),
+                };
+                let (catalog_name, schema_name) =
+                    database_idents_to_full_name(&argument.database_name, query_ctx.clone())?;
+                let req = CopyDatabaseRequest {
+                    catalog_name,
+                    schema_name,
+                    location: argument.location,
+                    with: argument.with,
+                    connection: argument.connection,
+                    direction,
+                };
+
+                match req.direction {
+                    CopyDirection::Export => self.copy_database_to(req).await,
+                    CopyDirection::Import => self.copy_database_from(req).await,
+                }
+            }
             QueryStatement::Sql(Statement::Query(_))
             | QueryStatement::Sql(Statement::Explain(_))
             | QueryStatement::Sql(Statement::Use(_))
@@ -183,6 +277,63 @@ impl Instance {
         }
     }
 
+    pub(crate) async fn create_table_by_stmt(
+        &self,
+        create_table: CreateTable,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let table_id = self
+            .table_id_provider
+            .as_ref()
+            .context(TableIdProviderNotFoundSnafu)?
+            .next_table_id()
+            .await
+            .context(BumpTableIdSnafu)?;
+        let _engine_name = create_table.engine.clone();
+        // TODO(hl): Select table engine by engine_name
+
+        let name = create_table.name.clone();
+        let (catalog, schema, table) = table_idents_to_full_name(&name, query_ctx.clone())?;
+        let table_ref = TableReference::full(&catalog, &schema, &table);
+        let request = self
+            .sql_handler
+            .create_to_request(table_id, create_table, &table_ref)?;
+        let table_id = request.id;
+        info!("Creating table: {table_ref}, table id = {table_id}",);
+
+        self.sql_handler
+            .execute(SqlRequest::CreateTable(request), query_ctx)
+            .await
+    }
+
+    pub(crate) async fn create_external_table_by_stmt(
+        &self,
+        create_external_table: CreateExternalTable,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let table_id = self
+            .table_id_provider
+            .as_ref()
+            .context(TableIdProviderNotFoundSnafu)?
+            .next_table_id()
+            .await
+            .context(BumpTableIdSnafu)?;
+
+        let name = create_external_table.name.clone();
+        let (catalog, schema, table) = table_idents_to_full_name(&name, query_ctx.clone())?;
+        let table_ref = TableReference::full(&catalog, &schema, &table);
+        let request = self.sql_handler.create_external_to_request(
+            table_id,
+            create_external_table,
+            &table_ref,
+        )?;
+        info!("Creating external table: {table_ref}, table id = {table_id}",);
+
+        self.sql_handler
+            .execute(SqlRequest::CreateTable(request), query_ctx)
+            .await
+    }
+
     pub async fn execute_promql(
         &self,
         promql: &PromQuery,
@@ -275,6 +426,23 @@ pub fn table_idents_to_full_name(
     }
 }
 
+/// Converts maybe fully-qualified database name (`<catalog>.<schema>`) to tuple.
+pub fn database_idents_to_full_name(
+    obj_name: &ObjectName,
+    query_ctx: QueryContextRef,
+) -> Result<(String, String)> {
+    match &obj_name.0[..] {
+        [schema] => Ok((query_ctx.current_catalog(), schema.value.clone())),
+        [catalog, schema] => Ok((catalog.value.clone(), schema.value.clone())),
+        _ => error::InvalidSqlSnafu {
+            msg: format!(
+                "expect database name to be <catalog>.<schema> or <schema>, actual: {obj_name}",
+            ),
+        }
+        .fail(),
+    }
+}
+
 #[async_trait]
 impl StatementHandler for Instance {
     async fn handle_statement(
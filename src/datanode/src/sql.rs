@@ -19,10 +19,10 @@ use common_error::prelude::BoxedError;
 use common_procedure::ProcedureManagerRef;
 use common_query::Output;
 use common_telemetry::error;
-use query::sql::{show_databases, show_tables};
+use query::sql::{show_create_table, show_databases, show_tables};
 use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
-use sql::statements::show::{ShowDatabases, ShowTables};
+use sql::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
 use table::engine::manager::TableEngineManagerRef;
 use table::engine::{TableEngineProcedureRef, TableEngineRef, TableReference};
 use table::requests::*;
@@ -35,12 +35,16 @@ use crate::error::{
 use crate::instance::sql::table_idents_to_full_name;
 
 mod alter;
+mod compact_table;
 mod copy_table_from;
-mod copy_table_to;
+pub(crate) mod copy_table_to;
 mod create;
 mod drop_table;
 mod flush_table;
 pub(crate) mod insert;
+mod restore_table;
+mod snapshot;
+mod truncate_table;
 
 #[derive(Debug)]
 pub enum SqlRequest {
@@ -49,8 +53,14 @@ pub enum SqlRequest {
     Alter(AlterTableRequest),
     DropTable(DropTableRequest),
     FlushTable(FlushTableRequest),
+    CompactTable(CompactTableRequest),
+    TruncateTable(TruncateTableRequest),
+    CreateSnapshot(CreateSnapshotRequest),
+    DropSnapshot(DropSnapshotRequest),
+    RestoreTable(RestoreTableRequest),
     ShowDatabases(ShowDatabases),
     ShowTables(ShowTables),
+    ShowCreateTable(ShowCreateTable),
     CopyTable(CopyTableRequest),
 }
 
@@ -99,7 +109,19 @@ impl SqlHandler {
                 show_tables(req, self.catalog_manager.clone(), query_ctx.clone())
                     .context(ExecuteSqlSnafu)
             }
+            SqlRequest::ShowCreateTable(req) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&req.table_name, query_ctx.clone())?;
+                let table_ref = TableReference::full(&catalog, &schema, &table);
+                let table = self.get_table(&table_ref).await?;
+                show_create_table(table, None).context(ExecuteSqlSnafu)
+            }
             SqlRequest::FlushTable(req) => self.flush_table(req).await,
+            SqlRequest::CompactTable(req) => self.compact_table(req).await,
+            SqlRequest::TruncateTable(req) => self.truncate_table(req).await,
+            SqlRequest::CreateSnapshot(req) => self.create_snapshot(req).await,
+            SqlRequest::DropSnapshot(req) => self.drop_snapshot(req).await,
+            SqlRequest::RestoreTable(req) => self.restore_table(req).await,
         };
         if let Err(e) = &result {
             error!(e; "{query_ctx}");
@@ -18,9 +18,10 @@ use snafu::prelude::*;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
 use sql::statements::column_def_to_schema;
 use table::engine::{EngineContext, TableReference};
-use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest};
+use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, ModifyColumnRequest};
 
 use crate::error::{self, Result};
+use crate::sql::create::stmt_options_to_map;
 use crate::sql::SqlHandler;
 
 impl SqlHandler {
@@ -94,9 +95,21 @@ impl SqlHandler {
             AlterTableOperation::DropColumn { name } => AlterKind::DropColumns {
                 names: vec![name.value.clone()],
             },
+            AlterTableOperation::ModifyColumn { column_def } => AlterKind::ModifyColumns {
+                columns: vec![ModifyColumnRequest {
+                    column_schema: column_def_to_schema(column_def, false)
+                        .context(error::ParseSqlSnafu)?,
+                }],
+            },
             AlterTableOperation::RenameTable { new_table_name } => AlterKind::RenameTable {
                 new_table_name: new_table_name.clone(),
             },
+            AlterTableOperation::SetTableOptions { options } => AlterKind::SetTableOptions {
+                options: stmt_options_to_map(options),
+            },
+            AlterTableOperation::UnsetTableOptions { keys } => {
+                AlterKind::UnsetTableOptions { keys: keys.clone() }
+            }
         };
         Ok(AlterTableRequest {
             catalog_name: table_ref.catalog.to_string(),
@@ -110,6 +123,7 @@ impl SqlHandler {
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
+    use std::collections::HashMap;
 
     use datatypes::prelude::ConcreteDataType;
     use sql::dialect::GenericDialect;
@@ -158,6 +172,68 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_alter_to_request_with_modifying_column() {
+        let handler = create_mock_sql_handler().await;
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY COLUMN cpu DOUBLE DEFAULT 0;");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        assert_eq!(req.table_name, "my_metric_1");
+
+        let alter_kind = req.alter_kind;
+        assert_matches!(alter_kind, AlterKind::ModifyColumns { .. });
+        match alter_kind {
+            AlterKind::ModifyColumns { columns } => {
+                let column = &columns[0].column_schema;
+
+                assert_eq!(column.name, "cpu");
+                assert!(column.is_nullable());
+                assert_eq!(column.data_type, ConcreteDataType::float64_datatype());
+                assert!(column.default_constraint().is_some());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_alter_to_request_with_table_options() {
+        let handler = create_mock_sql_handler().await;
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 SET ('ttl'='7d');");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::SetTableOptions { options } => {
+                assert_eq!(
+                    HashMap::from([("ttl".to_string(), "7d".to_string())]),
+                    options
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 UNSET ('ttl');");
+        let req = handler
+            .alter_to_request(
+                alter_table,
+                TableReference::full("greptime", "public", "my_metric_1"),
+            )
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::UnsetTableOptions { keys } => {
+                assert_eq!(vec!["ttl".to_string()], keys);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn test_alter_to_request_with_renaming_table() {
         let handler = create_mock_sql_handler().await;
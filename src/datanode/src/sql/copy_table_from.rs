@@ -14,21 +14,21 @@
 
 use std::collections::HashMap;
 
-use async_compat::CompatExt;
+use common_datasource::file_format::Format;
 use common_datasource::lister::{Lister, Source};
 use common_datasource::object_store::{build_backend, parse_url};
 use common_datasource::util::find_dir_and_filename;
 use common_query::Output;
 use common_recordbatch::error::DataTypesSnafu;
-use datafusion::parquet::arrow::ParquetRecordBatchStreamBuilder;
+use datatypes::arrow::compute::{cast_with_options, CastOptions};
 use datatypes::arrow::record_batch::RecordBatch;
+use datatypes::schema::SchemaRef;
 use datatypes::vectors::{Helper, VectorRef};
-use futures_util::TryStreamExt;
+use futures::TryStreamExt;
 use regex::Regex;
-use snafu::{ensure, ResultExt};
+use snafu::ResultExt;
 use table::engine::TableReference;
 use table::requests::{CopyTableRequest, InsertRequest};
-use tokio::io::BufReader;
 
 use crate::error::{self, Result};
 use crate::sql::SqlHandler;
@@ -42,6 +42,8 @@ impl SqlHandler {
         };
         let table = self.get_table(&table_ref).await?;
 
+        let format = Format::try_from(&req.with).context(error::ParseFileFormatSnafu)?;
+
         let (_schema, _host, path) = parse_url(&req.location).context(error::ParseUrlSnafu)?;
 
         let object_store =
@@ -65,81 +67,85 @@ impl SqlHandler {
 
         let entries = lister.list().await.context(error::ListObjectsSnafu)?;
 
-        let mut buf: Vec<RecordBatch> = Vec::new();
-
+        let table_schema = table.schema();
+        let mut rows_inserted = 0;
         for entry in entries.iter() {
             let path = entry.path();
-            let reader = object_store
-                .reader(path)
+            // Skips sub directories.
+            if path.ends_with('/') {
+                continue;
+            }
+
+            // Only columns in the table schema are read, and the data types are
+            // inferred from the file, so we need to cast them to the table's types.
+            let mut stream = format
+                .read(
+                    &object_store,
+                    path,
+                    table_schema.arrow_schema().clone(),
+                    None,
+                )
                 .await
-                .context(error::ReadObjectSnafu { path })?;
+                .context(error::ReadFileSnafu { path })?;
 
-            let buf_reader = BufReader::new(reader.compat());
-
-            let builder = ParquetRecordBatchStreamBuilder::new(buf_reader)
-                .await
-                .context(error::ReadParquetSnafu)?;
-
-            ensure!(
-                builder.schema() == table.schema().arrow_schema(),
-                error::InvalidSchemaSnafu {
-                    table_schema: table.schema().arrow_schema().to_string(),
-                    file_schema: (*(builder.schema())).to_string()
+            // Inserts batches one by one, so the file is never held in memory.
+            while let Some(batch) = stream.try_next().await.context(error::PollStreamSnafu)? {
+                if batch.num_rows() == 0 {
+                    continue;
                 }
-            );
-
-            let stream = builder
-                .build()
-                .context(error::BuildParquetRecordBatchStreamSnafu)?;
-
-            let chunk = stream
-                .try_collect::<Vec<_>>()
-                .await
-                .context(error::ReadParquetSnafu)?;
-
-            buf.extend(chunk.into_iter());
-        }
-
-        let fields = table
-            .schema()
-            .arrow_schema()
-            .fields()
-            .iter()
-            .map(|f| f.name().to_string())
-            .collect::<Vec<_>>();
-
-        // Vec<Columns>
-        let column_chunks = buf
-            .into_iter()
-            .map(|c| Helper::try_into_vectors(c.columns()).context(DataTypesSnafu))
-            .collect::<Vec<_>>();
-
-        let mut futs = Vec::with_capacity(column_chunks.len());
-
-        for column_chunk in column_chunks.into_iter() {
-            let column_chunk = column_chunk.context(error::ParseDataTypesSnafu)?;
-            let columns_values = fields
-                .iter()
-                .cloned()
-                .zip(column_chunk.into_iter())
-                .collect::<HashMap<String, VectorRef>>();
-
-            futs.push(table.insert(InsertRequest {
-                catalog_name: req.catalog_name.to_string(),
-                schema_name: req.schema_name.to_string(),
-                table_name: req.table_name.to_string(),
-                columns_values,
-                //TODO: support multi-regions
-                region_number: 0,
-            }))
+                let columns_values = coerce_batch(path, &batch, &table_schema)?;
+
+                rows_inserted += table
+                    .insert(InsertRequest {
+                        catalog_name: req.catalog_name.to_string(),
+                        schema_name: req.schema_name.to_string(),
+                        table_name: req.table_name.to_string(),
+                        columns_values,
+                        //TODO: support multi-regions
+                        region_number: 0,
+                    })
+                    .await
+                    .context(error::InsertSnafu {
+                        table_name: req.table_name.to_string(),
+                    })?;
+            }
         }
 
-        let result = futures::future::try_join_all(futs)
-            .await
-            .context(error::InsertSnafu {
-                table_name: req.table_name.to_string(),
-            })?;
-
-        Ok(Output::AffectedRows(result.iter().sum()))
+        Ok(Output::AffectedRows(rows_inserted))
     }
 }
+
+/// Casts columns in `batch` to the types of table columns with the same names. Columns
+/// absent in the file are left to the table, which fills them with default values.
+fn coerce_batch(
+    path: &str,
+    batch: &RecordBatch,
+    table_schema: &SchemaRef,
+) -> Result<HashMap<String, VectorRef>> {
+    // Returns an error instead of null if a value can't be casted.
+    let cast_options = CastOptions { safe: false };
+    let arrow_schema = table_schema.arrow_schema();
+
+    batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, array)| {
+            let column = field.name();
+            let table_field = arrow_schema
+                .field_with_name(column)
+                .context(error::CoerceColumnSnafu { column, path })?;
+            let array = if array.data_type() == table_field.data_type() {
+                array.clone()
+            } else {
+                cast_with_options(array, table_field.data_type(), &cast_options)
+                    .context(error::CoerceColumnSnafu { column, path })?
+            };
+            let vector = Helper::try_into_vector(array)
+                .context(DataTypesSnafu)
+                .context(error::ParseDataTypesSnafu)?;
+            Ok((column.clone(), vector))
+        })
+        .collect()
+}
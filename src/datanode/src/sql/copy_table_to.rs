@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use common_datasource;
+use common_datasource::file_format::{Format, TextWriter};
 use common_datasource::object_store::{build_backend, parse_url};
 use common_query::physical_plan::SessionContext;
 use common_query::Output;
+use common_recordbatch::SendableRecordBatchStream;
+use futures::StreamExt;
 use snafu::ResultExt;
 use storage::sst::SstInfo;
 use storage::{ParquetWriter, Source};
@@ -45,19 +50,48 @@ impl SqlHandler {
             .execute(0, SessionContext::default().task_ctx())
             .context(error::TableScanExecSnafu)?;
 
-        let (_schema, _host, path) = parse_url(&req.location).context(error::ParseUrlSnafu)?;
-        let object_store =
-            build_backend(&req.location, req.connection).context(error::BuildBackendSnafu)?;
+        let rows_copied = copy_stream_to(stream, &req.location, &req.with, req.connection).await?;
+        Ok(Output::AffectedRows(rows_copied))
+    }
+}
 
-        let writer = ParquetWriter::new(&path, Source::Stream(stream), object_store);
+/// Writes all record batches in `stream` to the file at `location` in the format
+/// specified by `with`, returns the number of rows written.
+pub(crate) async fn copy_stream_to(
+    mut stream: SendableRecordBatchStream,
+    location: &str,
+    with: &HashMap<String, String>,
+    connection: HashMap<String, String>,
+) -> Result<usize> {
+    let format = Format::try_from(with).context(error::ParseFileFormatSnafu)?;
+    let (_schema, _host, path) = parse_url(location).context(error::ParseUrlSnafu)?;
+    let object_store = build_backend(location, connection).context(error::BuildBackendSnafu)?;
 
-        let rows_copied = writer
-            .write_sst(&storage::sst::WriteOptions::default())
+    if !matches!(format, Format::Parquet(_)) {
+        let mut writer = TextWriter::try_new(&object_store, &path, format)
             .await
-            .context(WriteParquetSnafu)?
-            .map(|SstInfo { num_rows, .. }| num_rows)
-            .unwrap_or(0);
-
-        Ok(Output::AffectedRows(rows_copied))
+            .context(error::WriteFileSnafu { path: &path })?;
+        while let Some(batch) = stream.next().await {
+            let batch = batch.context(error::CollectRecordBatchesSnafu)?;
+            writer
+                .write(batch.df_record_batch())
+                .await
+                .context(error::WriteFileSnafu { path: &path })?;
+        }
+        return writer
+            .close()
+            .await
+            .context(error::WriteFileSnafu { path: &path });
     }
+
+    let writer = ParquetWriter::new(&path, Source::Stream(stream), object_store);
+
+    let rows_copied = writer
+        .write_sst(&storage::sst::WriteOptions::default())
+        .await
+        .context(WriteParquetSnafu)?
+        .map(|SstInfo { num_rows, .. }| num_rows)
+        .unwrap_or(0);
+
+    Ok(rows_copied)
 }
@@ -15,15 +15,16 @@
 use std::collections::HashMap;
 
 use catalog::{RegisterSchemaRequest, RegisterTableRequest};
+use common_catalog::consts::{FILE_ENGINE, MITO_ENGINE};
 use common_procedure::{watcher, ProcedureManagerRef, ProcedureWithId};
 use common_query::Output;
 use common_telemetry::tracing::{error, info};
 use datatypes::schema::RawSchema;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use sql::ast::{ColumnOption, SqlOption, TableConstraint, Value};
+use sql::ast::{ColumnDef, ColumnOption, SqlOption, TableConstraint, Value};
 use sql::statements::column_def_to_schema;
-use sql::statements::create::CreateTable;
+use sql::statements::create::{CreateExternalTable, CreateTable};
 use store_api::storage::consts::TIME_INDEX_NAME;
 use table::engine::{EngineContext, TableReference};
 use table::metadata::TableId;
@@ -73,8 +74,11 @@ impl SqlHandler {
     }
 
     pub(crate) async fn create_table(&self, req: CreateTableRequest) -> Result<Output> {
-        if let Some(procedure_manager) = &self.procedure_manager {
-            return self.create_table_by_procedure(procedure_manager, req).await;
+        // Only mito engine supports creating table by procedure.
+        if req.engine == MITO_ENGINE {
+            if let Some(procedure_manager) = &self.procedure_manager {
+                return self.create_table_by_procedure(procedure_manager, req).await;
+            }
         }
 
         let ctx = EngineContext {};
@@ -180,131 +184,185 @@ impl SqlHandler {
         stmt: CreateTable,
         table_ref: &TableReference,
     ) -> Result<CreateTableRequest> {
-        let mut ts_index = usize::MAX;
-        let mut primary_keys = vec![];
+        let (primary_keys, ts_index) = find_key_indices(&stmt.columns, stmt.constraints)?;
+        let ts_index = ts_index.context(error::MissingTimestampColumnSnafu)?;
 
-        let col_map = stmt
+        let columns_schemas: Vec<_> = stmt
             .columns
             .iter()
-            .map(|e| e.name.value.clone())
             .enumerate()
-            .map(|(k, v)| (v, k))
-            .collect::<HashMap<_, _>>();
-
-        let pk_map = stmt
-            .columns
-            .iter()
-            .filter(|col| {
-                col.options.iter().any(|options| match options.option {
-                    ColumnOption::Unique { is_primary } => is_primary,
-                    _ => false,
-                })
+            .map(|(index, column)| {
+                column_def_to_schema(column, index == ts_index).context(error::ParseSqlSnafu)
             })
-            .map(|col| col.name.value.clone())
-            .collect::<Vec<_>>();
-
-        ensure!(
-            pk_map.len() < 2,
-            IllegalPrimaryKeysDefSnafu {
-                msg: "not allowed to inline multiple primary keys in columns options"
-            }
-        );
-
-        if let Some(pk) = pk_map.first() {
-            // # Safety: Both pk_map and col_map are collected from stmt.columns
-            primary_keys.push(*col_map.get(pk).unwrap());
-        }
-
-        for c in stmt.constraints {
-            match c {
-                TableConstraint::Unique {
-                    name,
-                    columns,
-                    is_primary,
-                } => {
-                    if let Some(name) = name {
-                        if name.value == TIME_INDEX_NAME {
-                            ts_index = *col_map.get(&columns[0].value).context(
-                                KeyColumnNotFoundSnafu {
-                                    name: columns[0].value.to_string(),
-                                },
-                            )?;
-                        } else {
-                            return error::InvalidSqlSnafu {
-                                msg: format!("Cannot recognize named UNIQUE constraint: {name}"),
-                            }
-                            .fail();
-                        }
-                    } else if is_primary {
-                        if !primary_keys.is_empty() {
-                            return IllegalPrimaryKeysDefSnafu {
-                                msg: "found definitions of primary keys in multiple places",
-                            }
-                            .fail();
-                        }
-                        for col in columns {
-                            primary_keys.push(*col_map.get(&col.value).context(
-                                KeyColumnNotFoundSnafu {
-                                    name: col.value.to_string(),
-                                },
-                            )?);
-                        }
-                    } else {
-                        return error::InvalidSqlSnafu {
-                            msg: format!(
-                                "Unrecognized non-primary unnamed UNIQUE constraint: {name:?}",
-                            ),
-                        }
-                        .fail();
-                    }
-                }
-                _ => {
-                    return ConstraintNotSupportedSnafu {
-                        constraint: format!("{c:?}"),
-                    }
-                    .fail();
-                }
-            }
-        }
+            .collect::<Result<Vec<_>>>()?;
 
-        ensure!(
-            !primary_keys.iter().any(|index| *index == ts_index),
-            IllegalPrimaryKeysDefSnafu {
-                msg: "time index column can't be included in primary key"
-            }
-        );
+        let table_options = stmt_options_to_table_options(&stmt.options)?;
+        let schema = RawSchema::new(columns_schemas);
+        let request = CreateTableRequest {
+            id: table_id,
+            catalog_name: table_ref.catalog.to_string(),
+            schema_name: table_ref.schema.to_string(),
+            table_name: table_ref.table.to_string(),
+            desc: None,
+            schema,
+            region_numbers: vec![0],
+            primary_key_indices: primary_keys,
+            create_if_not_exists: stmt.if_not_exists,
+            table_options,
+            engine: stmt.engine,
+        };
+        Ok(request)
+    }
 
-        ensure!(ts_index != usize::MAX, error::MissingTimestampColumnSnafu);
+    /// Converts [CreateExternalTable] to [SqlRequest::CreateTable] of the file engine.
+    ///
+    /// Columns are optional for external tables, the file engine infers the schema from
+    /// files if no column is defined. The time index is optional as well.
+    pub(crate) fn create_external_to_request(
+        &self,
+        table_id: TableId,
+        stmt: CreateExternalTable,
+        table_ref: &TableReference,
+    ) -> Result<CreateTableRequest> {
+        let (primary_keys, ts_index) = find_key_indices(&stmt.columns, stmt.constraints)?;
 
         let columns_schemas: Vec<_> = stmt
             .columns
             .iter()
             .enumerate()
             .map(|(index, column)| {
-                column_def_to_schema(column, index == ts_index).context(error::ParseSqlSnafu)
+                column_def_to_schema(column, Some(index) == ts_index).context(error::ParseSqlSnafu)
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let table_options = stmt_options_to_table_options(&stmt.options)?;
-        let schema = RawSchema::new(columns_schemas);
+        let table_options =
+            TableOptions::try_from(&stmt.options).context(UnrecognizedTableOptionSnafu)?;
         let request = CreateTableRequest {
             id: table_id,
             catalog_name: table_ref.catalog.to_string(),
             schema_name: table_ref.schema.to_string(),
             table_name: table_ref.table.to_string(),
             desc: None,
-            schema,
+            schema: RawSchema::new(columns_schemas),
             region_numbers: vec![0],
             primary_key_indices: primary_keys,
-            create_if_not_exists: stmt.if_not_exists,
+            create_if_not_exists: false,
             table_options,
-            engine: stmt.engine,
+            engine: FILE_ENGINE.to_string(),
         };
         Ok(request)
     }
 }
 
+/// Finds indices of the primary key columns and the time index column in `columns`
+/// from column options and table constraints.
+fn find_key_indices(
+    columns: &[ColumnDef],
+    constraints: Vec<TableConstraint>,
+) -> Result<(Vec<usize>, Option<usize>)> {
+    let mut ts_index = None;
+    let mut primary_keys = vec![];
+
+    let col_map = columns
+        .iter()
+        .map(|e| e.name.value.clone())
+        .enumerate()
+        .map(|(k, v)| (v, k))
+        .collect::<HashMap<_, _>>();
+
+    let pk_map = columns
+        .iter()
+        .filter(|col| {
+            col.options.iter().any(|options| match options.option {
+                ColumnOption::Unique { is_primary } => is_primary,
+                _ => false,
+            })
+        })
+        .map(|col| col.name.value.clone())
+        .collect::<Vec<_>>();
+
+    ensure!(
+        pk_map.len() < 2,
+        IllegalPrimaryKeysDefSnafu {
+            msg: "not allowed to inline multiple primary keys in columns options"
+        }
+    );
+
+    if let Some(pk) = pk_map.first() {
+        // # Safety: Both pk_map and col_map are collected from columns
+        primary_keys.push(*col_map.get(pk).unwrap());
+    }
+
+    for c in constraints {
+        match c {
+            TableConstraint::Unique {
+                name,
+                columns,
+                is_primary,
+            } => {
+                if let Some(name) = name {
+                    if name.value == TIME_INDEX_NAME {
+                        ts_index = Some(*col_map.get(&columns[0].value).context(
+                            KeyColumnNotFoundSnafu {
+                                name: columns[0].value.to_string(),
+                            },
+                        )?);
+                    } else {
+                        return error::InvalidSqlSnafu {
+                            msg: format!("Cannot recognize named UNIQUE constraint: {name}"),
+                        }
+                        .fail();
+                    }
+                } else if is_primary {
+                    if !primary_keys.is_empty() {
+                        return IllegalPrimaryKeysDefSnafu {
+                            msg: "found definitions of primary keys in multiple places",
+                        }
+                        .fail();
+                    }
+                    for col in columns {
+                        primary_keys.push(*col_map.get(&col.value).context(
+                            KeyColumnNotFoundSnafu {
+                                name: col.value.to_string(),
+                            },
+                        )?);
+                    }
+                } else {
+                    return error::InvalidSqlSnafu {
+                        msg: format!(
+                            "Unrecognized non-primary unnamed UNIQUE constraint: {name:?}",
+                        ),
+                    }
+                    .fail();
+                }
+            }
+            _ => {
+                return ConstraintNotSupportedSnafu {
+                    constraint: format!("{c:?}"),
+                }
+                .fail();
+            }
+        }
+    }
+
+    ensure!(
+        !primary_keys.iter().any(|index| Some(*index) == ts_index),
+        IllegalPrimaryKeysDefSnafu {
+            msg: "time index column can't be included in primary key"
+        }
+    );
+
+    Ok((primary_keys, ts_index))
+}
+
 fn stmt_options_to_table_options(opts: &[SqlOption]) -> error::Result<TableOptions> {
+    let map = stmt_options_to_map(opts);
+    let options = TableOptions::try_from(&map).context(UnrecognizedTableOptionSnafu)?;
+    Ok(options)
+}
+
+/// Converts options in the statement to a map, quotes of string values are trimmed.
+pub(crate) fn stmt_options_to_map(opts: &[SqlOption]) -> HashMap<String, String> {
     let mut map = HashMap::with_capacity(opts.len());
     for SqlOption { name, value } in opts {
         let value_str = match value {
@@ -314,8 +372,7 @@ fn stmt_options_to_table_options(opts: &[SqlOption]) -> error::Result<TableOptio
         };
         map.insert(name.value.clone(), value_str);
     }
-    let options = TableOptions::try_from(&map).context(UnrecognizedTableOptionSnafu)?;
-    Ok(options)
+    map
 }
 
 #[cfg(test)]
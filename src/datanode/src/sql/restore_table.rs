@@ -0,0 +1,87 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use catalog::error::{SchemaNotFoundSnafu, TableExistsSnafu};
+use common_error::prelude::BoxedError;
+use common_query::Output;
+use common_telemetry::info;
+use snafu::{OptionExt, ResultExt};
+use table::engine::{EngineContext, TableReference};
+use table::requests::RestoreTableRequest;
+
+use crate::error::{self, Result};
+use crate::sql::SqlHandler;
+
+impl SqlHandler {
+    /// Restores a read-only table from the snapshot of another table.
+    ///
+    /// The restored table shares data with the source table, so it's only registered in memory
+    /// and won't be reopened after restarting.
+    pub(crate) async fn restore_table(&self, req: RestoreTableRequest) -> Result<Output> {
+        let table_name =
+            TableReference::full(&req.catalog_name, &req.schema_name, &req.table_name).to_string();
+        let source_ref =
+            TableReference::full(&req.catalog_name, &req.schema_name, &req.source_table_name);
+        let source_name = source_ref.to_string();
+        let source = self.get_table(&source_ref).await?;
+        let engine = self.table_engine(source)?;
+
+        let schema = self
+            .catalog_manager
+            .schema(&req.catalog_name, &req.schema_name)
+            .context(error::CatalogSnafu)?
+            .context(SchemaNotFoundSnafu {
+                catalog: &req.catalog_name,
+                schema: &req.schema_name,
+            })
+            .map_err(BoxedError::new)
+            .context(error::RestoreTableSnafu {
+                table_name: &table_name,
+            })?;
+        if schema
+            .table_exist(&req.table_name)
+            .context(error::CatalogSnafu)?
+        {
+            return TableExistsSnafu { table: &table_name }
+                .fail()
+                .map_err(BoxedError::new)
+                .context(error::RestoreTableSnafu {
+                    table_name: &table_name,
+                });
+        }
+
+        let new_table_name = req.table_name.clone();
+        let snapshot_name = req.snapshot_name.clone();
+        let table = engine
+            .restore_table(&EngineContext::default(), req)
+            .await
+            .map_err(BoxedError::new)
+            .context(error::RestoreTableSnafu {
+                table_name: &table_name,
+            })?;
+        schema
+            .register_table(new_table_name, table)
+            .map_err(BoxedError::new)
+            .context(error::RestoreTableSnafu {
+                table_name: &table_name,
+            })?;
+
+        info!(
+            "Successfully restored table {} from snapshot {} of table {}",
+            table_name, snapshot_name, source_name
+        );
+
+        Ok(Output::AffectedRows(0))
+    }
+}
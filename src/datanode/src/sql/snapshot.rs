@@ -0,0 +1,60 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_query::Output;
+use common_telemetry::logging::info;
+use snafu::ResultExt;
+use table::engine::TableReference;
+use table::requests::{CreateSnapshotRequest, DropSnapshotRequest};
+
+use crate::error::{self, Result};
+use crate::sql::SqlHandler;
+
+impl SqlHandler {
+    pub(crate) async fn create_snapshot(&self, req: CreateSnapshotRequest) -> Result<Output> {
+        let table_ref = TableReference::full(&req.catalog_name, &req.schema_name, &req.table_name);
+        let table = self.get_table(&table_ref).await?;
+
+        info!(
+            "Creating snapshot {} of table {}",
+            req.snapshot_name, table_ref
+        );
+        table
+            .create_snapshot(&req.snapshot_name)
+            .await
+            .context(error::CreateSnapshotSnafu {
+                table_name: table_ref.to_string(),
+                snapshot: &req.snapshot_name,
+            })?;
+        Ok(Output::AffectedRows(0))
+    }
+
+    pub(crate) async fn drop_snapshot(&self, req: DropSnapshotRequest) -> Result<Output> {
+        let table_ref = TableReference::full(&req.catalog_name, &req.schema_name, &req.table_name);
+        let table = self.get_table(&table_ref).await?;
+
+        info!(
+            "Dropping snapshot {} of table {}",
+            req.snapshot_name, table_ref
+        );
+        table
+            .drop_snapshot(&req.snapshot_name)
+            .await
+            .context(error::DropSnapshotSnafu {
+                table_name: table_ref.to_string(),
+                snapshot: &req.snapshot_name,
+            })?;
+        Ok(Output::AffectedRows(0))
+    }
+}
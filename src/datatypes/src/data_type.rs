@@ -143,6 +143,42 @@ impl ConcreteDataType {
             _ => None,
         }
     }
+
+    /// Returns true if values of this type could be casted to `to` without loss, e.g.
+    /// from `Int32` to `Int64` or from `Float32` to `Float64`.
+    pub fn can_widen_to(&self, to: &ConcreteDataType) -> bool {
+        use ConcreteDataType::*;
+
+        if self == to {
+            return true;
+        }
+
+        matches!(
+            (self, to),
+            (
+                Int8(_),
+                Int16(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+            ) | (Int16(_), Int32(_) | Int64(_) | Float32(_) | Float64(_))
+                | (Int32(_), Int64(_) | Float64(_))
+                | (
+                    UInt8(_),
+                    UInt16(_)
+                        | UInt32(_)
+                        | UInt64(_)
+                        | Int16(_)
+                        | Int32(_)
+                        | Int64(_)
+                        | Float32(_)
+                        | Float64(_)
+                )
+                | (
+                    UInt16(_),
+                    UInt32(_) | UInt64(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+                )
+                | (UInt32(_), UInt64(_) | Int64(_) | Float64(_))
+                | (Float32(_), Float64(_))
+        )
+    }
 }
 
 impl TryFrom<&ArrowDataType> for ConcreteDataType {
@@ -422,6 +458,49 @@ mod tests {
         assert!(!ConcreteDataType::uint64_datatype().is_timestamp_compatible());
     }
 
+    #[test]
+    fn test_can_widen_to() {
+        let widen = |from: ConcreteDataType, to: ConcreteDataType| from.can_widen_to(&to);
+
+        assert!(widen(
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::int64_datatype()
+        ));
+        assert!(widen(
+            ConcreteDataType::float32_datatype(),
+            ConcreteDataType::float64_datatype()
+        ));
+        assert!(widen(
+            ConcreteDataType::uint32_datatype(),
+            ConcreteDataType::int64_datatype()
+        ));
+        assert!(widen(
+            ConcreteDataType::int16_datatype(),
+            ConcreteDataType::float32_datatype()
+        ));
+        assert!(widen(
+            ConcreteDataType::string_datatype(),
+            ConcreteDataType::string_datatype()
+        ));
+
+        assert!(!widen(
+            ConcreteDataType::int64_datatype(),
+            ConcreteDataType::int32_datatype()
+        ));
+        assert!(!widen(
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::uint64_datatype()
+        ));
+        assert!(!widen(
+            ConcreteDataType::int64_datatype(),
+            ConcreteDataType::float64_datatype()
+        ));
+        assert!(!widen(
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::string_datatype()
+        ));
+    }
+
     #[test]
     fn test_is_null() {
         assert!(ConcreteDataType::null_datatype().is_null());
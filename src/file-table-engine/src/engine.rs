@@ -0,0 +1,330 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+pub use common_catalog::consts::FILE_ENGINE;
+use common_catalog::format_full_table_name;
+use common_error::ext::BoxedError;
+use common_telemetry::logging;
+use datatypes::schema::Schema;
+use object_store::ObjectStore;
+use snafu::ResultExt;
+use table::engine::{table_dir, EngineContext, TableEngine, TableReference};
+use table::error::{self as table_error, Result as TableResult};
+use table::metadata::{RawTableInfo, TableInfo, TableInfoBuilder, TableMetaBuilder, TableType};
+use table::requests::{
+    AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest, RestoreTableRequest,
+    TruncateTableRequest,
+};
+use table::TableRef;
+use tokio::sync::Mutex;
+
+use crate::error::{self, ConvertRawSnafu, InvalidRawSchemaSnafu, Result, TableExistsSnafu};
+use crate::manifest::{self, FileTableMetadata};
+use crate::table::{FileSource, FileTable};
+
+const INIT_TABLE_VERSION: u64 = 0;
+
+/// [TableEngine] for read-only tables backed by files (csv, json or parquet) in object stores.
+///
+/// The table metadata is persisted in the engine's object store, while the files are
+/// read from the location given by table options.
+#[derive(Clone)]
+pub struct FileTableEngine {
+    inner: Arc<FileTableEngineInner>,
+}
+
+impl FileTableEngine {
+    pub fn new(object_store: ObjectStore) -> Self {
+        Self {
+            inner: Arc::new(FileTableEngineInner::new(object_store)),
+        }
+    }
+}
+
+#[async_trait]
+impl TableEngine for FileTableEngine {
+    fn name(&self) -> &str {
+        FILE_ENGINE
+    }
+
+    async fn create_table(
+        &self,
+        _ctx: &EngineContext,
+        request: CreateTableRequest,
+    ) -> TableResult<TableRef> {
+        self.inner
+            .create_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn open_table(
+        &self,
+        _ctx: &EngineContext,
+        request: OpenTableRequest,
+    ) -> TableResult<Option<TableRef>> {
+        self.inner
+            .open_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn alter_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: AlterTableRequest,
+    ) -> TableResult<TableRef> {
+        table_error::UnsupportedSnafu {
+            operation: "ALTER TABLE on file table",
+        }
+        .fail()
+    }
+
+    fn get_table(
+        &self,
+        _ctx: &EngineContext,
+        table_ref: &TableReference,
+    ) -> TableResult<Option<TableRef>> {
+        Ok(self.inner.get_table(table_ref))
+    }
+
+    fn table_exists(&self, _ctx: &EngineContext, table_ref: &TableReference) -> bool {
+        self.inner.get_table(table_ref).is_some()
+    }
+
+    async fn drop_table(
+        &self,
+        _ctx: &EngineContext,
+        request: DropTableRequest,
+    ) -> TableResult<bool> {
+        self.inner
+            .drop_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
+    async fn truncate_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: TruncateTableRequest,
+    ) -> TableResult<bool> {
+        table_error::UnsupportedSnafu {
+            operation: "TRUNCATE TABLE on file table",
+        }
+        .fail()
+    }
+
+    async fn restore_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: RestoreTableRequest,
+    ) -> TableResult<TableRef> {
+        table_error::UnsupportedSnafu {
+            operation: "RESTORE TABLE on file table",
+        }
+        .fail()
+    }
+
+    async fn close(&self) -> TableResult<()> {
+        Ok(())
+    }
+}
+
+struct FileTableEngineInner {
+    /// All tables opened by the engine. Map key is formatted [TableReference].
+    ///
+    /// Writing to `tables` should also hold the `table_mutex`.
+    tables: RwLock<HashMap<String, Arc<FileTable>>>,
+    /// Object store to persist table manifests.
+    object_store: ObjectStore,
+    /// Table mutex is used to protect the operations such as creating/opening/dropping
+    /// a table, to avoid things like opening the same table simultaneously.
+    table_mutex: Mutex<()>,
+}
+
+impl FileTableEngineInner {
+    fn new(object_store: ObjectStore) -> Self {
+        Self {
+            tables: RwLock::new(HashMap::default()),
+            object_store,
+            table_mutex: Mutex::new(()),
+        }
+    }
+
+    async fn create_table(&self, request: CreateTableRequest) -> Result<TableRef> {
+        let catalog_name = &request.catalog_name;
+        let schema_name = &request.schema_name;
+        let table_name = &request.table_name;
+        let table_ref = TableReference {
+            catalog: catalog_name,
+            schema: schema_name,
+            table: table_name,
+        };
+
+        let _lock = self.table_mutex.lock().await;
+        if let Some(table) = self.get_table(&table_ref) {
+            return if request.create_if_not_exists {
+                Ok(table)
+            } else {
+                TableExistsSnafu {
+                    table_name: format_full_table_name(catalog_name, schema_name, table_name),
+                }
+                .fail()
+            };
+        }
+
+        let source = FileSource::try_new(table_name, &request.table_options.extra_options)?;
+        // Infers the schema from files if no column is specified.
+        let table_schema = if request.schema.column_schemas.is_empty() {
+            Arc::new(source.infer_schema().await?)
+        } else {
+            Arc::new(Schema::try_from(request.schema.clone()).context(InvalidRawSchemaSnafu)?)
+        };
+
+        let table_id = request.id;
+        let next_column_id = table_schema.num_columns() as u32;
+        let table_meta = TableMetaBuilder::default()
+            .schema(table_schema)
+            .engine(FILE_ENGINE)
+            .next_column_id(next_column_id)
+            .primary_key_indices(request.primary_key_indices.clone())
+            .options(request.table_options.clone())
+            .region_numbers(request.region_numbers.clone())
+            .build()
+            .context(error::BuildTableMetaSnafu { table_name })?;
+
+        let table_info = TableInfoBuilder::new(table_name.clone(), table_meta)
+            .ident(table_id)
+            .table_version(INIT_TABLE_VERSION)
+            .table_type(TableType::Base)
+            .catalog_name(catalog_name.to_string())
+            .schema_name(schema_name.to_string())
+            .desc(request.desc.clone())
+            .build()
+            .context(error::BuildTableInfoSnafu { table_name })?;
+
+        let table_dir = table_dir(catalog_name, schema_name, table_id);
+        manifest::write_table_manifest(
+            table_name,
+            &table_dir,
+            &FileTableMetadata {
+                table_info: RawTableInfo::from(table_info.clone()),
+            },
+            &self.object_store,
+        )
+        .await?;
+
+        let table = Arc::new(FileTable::new(table_info, source));
+
+        logging::info!(
+            "File engine created table: {} in schema: {}, table_id: {}.",
+            table_name,
+            schema_name,
+            table_id
+        );
+
+        self.tables
+            .write()
+            .unwrap()
+            .insert(table_ref.to_string(), table.clone());
+
+        Ok(table)
+    }
+
+    async fn open_table(&self, request: OpenTableRequest) -> Result<Option<TableRef>> {
+        let catalog_name = &request.catalog_name;
+        let schema_name = &request.schema_name;
+        let table_name = &request.table_name;
+        let table_ref = TableReference {
+            catalog: catalog_name,
+            schema: schema_name,
+            table: table_name,
+        };
+
+        if let Some(table) = self.get_table(&table_ref) {
+            // Table has already been opened.
+            return Ok(Some(table));
+        }
+
+        let table = {
+            let _lock = self.table_mutex.lock().await;
+            // Checks again, read lock should be enough since we are guarded by the mutex.
+            if let Some(table) = self.get_table(&table_ref) {
+                return Ok(Some(table));
+            }
+
+            let table_dir = table_dir(catalog_name, schema_name, request.table_id);
+            let Some(metadata) =
+                manifest::read_table_manifest(table_name, &table_dir, &self.object_store).await?
+                else { return Ok(None) };
+
+            let table_info = TableInfo::try_from(metadata.table_info).context(ConvertRawSnafu)?;
+            let table = Arc::new(FileTable::try_new(table_info)?);
+
+            self.tables
+                .write()
+                .unwrap()
+                .insert(table_ref.to_string(), table.clone());
+            table
+        };
+
+        logging::info!(
+            "File engine opened table: {} in schema: {}",
+            table_name,
+            schema_name
+        );
+
+        Ok(Some(table))
+    }
+
+    fn get_table(&self, table_ref: &TableReference) -> Option<TableRef> {
+        self.tables
+            .read()
+            .unwrap()
+            .get(&table_ref.to_string())
+            .cloned()
+            .map(|table| table as _)
+    }
+
+    /// Drops the table and its manifest, files in the table location are kept.
+    async fn drop_table(&self, request: DropTableRequest) -> Result<bool> {
+        let table_ref = TableReference {
+            catalog: &request.catalog_name,
+            schema: &request.schema_name,
+            table: &request.table_name,
+        };
+
+        let _lock = self.table_mutex.lock().await;
+        let Some(table) = self.tables.read().unwrap().get(&table_ref.to_string()).cloned()
+            else { return Ok(false) };
+
+        let table_id = table.table_info().ident.table_id;
+        let table_dir = table_dir(&request.catalog_name, &request.schema_name, table_id);
+        manifest::delete_table_manifest(&request.table_name, &table_dir, &self.object_store)
+            .await?;
+
+        self.tables.write().unwrap().remove(&table_ref.to_string());
+        Ok(true)
+    }
+}
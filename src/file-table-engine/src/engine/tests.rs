@@ -0,0 +1,173 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for file table engine.
+
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_datasource::file_format::FORMAT_TYPE;
+use common_query::physical_plan::SessionContext;
+use common_recordbatch::util;
+use common_test_util::temp_dir::{create_temp_dir, TempDir};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, RawSchema};
+use object_store::services::Fs;
+use table::requests::TableOptions;
+
+use super::*;
+use crate::table::FILE_TABLE_LOCATION_KEY;
+
+const TABLE_NAME: &str = "demo";
+
+fn new_fs_store(dir: &str) -> ObjectStore {
+    let mut builder = Fs::default();
+    builder.root(dir);
+    ObjectStore::new(builder).unwrap().finish()
+}
+
+/// Returns the engine, and a temp dir that contains `cpu.csv`.
+async fn setup_engine() -> (FileTableEngine, TempDir, TempDir) {
+    let engine_dir = create_temp_dir("test_file_engine");
+    let data_dir = create_temp_dir("test_file_engine_data");
+    std::fs::write(
+        data_dir.path().join("cpu.csv"),
+        "host,ts,cpu\na,1,0.5\nb,2,1.5\nc,3,2.5\n",
+    )
+    .unwrap();
+
+    let engine = FileTableEngine::new(new_fs_store(engine_dir.path().to_str().unwrap()));
+    (engine, engine_dir, data_dir)
+}
+
+fn new_create_request(data_dir: &TempDir, schema: RawSchema) -> CreateTableRequest {
+    let mut table_options = TableOptions::default();
+    table_options.extra_options.insert(
+        FILE_TABLE_LOCATION_KEY.to_string(),
+        format!("{}/", data_dir.path().to_str().unwrap()),
+    );
+    table_options
+        .extra_options
+        .insert(FORMAT_TYPE.to_string(), "csv".to_string());
+
+    CreateTableRequest {
+        id: 1024,
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+        desc: None,
+        schema,
+        region_numbers: vec![0],
+        primary_key_indices: vec![],
+        create_if_not_exists: false,
+        table_options,
+        engine: FILE_ENGINE.to_string(),
+    }
+}
+
+fn table_reference() -> TableReference<'static> {
+    TableReference {
+        catalog: DEFAULT_CATALOG_NAME,
+        schema: DEFAULT_SCHEMA_NAME,
+        table: TABLE_NAME,
+    }
+}
+
+#[tokio::test]
+async fn test_create_table_infer_schema() {
+    let (engine, _engine_dir, data_dir) = setup_engine().await;
+    let ctx = EngineContext::default();
+
+    let request = new_create_request(&data_dir, RawSchema::new(vec![]));
+    let table = engine.create_table(&ctx, request.clone()).await.unwrap();
+    let schema = table.schema();
+    assert_eq!(3, schema.num_columns());
+    assert_eq!(
+        ConcreteDataType::int64_datatype(),
+        schema.column_schema_by_name("ts").unwrap().data_type
+    );
+    assert_eq!(FILE_ENGINE, table.table_info().meta.engine);
+    assert!(engine.table_exists(&ctx, &table_reference()));
+
+    // Creates the table again.
+    assert!(engine.create_table(&ctx, request.clone()).await.is_err());
+    let mut request = request;
+    request.create_if_not_exists = true;
+    assert!(engine.create_table(&ctx, request).await.is_ok());
+
+    let session_ctx = SessionContext::new();
+    let stream = table.scan(Some(&vec![2, 0]), &[], None).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect(stream).await.unwrap();
+    assert_eq!(1, batches.len());
+    let batch = &batches[0];
+    assert_eq!(2, batch.num_columns());
+    assert_eq!(3, batch.num_rows());
+    assert_eq!("cpu", batch.schema.column_schemas()[0].name);
+    assert_eq!("host", batch.schema.column_schemas()[1].name);
+}
+
+#[tokio::test]
+async fn test_open_and_drop_table() {
+    let (engine, engine_dir, data_dir) = setup_engine().await;
+    let ctx = EngineContext::default();
+
+    let column_schemas = vec![
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+        ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        // Column not in file.
+        ColumnSchema::new("memory", ConcreteDataType::float64_datatype(), true),
+    ];
+    let request = new_create_request(&data_dir, RawSchema::new(column_schemas));
+    let table = engine.create_table(&ctx, request).await.unwrap();
+    let table_info = table.table_info();
+
+    // Opens the table with a new engine.
+    let engine = FileTableEngine::new(new_fs_store(engine_dir.path().to_str().unwrap()));
+    let open_request = OpenTableRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+        table_id: 1024,
+    };
+    let reopened = engine
+        .open_table(&ctx, open_request.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(table_info, reopened.table_info());
+
+    let session_ctx = SessionContext::new();
+    let stream = reopened.scan(None, &[], None).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect(stream).await.unwrap();
+    assert_eq!(3, batches[0].num_columns());
+    assert_eq!(3, batches[0].column(2).null_count());
+
+    let drop_request = || DropTableRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+    };
+    assert!(engine.drop_table(&ctx, drop_request()).await.unwrap());
+    assert!(!engine.table_exists(&ctx, &table_reference()));
+    assert!(!engine.drop_table(&ctx, drop_request()).await.unwrap());
+
+    // Files are kept after dropping the table.
+    assert!(data_dir.path().join("cpu.csv").exists());
+    let engine = FileTableEngine::new(new_fs_store(engine_dir.path().to_str().unwrap()));
+    assert!(engine
+        .open_table(&ctx, open_request)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,207 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+
+use common_error::prelude::*;
+use datatypes::arrow::error::ArrowError;
+use snafu::Location;
+use table::metadata::{TableInfoBuilderError, TableMetaBuilderError};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum Error {
+    #[snafu(display(
+        "Failed to build table meta for table: {}, source: {}",
+        table_name,
+        source
+    ))]
+    BuildTableMeta {
+        source: TableMetaBuilderError,
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to build table info for table: {}, source: {}",
+        table_name,
+        source
+    ))]
+    BuildTableInfo {
+        source: TableInfoBuilderError,
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display("Table already exists: {}", table_name))]
+    TableExists {
+        location: Location,
+        table_name: String,
+    },
+
+    #[snafu(display("Missing required option {} for table: {}", key, table_name))]
+    MissingRequiredOption {
+        key: String,
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid schema, source: {}", source))]
+    InvalidRawSchema { source: datatypes::error::Error },
+
+    #[snafu(display("Failed to convert arrow schema, source: {}", source))]
+    ConvertSchema { source: datatypes::error::Error },
+
+    #[snafu(display("Failed to convert raw table info, source: {}", source))]
+    ConvertRaw {
+        #[snafu(backtrace)]
+        source: table::metadata::ConvertError,
+    },
+
+    #[snafu(display("Invalid pattern: {}, source: {}", pattern, source))]
+    BuildRegex {
+        pattern: String,
+        source: regex::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to access file table location, source: {}", source))]
+    AccessLocation {
+        #[snafu(backtrace)]
+        source: common_datasource::error::Error,
+    },
+
+    #[snafu(display("Invalid file format options, source: {}", source))]
+    ParseFileFormat {
+        #[snafu(backtrace)]
+        source: common_datasource::error::Error,
+    },
+
+    #[snafu(display("No file found in location: {}", location_url))]
+    EmptyLocation {
+        location_url: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to read file: {}, source: {}", path, source))]
+    ReadFile {
+        path: String,
+        #[snafu(backtrace)]
+        source: common_datasource::error::Error,
+    },
+
+    #[snafu(display("Column {} not found in file: {}", column_name, path))]
+    ColumnNotFound {
+        column_name: String,
+        path: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to convert record batch from file: {}, source: {}",
+        path,
+        source
+    ))]
+    ConvertRecordBatch {
+        path: String,
+        source: ArrowError,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to create record batch, source: {}", source))]
+    NewRecordBatch {
+        #[snafu(backtrace)]
+        source: common_recordbatch::error::Error,
+    },
+
+    #[snafu(display("Failed to read manifest of table: {}, source: {}", table_name, source))]
+    ReadManifest {
+        table_name: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to write manifest of table: {}, source: {}",
+        table_name,
+        source
+    ))]
+    WriteManifest {
+        table_name: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to delete manifest of table: {}, source: {}",
+        table_name,
+        source
+    ))]
+    DeleteManifest {
+        table_name: String,
+        source: object_store::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to encode manifest, source: {}", source))]
+    EncodeManifest {
+        source: serde_json::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to decode manifest, source: {}", source))]
+    DecodeManifest {
+        source: serde_json::Error,
+        location: Location,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl ErrorExt for Error {
+    fn status_code(&self) -> StatusCode {
+        use Error::*;
+
+        match self {
+            AccessLocation { source, .. }
+            | ParseFileFormat { source, .. }
+            | ReadFile { source, .. } => source.status_code(),
+
+            BuildTableMeta { .. }
+            | BuildTableInfo { .. }
+            | MissingRequiredOption { .. }
+            | InvalidRawSchema { .. }
+            | ConvertSchema { .. }
+            | BuildRegex { .. }
+            | EmptyLocation { .. }
+            | ColumnNotFound { .. }
+            | ConvertRecordBatch { .. } => StatusCode::InvalidArguments,
+
+            ConvertRaw { .. }
+            | NewRecordBatch { .. }
+            | EncodeManifest { .. }
+            | DecodeManifest { .. } => StatusCode::Unexpected,
+
+            TableExists { .. } => StatusCode::TableAlreadyExists,
+
+            ReadManifest { .. } | WriteManifest { .. } | DeleteManifest { .. } => {
+                StatusCode::StorageUnavailable
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
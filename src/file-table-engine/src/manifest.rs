@@ -0,0 +1,79 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Manifest of file tables.
+//!
+//! File tables are immutable, so the manifest is a single json file holding the
+//! table info, written once on creation and removed on dropping.
+
+use object_store::{ErrorKind, ObjectStore};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use table::metadata::RawTableInfo;
+
+use crate::error::{
+    DecodeManifestSnafu, DeleteManifestSnafu, EncodeManifestSnafu, ReadManifestSnafu, Result,
+    WriteManifestSnafu,
+};
+
+const MANIFEST_FILE_NAME: &str = "_file_table_manifest";
+
+#[inline]
+fn manifest_path(table_dir: &str) -> String {
+    format!("{table_dir}/manifest/{MANIFEST_FILE_NAME}")
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FileTableMetadata {
+    pub table_info: RawTableInfo,
+}
+
+pub(crate) async fn write_table_manifest(
+    table_name: &str,
+    table_dir: &str,
+    metadata: &FileTableMetadata,
+    object_store: &ObjectStore,
+) -> Result<()> {
+    let bs = serde_json::to_vec(metadata).context(EncodeManifestSnafu)?;
+    object_store
+        .write(&manifest_path(table_dir), bs)
+        .await
+        .context(WriteManifestSnafu { table_name })
+}
+
+/// Reads the manifest under `table_dir`, returns `None` if the manifest doesn't exist.
+pub(crate) async fn read_table_manifest(
+    table_name: &str,
+    table_dir: &str,
+    object_store: &ObjectStore,
+) -> Result<Option<FileTableMetadata>> {
+    let bs = match object_store.read(&manifest_path(table_dir)).await {
+        Ok(bs) => bs,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context(ReadManifestSnafu { table_name }),
+    };
+    let metadata = serde_json::from_slice(&bs).context(DecodeManifestSnafu)?;
+    Ok(Some(metadata))
+}
+
+pub(crate) async fn delete_table_manifest(
+    table_name: &str,
+    table_dir: &str,
+    object_store: &ObjectStore,
+) -> Result<()> {
+    object_store
+        .delete(&manifest_path(table_dir))
+        .await
+        .context(DeleteManifestSnafu { table_name })
+}
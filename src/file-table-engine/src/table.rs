@@ -0,0 +1,382 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_stream::try_stream;
+use async_trait::async_trait;
+use common_datasource::file_format::{self, Format};
+use common_datasource::lister::{Lister, Source};
+use common_datasource::object_store::{build_backend, parse_url};
+use common_datasource::util::find_dir_and_filename;
+use common_error::ext::BoxedError;
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_recordbatch::error::{ExternalSnafu, PollStreamSnafu};
+use common_recordbatch::{DfRecordBatch, RecordBatch, RecordBatchStreamAdaptor};
+use common_time::range::TimestampRange;
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datatypes::arrow::array::{
+    new_null_array, Array, ArrayRef, BooleanArray, Int64Array, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+};
+use datatypes::arrow::compute::{cast, filter_record_batch};
+use datatypes::arrow::datatypes::{DataType, TimeUnit as ArrowTimeUnit};
+use datatypes::arrow::record_batch::RecordBatchOptions;
+use datatypes::schema::{Schema, SchemaRef};
+use futures::TryStreamExt;
+use object_store::ObjectStore;
+use regex::Regex;
+use snafu::{OptionExt, ResultExt};
+use table::error::{self as table_error, Result as TableResult};
+use table::metadata::{FilterPushDownType, TableInfo, TableInfoRef, TableType};
+use table::predicate::TimeRangePredicateBuilder;
+use table::table::scan::SimpleTableScan;
+use table::Table;
+
+use crate::error::{
+    AccessLocationSnafu, BuildRegexSnafu, ColumnNotFoundSnafu, ConvertRecordBatchSnafu,
+    ConvertSchemaSnafu, MissingRequiredOptionSnafu, NewRecordBatchSnafu, ParseFileFormatSnafu,
+    ReadFileSnafu, Result,
+};
+
+/// Url of the files, e.g. `s3://bucket/path/to/dir/` or `/path/to/file.csv`.
+pub const FILE_TABLE_LOCATION_KEY: &str = "LOCATION";
+/// Regex to filter the files listed from a directory location.
+pub const FILE_TABLE_PATTERN_KEY: &str = "PATTERN";
+
+/// Where and how to read the files of a file table.
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    object_store: ObjectStore,
+    dir: String,
+    filename: Option<String>,
+    regex: Option<Regex>,
+    format: Format,
+}
+
+impl FileSource {
+    /// Builds the source from table options, whose keys are in upper case.
+    pub fn try_new(table_name: &str, options: &HashMap<String, String>) -> Result<Self> {
+        let location =
+            options
+                .get(FILE_TABLE_LOCATION_KEY)
+                .context(MissingRequiredOptionSnafu {
+                    key: FILE_TABLE_LOCATION_KEY,
+                    table_name,
+                })?;
+        let format = Format::try_from(options).context(ParseFileFormatSnafu)?;
+
+        let (_schema, _host, path) = parse_url(location).context(AccessLocationSnafu)?;
+        let object_store = build_backend(location, options.clone()).context(AccessLocationSnafu)?;
+        let (dir, filename) = find_dir_and_filename(&path);
+
+        let regex = options
+            .get(FILE_TABLE_PATTERN_KEY)
+            .map(|pattern| Regex::new(pattern).context(BuildRegexSnafu { pattern }))
+            .transpose()?;
+
+        Ok(Self {
+            object_store,
+            dir,
+            filename,
+            regex,
+            format,
+        })
+    }
+
+    /// Lists paths of all files in the location.
+    pub async fn list_files(&self) -> Result<Vec<String>> {
+        let source = match &self.filename {
+            Some(filename) => Source::Filename(filename.clone()),
+            None => Source::Dir,
+        };
+        let lister = Lister::new(
+            self.object_store.clone(),
+            source,
+            self.dir.clone(),
+            self.regex.clone(),
+        );
+        let entries = lister.list().await.context(AccessLocationSnafu)?;
+
+        Ok(entries
+            .iter()
+            .map(|entry| entry.path())
+            // Skips sub directories.
+            .filter(|path| !path.ends_with('/'))
+            .map(|path| path.to_string())
+            .collect())
+    }
+
+    /// Infers the schema from all files in the location.
+    pub async fn infer_schema(&self) -> Result<Schema> {
+        let files = self.list_files().await?;
+        let arrow_schema = file_format::infer_schemas(&self.object_store, &files, &self.format)
+            .await
+            .context(AccessLocationSnafu)?;
+        Schema::try_from(arrow_schema).context(ConvertSchemaSnafu)
+    }
+}
+
+/// A read-only [Table] that scans files in the location.
+pub struct FileTable {
+    table_info: TableInfoRef,
+    source: FileSource,
+}
+
+#[async_trait]
+impl Table for FileTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_info.meta.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.table_info.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        self.table_info.table_type
+    }
+
+    async fn scan(
+        &self,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> TableResult<PhysicalPlanRef> {
+        let table_schema = self.schema();
+        let projected_schema = match projection {
+            Some(projection) => {
+                let column_schemas = projection
+                    .iter()
+                    .map(|idx| table_schema.column_schemas()[*idx].clone())
+                    .collect();
+                Arc::new(
+                    Schema::try_new(column_schemas)
+                        .context(ConvertSchemaSnafu)
+                        .map_err(BoxedError::new)
+                        .context(table_error::TableOperationSnafu)?,
+                )
+            }
+            None => table_schema.clone(),
+        };
+        let time_range = table_schema
+            .timestamp_column()
+            .map(|ts_col| TimeRangePredicateBuilder::new(&ts_col.name, filters).build())
+            .unwrap_or_else(TimestampRange::min_to_max);
+
+        let files = if time_range.is_empty() {
+            vec![]
+        } else {
+            self.source
+                .list_files()
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?
+        };
+
+        let source = self.source.clone();
+        let file_schema = table_schema.arrow_schema().clone();
+        let projection = projection.cloned();
+        let schema = projected_schema.clone();
+        let stream = Box::pin(try_stream! {
+            for path in files {
+                let mut batches = source
+                    .format
+                    .read(&source.object_store, &path, file_schema.clone(), projection.as_deref())
+                    .await
+                    .context(ReadFileSnafu { path: &path })
+                    .map_err(BoxedError::new)
+                    .context(ExternalSnafu)?;
+
+                while let Some(batch) = batches.try_next().await.context(PollStreamSnafu)? {
+                    let batch = convert_batch(&path, batch, &schema, &time_range)
+                        .map_err(BoxedError::new)
+                        .context(ExternalSnafu)?;
+                    if let Some(batch) = batch {
+                        yield batch;
+                    }
+                }
+            }
+        });
+
+        let stream = Box::pin(RecordBatchStreamAdaptor {
+            schema: projected_schema,
+            stream,
+        });
+        Ok(Arc::new(SimpleTableScan::new(stream)))
+    }
+
+    fn supports_filters_pushdown(&self, filters: &[&Expr]) -> TableResult<Vec<FilterPushDownType>> {
+        Ok(vec![FilterPushDownType::Inexact; filters.len()])
+    }
+}
+
+impl FileTable {
+    pub fn new(table_info: TableInfo, source: FileSource) -> Self {
+        Self {
+            table_info: Arc::new(table_info),
+            source,
+        }
+    }
+
+    pub fn try_new(table_info: TableInfo) -> Result<Self> {
+        let source = FileSource::try_new(&table_info.name, &table_info.meta.options.extra_options)?;
+        Ok(Self::new(table_info, source))
+    }
+}
+
+/// Converts the `batch` read from file to `schema`, and filters out rows not in `time_range`.
+///
+/// Returns `None` if no row is left.
+fn convert_batch(
+    path: &str,
+    batch: DfRecordBatch,
+    schema: &SchemaRef,
+    time_range: &TimestampRange,
+) -> Result<Option<RecordBatch>> {
+    let num_rows = batch.num_rows();
+    let columns = schema
+        .arrow_schema()
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) if column.data_type() == field.data_type() => Ok(column.clone()),
+            Some(column) => {
+                cast(column, field.data_type()).context(ConvertRecordBatchSnafu { path })
+            }
+            None if field.is_nullable() => Ok(new_null_array(field.data_type(), num_rows)),
+            None => ColumnNotFoundSnafu {
+                column_name: field.name(),
+                path,
+            }
+            .fail(),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Sets the row count explicitly as no column is required by queries like `count(*)`.
+    let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+    let mut batch =
+        DfRecordBatch::try_new_with_options(schema.arrow_schema().clone(), columns, &options)
+            .context(ConvertRecordBatchSnafu { path })?;
+
+    if let Some(ts_index) = schema.timestamp_index() {
+        if *time_range != TimestampRange::min_to_max() {
+            if let Some(mask) = time_range_mask(batch.column(ts_index), time_range) {
+                batch =
+                    filter_record_batch(&batch, &mask).context(ConvertRecordBatchSnafu { path })?;
+            }
+        }
+    }
+
+    if batch.num_rows() == 0 {
+        return Ok(None);
+    }
+
+    RecordBatch::try_from_df_record_batch(schema.clone(), batch)
+        .context(NewRecordBatchSnafu)
+        .map(Some)
+}
+
+/// Returns a mask that selects timestamps in `time_range`, or `None` if the column
+/// is not a timestamp column.
+fn time_range_mask(ts_column: &ArrayRef, time_range: &TimestampRange) -> Option<BooleanArray> {
+    macro_rules! contains {
+        ($array_ty: ty, $unit: ident) => {{
+            let ts_column = ts_column.as_any().downcast_ref::<$array_ty>()?;
+            Some(BooleanArray::from_iter(ts_column.iter().map(|ts| {
+                ts.map(|value| time_range.contains(&Timestamp::new(value, TimeUnit::$unit)))
+            })))
+        }};
+    }
+
+    match ts_column.data_type() {
+        DataType::Timestamp(ArrowTimeUnit::Second, _) => {
+            contains!(TimestampSecondArray, Second)
+        }
+        DataType::Timestamp(ArrowTimeUnit::Millisecond, _) => {
+            contains!(TimestampMillisecondArray, Millisecond)
+        }
+        DataType::Timestamp(ArrowTimeUnit::Microsecond, _) => {
+            contains!(TimestampMicrosecondArray, Microsecond)
+        }
+        DataType::Timestamp(ArrowTimeUnit::Nanosecond, _) => {
+            contains!(TimestampNanosecondArray, Nanosecond)
+        }
+        DataType::Int64 => contains!(Int64Array, Millisecond),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use datatypes::arrow::array::Float64Array;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::ColumnSchema;
+
+    use super::*;
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            )
+            .with_time_index(true),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ]))
+    }
+
+    #[test]
+    fn test_convert_batch() {
+        let schema = test_schema();
+        // A file without host column and ts stored as int64.
+        let file_schema = Arc::new(datatypes::arrow::datatypes::Schema::new(vec![
+            datatypes::arrow::datatypes::Field::new("ts", DataType::Int64, false),
+            datatypes::arrow::datatypes::Field::new("cpu", DataType::Float64, true),
+        ]));
+        let batch = DfRecordBatch::try_new(
+            file_schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(Float64Array::from(vec![0.1, 0.2, 0.3])),
+            ],
+        )
+        .unwrap();
+
+        let time_range = TimestampRange::with_unit(2, 10, TimeUnit::Millisecond).unwrap();
+        let converted = convert_batch("test", batch.clone(), &schema, &time_range)
+            .unwrap()
+            .unwrap();
+        assert_eq!(2, converted.num_rows());
+        assert_eq!(schema, converted.schema);
+        assert_eq!(2, converted.column(0).null_count());
+
+        let time_range = TimestampRange::with_unit(5, 10, TimeUnit::Millisecond).unwrap();
+        assert!(convert_batch("test", batch, &schema, &time_range)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -230,6 +230,12 @@ pub enum Error {
     #[snafu(display("Cannot find primary key column by name: {}", msg))]
     PrimaryKeyNotFound { msg: String, location: Location },
 
+    #[snafu(display("Failed to convert value to SQL value, source: {}", source))]
+    ConvertSqlValue {
+        #[snafu(backtrace)]
+        source: sql::error::Error,
+    },
+
     #[snafu(display("Failed to execute statement, source: {}", source))]
     ExecuteStatement {
         #[snafu(backtrace)]
@@ -399,7 +405,7 @@ impl ErrorExt for Error {
             Error::StartServer { source, .. } => source.status_code(),
             Error::ShutdownServer { source, .. } => source.status_code(),
 
-            Error::ParseSql { source } => source.status_code(),
+            Error::ParseSql { source } | Error::ConvertSqlValue { source } => source.status_code(),
 
             Error::Table { source } => source.status_code(),
 
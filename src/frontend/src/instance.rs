@@ -32,15 +32,21 @@ use async_trait::async_trait;
 use catalog::remote::MetaKvBackend;
 use catalog::CatalogManagerRef;
 use common_base::Plugins;
-use common_catalog::consts::MITO_ENGINE;
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MITO_ENGINE};
 use common_error::ext::BoxedError;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
 use common_query::Output;
 use common_recordbatch::RecordBatches;
 use common_telemetry::logging::{debug, info};
 use common_telemetry::timer;
+use datafusion::datasource::provider_as_source;
+use datafusion::optimizer::utils::conjunction;
+use datafusion::prelude::{Column, Expr as DfExpr};
+use datafusion::scalar::ScalarValue;
 use datafusion::sql::sqlparser::ast::ObjectName;
-use datanode::instance::sql::table_idents_to_full_name;
+use datafusion::sql::TableReference as DfTableReference;
+use datafusion_expr::LogicalPlanBuilder;
+use datanode::instance::sql::{database_idents_to_full_name, table_idents_to_full_name};
 use datanode::instance::InstanceRef as DnInstanceRef;
 use datatypes::schema::Schema;
 use distributed::DistInstance;
@@ -48,14 +54,16 @@ use meta_client::client::{MetaClient, MetaClientBuilder};
 use meta_client::MetaClientOptions;
 use partition::manager::PartitionRuleManager;
 use partition::route::TableRoutes;
+use promql::planner::PromPlanner;
 use query::parser::{PromQuery, QueryLanguageParser, QueryStatement};
+use query::plan::LogicalPlan;
 use query::query_engine::options::{validate_catalog_and_schema, QueryOptions};
 use query::query_engine::StatementHandlerRef;
 use query::{QueryEngineFactory, QueryEngineRef};
 use servers::error as server_error;
 use servers::error::{ExecuteQuerySnafu, ParsePromQLSnafu};
 use servers::interceptor::{SqlQueryInterceptor, SqlQueryInterceptorRef};
-use servers::prom::PromHandler;
+use servers::prom::{PromHandler, SeriesSelector};
 use servers::query_handler::grpc::{GrpcQueryHandler, GrpcQueryHandlerRef};
 use servers::query_handler::sql::SqlQueryHandler;
 use servers::query_handler::{
@@ -65,18 +73,19 @@ use session::context::{QueryContext, QueryContextRef};
 use snafu::prelude::*;
 use sql::dialect::GenericDialect;
 use sql::parser::ParserContext;
-use sql::statements::copy::CopyTable;
+use sql::statements::copy::{CopyDatabase, CopyTable};
 use sql::statements::describe::DescribeTable;
 use sql::statements::statement::Statement;
 use sql::statements::tql::Tql;
+use table::table::adapter::DfTableProviderAdapter;
 
 use crate::catalog::FrontendCatalogManager;
 use crate::datanode::DatanodeClients;
 use crate::error::{
     self, CatalogSnafu, DescribeStatementSnafu, Error, ExecLogicalPlanSnafu, ExecutePromqlSnafu,
     ExecuteStatementSnafu, ExternalSnafu, InvalidInsertRequestSnafu, MissingMetasrvOptsSnafu,
-    NotSupportedSnafu, ParseQuerySnafu, ParseSqlSnafu, PlanStatementSnafu, Result,
-    SqlExecInterceptedSnafu, TableNotFoundSnafu,
+    ParseQuerySnafu, ParseSqlSnafu, PlanStatementSnafu, Result, SqlExecInterceptedSnafu,
+    TableNotFoundSnafu,
 };
 use crate::expr_factory::{CreateExprFactoryRef, DefaultCreateExprFactory};
 use crate::frontend::FrontendOptions;
@@ -524,16 +533,20 @@ impl Instance {
             | Statement::Insert(_)
             | Statement::Alter(_)
             | Statement::DropTable(_)
-            | Statement::Copy(_) => self
+            | Statement::Copy(_)
+            | Statement::CopyDatabase(_)
+            | Statement::CopyQueryTo(_)
+            | Statement::CompactTable(_)
+            | Statement::TruncateTable(_)
+            | Statement::CreateSnapshot(_)
+            | Statement::DropSnapshot(_)
+            | Statement::RestoreTable(_)
+            | Statement::ShowCreateTable(_) => self
                 .statement_handler
                 .handle_statement(QueryStatement::Sql(stmt), query_ctx)
                 .await
                 .context(ExecuteStatementSnafu),
             Statement::Use(db) => self.handle_use(db, query_ctx),
-            Statement::ShowCreateTable(_) => NotSupportedSnafu {
-                feat: format!("{stmt:?}"),
-            }
-            .fail(),
         }
     }
 }
@@ -637,6 +650,110 @@ impl PromHandler for Instance {
                 query: format!("{query:?}"),
             })
     }
+
+    async fn metric_names(&self) -> server_error::Result<Vec<String>> {
+        let schema = self
+            .catalog_manager
+            .schema(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME)
+            .context(server_error::CatalogSnafu)?;
+        match schema {
+            Some(schema) => schema.table_names().context(server_error::CatalogSnafu),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn label_names(&self, metric: &str) -> server_error::Result<Option<Vec<String>>> {
+        let table = self
+            .catalog_manager
+            .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, metric)
+            .await
+            .context(server_error::CatalogSnafu)?;
+        Ok(table.map(|table| {
+            let table_info = table.table_info();
+            let column_schemas = table_info.meta.schema.column_schemas();
+            table_info
+                .meta
+                .primary_key_indices
+                .iter()
+                .map(|index| column_schemas[*index].name.clone())
+                .collect()
+        }))
+    }
+
+    async fn series_labels(
+        &self,
+        selector: &SeriesSelector,
+        labels: &[String],
+    ) -> server_error::Result<Output> {
+        self.scan_series(selector, labels)
+            .await
+            .map_err(BoxedError::new)
+            .with_context(|_| ExecuteQuerySnafu {
+                query: format!("{selector:?}"),
+            })
+    }
+}
+
+impl Instance {
+    /// Scans distinct values of `labels` in series selected by the `selector`. Returns at
+    /// most one row if `labels` is empty, which tells whether any series is selected.
+    async fn scan_series(&self, selector: &SeriesSelector, labels: &[String]) -> Result<Output> {
+        let table = self
+            .catalog_manager
+            .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, &selector.metric)
+            .await
+            .context(CatalogSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: &selector.metric,
+            })?;
+
+        let mut filters = PromPlanner::matchers_to_expr(selector.matchers.clone())
+            .map_err(BoxedError::new)
+            .context(ExternalSnafu)?;
+        if let Some(ts_column) = table.schema().timestamp_column() {
+            let ts_expr = DfExpr::Column(Column::from_name(&ts_column.name));
+            if let Some(start) = selector.start {
+                filters.push(ts_expr.clone().gt_eq(DfExpr::Literal(
+                    ScalarValue::TimestampMillisecond(Some(start), None),
+                )));
+            }
+            if let Some(end) = selector.end {
+                filters.push(
+                    ts_expr.lt_eq(DfExpr::Literal(ScalarValue::TimestampMillisecond(
+                        Some(end),
+                        None,
+                    ))),
+                );
+            }
+        }
+
+        let build_plan = || {
+            let table_ref = DfTableReference::full(
+                DEFAULT_CATALOG_NAME,
+                DEFAULT_SCHEMA_NAME,
+                selector.metric.as_str(),
+            );
+            let source = provider_as_source(Arc::new(DfTableProviderAdapter::new(table)));
+            let mut builder = LogicalPlanBuilder::scan(table_ref, source, None)?;
+            if let Some(filter) = conjunction(filters) {
+                builder = builder.filter(filter)?;
+            }
+            if labels.is_empty() {
+                builder.limit(0, Some(1))?.build()
+            } else {
+                let columns = labels
+                    .iter()
+                    .map(|label| DfExpr::Column(Column::from_name(label)));
+                builder.project(columns)?.distinct()?.build()
+            }
+        };
+        let plan = build_plan().context(error::BuildDfLogicalPlanSnafu)?;
+
+        self.query_engine
+            .execute(LogicalPlan::DfPlan(plan), QueryContext::arc())
+            .await
+            .context(ExecLogicalPlanSnafu)
+    }
 }
 
 pub fn check_permission(
@@ -655,12 +772,15 @@ pub fn check_permission(
 
     match stmt {
         // These are executed by query engine, and will be checked there.
-        Statement::Query(_) | Statement::Explain(_) | Statement::Tql(_) | Statement::Delete(_) => {}
+        Statement::Query(_)
+        | Statement::Explain(_)
+        | Statement::Tql(_)
+        | Statement::Delete(_)
+        | Statement::CopyQueryTo(_) => {}
         // database ops won't be checked
         Statement::CreateDatabase(_) | Statement::ShowDatabases(_) | Statement::Use(_) => {}
-        // show create table and alter are not supported yet
-        Statement::ShowCreateTable(_) | Statement::CreateExternalTable(_) | Statement::Alter(_) => {
-        }
+        // alter is not supported yet
+        Statement::CreateExternalTable(_) | Statement::Alter(_) => {}
 
         Statement::Insert(insert) => {
             validate_param(insert.table_name(), query_ctx)?;
@@ -681,6 +801,38 @@ pub fn check_permission(
         Statement::DescribeTable(stmt) => {
             validate_param(stmt.name(), query_ctx)?;
         }
+        Statement::ShowCreateTable(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+        }
+        Statement::CompactTable(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+        }
+        Statement::TruncateTable(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+        }
+        Statement::CreateSnapshot(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+        }
+        Statement::DropSnapshot(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+        }
+        Statement::RestoreTable(stmt) => {
+            validate_param(&stmt.table_name, query_ctx)?;
+            validate_param(&stmt.source_table_name, query_ctx)?;
+        }
+        Statement::CopyDatabase(stmt) => {
+            let database = match stmt {
+                CopyDatabase::To(argument) | CopyDatabase::From(argument) => {
+                    &argument.database_name
+                }
+            };
+            let (catalog, schema) = database_idents_to_full_name(database, query_ctx.clone())
+                .map_err(BoxedError::new)
+                .context(ExternalSnafu)?;
+            validate_catalog_and_schema(&catalog, &schema, query_ctx)
+                .map_err(BoxedError::new)
+                .context(SqlExecInterceptedSnafu)?;
+        }
         Statement::Copy(stmd) => match stmd {
             CopyTable::To(copy_table_to) => validate_param(&copy_table_to.table_name, query_ctx)?,
             CopyTable::From(copy_table_from) => {
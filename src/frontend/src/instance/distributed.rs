@@ -42,6 +42,7 @@ use meta_client::rpc::{
     CompareAndPutRequest, CreateRequest as MetaCreateRequest, Partition as MetaPartition,
     RouteRequest, RouteResponse, TableName,
 };
+use partition::manager::PartitionInfo;
 use partition::partition::{PartitionBound, PartitionDef};
 use query::error::QueryExecutionSnafu;
 use query::parser::QueryStatement;
@@ -49,10 +50,13 @@ use query::query_engine::StatementHandler;
 use query::sql::{show_databases, show_tables};
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use sql::ast::Value as SqlValue;
-use sql::statements::create::Partitions;
-use sql::statements::sql_value_to_value;
+use sql::ast::{Ident, ObjectName, Value as SqlValue};
+use sql::statements::compact::CompactTable;
+use sql::statements::create::{PartitionEntry, Partitions};
+use sql::statements::snapshot::{CreateSnapshot, DropSnapshot};
 use sql::statements::statement::Statement;
+use sql::statements::truncate::TruncateTable;
+use sql::statements::{sql_value_to_value, value_to_sql_value};
 use table::metadata::{RawTableInfo, RawTableMeta, TableIdent, TableType};
 use table::requests::TableOptions;
 use table::table::AlterContext;
@@ -312,6 +316,97 @@ impl DistInstance {
         Ok(Output::AffectedRows(0))
     }
 
+    /// Compacts the table on datanodes holding its regions. The statement is forwarded to
+    /// datanodes as SQL, with the table name fully qualified.
+    async fn compact_table(&self, table_name: TableName, stmt: CompactTable) -> Result<Output> {
+        let region_number = stmt.region_number;
+        let sql = CompactTable {
+            table_name: full_object_name(&table_name),
+            ..stmt
+        }
+        .to_string();
+        self.execute_on_leaders(&table_name, &sql, region_number)
+            .await
+    }
+
+    /// Truncates the table on datanodes holding its regions. The statement is forwarded to
+    /// datanodes as SQL, with the table name fully qualified.
+    async fn truncate_table(&self, table_name: TableName) -> Result<Output> {
+        let sql = TruncateTable {
+            table_name: full_object_name(&table_name),
+        }
+        .to_string();
+        self.execute_on_leaders(&table_name, &sql, None).await
+    }
+
+    async fn create_snapshot(&self, table_name: TableName, stmt: CreateSnapshot) -> Result<Output> {
+        let sql = CreateSnapshot {
+            table_name: full_object_name(&table_name),
+            ..stmt
+        }
+        .to_string();
+        self.execute_on_leaders(&table_name, &sql, None).await
+    }
+
+    async fn drop_snapshot(&self, table_name: TableName, stmt: DropSnapshot) -> Result<Output> {
+        let sql = DropSnapshot {
+            table_name: full_object_name(&table_name),
+            ..stmt
+        }
+        .to_string();
+        self.execute_on_leaders(&table_name, &sql, None).await
+    }
+
+    /// Executes the SQL on all datanodes holding leader regions of the table. If
+    /// `region_number` is given, only routes containing that region are considered.
+    async fn execute_on_leaders(
+        &self,
+        table_name: &TableName,
+        sql: &str,
+        region_number: Option<u32>,
+    ) -> Result<Output> {
+        let _ = self
+            .catalog_manager
+            .table(
+                &table_name.catalog_name,
+                &table_name.schema_name,
+                &table_name.table_name,
+            )
+            .await
+            .context(CatalogSnafu)?
+            .with_context(|| TableNotFoundSnafu {
+                table_name: table_name.to_string(),
+            })?;
+
+        let route_response = self
+            .meta_client
+            .route(RouteRequest {
+                table_names: vec![table_name.clone()],
+            })
+            .await
+            .context(RequestMetaSnafu)?;
+
+        for table_route in &route_response.table_routes {
+            let should_send_rpc = table_route.region_routes.iter().any(|route| {
+                region_number
+                    .map(|region_number| region_number == route.region.id as u32)
+                    .unwrap_or(true)
+            });
+            if !should_send_rpc {
+                continue;
+            }
+            for datanode in table_route.find_leaders() {
+                debug!("Executing {sql} on Datanode {datanode:?}");
+
+                let client = self.datanode_clients.get_client(&datanode).await;
+                let client =
+                    Database::new(&table_name.catalog_name, &table_name.schema_name, client);
+                client.sql(sql).await.context(RequestDatanodeSnafu)?;
+            }
+        }
+        Ok(Output::AffectedRows(0))
+    }
+
     async fn handle_statement(
         &self,
         stmt: Statement,
@@ -342,7 +437,55 @@ impl DistInstance {
                 let table_name = TableName::new(catalog, schema, table);
                 return self.drop_table(table_name).await;
             }
+            Statement::CompactTable(stmt) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&stmt.table_name, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+                let table_name = TableName::new(catalog, schema, table);
+                return self.compact_table(table_name, stmt).await;
+            }
+            Statement::TruncateTable(stmt) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&stmt.table_name, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+                let table_name = TableName::new(catalog, schema, table);
+                return self.truncate_table(table_name).await;
+            }
+            Statement::CreateSnapshot(stmt) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&stmt.table_name, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+                let table_name = TableName::new(catalog, schema, table);
+                return self.create_snapshot(table_name, stmt).await;
+            }
+            Statement::DropSnapshot(stmt) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&stmt.table_name, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+                let table_name = TableName::new(catalog, schema, table);
+                return self.drop_snapshot(table_name, stmt).await;
+            }
             Statement::ShowDatabases(stmt) => show_databases(stmt, self.catalog_manager.clone()),
+            Statement::ShowCreateTable(show) => {
+                let (catalog, schema, table) =
+                    table_idents_to_full_name(&show.table_name, query_ctx)
+                        .map_err(BoxedError::new)
+                        .context(error::ExternalSnafu)?;
+
+                let table_ref = self
+                    .catalog_manager
+                    .table(&catalog, &schema, &table)
+                    .await
+                    .context(CatalogSnafu)?
+                    .context(TableNotFoundSnafu { table_name: &table })?;
+                let table_name = TableName::new(catalog, schema, table);
+
+                return self.show_create_table(table_name, table_ref).await;
+            }
             Statement::ShowTables(stmt) => {
                 show_tables(stmt, self.catalog_manager.clone(), query_ctx)
             }
@@ -675,6 +818,69 @@ fn find_partition_entries(
     Ok(entries)
 }
 
+/// Converts partitions in the table route to the `PARTITION BY` clause. Returns `None` if the
+/// table only has the default partition, which is created when no partition is specified.
+fn create_partitions_stmt(partitions: Vec<PartitionInfo>) -> Result<Option<Partitions>> {
+    let Some(first) = partitions.first() else { return Ok(None) };
+    if partitions.len() == 1
+        && first
+            .partition
+            .partition_bounds()
+            .iter()
+            .all(|b| matches!(b, PartitionBound::MaxValue))
+    {
+        return Ok(None);
+    }
+
+    let column_list = first
+        .partition
+        .partition_columns()
+        .iter()
+        .map(|name| Ident::with_quote('"', name))
+        .collect();
+
+    let entries = partitions
+        .iter()
+        .enumerate()
+        .map(|(i, info)| {
+            let value_list = info
+                .partition
+                .partition_bounds()
+                .iter()
+                .map(|b| match b {
+                    PartitionBound::Value(v) => {
+                        value_to_sql_value(v).context(error::ConvertSqlValueSnafu)
+                    }
+                    PartitionBound::MaxValue => Ok(SqlValue::Number("MAXVALUE".to_string(), false)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(PartitionEntry {
+                name: Ident::new(format!("r{i}")),
+                value_list,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(Partitions {
+        column_list,
+        entries,
+    }))
+}
+
+/// Returns the fully qualified and quoted name of the table, to forward statements to datanodes.
+fn full_object_name(table_name: &TableName) -> ObjectName {
+    ObjectName(
+        [
+            &table_name.catalog_name,
+            &table_name.schema_name,
+            &table_name.table_name,
+        ]
+        .into_iter()
+        .map(|ident| Ident::with_quote('"', ident))
+        .collect(),
+    )
+}
+
 fn find_partition_columns(
     create_table: &CreateTableExpr,
     partitions: &Option<Partitions>,
@@ -100,6 +100,19 @@ pub(crate) fn to_alter_expr(
                 name: name.value.to_string(),
             }],
         }),
+        AlterTableOperation::ModifyColumn { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "MODIFY COLUMN",
+            }
+            .fail();
+        }
+        AlterTableOperation::SetTableOptions { .. }
+        | AlterTableOperation::UnsetTableOptions { .. } => {
+            return error::NotSupportedSnafu {
+                feat: "SET or UNSET table options",
+            }
+            .fail();
+        }
         AlterTableOperation::RenameTable { new_table_name } => Kind::RenameTable(RenameTable {
             new_table_name: new_table_name.to_string(),
         }),
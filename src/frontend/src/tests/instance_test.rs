@@ -19,6 +19,7 @@ use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
 use common_recordbatch::util;
 use common_telemetry::logging;
+use common_test_util::temp_dir::create_temp_dir;
 use datatypes::vectors::{Int64Vector, StringVector, UInt64Vector, VectorRef};
 use rstest::rstest;
 use rstest_reuse::apply;
@@ -444,6 +445,90 @@ async fn test_execute_create(instance: Arc<dyn MockInstance>) {
     assert!(matches!(output, Output::AffectedRows(0)));
 }
 
+#[apply(both_instances_cases)]
+async fn test_show_create_table(instance: Arc<dyn MockInstance>) {
+    let frontend = instance.frontend();
+
+    let sql = if instance.is_distributed_mode() {
+        r#"create table demo(
+                host string,
+                ts timestamp default '2022-02-21 16:01:01+0000',
+                cpu double default 0,
+                TIME INDEX (ts),
+                PRIMARY KEY(host)
+            )
+            PARTITION BY RANGE COLUMNS (host) (
+                PARTITION r0 VALUES LESS THAN ('a'),
+                PARTITION r1 VALUES LESS THAN (MAXVALUE)
+            )
+            engine=mito with(ttl='7d');"#
+    } else {
+        r#"create table demo(
+                host string,
+                ts timestamp default '2022-02-21 16:01:01+0000',
+                cpu double default 0,
+                TIME INDEX (ts),
+                PRIMARY KEY(host)
+            ) engine=mito with(ttl='7d');"#
+    };
+    let output = execute_sql(&frontend, sql).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&frontend, "show create table demo").await;
+    let Output::RecordBatches(batches) = output else { unreachable!() };
+    let batches = batches.take();
+    assert_eq!(1, batches[0].num_rows());
+    let create_sql = batches[0]
+        .column(1)
+        .get_ref(0)
+        .as_string()
+        .unwrap()
+        .unwrap()
+        .to_string();
+    assert!(
+        create_sql.contains(r#""cpu" DOUBLE NULL DEFAULT"#),
+        "{create_sql}"
+    );
+    assert!(
+        create_sql.contains(r#""ts" TIMESTAMP(3) NOT NULL DEFAULT '2022-02-21 16:01:01+0000'"#),
+        "{create_sql}"
+    );
+    assert!(create_sql.contains(r#"TIME INDEX ("ts")"#), "{create_sql}");
+    assert!(
+        create_sql.contains(r#"PRIMARY KEY ("host")"#),
+        "{create_sql}"
+    );
+    assert!(create_sql.contains("ENGINE=mito"), "{create_sql}");
+    assert!(create_sql.contains("ttl = '7days'"), "{create_sql}");
+    assert_eq!(
+        instance.is_distributed_mode(),
+        create_sql.contains("PARTITION BY RANGE COLUMNS"),
+        "{create_sql}"
+    );
+
+    // The output can be used to create the table again.
+    let output = execute_sql(&frontend, "drop table demo").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&frontend, &create_sql).await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    // The default value of the time index is still a timestamp.
+    let output = execute_sql(
+        &frontend,
+        "insert into demo(host, cpu) values ('host1', 1.1)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&frontend, "select host, cpu, ts from demo").await;
+    let expected = "\
++-------+-----+---------------------+
+| host  | cpu | ts                  |
++-------+-----+---------------------+
+| host1 | 1.1 | 2022-02-21T16:01:01 |
++-------+-----+---------------------+";
+    check_output_stream(output, expected.to_string()).await;
+}
+
 #[apply(standalone_instance_case)]
 async fn test_rename_table(instance: Arc<dyn MockInstance>) {
     let instance = instance.frontend();
@@ -639,6 +724,123 @@ async fn test_alter_table(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expected).await;
 }
 
+async fn show_create_table_sql(instance: &Arc<Instance>, table_name: &str) -> String {
+    let output = execute_sql(instance, &format!("show create table {table_name}")).await;
+    let Output::RecordBatches(batches) = output else { unreachable!() };
+    let batches = batches.take();
+    batches[0]
+        .column(1)
+        .get_ref(0)
+        .as_string()
+        .unwrap()
+        .unwrap()
+        .to_string()
+}
+
+// Distributed instance doesn't support altering table options yet.
+#[apply(standalone_instance_case)]
+async fn test_alter_table_options(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index) with(ttl='7d')",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "alter table demo set ('ttl'='1d', 'write_buffer_size'='1MB')",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let create_sql = show_create_table_sql(&instance, "demo").await;
+    assert!(create_sql.contains("ttl = '1day'"), "{create_sql}");
+    assert!(
+        create_sql.contains("write_buffer_size = '1.0MiB'"),
+        "{create_sql}"
+    );
+
+    let output = execute_sql(&instance, "alter table demo unset ('ttl')").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let create_sql = show_create_table_sql(&instance, "demo").await;
+    assert!(!create_sql.contains("ttl"), "{create_sql}");
+    assert!(create_sql.contains("write_buffer_size"), "{create_sql}");
+
+    // The table is still writable after altering options.
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = try_execute_sql(&instance, "alter table demo set ('ttl'='a week')").await;
+    assert!(output.is_err());
+}
+
+// Distributed instance doesn't support modifying columns yet.
+#[apply(standalone_instance_case)]
+async fn test_alter_table_modify_column(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu float, memory int, ts timestamp time index, primary key(host));",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, memory, ts) values ('host1', 1.5, 100, 1000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    // Widen the types of field columns, existing rows are read in the new types.
+    let output = execute_sql(&instance, "alter table demo modify column cpu double").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "alter table demo modify column memory bigint default 0",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, memory, ts) values ('host2', 2.5, 8589934592, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    // The new default value is used for absent columns.
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host3', 3.5, 3000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "select * from demo order by ts").await;
+    let expected = "\
++-------+-----+------------+---------------------+
+| host  | cpu | memory     | ts                  |
++-------+-----+------------+---------------------+
+| host1 | 1.5 | 100        | 1970-01-01T00:00:01 |
+| host2 | 2.5 | 8589934592 | 1970-01-01T00:00:02 |
+| host3 | 3.5 | 0          | 1970-01-01T00:00:03 |
++-------+-----+------------+---------------------+\
+    "
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Narrowing the type or modifying a key column is not allowed.
+    let output = try_execute_sql(&instance, "alter table demo modify column cpu float").await;
+    assert!(output.is_err());
+    let output = try_execute_sql(&instance, "alter table demo modify column host int").await;
+    assert!(output.is_err());
+}
+
 async fn test_insert_with_default_value_for_type(instance: Arc<Instance>, type_name: &str) {
     let table_name = format!("test_table_with_{type_name}");
     let create_sql = format!(
@@ -799,6 +1001,700 @@ async fn test_delete(instance: Arc<dyn MockInstance>) {
     check_output_stream(output, expect).await;
 }
 
+#[apply(standalone_instance_case)]
+async fn test_delete_range(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        r#"create table test_table(
+                            host string,
+                            ts timestamp,
+                            cpu double default 0,
+                            TIME INDEX (ts),
+                            PRIMARY KEY(host)
+                        ) engine=mito with(regions=1);"#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        r#"insert into test_table(host, cpu, ts) values
+                           ('host1', 66.6, 1655276557000),
+                           ('host2', 77.7, 1655276558000),
+                           ('host3', 88.8, 1655276559000)
+                           "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(3)));
+
+    // Rows in the range are deleted by a range tombstone instead of by their keys.
+    let output = execute_sql(
+        &instance,
+        "delete from test_table where host = 'host3' and ts >= 1655276558000",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(
+        &instance,
+        "delete from test_table where ts >= 1655276558000",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let output = execute_sql(&instance, "select * from test_table").await;
+    let expect = "\
++-------+---------------------+------+
+| host  | ts                  | cpu  |
++-------+---------------------+------+
+| host1 | 2022-06-15T07:02:37 | 66.6 |
++-------+---------------------+------+\
+"
+    .to_string();
+    check_output_stream(output, expect).await;
+}
+
+#[apply(both_instances_cases)]
+async fn test_compact_table(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "compact table demo wait").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "compact table public.demo region 0").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "select host, cpu from demo order by host").await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host1 | 1.1 |
+| host2 | 2.2 |
++-------+-----+";
+    check_output_stream(output, expected.to_string()).await;
+
+    assert!(try_execute_sql(&instance, "compact table not_exist")
+        .await
+        .is_err());
+}
+
+#[apply(both_instances_cases)]
+async fn test_truncate_table(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "truncate table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "select count(*) from demo").await;
+    let expected = "\
++-----------------+
+| COUNT(UInt8(1)) |
++-----------------+
+| 0               |
++-----------------+";
+    check_output_stream(output, expected.to_string()).await;
+
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host4', 4.4, 4000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "select host, cpu from demo").await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host4 | 4.4 |
++-------+-----+";
+    check_output_stream(output, expected.to_string()).await;
+
+    assert!(try_execute_sql(&instance, "truncate table not_exist")
+        .await
+        .is_err());
+}
+
+#[apply(standalone_instance_case)]
+async fn test_table_snapshot(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "create snapshot s1 on demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert!(try_execute_sql(&instance, "create snapshot s1 on demo")
+        .await
+        .is_err());
+    let output = execute_sql(&instance, "truncate table demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "restore table demo_bak from demo snapshot s1").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(&instance, "select host, cpu from demo_bak order by host").await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host1 | 1.1 |
+| host2 | 2.2 |
++-------+-----+";
+    check_output_stream(output, expected.to_string()).await;
+    assert!(try_execute_sql(
+        &instance,
+        "insert into demo_bak(host, cpu, ts) values ('host3', 3.3, 3000)"
+    )
+    .await
+    .is_err());
+
+    // The snapshot is in use until the restored table is dropped.
+    assert!(try_execute_sql(&instance, "drop snapshot s1 on demo")
+        .await
+        .is_err());
+    let output = execute_sql(&instance, "drop table demo_bak").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "drop snapshot s1 on demo").await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert!(try_execute_sql(&instance, "drop snapshot s1 on demo")
+        .await
+        .is_err());
+    assert!(
+        try_execute_sql(&instance, "restore table demo_bak2 from demo snapshot s1")
+            .await
+            .is_err()
+    );
+}
+
+#[apply(standalone_instance_case)]
+async fn test_select_as_of(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let output = execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host))",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.1, 1000), ('host2', 2.2, 2000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    let output = execute_sql(
+        &instance,
+        "insert into demo(host, cpu, ts) values ('host1', 1.2, 1000), ('host3', 3.3, 3000)",
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(
+        &instance,
+        "select host, cpu from demo order by host as of sequence 1",
+    )
+    .await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host1 | 1.1 |
+| host2 | 2.2 |
++-------+-----+";
+    check_output_stream(output, expected.to_string()).await;
+
+    let output = execute_sql(
+        &instance,
+        "select host, cpu from demo as of timestamp '2100-01-01 00:00:00'",
+    )
+    .await;
+    let expected = "\
++-------+-----+
+| host  | cpu |
++-------+-----+
+| host1 | 1.2 |
+| host2 | 2.2 |
+| host3 | 3.3 |
++-------+-----+";
+    check_output_stream(output, expected.to_string()).await;
+
+    let output = execute_sql(
+        &instance,
+        "select count(*) from demo as of timestamp '2000-01-01 00:00:00'",
+    )
+    .await;
+    let expected = "\
++-----------------+
+| COUNT(UInt8(1)) |
++-----------------+
+| 0               |
++-----------------+";
+    check_output_stream(output, expected.to_string()).await;
+
+    assert!(
+        try_execute_sql(&instance, "select * from demo as of timestamp 'yesterday'")
+            .await
+            .is_err()
+    );
+}
+
+#[apply(standalone_instance_case)]
+async fn test_create_external_table(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    let dir = create_temp_dir("test_create_external_table");
+    std::fs::write(
+        dir.path().join("demo.csv"),
+        "host,cpu,ts\nhost1,66.6,1655276557000\nhost2,88.8,1655276558000\n",
+    )
+    .unwrap();
+    let location = format!("{}/", dir.path().to_str().unwrap());
+
+    let output = execute_sql(
+        &instance,
+        &format!("create external table demo with (location='{location}', format='csv')"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(&instance, "select * from demo order by ts").await;
+    let expected = "\
++-------+------+---------------+
+| host  | cpu  | ts            |
++-------+------+---------------+
+| host1 | 66.6 | 1655276557000 |
+| host2 | 88.8 | 1655276558000 |
++-------+------+---------------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+
+    // Only reads the columns defined in the table, and filters by the time index.
+    let output = execute_sql(
+        &instance,
+        &format!(
+            "create external table demo2(host string, ts timestamp, time index(ts)) \
+             with (location='{location}', format='csv')"
+        ),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(0)));
+
+    let output = execute_sql(
+        &instance,
+        "select host from demo2 where ts >= 1655276558000",
+    )
+    .await;
+    let expected = "\
++-------+
+| host  |
++-------+
+| host2 |
++-------+\
+"
+    .to_string();
+    check_output_stream(output, expected).await;
+}
+
+#[apply(standalone_instance_case)]
+async fn test_execute_copy_text_formats(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, memory double, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, memory, ts) values
+                    ('host1', 66.6, 1024, 1655276557000),
+                    ('host2', 88.8,  333.3, 1655276558000)
+                    "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let dir = create_temp_dir("test_execute_copy_text_formats");
+    let dir = dir.path().to_str().unwrap();
+
+    let tests = [
+        ("csv_table", "demo.csv", "FORMAT = 'csv'"),
+        (
+            "csv_gzip_table",
+            "demo.csv.gz",
+            "FORMAT = 'csv', DELIMITER = '|', COMPRESSION_TYPE = 'gzip'",
+        ),
+        ("json_table", "demo.json", "FORMAT = 'json'"),
+        (
+            "json_zstd_table",
+            "demo.json.zst",
+            "FORMAT = 'json', COMPRESSION_TYPE = 'zstd'",
+        ),
+    ];
+    for (table_name, filename, options) in tests {
+        let output = execute_sql(
+            &instance,
+            &format!("copy demo to '{dir}/{filename}' with ({options})"),
+        )
+        .await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        execute_sql(
+            &instance,
+            &format!(
+                "create table {table_name}(host string, cpu double, memory double, ts timestamp time index);"
+            ),
+        )
+        .await;
+        let output = execute_sql(
+            &instance,
+            &format!("copy {table_name} from '{dir}/{filename}' with ({options})"),
+        )
+        .await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        let output = execute_sql(
+            &instance,
+            &format!("select * from {table_name} order by ts"),
+        )
+        .await;
+        let expected = "\
++-------+------+--------+---------------------+
+| host  | cpu  | memory | ts                  |
++-------+------+--------+---------------------+
+| host1 | 66.6 | 1024.0 | 2022-06-15T07:02:37 |
+| host2 | 88.8 | 333.3  | 2022-06-15T07:02:38 |
++-------+------+--------+---------------------+"
+            .to_string();
+        check_output_stream(output, expected).await;
+    }
+
+    // Columns are matched by name and casted to the table's types, absent columns
+    // are filled with default values.
+    std::fs::write(
+        format!("{dir}/partner.csv"),
+        "ts,cpu,host\n1655276557000,66,host1\n1655276558000,88.8,host2\n",
+    )
+    .unwrap();
+    execute_sql(
+        &instance,
+        "create table partner(host string, cpu double, memory double, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        &format!("copy partner from '{dir}/partner.csv' with (format = 'csv', has_header = true)"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "select * from partner order by ts").await;
+    let expected = "\
++-------+------+--------+---------------------+
+| host  | cpu  | memory | ts                  |
++-------+------+--------+---------------------+
+| host1 | 66.0 |        | 2022-06-15T07:02:37 |
+| host2 | 88.8 |        | 2022-06-15T07:02:38 |
++-------+------+--------+---------------------+"
+        .to_string();
+    check_output_stream(output, expected).await;
+
+    // Fields are quoted by the single quote, which is escaped as '' in sql.
+    execute_sql(
+        &instance,
+        "create table quoted(host string, cpu double, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        r#"insert into quoted(host, cpu, ts) values
+                    ('host1,a', 66.6, 1655276557000),
+                    ('host2''s', 88.8, 1655276558000)
+                    "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    let options = "format = 'csv', quote = ''''";
+    let output = execute_sql(
+        &instance,
+        &format!("copy quoted to '{dir}/quoted.csv' with ({options})"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    let data = std::fs::read_to_string(format!("{dir}/quoted.csv")).unwrap();
+    assert!(data.contains("'host1,a'"), "{data}");
+    assert!(data.contains("'host2''s'"), "{data}");
+
+    execute_sql(
+        &instance,
+        "create table quoted_copy(host string, cpu double, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        &format!("copy quoted_copy from '{dir}/quoted.csv' with ({options})"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql(&instance, "select * from quoted_copy order by ts").await;
+    let expected = "\
++---------+------+---------------------+
+| host    | cpu  | ts                  |
++---------+------+---------------------+
+| host1,a | 66.6 | 2022-06-15T07:02:37 |
+| host2's | 88.8 | 2022-06-15T07:02:38 |
++---------+------+---------------------+"
+        .to_string();
+    check_output_stream(output, expected).await;
+}
+
+#[apply(standalone_instance_case)]
+async fn test_execute_copy_query_to(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, ts) values
+                    ('host1', 66.6, 1680307199000),
+                    ('host2', 88.8, 1680307200000),
+                    ('host3', 77.7, 1680393599000),
+                    ('host4', 99.9, 1680393600000)
+                    "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(4)));
+
+    let dir = create_temp_dir("test_execute_copy_query_to");
+    let dir = dir.path().to_str().unwrap();
+
+    // Exports one day of data.
+    let tests = [
+        (
+            "day_parquet",
+            format!("copy demo to '{dir}/day.parquet' with (start_time = '2023-04-01T00:00:00Z', end_time = 1680393600000)"),
+            format!("copy day_parquet from '{dir}/day.parquet'"),
+        ),
+        (
+            "day_csv",
+            format!("copy (select * from demo where ts >= 1680307200000 and ts < 1680393600000) to '{dir}/day.csv' with (format = 'csv')"),
+            format!("copy day_csv from '{dir}/day.csv' with (format = 'csv')"),
+        ),
+    ];
+    for (table_name, copy_to, copy_from) in tests {
+        let output = execute_sql(&instance, &copy_to).await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        execute_sql(
+            &instance,
+            &format!(
+                "create table {table_name}(host string, cpu double, ts timestamp time index);"
+            ),
+        )
+        .await;
+        let output = execute_sql(&instance, &copy_from).await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        let output = execute_sql(
+            &instance,
+            &format!("select * from {table_name} order by ts"),
+        )
+        .await;
+        let expected = "\
++-------+------+---------------------+
+| host  | cpu  | ts                  |
++-------+------+---------------------+
+| host2 | 88.8 | 2023-04-01T00:00:00 |
+| host3 | 77.7 | 2023-04-01T23:59:59 |
++-------+------+---------------------+"
+            .to_string();
+        check_output_stream(output, expected).await;
+    }
+
+    // Exports the result of an aggregation.
+    let output = execute_sql(
+        &instance,
+        &format!("copy (select count(*) as cnt from demo where ts >= 1680307200000) to '{dir}/count.json' with (format = 'json')"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let content = std::fs::read_to_string(format!("{dir}/count.json")).unwrap();
+    assert_eq!("{\"cnt\":3}\n", content);
+}
+
+#[apply(standalone_instance_case)]
+async fn test_execute_copy_database(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    execute_sql(&instance, "create database source").await;
+    execute_sql(
+        &instance,
+        "create table source.demo(host string, cpu double, ts timestamp time index, primary key(host));",
+    )
+    .await;
+    execute_sql(
+        &instance,
+        "create table source.empty(host string, ts timestamp time index);",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        r#"insert into source.demo(host, cpu, ts) values
+                    ('host1', 66.6, 1655276557000),
+                    ('host2', 88.8, 1655276558000)
+                    "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let dir = create_temp_dir("test_execute_copy_database");
+    let dir = dir.path().to_str().unwrap();
+
+    for (i, options) in ["format = 'parquet'", "format = 'csv'"].iter().enumerate() {
+        let location = format!("{dir}/backup{i}");
+        let output = execute_sql(
+            &instance,
+            &format!("copy database source to '{location}' with ({options})"),
+        )
+        .await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        let target = format!("target{i}");
+        execute_sql(&instance, &format!("create database {target}")).await;
+        let output = execute_sql(
+            &instance,
+            &format!("copy database {target} from '{location}' with ({options})"),
+        )
+        .await;
+        assert!(matches!(output, Output::AffectedRows(2)));
+
+        let output = execute_sql_in_db(&instance, "show tables", &target).await;
+        let expected = "\
++--------+
+| Tables |
++--------+
+| demo   |
+| empty  |
++--------+\
+";
+        check_unordered_output_stream(output, expected).await;
+
+        let output = execute_sql_in_db(&instance, "select * from demo order by ts", &target).await;
+        let expected = "\
++-------+------+---------------------+
+| host  | cpu  | ts                  |
++-------+------+---------------------+
+| host1 | 66.6 | 2022-06-15T07:02:37 |
+| host2 | 88.8 | 2022-06-15T07:02:38 |
++-------+------+---------------------+"
+            .to_string();
+        check_output_stream(output, expected).await;
+    }
+}
+
+#[apply(standalone_instance_case)]
+async fn test_execute_copy_default_database(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    execute_sql(
+        &instance,
+        "create table demo(host string, cpu double, ts timestamp time index, primary key(host));",
+    )
+    .await;
+    let output = execute_sql(
+        &instance,
+        r#"insert into demo(host, cpu, ts) values
+                    ('host1', 66.6, 1655276557000),
+                    ('host2', 88.8, 1655276558000)
+                    "#,
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+    execute_sql(&instance, "create snapshot s1 on demo").await;
+    execute_sql(&instance, "restore table demo_bak from demo snapshot s1").await;
+
+    let dir = create_temp_dir("test_execute_copy_default_database");
+    let location = format!("{}/backup", dir.path().to_str().unwrap());
+
+    // The `numbers` table and the restored table are not exported.
+    let output = execute_sql(
+        &instance,
+        &format!("copy database public to '{location}' with (format = 'parquet')"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    execute_sql(&instance, "create database target").await;
+    let output = execute_sql(
+        &instance,
+        &format!("copy database target from '{location}' with (format = 'parquet')"),
+    )
+    .await;
+    assert!(matches!(output, Output::AffectedRows(2)));
+
+    let output = execute_sql_in_db(&instance, "show tables", "target").await;
+    let expected = "\
++--------+
+| Tables |
++--------+
+| demo   |
++--------+\
+";
+    check_unordered_output_stream(output, expected).await;
+
+    let output = execute_sql_in_db(&instance, "select * from demo order by ts", "target").await;
+    let expected = "\
++-------+------+---------------------+
+| host  | cpu  | ts                  |
++-------+------+---------------------+
+| host1 | 66.6 | 2022-06-15T07:02:37 |
+| host2 | 88.8 | 2022-06-15T07:02:38 |
++-------+------+---------------------+"
+        .to_string();
+    check_output_stream(output, expected).await;
+}
+
 #[apply(standalone_instance_case)]
 async fn test_execute_copy_to_s3(instance: Arc<dyn MockInstance>) {
     if let Ok(bucket) = env::var("GT_S3_BUCKET") {
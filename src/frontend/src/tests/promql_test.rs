@@ -15,9 +15,13 @@
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use common_query::Output;
+use common_recordbatch::util;
+use promql_parser::parser::{Expr, VectorSelector};
 use query::parser::{PromQuery, QueryLanguageParser, QueryStatement};
 use rstest::rstest;
 use rstest_reuse::apply;
+use servers::prom::{PromHandler, SeriesSelector};
 use servers::query_handler::sql::SqlQueryHandler;
 use session::context::QueryContext;
 
@@ -173,6 +177,95 @@ async fn sql_insert_promql_query_ceil(instance: Arc<dyn MockInstance>) {
     .await;
 }
 
+// Instant queries are evaluated with the same start and end.
+#[apply(standalone_instance_case)]
+async fn sql_insert_promql_instant_query(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+    let time = UNIX_EPOCH.checked_add(Duration::from_secs(8)).unwrap();
+
+    create_insert_query_assert(
+        instance,
+        r#"create table http_requests_total (
+            host string,
+            cpu double,
+            memory double,
+            ts timestamp TIME INDEX,
+            PRIMARY KEY (host),
+        );"#,
+        r#"insert into http_requests_total(host, cpu, memory, ts) values
+            ('host1', 66.6, 1024, 0),
+            ('host1', 43.1, 8192, 7000),
+            ('host2', 19.1, 10240, 9000);
+        "#,
+        "ceil(http_requests_total)",
+        time,
+        time,
+        Duration::from_secs(1),
+        Duration::from_secs(300),
+        "+---------------------+-----------+--------------+-------+\
+        \n| ts                  | ceil(cpu) | ceil(memory) | host  |\
+        \n+---------------------+-----------+--------------+-------+\
+        \n| 1970-01-01T00:00:08 | 44.0      | 8192.0       | host1 |\
+        \n+---------------------+-----------+--------------+-------+",
+    )
+    .await;
+}
+
+#[apply(standalone_instance_case)]
+async fn select_series_labels(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+    instance
+        .do_query(AGGREGATORS_CREATE_TABLE, QueryContext::arc())
+        .await;
+    instance
+        .do_query(
+            r#"insert into http_requests(job, instance, group, value, ts) values
+                ('api-server', '0', 'production', 100, 0),
+                ('api-server', '1', 'production', 200, 0),
+                ('api-server', '0', 'canary', 300, 10000),
+                ('app-server', '0', 'production', 500, 20000);"#,
+            QueryContext::arc(),
+        )
+        .await;
+
+    assert!(PromHandler::metric_names(instance.as_ref())
+        .await
+        .unwrap()
+        .contains(&"http_requests".to_string()));
+    let mut labels = PromHandler::label_names(instance.as_ref(), "http_requests")
+        .await
+        .unwrap()
+        .unwrap();
+    labels.sort();
+    assert_eq!(vec!["group", "instance", "job"], labels);
+    assert!(PromHandler::label_names(instance.as_ref(), "not_exist")
+        .await
+        .unwrap()
+        .is_none());
+
+    let Expr::VectorSelector(VectorSelector { matchers, .. }) =
+        promql_parser::parser::parse(r#"{job="api-server"}"#).unwrap() else { unreachable!() };
+    let selector = SeriesSelector {
+        metric: "http_requests".to_string(),
+        matchers,
+        start: Some(0),
+        end: Some(5000),
+    };
+    let output = instance
+        .series_labels(&selector, &["job".to_string(), "group".to_string()])
+        .await
+        .unwrap();
+    check_unordered_output_stream(
+        output,
+        "+------------+------------+\
+        \n| job        | group      |\
+        \n+------------+------------+\
+        \n| api-server | production |\
+        \n+------------+------------+",
+    )
+    .await;
+}
+
 const AGGREGATORS_CREATE_TABLE: &str = r#"create table http_requests (
     job string,
     instance string,
@@ -508,3 +601,292 @@ async fn binary_op_plain_columns(instance: Arc<dyn MockInstance>) {
     )
     .await;
 }
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn binary_op_on_labels(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="canary"} / on(job, instance) http_requests{group="production"}"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+---------------------+-------------------------------------------+\
+        \n| job        | instance | ts                  | http_requests.value / http_requests.value |\
+        \n+------------+----------+---------------------+-------------------------------------------+\
+        \n| api-server | 0        | 1970-01-01T00:00:00 | 3.0                                       |\
+        \n| api-server | 1        | 1970-01-01T00:00:00 | 2.0                                       |\
+        \n| app-server | 0        | 1970-01-01T00:00:00 | 1.4                                       |\
+        \n| app-server | 1        | 1970-01-01T00:00:00 | 1.3333333333333333                        |\
+        \n+------------+----------+---------------------+-------------------------------------------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn binary_op_group_left(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="production"} + on(job) group_left sum by (job) (http_requests)"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+------------+---------------------+------------------------------------------------+\
+        \n| job        | instance | group      | ts                  | http_requests.value + SUM(http_requests.value) |\
+        \n+------------+----------+------------+---------------------+------------------------------------------------+\
+        \n| api-server | 0        | production | 1970-01-01T00:00:00 | 1100.0                                         |\
+        \n| api-server | 1        | production | 1970-01-01T00:00:00 | 1200.0                                         |\
+        \n| app-server | 0        | production | 1970-01-01T00:00:00 | 3100.0                                         |\
+        \n| app-server | 1        | production | 1970-01-01T00:00:00 | 3200.0                                         |\
+        \n+------------+----------+------------+---------------------+------------------------------------------------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn set_op_and(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="canary"} and http_requests{instance="0"}"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+--------+---------------------+-------+\
+        \n| job        | instance | group  | ts                  | value |\
+        \n+------------+----------+--------+---------------------+-------+\
+        \n| api-server | 0        | canary | 1970-01-01T00:00:00 | 300.0 |\
+        \n| app-server | 0        | canary | 1970-01-01T00:00:00 | 700.0 |\
+        \n+------------+----------+--------+---------------------+-------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn set_op_or(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="canary"} or http_requests{instance="0"}"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+------------+---------------------+-------+\
+        \n| job        | instance | group      | ts                  | value |\
+        \n+------------+----------+------------+---------------------+-------+\
+        \n| api-server | 0        | canary     | 1970-01-01T00:00:00 | 300.0 |\
+        \n| api-server | 1        | canary     | 1970-01-01T00:00:00 | 400.0 |\
+        \n| app-server | 0        | canary     | 1970-01-01T00:00:00 | 700.0 |\
+        \n| app-server | 1        | canary     | 1970-01-01T00:00:00 | 800.0 |\
+        \n| api-server | 0        | production | 1970-01-01T00:00:00 | 100.0 |\
+        \n| app-server | 0        | production | 1970-01-01T00:00:00 | 500.0 |\
+        \n+------------+----------+------------+---------------------+-------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn set_op_unless(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests unless on(job) http_requests{job="api-server"}"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+------------+---------------------+-------+\
+        \n| job        | instance | group      | ts                  | value |\
+        \n+------------+----------+------------+---------------------+-------+\
+        \n| app-server | 0        | production | 1970-01-01T00:00:00 | 500.0 |\
+        \n| app-server | 1        | production | 1970-01-01T00:00:00 | 600.0 |\
+        \n| app-server | 0        | canary     | 1970-01-01T00:00:00 | 700.0 |\
+        \n| app-server | 1        | canary     | 1970-01-01T00:00:00 | 800.0 |\
+        \n+------------+----------+------------+---------------------+-------+",
+    )
+    .await;
+}
+
+const NULLABLE_TAGS_CREATE_TABLE: &str = r#"create table nullable_tags (
+    host string,
+    idc string,
+    "value" double,
+    ts timestamp TIME INDEX,
+    PRIMARY KEY (host, idc),
+);"#;
+
+const NULLABLE_TAGS_INSERT_DATA: &str = r#"insert into nullable_tags(host, idc, value, ts) values
+    ('host1', 'a', 1, 0),
+    ('host2', NULL, 2, 0);"#;
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn binary_op_nullable_tags(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    // series without the idc tag still match each other
+    create_insert_query_assert(
+        instance,
+        NULLABLE_TAGS_CREATE_TABLE,
+        NULLABLE_TAGS_INSERT_DATA,
+        r#"nullable_tags - nullable_tags"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+-------+-----+---------------------+-------------------------------------------+\
+        \n| host  | idc | ts                  | nullable_tags.value - nullable_tags.value |\
+        \n+-------+-----+---------------------+-------------------------------------------+\
+        \n| host1 | a   | 1970-01-01T00:00:00 | 0.0                                       |\
+        \n| host2 |     | 1970-01-01T00:00:00 | 0.0                                       |\
+        \n+-------+-----+---------------------+-------------------------------------------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn set_op_nullable_tags(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        NULLABLE_TAGS_CREATE_TABLE,
+        NULLABLE_TAGS_INSERT_DATA,
+        r#"nullable_tags and nullable_tags{host="host2"}"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+-------+-----+---------------------+-------+\
+        \n| host  | idc | ts                  | value |\
+        \n+-------+-----+---------------------+-------+\
+        \n| host2 |     | 1970-01-01T00:00:00 | 2.0   |\
+        \n+-------+-----+---------------------+-------+",
+    )
+    .await;
+}
+
+// eval instant at 50m http_requests / on(job) http_requests
+//   expect fail
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn binary_op_many_to_many_error(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+    instance
+        .do_query(AGGREGATORS_CREATE_TABLE, QueryContext::arc())
+        .await;
+    instance
+        .do_query(AGGREGATORS_INSERT_DATA, QueryContext::arc())
+        .await;
+
+    let query = PromQuery {
+        query: "http_requests / on(job) http_requests".to_string(),
+        start: "0".to_string(),
+        end: "100".to_string(),
+        step: "60s".to_string(),
+    };
+    let statement = QueryLanguageParser::parse_promql(&query).unwrap();
+    let Output::Stream(stream) = instance
+        .plan_exec(statement, QueryContext::arc())
+        .await
+        .unwrap() else { unreachable!() };
+    let err = util::collect_batches(stream).await.unwrap_err();
+    assert!(
+        err.to_string().contains(
+            "many-to-many matching not allowed: matching labels must be unique on one side"
+        ),
+        "{err}"
+    );
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn offset_modifier(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    // samples at 0s are shifted to 60s
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="canary"} offset 1m"#,
+        UNIX_EPOCH.checked_add(Duration::from_secs(60)).unwrap(),
+        UNIX_EPOCH.checked_add(Duration::from_secs(60)).unwrap(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+--------+---------------------+-------+\
+        \n| job        | instance | group  | ts                  | value |\
+        \n+------------+----------+--------+---------------------+-------+\
+        \n| api-server | 0        | canary | 1970-01-01T00:01:00 | 300.0 |\
+        \n| api-server | 1        | canary | 1970-01-01T00:01:00 | 400.0 |\
+        \n| app-server | 0        | canary | 1970-01-01T00:01:00 | 700.0 |\
+        \n| app-server | 1        | canary | 1970-01-01T00:01:00 | 800.0 |\
+        \n+------------+----------+--------+---------------------+-------+",
+    )
+    .await;
+}
+
+// This is not derived from prometheus
+// should apply to both instances. tracked in #1296
+#[apply(standalone_instance_case)]
+async fn at_modifier(instance: Arc<dyn MockInstance>) {
+    let instance = instance.frontend();
+
+    create_insert_query_assert(
+        instance,
+        AGGREGATORS_CREATE_TABLE,
+        AGGREGATORS_INSERT_DATA,
+        r#"http_requests{group="canary"} @ 0"#,
+        UNIX_EPOCH,
+        unix_epoch_plus_100s(),
+        Duration::from_secs(60),
+        Duration::from_secs(0),
+        "+------------+----------+--------+---------------------+-------+\
+        \n| job        | instance | group  | ts                  | value |\
+        \n+------------+----------+--------+---------------------+-------+\
+        \n| api-server | 0        | canary | 1970-01-01T00:00:00 | 300.0 |\
+        \n| api-server | 0        | canary | 1970-01-01T00:01:00 | 300.0 |\
+        \n| api-server | 1        | canary | 1970-01-01T00:00:00 | 400.0 |\
+        \n| api-server | 1        | canary | 1970-01-01T00:01:00 | 400.0 |\
+        \n| app-server | 0        | canary | 1970-01-01T00:00:00 | 700.0 |\
+        \n| app-server | 0        | canary | 1970-01-01T00:01:00 | 700.0 |\
+        \n| app-server | 1        | canary | 1970-01-01T00:00:00 | 800.0 |\
+        \n| app-server | 1        | canary | 1970-01-01T00:01:00 | 800.0 |\
+        \n+------------+----------+--------+---------------------+-------+",
+    )
+    .await;
+}
@@ -42,6 +42,7 @@ use table::error::TableOperationSnafu;
 use table::metadata::{TableInfo, TableInfoBuilder, TableMetaBuilder, TableType, TableVersion};
 use table::requests::{
     AlterKind, AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest,
+    RestoreTableRequest, TruncateTableRequest,
 };
 use table::table::{AlterContext, TableRef};
 use table::{error as table_error, Result as TableResult, Table};
@@ -150,6 +151,26 @@ impl<S: StorageEngine> TableEngine for MitoEngine<S> {
             .context(table_error::TableOperationSnafu)
     }
 
+    async fn truncate_table(
+        &self,
+        _ctx: &EngineContext,
+        request: TruncateTableRequest,
+    ) -> TableResult<bool> {
+        self.inner.truncate_table(request).await
+    }
+
+    async fn restore_table(
+        &self,
+        _ctx: &EngineContext,
+        request: RestoreTableRequest,
+    ) -> TableResult<TableRef> {
+        self.inner
+            .restore_table(request)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)
+    }
+
     async fn close(&self) -> TableResult<()> {
         self.inner.close().await
     }
@@ -306,6 +327,21 @@ fn build_column_family(
     ))
 }
 
+/// Returns options to open regions of the table.
+fn region_open_options(table_dir: &str, table_info: &TableInfo) -> OpenOptions {
+    let options = &table_info.meta.options;
+    OpenOptions {
+        parent_dir: table_dir.to_string(),
+        write_buffer_size: options.write_buffer_size.map(|s| s.0 as usize),
+        ttl: options.ttl,
+        compaction_time_window: options.compaction_time_window,
+        compaction_strategy: options.compaction_strategy,
+        sst_compression: options.sst_compression,
+        append_mode: options.append_mode,
+        snapshot: None,
+    }
+}
+
 fn validate_create_table_request(request: &CreateTableRequest) -> Result<()> {
     let ts_index = request
         .schema
@@ -408,6 +444,9 @@ impl<S: StorageEngine> MitoEngineInner<S> {
                     .map(|size| size.0 as usize),
                 ttl: request.table_options.ttl,
                 compaction_time_window: request.table_options.compaction_time_window,
+                compaction_strategy: request.table_options.compaction_strategy,
+                sst_compression: request.table_options.sst_compression,
+                append_mode: request.table_options.append_mode,
             };
 
             let region = self
@@ -506,16 +545,7 @@ impl<S: StorageEngine> MitoEngineInner<S> {
                 .await.map_err(BoxedError::new)
                 .context(TableOperationSnafu)? else { return Ok(None) };
 
-            let opts = OpenOptions {
-                parent_dir: table_dir.to_string(),
-                write_buffer_size: table_info
-                    .meta
-                    .options
-                    .write_buffer_size
-                    .map(|s| s.0 as usize),
-                ttl: table_info.meta.options.ttl,
-                compaction_time_window: table_info.meta.options.compaction_time_window,
-            };
+            let opts = region_open_options(&table_dir, &table_info);
 
             debug!(
                 "Opening table {}, table info recovered: {:?}",
@@ -655,6 +685,98 @@ impl<S: StorageEngine> MitoEngineInner<S> {
             .is_some())
     }
 
+    /// Opens regions of the source table at the snapshot and registers them as a read-only
+    /// table under the new name.
+    async fn restore_table(&self, req: RestoreTableRequest) -> Result<TableRef> {
+        let table_ref = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.table_name,
+        };
+        let source_ref = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.source_table_name,
+        };
+
+        let _lock = self.table_mutex.lock().await;
+        ensure!(
+            self.get_table(&table_ref).is_none(),
+            TableExistsSnafu {
+                table_name: table_ref.to_string(),
+            }
+        );
+        let source = self
+            .get_mito_table(&source_ref)
+            .context(error::TableNotFoundSnafu {
+                table_name: source_ref.to_string(),
+            })?;
+
+        // The pin is released once the restored table is closed or dropped.
+        let (mut table_info, snapshot_pin) = source.pin_snapshot(&req.snapshot_name).await?;
+        let table_id = table_info.ident.table_id;
+        table_info.name = req.table_name.clone();
+        // The restored table is only registered in memory.
+        table_info.table_type = TableType::Temporary;
+        let table_dir = table_dir(&req.catalog_name, &req.schema_name, table_id);
+        let opts = OpenOptions {
+            snapshot: Some(req.snapshot_name.clone()),
+            ..region_open_options(&table_dir, &table_info)
+        };
+
+        let engine_ctx = StorageEngineContext::default();
+        let mut regions = HashMap::with_capacity(table_info.meta.region_numbers.len());
+        for region_number in &table_info.meta.region_numbers {
+            let region_name = region_name(table_id, *region_number);
+            let region = self
+                .storage_engine
+                .open_region(&engine_ctx, &region_name, &opts)
+                .await
+                .map_err(BoxedError::new)
+                .context(error::OpenRegionSnafu {
+                    region_name: &region_name,
+                })?
+                .context(RegionNotFoundSnafu {
+                    table: source_ref.to_string(),
+                    region: *region_number,
+                })?;
+            regions.insert(*region_number, region);
+        }
+
+        let table = Arc::new(MitoTable::new_read_only(
+            table_info,
+            regions,
+            source.manifest().clone(),
+            snapshot_pin,
+        ));
+        self.tables
+            .write()
+            .unwrap()
+            .insert(table_ref.to_string(), table.clone());
+
+        logging::info!(
+            "Mito engine restored table {} from snapshot {} of table {}",
+            table_ref,
+            req.snapshot_name,
+            source_ref
+        );
+
+        Ok(table)
+    }
+
+    async fn truncate_table(&self, req: TruncateTableRequest) -> TableResult<bool> {
+        let table_ref = TableReference {
+            catalog: &req.catalog_name,
+            schema: &req.schema_name,
+            table: &req.table_name,
+        };
+        let Some(table) = self.get_mito_table(&table_ref) else { return Ok(false) };
+
+        logging::info!("Truncate table {}", table_ref);
+        table.truncate().await?;
+        Ok(true)
+    }
+
     async fn close(&self) -> TableResult<()> {
         let _lock = self.table_mutex.lock().await;
 
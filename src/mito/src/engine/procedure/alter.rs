@@ -32,7 +32,7 @@ use crate::error::{
     BuildTableMetaSnafu, TableNotFoundSnafu, UpdateTableManifestSnafu, VersionChangedSnafu,
 };
 use crate::manifest::action::{TableChange, TableMetaAction, TableMetaActionList};
-use crate::table::{create_alter_operation, MitoTable};
+use crate::table::{create_alter_operation, create_alter_options, MitoTable};
 
 /// Procedure to alter a [MitoTable].
 pub(crate) struct AlterMitoTable<S: StorageEngine> {
@@ -186,6 +186,22 @@ impl<S: StorageEngine> AlterMitoTable<S> {
         let new_info = self.new_info.as_mut().unwrap();
         let table_name = &self.data.request.table_name;
 
+        if let Some(options) = create_alter_options(&self.data.request.alter_kind, &new_info.meta) {
+            // Altering options is idempotent, so we always apply them to all regions.
+            for region in self.table.regions().values() {
+                logging::debug!(
+                    "start altering options of region {} of table {}, with options {:?}",
+                    region.name(),
+                    table_name,
+                    options,
+                );
+                region
+                    .alter_options(options.clone())
+                    .await
+                    .map_err(Error::from_error_ext)?;
+            }
+        }
+
         let Some(alter_op) = create_alter_operation(table_name, &self.data.request.alter_kind, &mut new_info.meta)
             .map_err(Error::from_error_ext)? else {
                 // Don't need to alter the region.
@@ -286,7 +302,11 @@ impl<S: StorageEngine> AlterMitoTable<S> {
             AlterKind::RenameTable { new_table_name } => {
                 new_info.name = new_table_name.clone();
             }
-            AlterKind::AddColumns { .. } | AlterKind::DropColumns { .. } => {
+            AlterKind::AddColumns { .. }
+            | AlterKind::DropColumns { .. }
+            | AlterKind::ModifyColumns { .. }
+            | AlterKind::SetTableOptions { .. }
+            | AlterKind::UnsetTableOptions { .. } => {
                 let table_meta = &current_info.meta;
                 let new_meta = table_meta
                     .builder_with_alter_kind(table_name, &self.data.request.alter_kind)
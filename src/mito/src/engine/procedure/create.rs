@@ -164,17 +164,27 @@ impl<S: StorageEngine> CreateMitoTable<S> {
         let write_buffer_size = table_options.write_buffer_size.map(|size| size.0 as usize);
         let ttl = table_options.ttl;
         let compaction_time_window = table_options.compaction_time_window;
+        let compaction_strategy = table_options.compaction_strategy;
+        let sst_compression = table_options.sst_compression;
+        let append_mode = table_options.append_mode;
         let open_opts = OpenOptions {
             parent_dir: table_dir.clone(),
             write_buffer_size,
             ttl,
             compaction_time_window,
+            compaction_strategy,
+            sst_compression,
+            append_mode,
+            snapshot: None,
         };
         let create_opts = CreateOptions {
             parent_dir: table_dir,
             write_buffer_size,
             ttl,
             compaction_time_window,
+            compaction_strategy,
+            sst_compression,
+            append_mode,
         };
 
         let primary_key_indices = &self.data.request.primary_key_indices;
@@ -14,10 +14,14 @@
 
 //! Tests for mito table engine.
 
+use std::time::Duration;
+
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_query::logical_plan::{DfExpr, Expr};
 use common_query::physical_plan::SessionContext;
 use common_recordbatch::util;
 use common_test_util::temp_dir::TempDir;
+use datafusion_common::{Column, ScalarValue};
 use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, RawSchema};
 use datatypes::value::Value;
@@ -30,14 +34,16 @@ use storage::config::EngineConfig as StorageEngineConfig;
 use storage::region::RegionImpl;
 use storage::EngineImpl;
 use store_api::manifest::Manifest;
-use store_api::storage::ReadContext;
+use store_api::storage::{CompactionStrategy, ReadContext};
 use table::requests::{
-    AddColumnRequest, AlterKind, DeleteRequest, FlushTableRequest, TableOptions,
+    AddColumnRequest, AlterKind, AsOf, DeleteRequest, FlushTableRequest, InsertRequest,
+    TableOptions,
 };
 
 use super::*;
 use crate::table::test_util::{
-    self, new_insert_request, schema_for_test, setup_table, TestEngineComponents, TABLE_NAME,
+    self, new_alter_request, new_insert_request, schema_for_test, setup_table,
+    TestEngineComponents, TABLE_NAME,
 };
 
 pub fn has_parquet_file(sst_dir: &str) -> bool {
@@ -580,6 +586,66 @@ async fn test_alter_table_remove_column() {
     assert_eq!(new_meta.region_numbers, old_meta.region_numbers);
 }
 
+#[tokio::test]
+async fn test_alter_table_options() {
+    let TestEngineComponents {
+        table_engine,
+        storage_engine,
+        object_store,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+    let ctx = EngineContext::default();
+
+    let new_alter_req = |alter_kind| AlterTableRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+        alter_kind,
+    };
+    let req = new_alter_req(AlterKind::SetTableOptions {
+        options: HashMap::from([
+            ("ttl".to_string(), "1h".to_string()),
+            ("compaction_time_window".to_string(), "60".to_string()),
+            ("compaction_strategy".to_string(), "twcs".to_string()),
+            ("twcs_max_files_in_window".to_string(), "8".to_string()),
+        ]),
+    });
+    let table = table_engine.alter_table(&ctx, req).await.unwrap();
+    let options = table.table_info().meta.options.clone();
+    assert_eq!(Some(Duration::from_secs(60 * 60)), options.ttl);
+    assert_eq!(Some(60), options.compaction_time_window);
+    assert_eq!(
+        CompactionStrategy::Twcs {
+            max_files_in_window: 8
+        },
+        options.compaction_strategy
+    );
+
+    let req = new_alter_req(AlterKind::UnsetTableOptions {
+        keys: vec!["compaction_time_window".to_string()],
+    });
+    let table = table_engine.alter_table(&ctx, req).await.unwrap();
+    let options = table.table_info().meta.options.clone();
+    assert_eq!(Some(Duration::from_secs(60 * 60)), options.ttl);
+    assert_eq!(None, options.compaction_time_window);
+
+    // Options are persisted in the manifest of the table.
+    let table_engine = MitoEngine::new(EngineConfig::default(), storage_engine, object_store);
+    let open_req = OpenTableRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: TABLE_NAME.to_string(),
+        table_id: 1,
+    };
+    let reopened = table_engine
+        .open_table(&ctx, open_req)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(options, reopened.table_info().meta.options);
+}
+
 #[tokio::test]
 async fn test_alter_rename_table() {
     let TestEngineComponents {
@@ -776,6 +842,148 @@ async fn test_table_delete_rows() {
     );
 }
 
+#[tokio::test]
+async fn test_table_get_row() {
+    let TestEngineComponents {
+        table_ref: table,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+    let hosts: VectorRef = Arc::new(StringVector::from(vec!["host1", "host2", "host1"]));
+    let cpus: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0]));
+    let memories: VectorRef = Arc::new(Float64Vector::from_vec(vec![1.0, 2.0, 3.0]));
+    let tss: VectorRef = Arc::new(TimestampMillisecondVector::from_vec(vec![1, 1, 2]));
+
+    columns_values.insert("host".to_string(), hosts);
+    columns_values.insert("cpu".to_string(), cpus);
+    columns_values.insert("memory".to_string(), memories);
+    columns_values.insert("ts".to_string(), tss);
+
+    let insert_req = new_insert_request("demo".to_string(), columns_values);
+    assert_eq!(3, table.insert(insert_req).await.unwrap());
+    table.flush(None, Some(true)).await.unwrap();
+
+    let row_key = |host: &str, ts: i64| {
+        vec![
+            Value::from(host),
+            Value::Timestamp(common_time::Timestamp::new_millisecond(ts)),
+        ]
+    };
+
+    let batch = table
+        .get(None, &row_key("host1", 2))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(1, batch.num_rows());
+    assert_eq!(Value::from(3.0f64), batch.column(1).get(0));
+
+    // Get with projection: cpu and memory.
+    let batch = table
+        .get(Some(&vec![1, 2]), &row_key("host2", 1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(2, batch.num_columns());
+    assert_eq!(Value::from(2.0f64), batch.column(0).get(0));
+
+    assert!(table
+        .get(None, &row_key("host2", 2))
+        .await
+        .unwrap()
+        .is_none());
+
+    // Scan with filters pinning the row key.
+    let filters = vec![Expr::from(
+        DfExpr::Column(Column::from_name("host"))
+            .eq(DfExpr::Literal(ScalarValue::Utf8(Some(
+                "host1".to_string(),
+            ))))
+            .and(DfExpr::Column(Column::from_name("ts")).eq(DfExpr::Literal(
+                ScalarValue::TimestampMillisecond(Some(1), None),
+            ))),
+    )];
+    let session_ctx = SessionContext::new();
+    let stream = table.scan(None, &filters, None).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect_batches(stream).await.unwrap();
+
+    assert_eq!(
+        batches.pretty_print().unwrap(),
+        "\
++-------+-----+--------+-------------------------+
+| host  | cpu | memory | ts                      |
++-------+-----+--------+-------------------------+
+| host1 | 1.0 | 1.0    | 1970-01-01T00:00:00.001 |
++-------+-----+--------+-------------------------+"
+    );
+}
+
+#[tokio::test]
+async fn test_scan_row_key_in_append_mode() {
+    let (_dir, object_store) =
+        test_util::new_test_object_store("test_scan_row_key_in_append_mode").await;
+    let compaction_scheduler = Arc::new(NoopCompactionScheduler::default());
+    let table_engine = MitoEngine::new(
+        EngineConfig::default(),
+        EngineImpl::new(
+            StorageEngineConfig::default(),
+            Arc::new(NoopLogStore::default()),
+            object_store.clone(),
+            compaction_scheduler,
+        ),
+        object_store,
+    );
+    let mut request = test_util::new_create_request(Arc::new(schema_for_test()));
+    request.table_options.append_mode = true;
+    let table = table_engine
+        .create_table(&EngineContext::default(), request)
+        .await
+        .unwrap();
+
+    // Writes the same row key twice.
+    for cpu in [1.0, 2.0] {
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+        columns_values.insert(
+            "host".to_string(),
+            Arc::new(StringVector::from(vec!["host1"])),
+        );
+        columns_values.insert(
+            "cpu".to_string(),
+            Arc::new(Float64Vector::from_vec(vec![cpu])),
+        );
+        columns_values.insert(
+            "memory".to_string(),
+            Arc::new(Float64Vector::from_vec(vec![cpu])),
+        );
+        columns_values.insert(
+            "ts".to_string(),
+            Arc::new(TimestampMillisecondVector::from_vec(vec![1])),
+        );
+        let insert_req = new_insert_request("demo".to_string(), columns_values);
+        assert_eq!(1, table.insert(insert_req).await.unwrap());
+    }
+
+    // Filters pinning the row key still return all duplicate rows.
+    let filters = vec![Expr::from(
+        DfExpr::Column(Column::from_name("host"))
+            .eq(DfExpr::Literal(ScalarValue::Utf8(Some(
+                "host1".to_string(),
+            ))))
+            .and(DfExpr::Column(Column::from_name("ts")).eq(DfExpr::Literal(
+                ScalarValue::TimestampMillisecond(Some(1), None),
+            ))),
+    )];
+    let session_ctx = SessionContext::new();
+    let stream = table.scan(None, &filters, None).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect_batches(stream).await.unwrap();
+    let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+    assert_eq!(2, num_rows);
+}
+
 #[tokio::test]
 async fn test_flush_table_all_regions() {
     let TestEngineComponents {
@@ -848,3 +1056,195 @@ async fn test_flush_table_with_region_id() {
 
     assert!(has_parquet_file(&region_dir));
 }
+
+fn new_row_request() -> InsertRequest {
+    let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+    columns_values.insert(
+        "host".to_string(),
+        Arc::new(StringVector::from(vec!["host5"])),
+    );
+    columns_values.insert(
+        "cpu".to_string(),
+        Arc::new(Float64Vector::from_vec(vec![5.0])),
+    );
+    columns_values.insert(
+        "memory".to_string(),
+        Arc::new(Float64Vector::from_vec(vec![5.0])),
+    );
+    columns_values.insert(
+        "ts".to_string(),
+        Arc::new(TimestampMillisecondVector::from_vec(vec![5])),
+    );
+    new_insert_request(TABLE_NAME.to_string(), columns_values)
+}
+
+async fn count_rows(table: &TableRef) -> usize {
+    let session_ctx = SessionContext::new();
+    let stream = table.scan(None, &[], None).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect(stream).await.unwrap();
+    batches.iter().map(|batch| batch.num_rows()).sum()
+}
+
+async fn count_rows_as_of(table: &TableRef, as_of: AsOf) -> usize {
+    let session_ctx = SessionContext::new();
+    let stream = table.scan_as_of(None, &[], None, as_of).await.unwrap();
+    let stream = stream.execute(0, session_ctx.task_ctx()).unwrap();
+    let batches = util::collect(stream).await.unwrap();
+    batches.iter().map(|batch| batch.num_rows()).sum()
+}
+
+#[tokio::test]
+async fn test_scan_as_of() {
+    let TestEngineComponents {
+        table_ref: table,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    setup_table(table.clone()).await;
+    assert_eq!(1, table.insert(new_row_request()).await.unwrap());
+
+    assert_eq!(0, count_rows_as_of(&table, AsOf::Sequence(0)).await);
+    assert_eq!(4, count_rows_as_of(&table, AsOf::Sequence(1)).await);
+    assert_eq!(5, count_rows_as_of(&table, AsOf::Sequence(2)).await);
+
+    // Rows are committed after the table is created.
+    let commit_time = |millis| AsOf::CommitTime(common_time::Timestamp::new_millisecond(millis));
+    assert_eq!(0, count_rows_as_of(&table, commit_time(0)).await);
+    let future = common_time::util::current_time_millis() + 3_600_000;
+    assert_eq!(5, count_rows_as_of(&table, commit_time(future)).await);
+}
+
+#[tokio::test]
+async fn test_table_get_row_without_region() {
+    let TestEngineComponents {
+        table_engine,
+        schema_ref,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    let mut request = test_util::new_create_request(schema_ref);
+    request.id = 2;
+    request.table_name = "no_region".to_string();
+    request.region_numbers = vec![];
+    let table = table_engine
+        .create_table(&EngineContext::default(), request)
+        .await
+        .unwrap();
+
+    let row_key = vec![
+        Value::from("host1"),
+        Value::Timestamp(common_time::Timestamp::new_millisecond(1)),
+    ];
+    let err = table.get(None, &row_key).await.unwrap_err();
+    assert!(err.to_string().contains("has no region"), "{err}");
+}
+
+#[tokio::test]
+async fn test_scan_as_of_multi_regions() {
+    let TestEngineComponents {
+        table_engine,
+        schema_ref,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    let mut request = test_util::new_create_request(schema_ref);
+    request.id = 2;
+    request.table_name = "multi_regions".to_string();
+    request.region_numbers = vec![0, 1];
+    let table = table_engine
+        .create_table(&EngineContext::default(), request)
+        .await
+        .unwrap();
+    setup_table(table.clone()).await;
+
+    // Regions have their own sequences.
+    assert!(table
+        .scan_as_of(None, &[], None, AsOf::Sequence(1))
+        .await
+        .is_err());
+    let future = common_time::util::current_time_millis() + 3_600_000;
+    let as_of = AsOf::CommitTime(common_time::Timestamp::new_millisecond(future));
+    assert_eq!(4, count_rows_as_of(&table, as_of).await);
+}
+
+#[tokio::test]
+async fn test_table_snapshot_and_restore() {
+    let TestEngineComponents {
+        table_engine,
+        table_ref: table,
+        dir: _dir,
+        ..
+    } = test_util::setup_test_engine_and_table().await;
+
+    setup_table(table.clone()).await;
+    table.create_snapshot("s1").await.unwrap();
+    // The name is taken.
+    assert!(table.create_snapshot("s1").await.is_err());
+    assert!(table.create_snapshot("s/1").await.is_err());
+
+    assert_eq!(1, table.insert(new_row_request()).await.unwrap());
+    assert_eq!(5, count_rows(&table).await);
+
+    let ctx = EngineContext::default();
+    let restore_request = |table_name: &str, snapshot_name: &str| RestoreTableRequest {
+        catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+        schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        table_name: table_name.to_string(),
+        source_table_name: TABLE_NAME.to_string(),
+        snapshot_name: snapshot_name.to_string(),
+    };
+    let restored = table_engine
+        .restore_table(&ctx, restore_request("restored", "s1"))
+        .await
+        .unwrap();
+    assert_eq!("restored", restored.table_info().name);
+    assert_eq!(TableType::Temporary, restored.table_type());
+    assert_eq!(4, count_rows(&restored).await);
+    assert!(table_engine.table_exists(
+        &ctx,
+        &TableReference {
+            catalog: DEFAULT_CATALOG_NAME,
+            schema: DEFAULT_SCHEMA_NAME,
+            table: "restored",
+        }
+    ));
+
+    // The restored table is read-only.
+    assert!(restored.insert(new_row_request()).await.is_err());
+    let alter_kind = AlterKind::RenameTable {
+        new_table_name: "foo".to_string(),
+    };
+    let mut alter_request = new_alter_request(alter_kind);
+    alter_request.table_name = "restored".to_string();
+    assert!(restored
+        .alter(AlterContext::new(), &alter_request)
+        .await
+        .is_err());
+    assert!(restored.drop_snapshot("s1").await.is_err());
+
+    // Names are taken or snapshot doesn't exist.
+    assert!(table_engine
+        .restore_table(&ctx, restore_request("restored", "s1"))
+        .await
+        .is_err());
+    assert!(table_engine
+        .restore_table(&ctx, restore_request("restored2", "s2"))
+        .await
+        .is_err());
+
+    // The source table is still writable.
+    assert_eq!(5, count_rows(&table).await);
+    // The snapshot can't be dropped until the restored table is closed.
+    assert!(table.drop_snapshot("s1").await.is_err());
+    restored.close().await.unwrap();
+    table.drop_snapshot("s1").await.unwrap();
+    assert!(table.drop_snapshot("s1").await.is_err());
+    assert!(table_engine
+        .restore_table(&ctx, restore_request("restored2", "s1"))
+        .await
+        .is_err());
+}
@@ -29,6 +29,13 @@ pub enum Error {
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to open region {}, source: {}", region_name, source))]
+    OpenRegion {
+        region_name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
     #[snafu(display(
         "Failed to build table meta for table: {}, source: {}",
         table_name,
@@ -180,6 +187,9 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Table {} has no region", table))]
+    NoRegion { table: String, location: Location },
+
     #[snafu(display("Invalid region name: {}", region_name))]
     InvalidRegionName {
         region_name: String,
@@ -194,6 +204,68 @@ pub enum Error {
         expect: TableVersion,
         actual: TableVersion,
     },
+
+    #[snafu(display(
+        "Failed to access snapshot {} of table {}, source: {}",
+        snapshot,
+        table_name,
+        source
+    ))]
+    TableSnapshot {
+        #[snafu(backtrace)]
+        source: storage::error::Error,
+        table_name: String,
+        snapshot: String,
+    },
+
+    #[snafu(display("Snapshot {} not found in table {}", snapshot, table_name))]
+    SnapshotNotFound {
+        table_name: String,
+        snapshot: String,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to encode snapshot {}, source: {}", snapshot, source))]
+    EncodeSnapshot {
+        snapshot: String,
+        source: serde_json::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to decode snapshot {}, source: {}", snapshot, source))]
+    DecodeSnapshot {
+        snapshot: String,
+        source: serde_json::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Table {} is read-only", table_name))]
+    ReadOnlyTable {
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Snapshot {} of table {} is still read by restored tables",
+        snapshot,
+        table_name
+    ))]
+    SnapshotInUse {
+        table_name: String,
+        snapshot: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Reading table {} at a sequence requires exactly one region, actual: {}",
+        table_name,
+        num_regions
+    ))]
+    SequenceOfMultiRegions {
+        table_name: String,
+        num_regions: usize,
+        location: Location,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -203,7 +275,7 @@ impl ErrorExt for Error {
         use Error::*;
 
         match self {
-            CreateRegion { source, .. } => source.status_code(),
+            CreateRegion { source, .. } | OpenRegion { source, .. } => source.status_code(),
 
             AlterTable { source, .. } => source.status_code(),
 
@@ -219,12 +291,20 @@ impl ErrorExt for Error {
             | MissingTimestampIndex { .. }
             | TableNotFound { .. }
             | InvalidRawSchema { .. }
-            | VersionChanged { .. } => StatusCode::InvalidArguments,
-
-            TableInfoNotFound { .. } | ConvertRaw { .. } => StatusCode::Unexpected,
-
-            ScanTableManifest { .. } | UpdateTableManifest { .. } => StatusCode::StorageUnavailable,
-            RegionNotFound { .. } => StatusCode::Internal,
+            | VersionChanged { .. }
+            | SnapshotNotFound { .. }
+            | SnapshotInUse { .. } => StatusCode::InvalidArguments,
+
+            TableInfoNotFound { .. }
+            | ConvertRaw { .. }
+            | EncodeSnapshot { .. }
+            | DecodeSnapshot { .. } => StatusCode::Unexpected,
+
+            ScanTableManifest { .. } | UpdateTableManifest { .. } | TableSnapshot { .. } => {
+                StatusCode::StorageUnavailable
+            }
+            ReadOnlyTable { .. } | SequenceOfMultiRegions { .. } => StatusCode::Unsupported,
+            RegionNotFound { .. } | NoRegion { .. } => StatusCode::Internal,
             InvalidRegionName { .. } => StatusCode::Internal,
         }
     }
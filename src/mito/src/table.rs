@@ -23,20 +23,23 @@ use std::sync::Arc;
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use common_error::ext::BoxedError;
-use common_query::logical_plan::Expr;
+use common_query::logical_plan::{DfExpr, Expr};
 use common_query::physical_plan::PhysicalPlanRef;
 use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
-use common_recordbatch::{RecordBatch, RecordBatchStream};
+use common_recordbatch::{RecordBatch, RecordBatchStream, RecordBatches};
 use common_telemetry::logging;
+use datafusion::logical_expr::{BinaryExpr, Operator};
 use datatypes::schema::Schema;
+use datatypes::value::Value;
 use futures::task::{Context, Poll};
 use futures::Stream;
 use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AddColumn, AlterOperation, AlterRequest, ChunkReader, FlushContext, ReadContext, Region,
-    RegionMeta, RegionNumber, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
+    AddColumn, AlterOperation, AlterOptions, AlterRequest, ChunkReader, CompactContext,
+    DeleteRange, FlushContext, GetRequest, ModifyColumn, ReadContext, Region, RegionMeta,
+    RegionNumber, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error as table_error;
 use table::error::{RegionSchemaMismatchSnafu, Result as TableResult, TableOperationSnafu};
@@ -44,7 +47,8 @@ use table::metadata::{
     FilterPushDownType, RawTableInfo, TableInfo, TableInfoRef, TableMeta, TableType,
 };
 use table::requests::{
-    AddColumnRequest, AlterKind, AlterTableRequest, DeleteRequest, InsertRequest,
+    AddColumnRequest, AlterKind, AlterTableRequest, AsOf, DeleteRangeRequest, DeleteRequest,
+    InsertRequest, ModifyColumnRequest,
 };
 use table::table::scan::SimpleTableScan;
 use table::table::{AlterContext, RegionStat, Table};
@@ -52,7 +56,9 @@ use tokio::sync::Mutex;
 
 use crate::error;
 use crate::error::{
-    ProjectedColumnNotFoundSnafu, RegionNotFoundSnafu, Result, ScanTableManifestSnafu,
+    DecodeSnapshotSnafu, EncodeSnapshotSnafu, NoRegionSnafu, ProjectedColumnNotFoundSnafu,
+    ReadOnlyTableSnafu, RegionNotFoundSnafu, Result, ScanTableManifestSnafu,
+    SequenceOfMultiRegionsSnafu, SnapshotInUseSnafu, SnapshotNotFoundSnafu, TableSnapshotSnafu,
     UpdateTableManifestSnafu,
 };
 use crate::manifest::action::*;
@@ -70,6 +76,60 @@ pub struct MitoTable<R: Region> {
     table_info: ArcSwap<TableInfo>,
     regions: HashMap<RegionNumber, R>,
     alter_lock: Mutex<()>,
+    /// Whether the table is restored from a snapshot and rejects modifications.
+    read_only: bool,
+    /// Snapshots of this table read by restored tables.
+    snapshot_readers: SnapshotReadersRef,
+    /// Pin of the source snapshot if the table is restored from it, released on close.
+    snapshot_pin: std::sync::Mutex<Option<SnapshotPin>>,
+}
+
+/// Number of restored tables reading each snapshot of a table.
+#[derive(Debug, Default)]
+pub(crate) struct SnapshotReaders {
+    counts: std::sync::Mutex<HashMap<String, usize>>,
+}
+
+type SnapshotReadersRef = Arc<SnapshotReaders>;
+
+impl SnapshotReaders {
+    fn is_pinned(&self, name: &str) -> bool {
+        self.counts.lock().unwrap().contains_key(name)
+    }
+}
+
+/// Keeps a snapshot from being dropped while a restored table reads its files.
+#[derive(Debug)]
+pub(crate) struct SnapshotPin {
+    readers: SnapshotReadersRef,
+    name: String,
+}
+
+impl SnapshotPin {
+    fn new(readers: SnapshotReadersRef, name: &str) -> SnapshotPin {
+        *readers
+            .counts
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_default() += 1;
+        SnapshotPin {
+            readers,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Drop for SnapshotPin {
+    fn drop(&mut self) {
+        let mut counts = self.readers.counts.lock().unwrap();
+        if let Some(count) = counts.get_mut(&self.name) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&self.name);
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -141,71 +201,38 @@ impl<R: Region> Table for MitoTable<R> {
         filters: &[Expr],
         _limit: Option<usize>,
     ) -> TableResult<PhysicalPlanRef> {
-        let read_ctx = ReadContext::default();
-        let mut readers = Vec::with_capacity(self.regions.len());
-        let mut first_schema: Option<Arc<Schema>> = None;
-
-        let table_info = self.table_info.load();
-        // TODO(hl): Currently the API between frontend and datanode is under refactoring in
-        // https://github.com/GreptimeTeam/greptimedb/issues/597 . Once it's finished, query plan
-        // can carry filtered region info to avoid scanning all regions on datanode.
-        for region in self.regions.values() {
-            let snapshot = region
-                .snapshot(&read_ctx)
+        // Filters pin all row key columns, so at most one row matches them, we could get
+        // the row directly instead of merging all data in regions. Rows with the same key
+        // aren't deduped in append mode, so we still need to scan all of them.
+        let append_mode = self.table_info.load().meta.options.append_mode;
+        if let Some(row_key) = self.row_key_from_filters(filters).filter(|_| !append_mode) {
+            let (schema, batch) = self.get_row(projection, &row_key).await?;
+            let batches = RecordBatches::try_new(schema, batch.into_iter().collect())
                 .map_err(BoxedError::new)
                 .context(table_error::TableOperationSnafu)?;
-            let projection = self
-                .transform_projection(region, projection.cloned())
-                .map_err(BoxedError::new)
-                .context(table_error::TableOperationSnafu)?;
-            let filters = filters.into();
-            let scan_request = ScanRequest {
-                projection,
-                filters,
-                ..Default::default()
-            };
-            let reader = snapshot
-                .scan(&read_ctx, scan_request)
-                .await
-                .map_err(BoxedError::new)
-                .context(table_error::TableOperationSnafu)?
-                .reader;
-
-            let schema = reader.user_schema().clone();
-            if let Some(first_schema) = &first_schema {
-                // TODO(hl): we assume all regions' schemas are the same, but undergoing table altering
-                // may make these schemas inconsistent.
-                ensure!(
-                    first_schema.version() == schema.version(),
-                    RegionSchemaMismatchSnafu {
-                        table: common_catalog::format_full_table_name(
-                            &table_info.catalog_name,
-                            &table_info.schema_name,
-                            &table_info.name
-                        )
-                    }
-                );
-            } else {
-                first_schema = Some(schema);
-            }
-            readers.push(reader);
+            return Ok(Arc::new(SimpleTableScan::new(batches.as_stream())));
         }
 
-        // TODO(hl): we assume table contains at least one region, but with region migration this
-        // assumption may become invalid.
-        let stream_schema = first_schema.unwrap();
-        let schema = stream_schema.clone();
-        let stream = Box::pin(async_stream::try_stream! {
-            for mut reader in readers {
-                while let Some(chunk) = reader.next_chunk().await.map_err(BoxedError::new).context(ExternalSnafu)? {
-                    let chunk = reader.project_chunk(chunk);
-                    yield RecordBatch::new(stream_schema.clone(), chunk.columns)?
-                }
-            }
-        });
+        self.scan_regions(projection, filters, None).await
+    }
 
-        let stream = Box::pin(ChunkStream { schema, stream });
-        Ok(Arc::new(SimpleTableScan::new(stream)))
+    async fn scan_as_of(
+        &self,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        _limit: Option<usize>,
+        as_of: AsOf,
+    ) -> TableResult<PhysicalPlanRef> {
+        self.scan_regions(projection, filters, Some(as_of)).await
+    }
+
+    async fn get(
+        &self,
+        projection: Option<&Vec<usize>>,
+        row_key: &[Value],
+    ) -> TableResult<Option<RecordBatch>> {
+        let (_, batch) = self.get_row(projection, row_key).await?;
+        Ok(batch)
     }
 
     fn supports_filters_pushdown(&self, filters: &[&Expr]) -> TableResult<Vec<FilterPushDownType>> {
@@ -215,6 +242,7 @@ impl<R: Region> Table for MitoTable<R> {
     /// Alter table changes the schemas of the table.
     async fn alter(&self, _context: AlterContext, req: &AlterTableRequest) -> TableResult<()> {
         let _lock = self.alter_lock.lock().await;
+        self.ensure_writable()?;
 
         let table_info = self.table_info();
         let table_name = &table_info.name;
@@ -225,7 +253,11 @@ impl<R: Region> Table for MitoTable<R> {
             AlterKind::RenameTable { new_table_name } => {
                 new_info.name = new_table_name.clone();
             }
-            AlterKind::AddColumns { .. } | AlterKind::DropColumns { .. } => {
+            AlterKind::AddColumns { .. }
+            | AlterKind::DropColumns { .. }
+            | AlterKind::ModifyColumns { .. }
+            | AlterKind::SetTableOptions { .. }
+            | AlterKind::UnsetTableOptions { .. } => {
                 let table_meta = &table_info.meta;
                 let new_meta = table_meta
                     .builder_with_alter_kind(table_name, &req.alter_kind)?
@@ -284,6 +316,21 @@ impl<R: Region> Table for MitoTable<R> {
                     .context(TableOperationSnafu)?;
             }
         }
+        if let Some(options) = create_alter_options(&req.alter_kind, &new_info.meta) {
+            for region in self.regions().values() {
+                logging::debug!(
+                    "start altering options of region {} of table {}, with options {:?}",
+                    region.name(),
+                    table_name,
+                    options,
+                );
+                region
+                    .alter_options(options.clone())
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(TableOperationSnafu)?;
+            }
+        }
         // Update in memory metadata of the table.
         self.set_table_info(new_info);
 
@@ -323,6 +370,34 @@ impl<R: Region> Table for MitoTable<R> {
         Ok(rows_deleted)
     }
 
+    async fn delete_range(&self, request: DeleteRangeRequest) -> TableResult<()> {
+        let range = DeleteRange {
+            keys: request.key_values,
+            start: request.start,
+            end: request.end,
+        };
+        logging::debug!(
+            "Delete range from table {}, range: {:?}",
+            self.table_info().name,
+            range
+        );
+
+        // Rows in the range may be in any region.
+        for region in self.regions.values() {
+            let mut write_request = region.write_request();
+            write_request
+                .delete_range(range.clone())
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            region
+                .write(&WriteContext::default(), write_request)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+        }
+        Ok(())
+    }
+
     async fn flush(
         &self,
         region_number: Option<RegionNumber>,
@@ -349,11 +424,122 @@ impl<R: Region> Table for MitoTable<R> {
         Ok(())
     }
 
+    async fn compact(
+        &self,
+        region_number: Option<RegionNumber>,
+        wait: Option<bool>,
+    ) -> TableResult<()> {
+        let compact_ctx = wait.map(|wait| CompactContext { wait }).unwrap_or_default();
+        if let Some(region_number) = region_number {
+            if let Some(region) = self.regions.get(&region_number) {
+                region
+                    .compact(&compact_ctx)
+                    .await
+                    .map_err(BoxedError::new)
+                    .context(table_error::TableOperationSnafu)?;
+            }
+        } else {
+            futures::future::try_join_all(
+                self.regions
+                    .values()
+                    .map(|region| region.compact(&compact_ctx)),
+            )
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+        }
+
+        Ok(())
+    }
+
+    async fn create_snapshot(&self, name: &str) -> TableResult<()> {
+        // Holds the alter lock so the saved table info matches schemas of regions.
+        let _lock = self.alter_lock.lock().await;
+        self.ensure_writable()?;
+
+        let table_info = self.table_info();
+        logging::info!("Create snapshot {} of table {}", name, table_info.name);
+
+        futures::future::try_join_all(
+            self.regions
+                .values()
+                .map(|region| region.save_snapshot(name)),
+        )
+        .await
+        .map_err(BoxedError::new)
+        .context(table_error::TableOperationSnafu)?;
+
+        // Saves the table info last, so a snapshot is visible only after all regions have it.
+        let raw_info = RawTableInfo::from(TableInfo::clone(&*table_info));
+        let bytes = serde_json::to_vec(&raw_info)
+            .context(EncodeSnapshotSnafu { snapshot: name })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+        self.manifest
+            .save_snapshot(name, &bytes)
+            .await
+            .context(TableSnapshotSnafu {
+                table_name: &table_info.name,
+                snapshot: name,
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+
+        Ok(())
+    }
+
+    async fn drop_snapshot(&self, name: &str) -> TableResult<()> {
+        let _lock = self.alter_lock.lock().await;
+        self.ensure_writable()?;
+
+        // Ensures the snapshot exists.
+        self.load_snapshot_info(name)
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+
+        let table_name = &self.table_info().name;
+        // Files of the snapshot are purged on drop, so restored tables must be closed first.
+        if self.snapshot_readers.is_pinned(name) {
+            return SnapshotInUseSnafu {
+                table_name,
+                snapshot: name,
+            }
+            .fail()
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu);
+        }
+        logging::info!("Drop snapshot {} of table {}", name, table_name);
+
+        futures::future::try_join_all(
+            self.regions
+                .values()
+                .map(|region| region.drop_snapshot(name)),
+        )
+        .await
+        .map_err(BoxedError::new)
+        .context(table_error::TableOperationSnafu)?;
+
+        self.manifest
+            .delete_snapshot(name)
+            .await
+            .context(TableSnapshotSnafu {
+                table_name,
+                snapshot: name,
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+
+        Ok(())
+    }
+
     async fn close(&self) -> TableResult<()> {
         futures::future::try_join_all(self.regions.values().map(|region| region.close()))
             .await
             .map_err(BoxedError::new)
             .context(table_error::TableOperationSnafu)?;
+        // Closed regions no longer read files of the snapshot.
+        self.snapshot_pin.lock().unwrap().take();
 
         Ok(())
     }
@@ -405,9 +591,38 @@ impl<R: Region> MitoTable<R> {
             regions,
             manifest,
             alter_lock: Mutex::new(()),
+            read_only: false,
+            snapshot_readers: SnapshotReadersRef::default(),
+            snapshot_pin: std::sync::Mutex::new(None),
         }
     }
 
+    /// Creates a read-only table from regions opened at the snapshot held by `snapshot_pin`.
+    pub(crate) fn new_read_only(
+        table_info: TableInfo,
+        regions: HashMap<RegionNumber, R>,
+        manifest: TableManifest,
+        snapshot_pin: SnapshotPin,
+    ) -> Self {
+        Self {
+            read_only: true,
+            snapshot_pin: std::sync::Mutex::new(Some(snapshot_pin)),
+            ..Self::new(table_info, regions, manifest)
+        }
+    }
+
+    fn ensure_writable(&self) -> TableResult<()> {
+        if self.read_only {
+            return ReadOnlyTableSnafu {
+                table_name: &self.table_info().name,
+            }
+            .fail()
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu);
+        }
+        Ok(())
+    }
+
     /// Transform projection which is based on table schema
     /// into projection based on region schema.
     fn transform_projection(
@@ -464,6 +679,175 @@ impl<R: Region> MitoTable<R> {
             .transpose()
     }
 
+    /// Scans all regions of the table, reads the latest version if `as_of` is `None`.
+    async fn scan_regions(
+        &self,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        as_of: Option<AsOf>,
+    ) -> TableResult<PhysicalPlanRef> {
+        // Sequences are assigned by each region, so a sequence only identifies a version
+        // of a table with one region.
+        if matches!(as_of, Some(AsOf::Sequence(_))) && self.regions.len() != 1 {
+            return SequenceOfMultiRegionsSnafu {
+                table_name: &self.table_info().name,
+                num_regions: self.regions.len(),
+            }
+            .fail()
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu);
+        }
+
+        let read_ctx = ReadContext::default();
+        let mut readers = Vec::with_capacity(self.regions.len());
+        let mut first_schema: Option<Arc<Schema>> = None;
+
+        let table_info = self.table_info.load();
+        // TODO(hl): Currently the API between frontend and datanode is under refactoring in
+        // https://github.com/GreptimeTeam/greptimedb/issues/597 . Once it's finished, query plan
+        // can carry filtered region info to avoid scanning all regions on datanode.
+        for region in self.regions.values() {
+            let snapshot = region
+                .snapshot(&read_ctx)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let projection = self
+                .transform_projection(region, projection.cloned())
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let filters = filters.into();
+            let mut scan_request = ScanRequest {
+                projection,
+                filters,
+                ..Default::default()
+            };
+            match as_of {
+                Some(AsOf::Sequence(sequence)) => scan_request.sequence = Some(sequence),
+                Some(AsOf::CommitTime(time)) => scan_request.commit_time = Some(time),
+                None => (),
+            }
+            let reader = snapshot
+                .scan(&read_ctx, scan_request)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?
+                .reader;
+
+            let schema = reader.user_schema().clone();
+            if let Some(first_schema) = &first_schema {
+                // TODO(hl): we assume all regions' schemas are the same, but undergoing table altering
+                // may make these schemas inconsistent.
+                ensure!(
+                    first_schema.version() == schema.version(),
+                    RegionSchemaMismatchSnafu {
+                        table: common_catalog::format_full_table_name(
+                            &table_info.catalog_name,
+                            &table_info.schema_name,
+                            &table_info.name
+                        )
+                    }
+                );
+            } else {
+                first_schema = Some(schema);
+            }
+            readers.push(reader);
+        }
+
+        // TODO(hl): we assume table contains at least one region, but with region migration this
+        // assumption may become invalid.
+        let stream_schema = first_schema.unwrap();
+        let schema = stream_schema.clone();
+        let stream = Box::pin(async_stream::try_stream! {
+            for mut reader in readers {
+                while let Some(chunk) = reader.next_chunk().await.map_err(BoxedError::new).context(ExternalSnafu)? {
+                    let chunk = reader.project_chunk(chunk);
+                    yield RecordBatch::new(stream_schema.clone(), chunk.columns)?
+                }
+            }
+        });
+
+        let stream = Box::pin(ChunkStream { schema, stream });
+        Ok(Arc::new(SimpleTableScan::new(stream)))
+    }
+
+    /// Gets the row by `row_key` from regions, returns the projected schema and the row.
+    async fn get_row(
+        &self,
+        projection: Option<&Vec<usize>>,
+        row_key: &[Value],
+    ) -> TableResult<(SchemaRef, Option<RecordBatch>)> {
+        let read_ctx = ReadContext::default();
+        let mut projected_schema = None;
+        for region in self.regions.values() {
+            let snapshot = region
+                .snapshot(&read_ctx)
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let projection = self
+                .transform_projection(region, projection.cloned())
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            let schema = project_schema(snapshot.schema(), projection.as_deref())
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+
+            let request = GetRequest {
+                projection,
+                row_key: row_key.to_vec(),
+                ..Default::default()
+            };
+            let response = snapshot
+                .get(&read_ctx, request)
+                .await
+                .map_err(BoxedError::new)
+                .context(table_error::TableOperationSnafu)?;
+            // Regions don't overlap, so the row is in at most one region.
+            if let Some(chunk) = response.chunk {
+                let batch = RecordBatch::new(schema.clone(), chunk.columns)
+                    .map_err(BoxedError::new)
+                    .context(table_error::TableOperationSnafu)?;
+                return Ok((schema, Some(batch)));
+            }
+            projected_schema = Some(schema);
+        }
+
+        // The table may have no region after its regions are migrated.
+        let schema = projected_schema
+            .with_context(|| NoRegionSnafu {
+                table: self.table_info().name.clone(),
+            })
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+        Ok((schema, None))
+    }
+
+    /// Returns the row key if `filters` pin every row key column to a value.
+    fn row_key_from_filters(&self, filters: &[Expr]) -> Option<Vec<Value>> {
+        let table_info = self.table_info();
+        let schema = &table_info.meta.schema;
+        let ts_index = schema.timestamp_index()?;
+
+        let mut equalities = HashMap::new();
+        for filter in filters {
+            collect_equalities(filter.df_expr(), &mut equalities);
+        }
+
+        // Row key of regions consists of the primary key and then the time index.
+        table_info
+            .meta
+            .primary_key_indices
+            .iter()
+            .filter(|idx| **idx != ts_index)
+            .chain(std::iter::once(&ts_index))
+            .map(|idx| {
+                let column = &schema.column_schemas()[*idx];
+                let value = equalities.get(column.name.as_str())?;
+                // Values of other types can't be used to compare with the row key.
+                (value.data_type() == column.data_type).then(|| value.clone())
+            })
+            .collect()
+    }
+
     pub async fn create(
         table_name: &str,
         table_dir: &str,
@@ -542,6 +926,49 @@ impl<R: Region> MitoTable<R> {
         &self.regions
     }
 
+    /// Removes all rows in regions of the table.
+    pub async fn truncate(&self) -> TableResult<()> {
+        futures::future::try_join_all(self.regions.values().map(|region| region.truncate()))
+            .await
+            .map_err(BoxedError::new)
+            .context(table_error::TableOperationSnafu)?;
+        Ok(())
+    }
+
+    /// Loads the table info saved in the named snapshot.
+    pub(crate) async fn load_snapshot_info(&self, name: &str) -> Result<TableInfo> {
+        let table_name = &self.table_info().name;
+        let bytes = self
+            .manifest
+            .load_snapshot(name)
+            .await
+            .context(TableSnapshotSnafu {
+                table_name,
+                snapshot: name,
+            })?
+            .context(SnapshotNotFoundSnafu {
+                table_name,
+                snapshot: name,
+            })?;
+        let raw_info: RawTableInfo =
+            serde_json::from_slice(&bytes).context(DecodeSnapshotSnafu { snapshot: name })?;
+
+        TableInfo::try_from(raw_info).context(error::ConvertRawSnafu)
+    }
+
+    /// Loads the table info saved in the named snapshot and pins the snapshot, so it can't
+    /// be dropped until the returned pin is released.
+    pub(crate) async fn pin_snapshot(&self, name: &str) -> Result<(TableInfo, SnapshotPin)> {
+        // Serializes with dropping snapshots.
+        let _lock = self.alter_lock.lock().await;
+        let table_info = self.load_snapshot_info(name).await?;
+
+        Ok((
+            table_info,
+            SnapshotPin::new(self.snapshot_readers.clone(), name),
+        ))
+    }
+
     pub fn set_table_info(&self, table_info: TableInfo) {
         self.table_info.swap(Arc::new(table_info));
     }
@@ -557,6 +984,36 @@ impl<R: Region> MitoTable<R> {
     }
 }
 
+/// Collects `column = literal` expressions connected by `AND` in `expr`.
+fn collect_equalities<'a>(expr: &'a DfExpr, equalities: &mut HashMap<&'a str, Value>) {
+    let DfExpr::BinaryExpr(BinaryExpr { left, op, right }) = expr else { return };
+    match (op, left.as_ref(), right.as_ref()) {
+        (Operator::And, left, right) => {
+            collect_equalities(left, equalities);
+            collect_equalities(right, equalities);
+        }
+        (Operator::Eq, DfExpr::Column(column), DfExpr::Literal(scalar))
+        | (Operator::Eq, DfExpr::Literal(scalar), DfExpr::Column(column)) => {
+            if let Ok(value) = Value::try_from(scalar.clone()) {
+                equalities.insert(&column.name, value);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn project_schema(
+    schema: &SchemaRef,
+    projection: Option<&[usize]>,
+) -> datatypes::error::Result<SchemaRef> {
+    let Some(projection) = projection else { return Ok(schema.clone()) };
+    let column_schemas = projection
+        .iter()
+        .map(|idx| schema.column_schemas()[*idx].clone())
+        .collect();
+    Ok(Arc::new(Schema::try_new(column_schemas)?))
+}
+
 /// Create [`AlterOperation`] according to given `alter_kind`.
 pub(crate) fn create_alter_operation(
     table_name: &str,
@@ -570,8 +1027,31 @@ pub(crate) fn create_alter_operation(
         AlterKind::DropColumns { names } => Ok(Some(AlterOperation::DropColumns {
             names: names.to_vec(),
         })),
+        AlterKind::ModifyColumns { columns } => Ok(Some(create_modify_columns_operation(columns))),
         // No need to build alter operation when reaming tables.
         AlterKind::RenameTable { .. } => Ok(None),
+        // Table options are applied to regions by [`create_alter_options`].
+        AlterKind::SetTableOptions { .. } | AlterKind::UnsetTableOptions { .. } => Ok(None),
+    }
+}
+
+/// Create [`AlterOptions`] for regions if `alter_kind` alters options of the table.
+pub(crate) fn create_alter_options(
+    alter_kind: &AlterKind,
+    table_meta: &TableMeta,
+) -> Option<AlterOptions> {
+    match alter_kind {
+        AlterKind::SetTableOptions { .. } | AlterKind::UnsetTableOptions { .. } => {
+            let options = &table_meta.options;
+            Some(AlterOptions {
+                write_buffer_size: options.write_buffer_size.map(|size| size.0 as usize),
+                ttl: options.ttl,
+                compaction_time_window: options.compaction_time_window,
+                compaction_strategy: options.compaction_strategy,
+                sst_compression: options.sst_compression,
+            })
+        }
+        _ => None,
     }
 }
 
@@ -596,6 +1076,23 @@ fn create_add_columns_operation(
     Ok(Some(AlterOperation::AddColumns { columns }))
 }
 
+fn create_modify_columns_operation(requests: &[ModifyColumnRequest]) -> AlterOperation {
+    let columns = requests
+        .iter()
+        .map(|request| {
+            let column_schema = &request.column_schema;
+            ModifyColumn {
+                name: column_schema.name.clone(),
+                data_type: column_schema.data_type.clone(),
+                is_nullable: column_schema.is_nullable(),
+                default_constraint: column_schema.default_constraint().cloned(),
+            }
+        })
+        .collect();
+
+    AlterOperation::ModifyColumns { columns }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,9 +26,10 @@ use datatypes::schema::{ColumnSchema, Schema};
 use storage::metadata::{RegionMetaImpl, RegionMetadata};
 use storage::write_batch::WriteBatch;
 use store_api::storage::{
-    AlterRequest, Chunk, ChunkReader, CreateOptions, EngineContext, FlushContext, GetRequest,
-    GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, ScanRequest,
-    ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
+    AlterOptions, AlterRequest, Chunk, ChunkReader, CompactContext, CreateOptions, EngineContext,
+    FlushContext, GetRequest, GetResponse, OpenOptions, ReadContext, Region, RegionDescriptor,
+    RegionId, ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext,
+    WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -124,7 +125,7 @@ impl Snapshot for MockSnapshot {
     }
 
     async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
-        Ok(GetResponse {})
+        Ok(GetResponse { chunk: None })
     }
 }
 
@@ -193,6 +194,10 @@ impl Region for MockRegion {
         Ok(())
     }
 
+    async fn alter_options(&self, _options: AlterOptions) -> Result<()> {
+        Ok(())
+    }
+
     async fn close(&self) -> Result<()> {
         Ok(())
     }
@@ -204,6 +209,22 @@ impl Region for MockRegion {
     async fn flush(&self, _ctx: &FlushContext) -> Result<()> {
         unimplemented!()
     }
+
+    async fn compact(&self, _ctx: &CompactContext) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn save_snapshot(&self, _name: &str) -> Result<()> {
+        unimplemented!()
+    }
+
+    async fn drop_snapshot(&self, _name: &str) -> Result<bool> {
+        unimplemented!()
+    }
 }
 
 impl MockRegionInner {
@@ -98,6 +98,25 @@ pub enum Error {
 
     #[snafu(display("Cannot find column {col}"))]
     ColumnNotFound { col: String, location: Location },
+
+    #[snafu(display(
+        "found duplicate series for the match group {group} on the {side} hand-side of the operation;many-to-many matching not allowed: matching labels must be unique on one side"
+    ))]
+    DuplicateMatchGroup {
+        group: String,
+        side: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "multiple matches for labels {labels}: many-to-one matching must be explicit (group_left/group_right)"
+    ))]
+    ImplicitManyToOne { labels: String, location: Location },
+
+    #[snafu(display(
+        "multiple matches for labels {labels}: grouping labels must ensure unique matches"
+    ))]
+    NonUniqueGroupingMatch { labels: String, location: Location },
 }
 
 impl ErrorExt for Error {
@@ -112,7 +131,10 @@ impl ErrorExt for Error {
             | ExpectExpr { .. }
             | ExpectRangeSelector { .. }
             | ZeroRangeSelector { .. }
-            | ColumnNotFound { .. } => StatusCode::InvalidArguments,
+            | ColumnNotFound { .. }
+            | DuplicateMatchGroup { .. }
+            | ImplicitManyToOne { .. }
+            | NonUniqueGroupingMatch { .. } => StatusCode::InvalidArguments,
 
             UnknownTable { .. }
             | DataFusionPlanning { .. }
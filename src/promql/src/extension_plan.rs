@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cardinality_check;
 mod empty_metric;
 mod instant_manipulate;
 mod normalize;
@@ -19,6 +20,9 @@ mod planner;
 mod range_manipulate;
 mod series_divide;
 
+pub use cardinality_check::{
+    CardinalityCheck, CardinalityCheckExec, CardinalityCheckStream, CardinalityViolation,
+};
 use datafusion::arrow::datatypes::{ArrowPrimitiveType, TimestampMillisecondType};
 pub use empty_metric::{EmptyMetric, EmptyMetricExec, EmptyMetricStream};
 pub use instant_manipulate::{InstantManipulate, InstantManipulateExec, InstantManipulateStream};
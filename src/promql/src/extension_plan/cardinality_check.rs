@@ -0,0 +1,416 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use datafusion::arrow::array::ArrayRef;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion::arrow::util::display::array_value_to_string;
+use datafusion::common::DFSchemaRef;
+use datafusion::error::Result as DataFusionResult;
+use datafusion::execution::context::TaskContext;
+use datafusion::logical_expr::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+use datafusion::physical_expr::PhysicalSortExpr;
+use datafusion::physical_plan::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use datafusion::physical_plan::{
+    DisplayFormatType, Distribution, ExecutionPlan, Partitioning, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use futures::{Stream, StreamExt};
+
+use crate::error::{DuplicateMatchGroupSnafu, ImplicitManyToOneSnafu, NonUniqueGroupingMatchSnafu};
+
+/// What a duplicated key means in vector matching, decides the error to report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardinalityViolation {
+    /// The left side is the "one" side of a `group_right` matching.
+    DuplicateLhs,
+    /// The right side is the "one" side of a one-to-one or `group_left` matching.
+    DuplicateRhs,
+    /// Multiple left series matched the same right series without `group_left`/`group_right`.
+    ImplicitManyToOne,
+    /// Labels copied by `group_left`/`group_right` made the result series collide.
+    NonUniqueGrouping,
+}
+
+/// Ensures the input contains at most one row for each combination of label
+/// columns and time index, i.e., one sample per series at each timestamp.
+/// Vector matching in PromQL relies on this to reject ambiguous matches.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct CardinalityCheck {
+    label_columns: Vec<String>,
+    time_index_column: Option<String>,
+    violation: CardinalityViolation,
+    input: LogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for CardinalityCheck {
+    fn name(&self) -> &str {
+        "CardinalityCheck"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "PromCardinalityCheck: labels={:?}, time index=[{}]",
+            self.label_columns,
+            self.time_index_column.as_deref().unwrap_or_default()
+        )
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        assert!(!inputs.is_empty());
+
+        Self {
+            label_columns: self.label_columns.clone(),
+            time_index_column: self.time_index_column.clone(),
+            violation: self.violation,
+            input: inputs[0].clone(),
+        }
+    }
+}
+
+impl CardinalityCheck {
+    pub fn new(
+        label_columns: Vec<String>,
+        time_index_column: Option<String>,
+        violation: CardinalityViolation,
+        input: LogicalPlan,
+    ) -> Self {
+        Self {
+            label_columns,
+            time_index_column,
+            violation,
+            input,
+        }
+    }
+
+    pub fn to_execution_plan(&self, exec_input: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        Arc::new(CardinalityCheckExec {
+            label_columns: self.label_columns.clone(),
+            time_index_column: self.time_index_column.clone(),
+            violation: self.violation,
+            input: exec_input,
+            metric: ExecutionPlanMetricsSet::new(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct CardinalityCheckExec {
+    label_columns: Vec<String>,
+    time_index_column: Option<String>,
+    violation: CardinalityViolation,
+    input: Arc<dyn ExecutionPlan>,
+    metric: ExecutionPlanMetricsSet,
+}
+
+impl ExecutionPlan for CardinalityCheckExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    // Keys are checked globally, so all rows need to go through one stream.
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.input.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true; self.children().len()]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DataFusionResult<Arc<dyn ExecutionPlan>> {
+        assert!(!children.is_empty());
+        Ok(Arc::new(Self {
+            label_columns: self.label_columns.clone(),
+            time_index_column: self.time_index_column.clone(),
+            violation: self.violation,
+            input: children[0].clone(),
+            metric: self.metric.clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> DataFusionResult<SendableRecordBatchStream> {
+        let baseline_metric = BaselineMetrics::new(&self.metric, partition);
+
+        let input = self.input.execute(partition, context)?;
+        let schema = input.schema();
+        let label_indices = self
+            .label_columns
+            .iter()
+            .map(|label| {
+                schema
+                    .column_with_name(label)
+                    .unwrap_or_else(|| panic!("label column not found {label}"))
+                    .0
+            })
+            .collect::<Vec<_>>();
+        let time_index = self.time_index_column.as_ref().map(|time_index| {
+            schema
+                .column_with_name(time_index)
+                .expect("time index column not found")
+                .0
+        });
+        let sort_fields = label_indices
+            .iter()
+            .chain(time_index.iter())
+            .map(|index| SortField::new(schema.field(*index).data_type().clone()))
+            .collect();
+        let converter = RowConverter::new(sort_fields)?;
+
+        Ok(Box::pin(CardinalityCheckStream {
+            label_indices,
+            time_index,
+            violation: self.violation,
+            converter,
+            seen: HashSet::new(),
+            num_rows: 0,
+            schema,
+            input,
+            metric: baseline_metric,
+        }))
+    }
+
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(
+                    f,
+                    "PromCardinalityCheckExec: labels={:?}, time index=[{}]",
+                    self.label_columns,
+                    self.time_index_column.as_deref().unwrap_or_default()
+                )
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metric.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        self.input.statistics()
+    }
+}
+
+pub struct CardinalityCheckStream {
+    label_indices: Vec<usize>,
+    time_index: Option<usize>,
+    violation: CardinalityViolation,
+    converter: RowConverter,
+    /// Keys (labels and timestamp) of all rows passed so far.
+    seen: HashSet<OwnedRow>,
+    /// Number of rows passed so far. All rows share the same key if there is no key column.
+    num_rows: usize,
+
+    schema: SchemaRef,
+    input: SendableRecordBatchStream,
+    metric: BaselineMetrics,
+}
+
+impl CardinalityCheckStream {
+    pub fn check(&mut self, batch: RecordBatch) -> DataFusionResult<RecordBatch> {
+        let key_columns = self
+            .label_indices
+            .iter()
+            .chain(self.time_index.iter())
+            .map(|index| batch.column(*index).clone())
+            .collect::<Vec<ArrayRef>>();
+
+        if key_columns.is_empty() {
+            if self.num_rows + batch.num_rows() > 1 {
+                return Err(self.violation_error(&batch, 0));
+            }
+        } else {
+            let rows = self.converter.convert_columns(&key_columns)?;
+            for (row_index, row) in rows.iter().enumerate() {
+                if !self.seen.insert(row.owned()) {
+                    return Err(self.violation_error(&batch, row_index));
+                }
+            }
+        }
+        self.num_rows += batch.num_rows();
+
+        Ok(batch)
+    }
+
+    fn violation_error(
+        &self,
+        batch: &RecordBatch,
+        row_index: usize,
+    ) -> datafusion::error::DataFusionError {
+        let labels = self
+            .label_indices
+            .iter()
+            .map(|index| {
+                let value =
+                    array_value_to_string(batch.column(*index), row_index).unwrap_or_default();
+                format!("{}=\"{}\"", self.schema.field(*index).name(), value)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let labels = format!("{{{labels}}}");
+
+        match self.violation {
+            CardinalityViolation::DuplicateLhs => DuplicateMatchGroupSnafu {
+                group: labels,
+                side: "left",
+            }
+            .build(),
+            CardinalityViolation::DuplicateRhs => DuplicateMatchGroupSnafu {
+                group: labels,
+                side: "right",
+            }
+            .build(),
+            CardinalityViolation::ImplicitManyToOne => ImplicitManyToOneSnafu { labels }.build(),
+            CardinalityViolation::NonUniqueGrouping => {
+                NonUniqueGroupingMatchSnafu { labels }.build()
+            }
+        }
+        .into()
+    }
+}
+
+impl RecordBatchStream for CardinalityCheckStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for CardinalityCheckStream {
+    type Item = DataFusionResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = match self.input.poll_next_unpin(cx) {
+            Poll::Ready(batch) => {
+                let elapsed_compute = self.metric.elapsed_compute().clone();
+                let _timer = elapsed_compute.timer();
+                Poll::Ready(batch.map(|batch| batch.and_then(|batch| self.check(batch))))
+            }
+            Poll::Pending => Poll::Pending,
+        };
+        self.metric.record_poll(poll)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::arrow::array::{Float64Array, StringArray, TimestampMillisecondArray};
+    use datafusion::arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use datafusion::from_slice::FromSlice;
+    use datafusion::physical_plan::memory::MemoryExec;
+    use datafusion::prelude::SessionContext;
+
+    use super::*;
+
+    const TIME_INDEX_COLUMN: &str = "timestamp";
+
+    fn prepare_test_data(hosts: [&str; 4]) -> MemoryExec {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new(
+                TIME_INDEX_COLUMN,
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                true,
+            ),
+            Field::new("value", DataType::Float64, true),
+        ]));
+        let host_column = Arc::new(StringArray::from_slice(hosts)) as _;
+        let timestamp_column =
+            Arc::new(TimestampMillisecondArray::from_slice([0, 5_000, 0, 5_000])) as _;
+        let value_column = Arc::new(Float64Array::from_slice([1.0, 2.0, 3.0, 4.0])) as _;
+        // split into two batches to cover keys seen in previous batches
+        let data = RecordBatch::try_new(
+            schema.clone(),
+            vec![host_column, timestamp_column, value_column],
+        )
+        .unwrap();
+
+        MemoryExec::try_new(&[vec![data.slice(0, 2), data.slice(2, 2)]], schema, None).unwrap()
+    }
+
+    async fn do_cardinality_check(hosts: [&str; 4]) -> DataFusionResult<usize> {
+        let memory_exec = Arc::new(prepare_test_data(hosts));
+        let check_exec = Arc::new(CardinalityCheckExec {
+            label_columns: vec!["host".to_string()],
+            time_index_column: Some(TIME_INDEX_COLUMN.to_string()),
+            violation: CardinalityViolation::DuplicateRhs,
+            input: memory_exec,
+            metric: ExecutionPlanMetricsSet::new(),
+        });
+        let session_context = SessionContext::default();
+        let result =
+            datafusion::physical_plan::collect(check_exec, session_context.task_ctx()).await?;
+        Ok(result.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn unique_series_pass_through() {
+        let num_rows = do_cardinality_check(["foo", "foo", "bar", "bar"])
+            .await
+            .unwrap();
+        assert_eq!(num_rows, 4);
+    }
+
+    #[tokio::test]
+    async fn duplicate_series_rejected() {
+        let err = do_cardinality_check(["foo", "foo", "foo", "bar"])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(
+            "found duplicate series for the match group {host=\"foo\"} on the right hand-side of the operation"
+        ));
+    }
+}
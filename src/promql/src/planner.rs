@@ -34,9 +34,10 @@ use datafusion::sql::TableReference;
 use datatypes::arrow::datatypes::DataType as ArrowDataType;
 use promql_parser::label::{MatchOp, Matcher, Matchers, METRIC_NAME};
 use promql_parser::parser::{
-    token, AggModifier, AggregateExpr, BinaryExpr as PromBinaryExpr, Call, EvalStmt,
-    Expr as PromExpr, Function, MatrixSelector, NumberLiteral, Offset, ParenExpr, StringLiteral,
-    SubqueryExpr, TokenType, UnaryExpr, VectorSelector,
+    token, AggModifier, AggregateExpr, AtModifier, BinModifier, BinaryExpr as PromBinaryExpr, Call,
+    EvalStmt, Expr as PromExpr, Function, MatrixSelector, NumberLiteral, Offset, ParenExpr,
+    StringLiteral, SubqueryExpr, TokenType, UnaryExpr, VectorMatchCardinality, VectorMatchModifier,
+    VectorSelector,
 };
 use snafu::{ensure, OptionExt, ResultExt};
 use table::table::adapter::DfTableProviderAdapter;
@@ -48,7 +49,8 @@ use crate::error::{
     UnsupportedExprSnafu, ValueNotFoundSnafu, ZeroRangeSelectorSnafu,
 };
 use crate::extension_plan::{
-    EmptyMetric, InstantManipulate, Millisecond, RangeManipulate, SeriesDivide, SeriesNormalize,
+    CardinalityCheck, CardinalityViolation, EmptyMetric, InstantManipulate, Millisecond,
+    RangeManipulate, SeriesDivide, SeriesNormalize,
 };
 use crate::functions::{
     AbsentOverTime, AvgOverTime, Changes, CountOverTime, Delta, IDelta, Increase, LastOverTime,
@@ -57,10 +59,19 @@ use crate::functions::{
 };
 
 const LEFT_PLAN_JOIN_ALIAS: &str = "lhs";
+const RIGHT_PLAN_JOIN_ALIAS: &str = "rhs";
+/// Tags absent in a series are null, and series are joined on their tags, so nulls must
+/// equal to each other in joins.
+const NULL_EQUALS_NULL: bool = true;
 
 /// `time()` function in PromQL.
 const SPECIAL_TIME_FUNCTION: &str = "time";
 
+/// Step of subqueries without an explicit step, which is the default evaluation interval
+/// of Prometheus. It doesn't depend on the step of the query, so results of a subquery
+/// don't change with the resolution of graphs.
+const DEFAULT_SUBQUERY_STEP: Millisecond = 60_000;
+
 /// default value column name for empty metric
 const DEFAULT_FIELD_COLUMN: &str = "value";
 
@@ -74,6 +85,10 @@ struct PromPlannerContext {
     end: Millisecond,
     interval: Millisecond,
     lookback_delta: Millisecond,
+    /// The original evaluation range of the query, which `@ start()` and `@ end()` refer to.
+    /// Unlike `start` and `end`, they are not changed by subqueries.
+    query_start: Millisecond,
+    query_end: Millisecond,
 
     // planner states
     table_name: Option<String>,
@@ -87,11 +102,15 @@ struct PromPlannerContext {
 
 impl PromPlannerContext {
     fn from_eval_stmt(stmt: &EvalStmt) -> Self {
+        let start = stmt.start.duration_since(UNIX_EPOCH).unwrap().as_millis() as _;
+        let end = stmt.end.duration_since(UNIX_EPOCH).unwrap().as_millis() as _;
         Self {
-            start: stmt.start.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
-            end: stmt.end.duration_since(UNIX_EPOCH).unwrap().as_millis() as _,
+            start,
+            end,
             interval: stmt.interval.as_millis() as _,
             lookback_delta: stmt.lookback_delta.as_millis() as _,
+            query_start: start,
+            query_end: end,
             ..Default::default()
         }
     }
@@ -232,57 +251,110 @@ impl PromPlanner {
                             self.projection_for_each_field_column(input, bin_expr_builder)?
                         }
                     }
-                    // both are columns. join them on time index
+                    // both are columns. join them on matching labels and time index
                     (None, None) => {
                         let left_input = self.prom_expr_to_plan(*lhs.clone()).await?;
-                        let left_field_columns = self.ctx.field_columns.clone();
-                        let left_schema = left_input.schema().clone();
-
+                        let left_ctx = self.ctx.clone();
                         let right_input = self.prom_expr_to_plan(*rhs.clone()).await?;
-                        let right_field_columns = self.ctx.field_columns.clone();
-                        let right_schema = right_input.schema().clone();
-
-                        let mut field_columns =
-                            left_field_columns.iter().zip(right_field_columns.iter());
-                        // the new ctx.field_columns for the generated join plan
-                        let join_plan = self.join_on_non_field_columns(left_input, right_input)?;
-                        let bin_expr_builder = |_: &String| {
-                            let (left_col_name, right_col_name) = field_columns.next().unwrap();
-                            let left_col = left_schema
-                                .field_with_name(None, left_col_name)
-                                .context(DataFusionPlanningSnafu)?
-                                .qualified_column();
-                            let right_col = right_schema
-                                .field_with_name(None, right_col_name)
-                                .context(DataFusionPlanningSnafu)?
-                                .qualified_column();
-
-                            let mut binary_expr = DfExpr::BinaryExpr(BinaryExpr {
-                                left: Box::new(DfExpr::Column(left_col)),
-                                op: Self::prom_token_to_binary_op(*op)?,
-                                right: Box::new(DfExpr::Column(right_col)),
-                            });
-                            if is_comparison_op && should_return_bool {
-                                binary_expr = DfExpr::Cast(Cast {
-                                    expr: Box::new(binary_expr),
-                                    data_type: ArrowDataType::Float64,
-                                });
-                            }
-                            Ok(binary_expr)
-                        };
-                        if is_comparison_op && !should_return_bool {
-                            self.filter_on_field_column(join_plan, bin_expr_builder)?
+                        if Self::is_token_a_set_op(*op) {
+                            self.set_op_to_plan(
+                                left_input,
+                                left_ctx,
+                                right_input,
+                                *op,
+                                modifier.as_ref(),
+                            )?
                         } else {
-                            self.projection_for_each_field_column(join_plan, bin_expr_builder)?
+                            self.vector_match_to_plan(
+                                left_input,
+                                left_ctx,
+                                right_input,
+                                *op,
+                                modifier.as_ref(),
+                            )?
                         }
                     }
                 }
             }
             PromExpr::Paren(ParenExpr { expr }) => self.prom_expr_to_plan(*expr.clone()).await?,
-            PromExpr::Subquery(SubqueryExpr { .. }) => UnsupportedExprSnafu {
-                name: "Prom Subquery",
+            PromExpr::Subquery(SubqueryExpr {
+                expr,
+                offset,
+                at,
+                range,
+                step,
+            }) => {
+                ensure!(!range.is_zero(), ZeroRangeSelectorSnafu);
+                let range_ms = range.as_millis() as Millisecond;
+                let step_ms = step
+                    .filter(|step| !step.is_zero())
+                    .map_or(DEFAULT_SUBQUERY_STEP, |step| step.as_millis() as _);
+                let offset_ms = Self::offset_to_millis(offset);
+                let (start, end) = match self.at_modifier_to_millis(at) {
+                    Some(at) => (at, at),
+                    None => (self.ctx.start, self.ctx.end),
+                };
+
+                // evaluate the inner expr at steps aligned to multiples of the subquery step,
+                // which cover the ranges of all outer evaluation steps
+                let outer_range = (self.ctx.start, self.ctx.end, self.ctx.interval);
+                let lower_bound = start - offset_ms - range_ms;
+                self.ctx.start = lower_bound.div_euclid(step_ms) * step_ms;
+                if self.ctx.start < lower_bound {
+                    self.ctx.start += step_ms;
+                }
+                self.ctx.end = end - offset_ms;
+                self.ctx.interval = step_ms;
+                let input = self.prom_expr_to_plan(*expr.clone()).await?;
+                (self.ctx.start, self.ctx.end, self.ctx.interval) = outer_range;
+
+                // aggregation removes time index from context but keeps it in the output
+                let time_index = match self.ctx.time_index_column.clone() {
+                    Some(time_index) => time_index,
+                    None => input
+                        .schema()
+                        .fields()
+                        .iter()
+                        .find(|field| matches!(field.data_type(), ArrowDataType::Timestamp(..)))
+                        .map(|field| field.name().clone())
+                        .with_context(|| TimeIndexNotFoundSnafu {
+                            table: self.ctx.table_name.clone().unwrap_or_default(),
+                        })?,
+                };
+                self.ctx.time_index_column = Some(time_index.clone());
+
+                // re-divide the evaluated series and fold them into ranges
+                let sort_plan = LogicalPlanBuilder::from(input)
+                    .sort(self.create_tag_and_time_index_column_sort_exprs()?)
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?;
+                let divide_plan = LogicalPlan::Extension(Extension {
+                    node: Arc::new(SeriesDivide::new(self.ctx.tag_columns.clone(), sort_plan)),
+                });
+                let series_normalize = LogicalPlan::Extension(Extension {
+                    node: Arc::new(SeriesNormalize::new(
+                        -offset_ms,
+                        time_index.clone(),
+                        divide_plan,
+                    )),
+                });
+                let manipulate = RangeManipulate::new(
+                    start,
+                    end,
+                    self.ctx.interval,
+                    range_ms,
+                    time_index,
+                    self.ctx.field_columns.clone(),
+                    series_normalize,
+                )
+                .context(DataFusionPlanningSnafu)?;
+                self.ctx.range = Some(range_ms);
+
+                LogicalPlan::Extension(Extension {
+                    node: Arc::new(manipulate),
+                })
             }
-            .fail()?,
             PromExpr::NumberLiteral(NumberLiteral { .. }) => UnsupportedExprSnafu {
                 name: "Prom Number Literal",
             }
@@ -295,10 +367,17 @@ impl PromPlanner {
                 name: _,
                 offset,
                 matchers,
-                at: _,
+                at,
             }) => {
                 let matchers = self.preprocess_label_matchers(matchers)?;
                 self.setup_context().await?;
+
+                // evaluate at the pinned timestamp only if `@` is present
+                let at = self.at_modifier_to_millis(at);
+                let outer_range = (self.ctx.start, self.ctx.end);
+                if let Some(at) = at {
+                    (self.ctx.start, self.ctx.end) = (at, at);
+                }
                 let normalize = self
                     .selector_to_series_normalize_plan(offset, matchers)
                     .await?;
@@ -313,16 +392,26 @@ impl PromPlanner {
                         .expect("time index should be set in `setup_context`"),
                     normalize,
                 );
-                LogicalPlan::Extension(Extension {
+                (self.ctx.start, self.ctx.end) = outer_range;
+
+                let plan = LogicalPlan::Extension(Extension {
                     node: Arc::new(manipulate),
-                })
+                });
+                if at.is_some() {
+                    self.broadcast_to_steps(plan)?
+                } else {
+                    plan
+                }
             }
             PromExpr::MatrixSelector(MatrixSelector {
                 vector_selector,
                 range,
             }) => {
                 let VectorSelector {
-                    offset, matchers, ..
+                    offset,
+                    matchers,
+                    at,
+                    ..
                 } = vector_selector;
                 let matchers = self.preprocess_label_matchers(matchers)?;
                 self.setup_context().await?;
@@ -331,6 +420,13 @@ impl PromPlanner {
                 let range_ms = range.as_millis() as _;
                 self.ctx.range = Some(range_ms);
 
+                // the range is only evaluated at the pinned timestamp if `@` is present. The
+                // result is broadcasted after the range function is applied.
+                let outer_range = (self.ctx.start, self.ctx.end);
+                if let Some(at) = self.at_modifier_to_millis(at) {
+                    (self.ctx.start, self.ctx.end) = (at, at);
+                }
+
                 let normalize = self
                     .selector_to_series_normalize_plan(offset, matchers)
                     .await?;
@@ -348,6 +444,7 @@ impl PromPlanner {
                     normalize,
                 )
                 .context(DataFusionPlanningSnafu)?;
+                (self.ctx.start, self.ctx.end) = outer_range;
 
                 LogicalPlan::Extension(Extension {
                     node: Arc::new(manipulate),
@@ -375,22 +472,27 @@ impl PromPlanner {
                 }
 
                 let args = self.create_function_args(&args.args)?;
-                let input = self
-                    .prom_expr_to_plan(args.input.with_context(|| ExpectExprSnafu {
-                        expr: prom_expr.clone(),
-                    })?)
-                    .await?;
+                let input_expr = args.input.with_context(|| ExpectExprSnafu {
+                    expr: prom_expr.clone(),
+                })?;
+                let is_input_pinned = Self::is_range_input_pinned(&input_expr);
+                let input = self.prom_expr_to_plan(input_expr).await?;
                 let mut func_exprs = self.create_function_expr(func, args.literals)?;
                 func_exprs.insert(0, self.create_time_index_column_expr()?);
                 func_exprs.extend_from_slice(&self.create_tag_column_exprs()?);
 
-                LogicalPlanBuilder::from(input)
+                let plan = LogicalPlanBuilder::from(input)
                     .project(func_exprs)
                     .context(DataFusionPlanningSnafu)?
                     .filter(self.create_empty_values_filter_expr()?)
                     .context(DataFusionPlanningSnafu)?
                     .build()
-                    .context(DataFusionPlanningSnafu)?
+                    .context(DataFusionPlanningSnafu)?;
+                if is_input_pinned {
+                    self.broadcast_to_steps(plan)?
+                } else {
+                    plan
+                }
             }
         };
         Ok(res)
@@ -423,21 +525,17 @@ impl PromPlanner {
     ) -> Result<LogicalPlan> {
         let table_name = self.ctx.table_name.clone().unwrap();
 
-        // make filter exprs
-        let offset_duration = -match offset {
-            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
-            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
-            None => 0,
-        };
-        let mut filters = self.matchers_to_expr(label_matchers)?;
+        // make filter exprs. a positive offset reads samples before the evaluation range
+        let offset_duration = -Self::offset_to_millis(offset);
+        let mut filters = Self::matchers_to_expr(label_matchers)?;
         filters.push(self.create_time_index_column_expr()?.gt_eq(DfExpr::Literal(
             ScalarValue::TimestampMillisecond(
-                Some(self.ctx.start - offset_duration - self.ctx.lookback_delta),
+                Some(self.ctx.start + offset_duration - self.ctx.lookback_delta),
                 None,
             ),
         )));
         filters.push(self.create_time_index_column_expr()?.lt_eq(DfExpr::Literal(
-            ScalarValue::TimestampMillisecond(Some(self.ctx.end - offset_duration), None),
+            ScalarValue::TimestampMillisecond(Some(self.ctx.end + offset_duration), None),
         )));
 
         // make table scan with filter exprs
@@ -543,6 +641,87 @@ impl PromPlanner {
         Ok(logical_plan)
     }
 
+    fn offset_to_millis(offset: &Option<Offset>) -> Millisecond {
+        match offset {
+            Some(Offset::Pos(duration)) => duration.as_millis() as Millisecond,
+            Some(Offset::Neg(duration)) => -(duration.as_millis() as Millisecond),
+            None => 0,
+        }
+    }
+
+    /// Resolve the timestamp in millisecond that the `@` modifier pins the evaluation at.
+    fn at_modifier_to_millis(&self, at: &Option<AtModifier>) -> Option<Millisecond> {
+        at.as_ref().map(|at| match at {
+            AtModifier::Start => self.ctx.query_start,
+            AtModifier::End => self.ctx.query_end,
+            AtModifier::At(time) => match time.duration_since(UNIX_EPOCH) {
+                Ok(duration) => duration.as_millis() as Millisecond,
+                Err(e) => -(e.duration().as_millis() as Millisecond),
+            },
+        })
+    }
+
+    /// Whether the range vector argument of a function is pinned by the `@` modifier.
+    fn is_range_input_pinned(expr: &PromExpr) -> bool {
+        match expr {
+            PromExpr::MatrixSelector(MatrixSelector {
+                vector_selector, ..
+            }) => vector_selector.at.is_some(),
+            PromExpr::Subquery(SubqueryExpr { at, .. }) => at.is_some(),
+            PromExpr::Paren(ParenExpr { expr }) => Self::is_range_input_pinned(expr),
+            _ => false,
+        }
+    }
+
+    /// Repeat the input, which is evaluated at a single timestamp pinned by the `@` modifier,
+    /// on every evaluation step of the current context.
+    fn broadcast_to_steps(&self, input: LogicalPlan) -> Result<LogicalPlan> {
+        let table_name = self.ctx.table_name.clone().unwrap_or_default();
+        let time_index =
+            self.ctx
+                .time_index_column
+                .clone()
+                .with_context(|| TimeIndexNotFoundSnafu {
+                    table: table_name.clone(),
+                })?;
+        let steps = LogicalPlan::Extension(Extension {
+            node: Arc::new(
+                EmptyMetric::new(
+                    self.ctx.start,
+                    self.ctx.end,
+                    self.ctx.interval,
+                    time_index.clone(),
+                    DEFAULT_FIELD_COLUMN.to_string(),
+                )
+                .context(DataFusionPlanningSnafu)?,
+            ),
+        });
+
+        // replace the pinned timestamp with the one of each step
+        let mut other_columns = vec![];
+        let mut output_columns = vec![];
+        for field in input.schema().fields() {
+            if field.name() == &time_index {
+                output_columns.push(DfExpr::Column(Column::new(Some(""), &time_index)));
+            } else {
+                other_columns.push(DfExpr::Column(field.qualified_column()));
+                output_columns.push(DfExpr::Column(field.qualified_column()));
+            }
+        }
+
+        LogicalPlanBuilder::from(input)
+            .project(other_columns)
+            .context(DataFusionPlanningSnafu)?
+            .cross_join(steps)
+            .context(DataFusionPlanningSnafu)?
+            .project(output_columns)
+            .context(DataFusionPlanningSnafu)?
+            .alias(&table_name)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
     /// Convert [AggModifier] to [Column] exprs for aggregation.
     /// Timestamp column and tag columns will be included.
     ///
@@ -610,8 +789,9 @@ impl PromPlanner {
         }
     }
 
+    /// Translates label matchers into filters on columns.
     // TODO(ruihang): ignore `MetricNameLabel` (`__name__`) matcher
-    fn matchers_to_expr(&self, label_matchers: Matchers) -> Result<Vec<DfExpr>> {
+    pub fn matchers_to_expr(label_matchers: Matchers) -> Result<Vec<DfExpr>> {
         let mut exprs = Vec::with_capacity(label_matchers.matchers.len());
         for matcher in label_matchers.matchers {
             let col = DfExpr::Column(Column::from_name(matcher.name));
@@ -1013,6 +1193,11 @@ impl PromPlanner {
         }
     }
 
+    /// Check if the given op is a [set operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#logical-set-binary-operators).
+    fn is_token_a_set_op(token: TokenType) -> bool {
+        matches!(token.id(), token::T_LAND | token::T_LOR | token::T_LUNLESS)
+    }
+
     /// Check if the given op is a [comparison operator](https://prometheus.io/docs/prometheus/latest/querying/operators/#comparison-binary-operators).
     fn is_token_a_comparison_op(token: TokenType) -> bool {
         matches!(
@@ -1026,41 +1211,604 @@ impl PromPlanner {
         )
     }
 
-    /// Build a inner join on time index column and tag columns to concat two logical plans.
-    /// The left plan will be alised as [`LEFT_PLAN_JOIN_ALIAS`].
-    fn join_on_non_field_columns(
-        &self,
+    /// Build the plan of a binary operation between two vectors, following the
+    /// [vector matching](https://prometheus.io/docs/prometheus/latest/querying/operators/#vector-matching)
+    /// semantics of Prometheus.
+    ///
+    /// Both sides are inner-joined on the time index and the matching labels, which are the
+    /// labels in `on(...)`, or all labels except those in `ignoring(...)`. The left plan will be
+    /// aliased as [`LEFT_PLAN_JOIN_ALIAS`] and the right one as [`RIGHT_PLAN_JOIN_ALIAS`].
+    ///
+    /// The result series take labels from the "many" side (the left side for one-to-one
+    /// matching), with labels listed in `group_left(...)`/`group_right(...)` copied from the
+    /// "one" side. [CardinalityCheck] plans are added where the inputs cannot guarantee
+    /// unique matches, to report the same errors as Prometheus.
+    ///
+    /// # Side effect
+    ///
+    /// This method will update the tag, field and time index columns in context to the
+    /// result plan's.
+    fn vector_match_to_plan(
+        &mut self,
         left: LogicalPlan,
+        left_ctx: PromPlannerContext,
         right: LogicalPlan,
+        op: TokenType,
+        modifier: Option<&BinModifier>,
     ) -> Result<LogicalPlan> {
-        let mut tag_columns = self
-            .ctx
+        let right_ctx = self.ctx.clone();
+        // the result is named by the qualified columns of the inputs, e.g.
+        // `http_requests.value - http_requests.value`
+        let left_schema = left.schema().clone();
+        let right_schema = right.schema().clone();
+        let left_time_index = left_ctx.time_index_column.clone();
+        let right_time_index = right_ctx.time_index_column.clone();
+        let left_tags = left_ctx
             .tag_columns
             .iter()
-            .map(Column::from_name)
-            .collect::<Vec<_>>();
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let right_tags = right_ctx
+            .tag_columns
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let matching_labels = Self::matching_labels(modifier, &left_tags, &right_tags);
+
+        // whether the right side is the "many" side, and labels to copy from the "one" side
+        let (is_group_right, include_labels) = match modifier.map(|m| &m.card) {
+            None | Some(VectorMatchCardinality::OneToOne) => (false, None),
+            Some(VectorMatchCardinality::ManyToOne(labels)) => (false, Some(labels)),
+            Some(VectorMatchCardinality::OneToMany(labels)) => (true, Some(labels)),
+            Some(VectorMatchCardinality::ManyToMany) => UnsupportedExprSnafu {
+                name: "many-to-many vector matching",
+            }
+            .fail()?,
+        };
 
-        // push time index column if it exist
-        if let Some(time_index_column) = &self.ctx.time_index_column {
-            tag_columns.push(Column::from_name(time_index_column));
-        }
+        // a matching label that only one side has is regarded as empty on the other side
+        let left = Self::filter_on_empty_labels(
+            left,
+            matching_labels
+                .iter()
+                .filter(|label| !right_tags.contains(*label)),
+        )?;
+        let right = Self::filter_on_empty_labels(
+            right,
+            matching_labels
+                .iter()
+                .filter(|label| !left_tags.contains(*label)),
+        )?;
+
+        // the "one" side should have at most one series in each match group
+        let (left, right) = if is_group_right {
+            let left = if left_tags.is_subset(&matching_labels) {
+                left
+            } else {
+                Self::cardinality_check(
+                    left,
+                    left_tags.intersection(&matching_labels).cloned().collect(),
+                    left_time_index.clone(),
+                    CardinalityViolation::DuplicateLhs,
+                )
+            };
+            (left, right)
+        } else {
+            let right = if right_tags.is_subset(&matching_labels) {
+                right
+            } else {
+                Self::cardinality_check(
+                    right,
+                    right_tags.intersection(&matching_labels).cloned().collect(),
+                    right_time_index.clone(),
+                    CardinalityViolation::DuplicateRhs,
+                )
+            };
+            (left, right)
+        };
 
-        // Inner Join on time index column to concat two operator
-        LogicalPlanBuilder::from(left)
+        // Inner Join on matching labels and time index column to concat two operator
+        let (left_keys, right_keys) = Self::join_keys(
+            &matching_labels,
+            &left_tags,
+            &right_tags,
+            left_time_index.as_ref().zip(right_time_index.as_ref()),
+        );
+        let right = LogicalPlanBuilder::from(right)
+            .alias(RIGHT_PLAN_JOIN_ALIAS)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let mut builder = LogicalPlanBuilder::from(left)
             .alias(LEFT_PLAN_JOIN_ALIAS)
             .context(DataFusionPlanningSnafu)?
-            .join(
+            .join_detailed(
                 right,
                 JoinType::Inner,
-                // (vec![time_index_column.clone()], vec![time_index_column]),
-                (tag_columns.clone(), tag_columns),
+                (left_keys, right_keys),
                 None,
+                NULL_EQUALS_NULL,
+            )
+            .context(DataFusionPlanningSnafu)?;
+
+        // build value columns. filtering comparison keeps the left side's value
+        let should_return_bool = modifier.map(|m| m.return_bool).unwrap_or(false);
+        let is_comparison_op = Self::is_token_a_comparison_op(op);
+        let field_column_pairs = left_ctx
+            .field_columns
+            .iter()
+            .zip(right_ctx.field_columns.iter())
+            .collect::<Vec<_>>();
+        let mut field_exprs = Vec::with_capacity(field_column_pairs.len());
+        if is_comparison_op && !should_return_bool {
+            ensure!(
+                field_column_pairs.len() == 1,
+                UnsupportedExprSnafu {
+                    name: "filter on multi-value input"
+                }
+            );
+            let (left_col, right_col) = field_column_pairs[0];
+            let filter_expr = DfExpr::BinaryExpr(BinaryExpr {
+                left: Box::new(DfExpr::Column(Column::new(
+                    Some(LEFT_PLAN_JOIN_ALIAS),
+                    left_col,
+                ))),
+                op: Self::prom_token_to_binary_op(op)?,
+                right: Box::new(DfExpr::Column(Column::new(
+                    Some(RIGHT_PLAN_JOIN_ALIAS),
+                    right_col,
+                ))),
+            });
+            builder = builder
+                .filter(filter_expr)
+                .context(DataFusionPlanningSnafu)?;
+            field_exprs.push(
+                DfExpr::Column(Column::new(Some(LEFT_PLAN_JOIN_ALIAS), left_col)).alias(left_col),
+            );
+        } else {
+            for (left_col, right_col) in field_column_pairs {
+                let mut binary_expr = DfExpr::BinaryExpr(BinaryExpr {
+                    left: Box::new(DfExpr::Column(Column::new(
+                        Some(LEFT_PLAN_JOIN_ALIAS),
+                        left_col,
+                    ))),
+                    op: Self::prom_token_to_binary_op(op)?,
+                    right: Box::new(DfExpr::Column(Column::new(
+                        Some(RIGHT_PLAN_JOIN_ALIAS),
+                        right_col,
+                    ))),
+                });
+                if is_comparison_op && should_return_bool {
+                    binary_expr = DfExpr::Cast(Cast {
+                        expr: Box::new(binary_expr),
+                        data_type: ArrowDataType::Float64,
+                    });
+                }
+                let name = DfExpr::BinaryExpr(BinaryExpr {
+                    left: Box::new(DfExpr::Column(
+                        left_schema
+                            .field_with_unqualified_name(left_col)
+                            .context(DataFusionPlanningSnafu)?
+                            .qualified_column(),
+                    )),
+                    op: Self::prom_token_to_binary_op(op)?,
+                    right: Box::new(DfExpr::Column(
+                        right_schema
+                            .field_with_unqualified_name(right_col)
+                            .context(DataFusionPlanningSnafu)?
+                            .qualified_column(),
+                    )),
+                })
+                .display_name()
+                .context(DataFusionPlanningSnafu)?;
+                field_exprs.push(binary_expr.alias(&name));
+            }
+        }
+
+        // build tag columns of the result series
+        let (many_alias, many_ctx, one_alias, one_tags, time_index) = if is_group_right {
+            (
+                RIGHT_PLAN_JOIN_ALIAS,
+                &right_ctx,
+                LEFT_PLAN_JOIN_ALIAS,
+                &left_tags,
+                right_time_index,
             )
+        } else {
+            (
+                LEFT_PLAN_JOIN_ALIAS,
+                &left_ctx,
+                RIGHT_PLAN_JOIN_ALIAS,
+                &right_tags,
+                left_time_index,
+            )
+        };
+        let mut result_tags = many_ctx
+            .tag_columns
+            .iter()
+            .filter(|tag| include_labels.is_some() || matching_labels.contains(*tag))
+            .map(|tag| (tag.clone(), many_alias))
+            .collect::<Vec<_>>();
+        let mut need_result_check = !many_ctx
+            .tag_columns
+            .iter()
+            .all(|tag| matching_labels.contains(tag));
+        let violation = if let Some(include_labels) = include_labels {
+            // copy included labels from the "one" side, or drop them if the "one" side
+            // doesn't have it
+            let include_labels = include_labels.iter().collect::<BTreeSet<_>>();
+            need_result_check = result_tags
+                .iter()
+                .any(|(tag, _)| include_labels.contains(tag));
+            result_tags.retain(|(tag, _)| !include_labels.contains(tag));
+            for label in include_labels {
+                if one_tags.contains(label) {
+                    result_tags.push((label.clone(), one_alias));
+                }
+            }
+            CardinalityViolation::NonUniqueGrouping
+        } else {
+            CardinalityViolation::ImplicitManyToOne
+        };
+
+        let project_exprs = result_tags
+            .iter()
+            .map(|(tag, alias)| DfExpr::Column(Column::new(Some(*alias), tag)))
+            .chain(
+                time_index
+                    .iter()
+                    .map(|time_index| DfExpr::Column(Column::new(Some(many_alias), time_index))),
+            )
+            .chain(field_exprs.iter().cloned())
+            .collect::<Vec<_>>();
+        let mut plan = builder
+            .project(project_exprs)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        // each match group of the "many" side should match the "one" side at most once
+        let result_tag_names = result_tags
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect::<Vec<_>>();
+        if need_result_check {
+            plan = Self::cardinality_check(
+                plan,
+                result_tag_names.clone(),
+                time_index.clone(),
+                violation,
+            );
+        }
+
+        // re-qualify result columns with the table name, like the result of other exprs
+        let table_name = self
+            .ctx
+            .table_name
+            .clone()
+            .context(TableNameNotFoundSnafu)?;
+        let plan = LogicalPlanBuilder::from(plan)
+            .alias(&table_name)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+
+        self.ctx.tag_columns = result_tag_names;
+        self.ctx.time_index_column = time_index;
+        self.ctx.field_columns = field_exprs
+            .iter()
+            .map(|expr| expr.display_name())
+            .collect::<DfResult<Vec<_>>>()
+            .context(DataFusionPlanningSnafu)?;
+
+        Ok(plan)
+    }
+
+    /// Build the plan of a set operation (`and`, `or` and `unless`) between two vectors.
+    ///
+    /// Series are matched on the time index and the matching labels like
+    /// [`Self::vector_match_to_plan`], while matching is always many-to-many:
+    /// - `and` is a semi join that keeps left series having a match on the right side.
+    /// - `unless` is an anti join that keeps left series having no match on the right side.
+    /// - `or` is a union of the left side and the right series having no match on the left side.
+    ///
+    /// # Side effect
+    ///
+    /// This method will update the tag, field and time index columns in context to the
+    /// result plan's.
+    fn set_op_to_plan(
+        &mut self,
+        left: LogicalPlan,
+        left_ctx: PromPlannerContext,
+        right: LogicalPlan,
+        op: TokenType,
+        modifier: Option<&BinModifier>,
+    ) -> Result<LogicalPlan> {
+        // the result starts from the left side
+        let right_ctx = std::mem::replace(&mut self.ctx, left_ctx);
+        let left_tags = self
+            .ctx
+            .tag_columns
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let right_tags = right_ctx
+            .tag_columns
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let matching_labels = Self::matching_labels(modifier, &left_tags, &right_tags);
+        let time_index = self
+            .ctx
+            .time_index_column
+            .clone()
+            .zip(right_ctx.time_index_column.clone());
+
+        let (left_keys, right_keys) = Self::join_keys(
+            &matching_labels,
+            &left_tags,
+            &right_tags,
+            time_index.as_ref().map(|(left, right)| (left, right)),
+        );
+        // a matching label that only one side has is regarded as empty on the other side
+        let mut join_filter_exprs = Self::empty_label_exprs(
+            Some(LEFT_PLAN_JOIN_ALIAS),
+            matching_labels
+                .iter()
+                .filter(|label| !right_tags.contains(*label)),
+        );
+        join_filter_exprs.extend(Self::empty_label_exprs(
+            Some(RIGHT_PLAN_JOIN_ALIAS),
+            matching_labels
+                .iter()
+                .filter(|label| !left_tags.contains(*label)),
+        ));
+        let join_filter = utils::conjunction(join_filter_exprs);
+
+        let left = LogicalPlanBuilder::from(left)
+            .alias(LEFT_PLAN_JOIN_ALIAS)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let right = LogicalPlanBuilder::from(right)
+            .alias(RIGHT_PLAN_JOIN_ALIAS)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)?;
+        let plan = match op.id() {
+            // the result series are the left series as is, so is the context
+            token::T_LAND | token::T_LUNLESS => {
+                let join_type = if op.id() == token::T_LAND {
+                    JoinType::LeftSemi
+                } else {
+                    JoinType::LeftAnti
+                };
+                LogicalPlanBuilder::from(left)
+                    .join_detailed(
+                        right,
+                        join_type,
+                        (left_keys, right_keys),
+                        join_filter,
+                        NULL_EQUALS_NULL,
+                    )
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?
+            }
+            token::T_LOR => {
+                ensure!(
+                    self.ctx.field_columns.len() == right_ctx.field_columns.len(),
+                    UnsupportedExprSnafu {
+                        name: "set operation on inputs with different numbers of value columns"
+                    }
+                );
+                let right_only = LogicalPlanBuilder::from(right)
+                    .join_detailed(
+                        left.clone(),
+                        JoinType::LeftAnti,
+                        (right_keys, left_keys),
+                        join_filter,
+                        NULL_EQUALS_NULL,
+                    )
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?;
+
+                // union on tags of both sides, with time index and value columns named after
+                // the left side
+                let result_tags = self
+                    .ctx
+                    .tag_columns
+                    .iter()
+                    .chain(
+                        right_ctx
+                            .tag_columns
+                            .iter()
+                            .filter(|tag| !left_tags.contains(*tag)),
+                    )
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let left_exprs = Self::union_input_exprs(
+                    LEFT_PLAN_JOIN_ALIAS,
+                    &left_tags,
+                    &result_tags,
+                    time_index.as_ref().map(|(left, _)| left),
+                    &self.ctx.field_columns,
+                    time_index.as_ref().map(|(left, _)| left),
+                    &self.ctx.field_columns,
+                );
+                let right_exprs = Self::union_input_exprs(
+                    RIGHT_PLAN_JOIN_ALIAS,
+                    &right_tags,
+                    &result_tags,
+                    time_index.as_ref().map(|(_, right)| right),
+                    &right_ctx.field_columns,
+                    time_index.as_ref().map(|(left, _)| left),
+                    &self.ctx.field_columns,
+                );
+                let right_only = LogicalPlanBuilder::from(right_only)
+                    .project(right_exprs)
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?;
+                let plan = LogicalPlanBuilder::from(left)
+                    .project(left_exprs)
+                    .context(DataFusionPlanningSnafu)?
+                    .union(right_only)
+                    .context(DataFusionPlanningSnafu)?
+                    .build()
+                    .context(DataFusionPlanningSnafu)?;
+
+                self.ctx.tag_columns = result_tags;
+                self.ctx.time_index_column = time_index.map(|(left, _)| left);
+                plan
+            }
+            _ => UnexpectedTokenSnafu { token: op }.fail()?,
+        };
+
+        // re-qualify result columns with the table name, like the result of other exprs
+        let table_name = self
+            .ctx
+            .table_name
+            .clone()
+            .context(TableNameNotFoundSnafu)?;
+        LogicalPlanBuilder::from(plan)
+            .alias(&table_name)
             .context(DataFusionPlanningSnafu)?
             .build()
             .context(DataFusionPlanningSnafu)
     }
 
+    /// Labels to match series of two sides on. These are the labels in `on(...)`, or all
+    /// labels except those in `ignoring(...)`. Nonexistence labels are ignored.
+    fn matching_labels(
+        modifier: Option<&BinModifier>,
+        left_tags: &BTreeSet<String>,
+        right_tags: &BTreeSet<String>,
+    ) -> BTreeSet<String> {
+        let all_tags = left_tags.union(right_tags).cloned();
+        match modifier.and_then(|m| m.matching.as_ref()) {
+            Some(VectorMatchModifier::On(labels)) => all_tags
+                .filter(|tag| labels.iter().any(|label| label == tag))
+                .collect(),
+            Some(VectorMatchModifier::Ignoring(labels)) => all_tags
+                .filter(|tag| !labels.iter().any(|label| label == tag))
+                .collect(),
+            None => all_tags.collect(),
+        }
+    }
+
+    /// Build join keys from the matching labels both sides have and the time index columns,
+    /// over plans aliased as [`LEFT_PLAN_JOIN_ALIAS`] and [`RIGHT_PLAN_JOIN_ALIAS`].
+    fn join_keys(
+        matching_labels: &BTreeSet<String>,
+        left_tags: &BTreeSet<String>,
+        right_tags: &BTreeSet<String>,
+        time_index: Option<(&String, &String)>,
+    ) -> (Vec<Column>, Vec<Column>) {
+        let mut left_keys = Vec::with_capacity(matching_labels.len() + 1);
+        let mut right_keys = Vec::with_capacity(matching_labels.len() + 1);
+        for label in matching_labels
+            .iter()
+            .filter(|label| left_tags.contains(*label) && right_tags.contains(*label))
+        {
+            left_keys.push(Column::new(Some(LEFT_PLAN_JOIN_ALIAS), label));
+            right_keys.push(Column::new(Some(RIGHT_PLAN_JOIN_ALIAS), label));
+        }
+
+        // push time index column if it exist
+        if let Some((left_time_index, right_time_index)) = time_index {
+            left_keys.push(Column::new(Some(LEFT_PLAN_JOIN_ALIAS), left_time_index));
+            right_keys.push(Column::new(Some(RIGHT_PLAN_JOIN_ALIAS), right_time_index));
+        }
+
+        (left_keys, right_keys)
+    }
+
+    /// Build exprs that project one input of the union in `or` operation to the result
+    /// columns. Tags the input doesn't have are filled with null, and the time index and
+    /// value columns are renamed to the result's.
+    fn union_input_exprs(
+        relation: &str,
+        input_tags: &BTreeSet<String>,
+        result_tags: &[String],
+        input_time_index: Option<&String>,
+        input_field_columns: &[String],
+        result_time_index: Option<&String>,
+        result_field_columns: &[String],
+    ) -> Vec<DfExpr> {
+        let tag_exprs = result_tags.iter().map(|tag| {
+            if input_tags.contains(tag) {
+                DfExpr::Column(Column::new(Some(relation), tag))
+            } else {
+                DfExpr::Literal(ScalarValue::Utf8(None)).alias(tag)
+            }
+        });
+        let non_tag_exprs = input_time_index
+            .into_iter()
+            .chain(input_field_columns.iter())
+            .zip(
+                result_time_index
+                    .into_iter()
+                    .chain(result_field_columns.iter()),
+            )
+            .map(|(input, result)| {
+                DfExpr::Column(Column::new(Some(relation), input)).alias(result)
+            });
+
+        tag_exprs.chain(non_tag_exprs).collect()
+    }
+
+    /// Build exprs that check whether the given labels are empty or null.
+    fn empty_label_exprs<'a>(
+        relation: Option<&str>,
+        labels: impl Iterator<Item = &'a String>,
+    ) -> Vec<DfExpr> {
+        labels
+            .map(|label| {
+                let col = DfExpr::Column(Column::new(relation, label));
+                col.clone()
+                    .is_null()
+                    .or(col.eq(DfExpr::Literal(ScalarValue::Utf8(Some(String::new())))))
+            })
+            .collect()
+    }
+
+    /// Build a filter plan that only keeps rows whose given labels are empty or null.
+    fn filter_on_empty_labels<'a>(
+        input: LogicalPlan,
+        labels: impl Iterator<Item = &'a String>,
+    ) -> Result<LogicalPlan> {
+        let exprs = Self::empty_label_exprs(None, labels);
+        let Some(filter_expr) = utils::conjunction(exprs) else {
+
+            return Ok(input);
+        };
+
+        LogicalPlanBuilder::from(input)
+            .filter(filter_expr)
+            .context(DataFusionPlanningSnafu)?
+            .build()
+            .context(DataFusionPlanningSnafu)
+    }
+
+    /// Wrap the input plan with a [CardinalityCheck] on given labels and time index.
+    fn cardinality_check(
+        input: LogicalPlan,
+        label_columns: Vec<String>,
+        time_index_column: Option<String>,
+        violation: CardinalityViolation,
+    ) -> LogicalPlan {
+        LogicalPlan::Extension(Extension {
+            node: Arc::new(CardinalityCheck::new(
+                label_columns,
+                time_index_column,
+                violation,
+                input,
+            )),
+        })
+    }
+
     /// Build a projection that project and perform operation expr for every value columns.
     /// Non-value columns (tag and timestamp) will be preserved in the projection.
     ///
@@ -1616,22 +2364,24 @@ mod test {
             .await
             .unwrap();
 
-        let  expected = String::from(
-            "Projection: some_metric.tag_0, some_metric.timestamp, some_metric.field_0 + some_metric.field_0 AS some_metric.field_0 + some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), some_metric.field_0 + some_metric.field_0:Float64;N]\
-            \n  Inner Join: lhs.tag_0 = some_metric.tag_0, lhs.timestamp = some_metric.timestamp [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n    SubqueryAlias: lhs [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n      PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n        PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n          PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n            Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n              Filter: some_metric.tag_0 = Utf8(\"foo\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n                TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"foo\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n    PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n      PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n        PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n          Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n            Filter: some_metric.tag_0 = Utf8(\"bar\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
-            \n              TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"bar\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
+        let expected = String::from(
+            "SubqueryAlias: some_metric [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), some_metric.field_0 + some_metric.field_0:Float64;N]\
+            \n  Projection: lhs.tag_0, lhs.timestamp, lhs.field_0 + rhs.field_0 AS some_metric.field_0 + some_metric.field_0 [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), some_metric.field_0 + some_metric.field_0:Float64;N]\
+            \n    Inner Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N, tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n      SubqueryAlias: lhs [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n        PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n          PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n            PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n              Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                Filter: some_metric.tag_0 = Utf8(\"foo\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                  TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"foo\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n      SubqueryAlias: rhs [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n        PromInstantManipulate: range=[0..100000000], lookback=[1000], interval=[5000], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n          PromSeriesNormalize: offset=[0], time index=[timestamp] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n            PromSeriesDivide: tags=[\"tag_0\"] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n              Sort: some_metric.tag_0 DESC NULLS LAST, some_metric.timestamp DESC NULLS LAST [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                Filter: some_metric.tag_0 = Utf8(\"bar\") AND some_metric.timestamp >= TimestampMillisecond(-1000, None) AND some_metric.timestamp <= TimestampMillisecond(100000000, None) [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]\
+            \n                  TableScan: some_metric, unsupported_filters=[tag_0 = Utf8(\"bar\"), timestamp >= TimestampMillisecond(-1000, None), timestamp <= TimestampMillisecond(100000000, None)] [tag_0:Utf8, timestamp:Timestamp(Millisecond, None), field_0:Float64;N]"
         );
 
         assert_eq!(plan.display_indent_schema().to_string(), expected);
@@ -1906,4 +2656,309 @@ mod test {
             assert!(plan.is_err(), "case: {:?}", case);
         }
     }
+
+    #[tokio::test]
+    async fn vector_matching() {
+        let mut eval_stmt = EvalStmt {
+            expr: PromExpr::NumberLiteral(NumberLiteral { val: 1.0 }),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        // (query, result columns, number of cardinality checks)
+        let cases = [
+            (
+                "some_metric + some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.tag_1",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 + some_metric.field_0",
+                ],
+                0,
+            ),
+            (
+                "some_metric + on(tag_0) some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 + some_metric.field_0",
+                ],
+                2,
+            ),
+            (
+                "some_metric + ignoring(tag_1) some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 + some_metric.field_0",
+                ],
+                2,
+            ),
+            (
+                "some_metric > on(tag_0) some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.timestamp",
+                    "some_metric.field_0",
+                ],
+                2,
+            ),
+            (
+                "some_metric * on(tag_0) group_left some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.tag_1",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 * some_metric.field_0",
+                ],
+                1,
+            ),
+            (
+                "some_metric * on(tag_0) group_right some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.tag_1",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 * some_metric.field_0",
+                ],
+                1,
+            ),
+            // copied label overrides the label of "many" side
+            (
+                "some_metric * on(tag_0) group_left(tag_1) some_metric",
+                vec![
+                    "some_metric.tag_0",
+                    "some_metric.tag_1",
+                    "some_metric.timestamp",
+                    "some_metric.some_metric.field_0 * some_metric.field_0",
+                ],
+                2,
+            ),
+        ];
+
+        for case in cases {
+            let prom_expr = parser::parse(case.0).unwrap();
+            eval_stmt.expr = prom_expr;
+            let table_provider = build_test_table_provider("some_metric".to_string(), 2, 1).await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt.clone())
+                .await
+                .unwrap();
+            let fields = plan.schema().field_names();
+            let expected = case.1.into_iter().map(String::from).collect::<Vec<_>>();
+            assert_eq!(fields, expected, "case: {:?}", case.0);
+            let num_checks = plan
+                .display_indent()
+                .to_string()
+                .matches("PromCardinalityCheck:")
+                .count();
+            assert_eq!(num_checks, case.2, "case: {:?}", case.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn set_operators() {
+        let mut eval_stmt = EvalStmt {
+            expr: PromExpr::NumberLiteral(NumberLiteral { val: 1.0 }),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+        let fields = vec![
+            "some_metric.tag_0",
+            "some_metric.tag_1",
+            "some_metric.timestamp",
+            "some_metric.field_0",
+        ];
+
+        // (query, join in the plan)
+        let cases = [
+            (
+                "some_metric and some_metric",
+                "LeftSemi Join: lhs.tag_0 = rhs.tag_0, lhs.tag_1 = rhs.tag_1, lhs.timestamp = rhs.timestamp",
+            ),
+            (
+                "some_metric unless on(tag_0) some_metric",
+                "LeftAnti Join: lhs.tag_0 = rhs.tag_0, lhs.timestamp = rhs.timestamp",
+            ),
+            (
+                "some_metric or ignoring(tag_1) some_metric",
+                "LeftAnti Join: rhs.tag_0 = lhs.tag_0, rhs.timestamp = lhs.timestamp",
+            ),
+        ];
+
+        for case in cases {
+            let prom_expr = parser::parse(case.0).unwrap();
+            eval_stmt.expr = prom_expr;
+            let table_provider = build_test_table_provider("some_metric".to_string(), 2, 1).await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt.clone())
+                .await
+                .unwrap();
+            assert_eq!(plan.schema().field_names(), fields, "case: {:?}", case.0);
+            let plan_display = plan.display_indent().to_string();
+            assert!(plan_display.contains(case.1), "case: {:?}", case.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn subquery() {
+        // The omitted step defaults to 1m instead of the step of the query.
+        let queries = [
+            "max_over_time(count_over_time(some_metric[5m])[1h:1m])",
+            "max_over_time(count_over_time(some_metric[5m])[1h:])",
+        ];
+        for query in queries {
+            let prom_expr = parser::parse(query).unwrap();
+            let eval_stmt = EvalStmt {
+                expr: prom_expr,
+                start: UNIX_EPOCH,
+                end: UNIX_EPOCH
+                    .checked_add(Duration::from_secs(100_000))
+                    .unwrap(),
+                interval: Duration::from_secs(5),
+                lookback_delta: Duration::from_secs(1),
+            };
+
+            let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+                .await
+                .unwrap();
+            let plan_display = plan.display_indent().to_string();
+
+            // outer range over the subquery steps
+            assert!(
+                plan_display.contains(
+                    "PromRangeManipulate: req range=[0..100000000], interval=[5000], eval range=[3600000]"
+                ),
+                "query: {query}"
+            );
+            // inner evaluation starts from the first step aligned to 1m within the first range
+            assert!(
+                plan_display.contains(
+                    "PromRangeManipulate: req range=[-3600000..100000000], interval=[60000], eval range=[300000]"
+                ),
+                "query: {query}"
+            );
+            assert!(plan_display.contains("PromSeriesDivide: tags=[\"tag_0\"]"));
+        }
+    }
+
+    #[tokio::test]
+    async fn offset() {
+        let mut eval_stmt = EvalStmt {
+            expr: PromExpr::NumberLiteral(NumberLiteral { val: 1.0 }),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        // (query, expected plan fragments)
+        let cases = [
+            (
+                "some_metric offset 1s",
+                vec![
+                    "some_metric.timestamp >= TimestampMillisecond(-2000, None) AND some_metric.timestamp <= TimestampMillisecond(99999000, None)",
+                    "PromSeriesNormalize: offset=[-1000]",
+                ],
+            ),
+            (
+                "some_metric offset -1s",
+                vec![
+                    "some_metric.timestamp >= TimestampMillisecond(0, None) AND some_metric.timestamp <= TimestampMillisecond(100001000, None)",
+                    "PromSeriesNormalize: offset=[1000]",
+                ],
+            ),
+            (
+                "some_metric @ end() offset 1s",
+                vec![
+                    "some_metric.timestamp >= TimestampMillisecond(99998000, None) AND some_metric.timestamp <= TimestampMillisecond(99999000, None)",
+                    "PromInstantManipulate: range=[100000000..100000000]",
+                ],
+            ),
+        ];
+
+        for case in cases {
+            let prom_expr = parser::parse(case.0).unwrap();
+            eval_stmt.expr = prom_expr;
+            let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt.clone())
+                .await
+                .unwrap();
+            let plan_display = plan.display_indent().to_string();
+            for expected in case.1 {
+                assert!(plan_display.contains(expected), "case: {:?}", case.0);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn at_modifier() {
+        let mut eval_stmt = EvalStmt {
+            expr: PromExpr::NumberLiteral(NumberLiteral { val: 1.0 }),
+            start: UNIX_EPOCH,
+            end: UNIX_EPOCH
+                .checked_add(Duration::from_secs(100_000))
+                .unwrap(),
+            interval: Duration::from_secs(5),
+            lookback_delta: Duration::from_secs(1),
+        };
+
+        // (query, expected plan fragments)
+        let cases = [
+            (
+                "some_metric @ 100",
+                vec![
+                    "some_metric.timestamp >= TimestampMillisecond(99000, None) AND some_metric.timestamp <= TimestampMillisecond(100000, None)",
+                    "PromInstantManipulate: range=[100000..100000]",
+                    "CrossJoin:",
+                    "EmptyMetric: range=[0..100000000], interval=[5000]",
+                ],
+            ),
+            (
+                "count_over_time(some_metric[5m] @ start())",
+                vec![
+                    "PromRangeManipulate: req range=[0..0], interval=[5000], eval range=[300000]",
+                    "EmptyMetric: range=[0..100000000], interval=[5000]",
+                ],
+            ),
+        ];
+
+        for case in cases {
+            let prom_expr = parser::parse(case.0).unwrap();
+            eval_stmt.expr = prom_expr;
+            let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+            let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt.clone())
+                .await
+                .unwrap();
+            let plan_display = plan.display_indent().to_string();
+            for expected in case.1 {
+                assert!(plan_display.contains(expected), "case: {:?}", case.0);
+            }
+        }
+
+        // pinned selectors keep the column layout of the plain one
+        eval_stmt.expr = parser::parse("some_metric @ 100").unwrap();
+        let table_provider = build_test_table_provider("some_metric".to_string(), 1, 1).await;
+        let plan = PromPlanner::stmt_to_plan(table_provider, eval_stmt)
+            .await
+            .unwrap();
+        assert_eq!(
+            plan.schema().field_names(),
+            vec![
+                "some_metric.tag_0",
+                "some_metric.timestamp",
+                "some_metric.field_0"
+            ]
+        );
+    }
 }
@@ -15,6 +15,7 @@
 //! Planner, QueryEngine implementations based on DataFusion.
 
 mod catalog_adapter;
+mod delete_range;
 mod error;
 mod planner;
 
@@ -22,7 +23,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use common_error::prelude::BoxedError;
+use common_error::prelude::{BoxedError, ErrorExt, StatusCode};
 use common_function::scalars::aggregate::AggregateFunctionMetaRef;
 use common_function::scalars::udf::create_udf;
 use common_function::scalars::FunctionRef;
@@ -35,8 +36,11 @@ use common_telemetry::timer;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion::physical_plan::ExecutionPlan;
 use datafusion_common::ResolvedTableReference;
-use datafusion_expr::{DmlStatement, LogicalPlan as DfLogicalPlan, WriteOp};
-use datatypes::prelude::VectorRef;
+use datafusion_expr::{
+    count, lit, DmlStatement, Expr as DfExpr, LogicalPlan as DfLogicalPlan, LogicalPlanBuilder,
+    WriteOp,
+};
+use datatypes::prelude::{Value, VectorRef};
 use datatypes::schema::Schema;
 use futures_util::StreamExt;
 use session::context::QueryContextRef;
@@ -45,6 +49,7 @@ use table::requests::{DeleteRequest, InsertRequest};
 use table::TableRef;
 
 pub use crate::datafusion::catalog_adapter::DfCatalogListAdapter;
+use crate::datafusion::delete_range::delete_range_of_plan;
 pub use crate::datafusion::planner::DfContextProviderAdapter;
 use crate::error::{
     CatalogNotFoundSnafu, CatalogSnafu, CreateRecordBatchSnafu, DataFusionSnafu,
@@ -96,6 +101,12 @@ impl DatafusionQueryEngine {
         let table_name = dml.table_name.resolve(&default_catalog, &default_schema);
         let table = self.find_table(&table_name).await?;
 
+        if dml.op == WriteOp::Delete {
+            if let Some(affected_rows) = self.delete_range(&table, &dml.input).await? {
+                return Ok(Output::AffectedRows(affected_rows));
+            }
+        }
+
         let output = self
             .exec_query_plan(LogicalPlan::DfPlan((*dml.input).clone()))
             .await?;
@@ -157,6 +168,55 @@ impl DatafusionQueryEngine {
             .context(QueryExecutionSnafu)
     }
 
+    /// Deletes rows by a range tombstone if the `DELETE` is a range and returns the number
+    /// of rows in the range. Returns `None` if the rows need to be scanned and deleted by
+    /// their keys.
+    async fn delete_range(&self, table: &TableRef, input: &DfLogicalPlan) -> Result<Option<usize>> {
+        let table_info = table.table_info();
+        let row_key_names = table_info
+            .meta
+            .row_key_column_names()
+            .collect::<Vec<&String>>();
+        let Some(request) = delete_range_of_plan(input, &table.schema(), &row_key_names)
+            else { return Ok(None) };
+
+        // Counting rows only reads the columns in filters, which is much cheaper than
+        // deleting rows by their keys. Rows written between counting and deleting are
+        // deleted but not counted.
+        let num_rows = self.count_rows(input).await?;
+        match table.delete_range(request).await {
+            Ok(()) => Ok(Some(num_rows)),
+            Err(e) if e.status_code() == StatusCode::Unsupported => Ok(None),
+            Err(e) => Err(BoxedError::new(e)).context(QueryExecutionSnafu),
+        }
+    }
+
+    /// Returns the number of rows of the `input` plan.
+    async fn count_rows(&self, input: &DfLogicalPlan) -> Result<usize> {
+        let plan = LogicalPlanBuilder::from(input.clone())
+            .aggregate(Vec::<DfExpr>::new(), vec![count(lit(1u8))])
+            .and_then(|builder| builder.build())
+            .context(DataFusionSnafu)?;
+        let output = self.exec_query_plan(LogicalPlan::DfPlan(plan)).await?;
+        let mut stream = match output {
+            Output::RecordBatches(batches) => batches.as_stream(),
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+
+        let mut num_rows = 0;
+        while let Some(batch) = stream.next().await {
+            let batch = batch.context(CreateRecordBatchSnafu)?;
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            if let Value::Int64(rows) = batch.column(0).get(0) {
+                num_rows += rows as usize;
+            }
+        }
+        Ok(num_rows)
+    }
+
     async fn insert<'a>(
         table_name: &ResolvedTableReference<'a>,
         table: &TableRef,
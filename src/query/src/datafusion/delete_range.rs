@@ -0,0 +1,210 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Converts the input plan of a `DELETE` statement into a range to delete, so the rows
+//! don't need to be scanned to collect their keys.
+
+use std::str::FromStr;
+
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datafusion_common::ScalarValue;
+use datafusion_expr::utils::split_conjunction;
+use datafusion_expr::{BinaryExpr, Expr, LogicalPlan, Operator};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::Schema;
+use datatypes::value::Value;
+use table::requests::DeleteRangeRequest;
+
+/// Returns the range to delete if the `DELETE` only filters rows by conjunctions of
+/// `tag = literal` and comparisons between the time index and literals, e.g.
+/// `host = 'a' AND ts >= '2023-01-01 00:00:00' AND ts < '2023-01-02 00:00:00'`.
+///
+/// Equality on the time index is left to the scan, which deletes the exact rows.
+pub(crate) fn delete_range_of_plan(
+    plan: &LogicalPlan,
+    schema: &Schema,
+    row_key_names: &[&String],
+) -> Option<DeleteRangeRequest> {
+    let mut plan = plan;
+    let mut predicate = None;
+    loop {
+        match plan {
+            LogicalPlan::Projection(projection) => plan = &projection.input,
+            LogicalPlan::SubqueryAlias(alias) => plan = &alias.input,
+            LogicalPlan::Filter(filter) if predicate.is_none() => {
+                predicate = Some(&filter.predicate);
+                plan = &filter.input;
+            }
+            LogicalPlan::TableScan(scan) if scan.filters.is_empty() && scan.fetch.is_none() => {
+                break
+            }
+            _ => return None,
+        }
+    }
+
+    let ts_column = schema.timestamp_column()?;
+    let ConcreteDataType::Timestamp(ts_type) = &ts_column.data_type else { return None };
+    let unit = ts_type.unit();
+
+    let mut request = DeleteRangeRequest::default();
+    for expr in split_conjunction(predicate?) {
+        let Expr::BinaryExpr(BinaryExpr { left, op, right }) = expr else { return None };
+        let (name, op, literal) = match (left.as_ref(), right.as_ref()) {
+            (Expr::Column(column), Expr::Literal(literal)) => (&column.name, *op, literal),
+            (Expr::Literal(literal), Expr::Column(column)) => (&column.name, op.swap()?, literal),
+            _ => return None,
+        };
+
+        if *name == ts_column.name {
+            let ts = timestamp_of_literal(literal, unit)?;
+            match op {
+                Operator::GtEq => request.start = request.start.max(Some(ts)),
+                Operator::Gt => request.start = request.start.max(Some(next_timestamp(ts)?)),
+                Operator::Lt => request.end = min_end(request.end, ts),
+                Operator::LtEq => request.end = min_end(request.end, next_timestamp(ts)?),
+                _ => return None,
+            }
+        } else if op == Operator::Eq && row_key_names.contains(&name) {
+            let column = schema.column_schema_by_name(name)?;
+            let value = Value::try_from(literal.clone()).ok()?;
+            if value.is_null() || value.data_type() != column.data_type {
+                return None;
+            }
+            match request.key_values.iter().find(|(key, _)| key == name) {
+                Some((_, existing)) if *existing != value => return None,
+                Some(_) => {}
+                None => request.key_values.push((name.clone(), value)),
+            }
+        } else {
+            return None;
+        }
+    }
+
+    // An empty range matches no rows, the scan handles it.
+    if let (Some(start), Some(end)) = (&request.start, &request.end) {
+        if start >= end {
+            return None;
+        }
+    }
+    Some(request)
+}
+
+/// Converts the literal compared with the time index to a timestamp in `unit`. Integers
+/// are values in `unit`, strings are parsed as timestamps.
+fn timestamp_of_literal(literal: &ScalarValue, unit: TimeUnit) -> Option<Timestamp> {
+    let ts = match literal {
+        ScalarValue::Int64(Some(v)) => Timestamp::new(*v, unit),
+        ScalarValue::Int32(Some(v)) => Timestamp::new(*v as i64, unit),
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => {
+            Timestamp::from_str(s).ok()?
+        }
+        ScalarValue::TimestampSecond(Some(v), _) => Timestamp::new_second(*v),
+        ScalarValue::TimestampMillisecond(Some(v), _) => Timestamp::new_millisecond(*v),
+        ScalarValue::TimestampMicrosecond(Some(v), _) => Timestamp::new_microsecond(*v),
+        ScalarValue::TimestampNanosecond(Some(v), _) => Timestamp::new_nanosecond(*v),
+        _ => return None,
+    };
+    // Truncating a timestamp of a finer unit would change the bound, so only exact
+    // conversions are accepted.
+    let converted = ts.convert_to(unit)?;
+    (converted == ts).then_some(converted)
+}
+
+fn next_timestamp(ts: Timestamp) -> Option<Timestamp> {
+    Some(Timestamp::new(ts.value().checked_add(1)?, ts.unit()))
+}
+
+fn min_end(end: Option<Timestamp>, ts: Timestamp) -> Option<Timestamp> {
+    Some(end.map_or(ts, |end| end.min(ts)))
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion_expr::logical_plan::builder::table_scan;
+    use datafusion_expr::{col, lit};
+    use datatypes::schema::ColumnSchema;
+
+    use super::*;
+
+    fn new_schema() -> Schema {
+        Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            )
+            .with_time_index(true),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ])
+    }
+
+    fn new_plan(schema: &Schema, predicate: Expr) -> LogicalPlan {
+        table_scan(Some("monitor"), schema.arrow_schema(), None)
+            .unwrap()
+            .filter(predicate)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_delete_range_of_plan() {
+        let schema = new_schema();
+        let host = "host".to_string();
+        let row_keys = [&host];
+        let range_of =
+            |predicate| delete_range_of_plan(&new_plan(&schema, predicate), &schema, &row_keys);
+
+        assert_eq!(
+            Some(DeleteRangeRequest {
+                key_values: vec![("host".to_string(), Value::from("host1"))],
+                start: Some(Timestamp::new_millisecond(1000)),
+                end: Some(Timestamp::new_millisecond(2001)),
+            }),
+            range_of(
+                col("host")
+                    .eq(lit("host1"))
+                    .and(col("ts").gt_eq(lit(1000i64)))
+                    .and(lit(2000i64).gt_eq(col("ts")))
+            )
+        );
+        assert_eq!(
+            Some(DeleteRangeRequest {
+                key_values: vec![],
+                start: Some(Timestamp::new_millisecond(1680307200001)),
+                end: None,
+            }),
+            range_of(col("ts").gt(lit("2023-04-01T00:00:00Z")))
+        );
+
+        // Rows need to be scanned.
+        assert_eq!(None, range_of(col("ts").eq(lit(1000i64))));
+        assert_eq!(None, range_of(col("cpu").eq(lit(0.5))));
+        assert_eq!(None, range_of(col("host").eq(lit(1i64))));
+        assert_eq!(
+            None,
+            range_of(col("host").eq(lit("host1")).or(col("ts").lt(lit(1000i64))))
+        );
+        assert_eq!(
+            None,
+            range_of(
+                col("ts")
+                    .gt_eq(lit(2000i64))
+                    .and(col("ts").lt(lit(1000i64)))
+            )
+        );
+    }
+}
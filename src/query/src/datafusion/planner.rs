@@ -32,6 +32,7 @@ use datafusion_physical_expr::var_provider::{is_system_variables, VarType};
 use datafusion_sql::parser::Statement as DfStatement;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
+use table::requests::AsOf;
 
 use crate::error::{CatalogSnafu, DataFusionSnafu, Result};
 use crate::query_engine::QueryEngineState;
@@ -49,6 +50,7 @@ impl DfContextProviderAdapter {
         session_state: SessionState,
         df_stmt: &DfStatement,
         query_ctx: QueryContextRef,
+        as_of: Option<AsOf>,
     ) -> Result<Self> {
         let table_names = session_state
             .resolve_table_references(df_stmt)
@@ -58,7 +60,8 @@ impl DfContextProviderAdapter {
             engine_state.catalog_list().clone(),
             engine_state.disallow_cross_schema_query(),
             query_ctx.as_ref(),
-        );
+        )
+        .with_as_of(as_of);
 
         let tables = resolve_tables(table_names, &mut table_provider).await?;
 
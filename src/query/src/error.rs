@@ -79,6 +79,13 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display("Failed to parse AS OF time `{}`: {}", raw, source))]
+    ParseAsOfTime {
+        raw: String,
+        source: common_time::error::Error,
+        location: Location,
+    },
+
     #[snafu(display("Failed to parse float number `{}`: {}", raw, source))]
     ParseFloat {
         raw: String,
@@ -123,6 +130,7 @@ impl ErrorExt for Error {
             | SchemaNotFound { .. }
             | TableNotFound { .. }
             | ParseTimestamp { .. }
+            | ParseAsOfTime { .. }
             | ParseFloat { .. } => StatusCode::InvalidArguments,
             QueryAccessDenied { .. } => StatusCode::AccessDenied,
             Catalog { source } => source.status_code(),
@@ -110,7 +110,8 @@ impl QueryLanguageParser {
         Ok(QueryStatement::Promql(eval_stmt))
     }
 
-    fn parse_promql_timestamp(timestamp: &str) -> Result<SystemTime> {
+    /// Parses a timestamp in rfc3339 format or in seconds since the unix epoch.
+    pub fn parse_promql_timestamp(timestamp: &str) -> Result<SystemTime> {
         // try rfc3339 format
         let rfc3339_result = DateTime::parse_from_rfc3339(timestamp)
             .context(ParseTimestampSnafu { raw: timestamp })
@@ -174,7 +175,7 @@ mod test {
             sort_by: [], \
             having: None, \
             qualify: None \
-            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] }, param_types: [] }))");
+            }), order_by: [], limit: None, offset: None, fetch: None, locks: [] }, param_types: [], as_of: None }))");
 
         assert_eq!(format!("{stmt:?}"), expected);
     }
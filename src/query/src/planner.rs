@@ -12,20 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use catalog::table_source::DfTableSourceProvider;
 use common_error::prelude::BoxedError;
+use common_time::Timestamp;
 use datafusion::execution::context::SessionState;
 use datafusion_sql::planner::{ParserOptions, SqlToRel};
 use promql::planner::PromPlanner;
 use promql_parser::parser::EvalStmt;
 use session::context::QueryContextRef;
 use snafu::ResultExt;
+use sql::statements::query::AsOf as SqlAsOf;
 use sql::statements::statement::Statement;
+use table::requests::AsOf;
 
-use crate::error::{PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
+use crate::error::{ParseAsOfTimeSnafu, PlanSqlSnafu, QueryPlanSnafu, Result, SqlSnafu};
 use crate::parser::QueryStatement;
 use crate::plan::LogicalPlan;
 use crate::query_engine::QueryEngineState;
@@ -52,12 +56,17 @@ impl DfLogicalPlanner {
 
     async fn plan_sql(&self, stmt: Statement, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
         let df_stmt = (&stmt).try_into().context(SqlSnafu)?;
+        let as_of = match &stmt {
+            Statement::Query(query) => query.as_of.as_ref().map(table_as_of).transpose()?,
+            _ => None,
+        };
 
         let context_provider = DfContextProviderAdapter::try_new(
             self.engine_state.clone(),
             self.session_state.clone(),
             &df_stmt,
             query_ctx,
+            as_of,
         )
         .await?;
 
@@ -94,6 +103,15 @@ impl DfLogicalPlanner {
     }
 }
 
+fn table_as_of(as_of: &SqlAsOf) -> Result<AsOf> {
+    match as_of {
+        SqlAsOf::Sequence(sequence) => Ok(AsOf::Sequence(*sequence)),
+        SqlAsOf::Timestamp(raw) => Timestamp::from_str(raw)
+            .map(AsOf::CommitTime)
+            .context(ParseAsOfTimeSnafu { raw }),
+    }
+}
+
 #[async_trait]
 impl LogicalPlanner for DfLogicalPlanner {
     async fn plan(&self, stmt: QueryStatement, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod show_create_table;
+
 use std::sync::Arc;
 
 use catalog::CatalogManagerRef;
-use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, FILE_ENGINE};
 use common_query::Output;
 use common_recordbatch::RecordBatches;
 use datatypes::prelude::*;
@@ -24,6 +26,7 @@ use datatypes::vectors::{Helper, StringVector};
 use once_cell::sync::Lazy;
 use session::context::QueryContextRef;
 use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::create::Partitions;
 use sql::statements::show::{ShowDatabases, ShowKind, ShowTables};
 use table::TableRef;
 
@@ -36,6 +39,8 @@ const COLUMN_TYPE_COLUMN: &str = "Type";
 const COLUMN_NULLABLE_COLUMN: &str = "Null";
 const COLUMN_DEFAULT_COLUMN: &str = "Default";
 const COLUMN_SEMANTIC_TYPE_COLUMN: &str = "Semantic Type";
+const TABLE_COLUMN: &str = "Table";
+const CREATE_TABLE_COLUMN: &str = "Create Table";
 
 const SEMANTIC_TYPE_PRIMARY_KEY: &str = "PRIMARY KEY";
 const SEMANTIC_TYPE_FIELD: &str = "FIELD";
@@ -74,6 +79,17 @@ static DESCRIBE_TABLE_OUTPUT_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
     ]))
 });
 
+static SHOW_CREATE_TABLE_OUTPUT_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
+    Arc::new(Schema::new(vec![
+        ColumnSchema::new(TABLE_COLUMN, ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new(
+            CREATE_TABLE_COLUMN,
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+    ]))
+});
+
 pub fn show_databases(stmt: ShowDatabases, catalog_manager: CatalogManagerRef) -> Result<Output> {
     // TODO(LFC): supports WHERE
     ensure!(
@@ -167,6 +183,34 @@ pub fn describe_table(table: TableRef) -> Result<Output> {
     Ok(Output::RecordBatches(records))
 }
 
+/// Shows the SQL to create the `table`, tables of the file engine are shown as external tables.
+///
+/// `partitions` of the table are stored in table routes, which should be provided by the
+/// caller in distributed mode.
+pub fn show_create_table(table: TableRef, partitions: Option<Partitions>) -> Result<Output> {
+    let table_info = table.table_info();
+    let sql = create_table_sql(&table, partitions)?;
+
+    let columns = vec![
+        Arc::new(StringVector::from(vec![table_info.name.clone()])) as _,
+        Arc::new(StringVector::from(vec![sql])) as _,
+    ];
+    let records = RecordBatches::try_from_columns(SHOW_CREATE_TABLE_OUTPUT_SCHEMA.clone(), columns)
+        .context(error::CreateRecordBatchSnafu)?;
+    Ok(Output::RecordBatches(records))
+}
+
+/// Returns the SQL to create the `table`, tables of the file engine are created by
+/// `CREATE EXTERNAL TABLE`.
+pub fn create_table_sql(table: &TableRef, partitions: Option<Partitions>) -> Result<String> {
+    let table_info = table.table_info();
+    if table_info.meta.engine == FILE_ENGINE {
+        Ok(show_create_table::create_external_table_stmt(&table_info)?.to_string())
+    } else {
+        Ok(show_create_table::create_table_stmt(&table_info, partitions)?.to_string())
+    }
+}
+
 fn describe_column_names(columns_schemas: &[ColumnSchema]) -> VectorRef {
     Arc::new(StringVector::from_iterator(
         columns_schemas.iter().map(|cs| cs.name.as_str()),
@@ -0,0 +1,218 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds the `CREATE TABLE` statement of a table from its metadata.
+
+use std::collections::HashMap;
+
+use datatypes::schema::Schema;
+use snafu::ResultExt;
+use sql::ast::{Ident, ObjectName, SqlOption, TableConstraint, Value as SqlValue};
+use sql::statements::column_schema_to_column_def;
+use sql::statements::create::{CreateExternalTable, CreateTable, Partitions, TIME_INDEX};
+use table::metadata::TableInfo;
+
+use crate::error::{self, Result};
+
+/// Quote style of identifiers, which is accepted by our SQL parser.
+const QUOTE_STYLE: char = '"';
+
+fn quoted_ident(name: &str) -> Ident {
+    Ident::with_quote(QUOTE_STYLE, name)
+}
+
+fn create_table_constraints(
+    schema: &Schema,
+    primary_key_indices: &[usize],
+) -> Vec<TableConstraint> {
+    let mut constraints = Vec::with_capacity(2);
+    if let Some(timestamp_column) = schema.timestamp_column() {
+        constraints.push(TableConstraint::Unique {
+            name: Some(TIME_INDEX.into()),
+            columns: vec![quoted_ident(&timestamp_column.name)],
+            is_primary: false,
+        });
+    }
+    if !primary_key_indices.is_empty() {
+        let columns = primary_key_indices
+            .iter()
+            .map(|index| quoted_ident(&schema.column_schemas()[*index].name))
+            .collect();
+        constraints.push(TableConstraint::Unique {
+            name: None,
+            columns,
+            is_primary: true,
+        });
+    }
+    constraints
+}
+
+fn create_column_defs(schema: &Schema) -> Result<Vec<sql::ast::ColumnDef>> {
+    schema
+        .column_schemas()
+        .iter()
+        .map(column_schema_to_column_def)
+        .collect::<sql::error::Result<Vec<_>>>()
+        .context(error::SqlSnafu)
+}
+
+/// Builds the [CreateTable] statement of the table.
+///
+/// `partitions` should be provided by the caller as they are stored in table routes
+/// rather than the table info.
+pub(crate) fn create_table_stmt(
+    table_info: &TableInfo,
+    partitions: Option<Partitions>,
+) -> Result<CreateTable> {
+    let table_meta = &table_info.meta;
+    let schema = &table_meta.schema;
+
+    let mut options = HashMap::<String, String>::from(&table_meta.options)
+        .into_iter()
+        .collect::<Vec<_>>();
+    // Sorts the options to get a stable output.
+    options.sort_unstable();
+    let options = options
+        .into_iter()
+        .map(|(name, value)| SqlOption {
+            name: Ident::new(name),
+            value: SqlValue::SingleQuotedString(value),
+        })
+        .collect();
+
+    Ok(CreateTable {
+        if_not_exists: false,
+        table_id: table_info.ident.table_id,
+        name: ObjectName(vec![quoted_ident(&table_info.name)]),
+        columns: create_column_defs(schema)?,
+        engine: table_meta.engine.clone(),
+        constraints: create_table_constraints(schema, &table_meta.primary_key_indices),
+        options,
+        partitions,
+    })
+}
+
+/// Builds the [CreateExternalTable] statement of a table of the file engine.
+pub(crate) fn create_external_table_stmt(table_info: &TableInfo) -> Result<CreateExternalTable> {
+    let table_meta = &table_info.meta;
+    let schema = &table_meta.schema;
+
+    Ok(CreateExternalTable {
+        name: ObjectName(vec![quoted_ident(&table_info.name)]),
+        columns: create_column_defs(schema)?,
+        constraints: create_table_constraints(schema, &table_meta.primary_key_indices),
+        options: table_meta.options.extra_options.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_catalog::consts::MITO_ENGINE;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema};
+    use sql::dialect::GenericDialect;
+    use sql::parser::ParserContext;
+    use sql::statements::create::PartitionEntry;
+    use sql::statements::statement::Statement;
+    use table::metadata::{TableInfoBuilder, TableMetaBuilder};
+    use table::requests::TableOptions;
+
+    use super::*;
+
+    #[test]
+    fn test_create_table_stmt() {
+        let column_schemas = vec![
+            ColumnSchema::new("id", ConcreteDataType::uint32_datatype(), true),
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true)
+                .with_default_constraint(Some(ColumnDefaultConstraint::Value(0.0f64.into())))
+                .unwrap(),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            )
+            .with_default_constraint(Some(ColumnDefaultConstraint::Function(
+                "current_timestamp()".to_string(),
+            )))
+            .unwrap()
+            .with_time_index(true),
+        ];
+        let schema = Arc::new(Schema::new(column_schemas));
+        let mut options = TableOptions {
+            ttl: Some(std::time::Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        options
+            .extra_options
+            .insert("regions".to_string(), "2".to_string());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0, 1])
+            .value_indices(vec![2])
+            .engine(MITO_ENGINE)
+            .next_column_id(4)
+            .options(options)
+            .build()
+            .unwrap();
+        let table_info = TableInfoBuilder::new("system_metrics", meta)
+            .ident(1024)
+            .build()
+            .unwrap();
+        let partitions = Partitions {
+            column_list: vec![quoted_ident("host")],
+            entries: vec![
+                PartitionEntry {
+                    name: "r0".into(),
+                    value_list: vec![SqlValue::SingleQuotedString("h".to_string())],
+                },
+                PartitionEntry {
+                    name: "r1".into(),
+                    value_list: vec![SqlValue::Number("MAXVALUE".to_string(), false)],
+                },
+            ],
+        };
+
+        let stmt = create_table_stmt(&table_info, Some(partitions)).unwrap();
+        let sql = stmt.to_string();
+        assert_eq!(
+            r#"CREATE TABLE "system_metrics" (
+  "id" INT UNSIGNED NULL,
+  "host" STRING NULL,
+  "cpu" DOUBLE NULL DEFAULT 0,
+  "ts" TIMESTAMP(3) NOT NULL DEFAULT current_timestamp(),
+  TIME INDEX ("ts"),
+  PRIMARY KEY ("id", "host")
+)
+PARTITION BY RANGE COLUMNS ("host") (
+  PARTITION r0 VALUES LESS THAN ('h'),
+  PARTITION r1 VALUES LESS THAN (MAXVALUE)
+)
+ENGINE=mito
+WITH(
+  regions = '2',
+  ttl = '1h'
+)"#,
+            sql
+        );
+
+        let mut stmts = ParserContext::create_with_dialect(&sql, &GenericDialect {}).unwrap();
+        let Statement::CreateTable(parsed) = stmts.pop().unwrap() else { unreachable!() };
+        assert_eq!(stmt.columns, parsed.columns);
+        assert_eq!(stmt.constraints, parsed.constraints);
+        assert_eq!(stmt.partitions, parsed.partitions);
+    }
+}
@@ -263,6 +263,9 @@ pub enum Error {
     #[snafu(display("Invalid flush argument: {}", err_msg))]
     InvalidFlushArgument { err_msg: String },
 
+    #[snafu(display("Invalid compact argument: {}", err_msg))]
+    InvalidCompactArgument { err_msg: String },
+
     #[snafu(display("Failed to build gRPC reflection service, source: {}", source))]
     GrpcReflectionService {
         source: tonic_reflection::server::Error,
@@ -345,7 +348,9 @@ impl ErrorExt for Error {
             DatabaseNotFound { .. } => StatusCode::DatabaseNotFound,
             #[cfg(feature = "mem-prof")]
             DumpProfileData { source, .. } => source.status_code(),
-            InvalidFlushArgument { .. } => StatusCode::InvalidArguments,
+            InvalidFlushArgument { .. } | InvalidCompactArgument { .. } => {
+                StatusCode::InvalidArguments
+            }
 
             ParsePromQL { source, .. } => source.status_code(),
         }
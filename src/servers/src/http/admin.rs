@@ -16,11 +16,14 @@ use std::collections::HashMap;
 
 use api::v1::ddl_request::Expr;
 use api::v1::greptime_request::Request;
-use api::v1::{DdlRequest, FlushTableExpr};
+use api::v1::query_request::Query as GrpcQuery;
+use api::v1::{DdlRequest, FlushTableExpr, QueryRequest};
 use axum::extract::{Query, RawBody, State};
 use axum::http::StatusCode;
 use session::context::QueryContext;
 use snafu::OptionExt;
+use sql::ast::{Ident, ObjectName};
+use sql::statements::compact::CompactTable;
 
 use crate::error;
 use crate::error::Result;
@@ -65,3 +68,68 @@ pub async fn flush(
     grpc_handler.do_query(request, QueryContext::arc()).await?;
     Ok((StatusCode::NO_CONTENT, ()))
 }
+
+/// Compacts a table, or a region of the table if `region` is present. Returns once the
+/// compaction is scheduled, or is done if `wait` is true.
+#[axum_macros::debug_handler]
+pub async fn compact(
+    State(grpc_handler): State<ServerGrpcQueryHandlerRef>,
+    Query(params): Query<HashMap<String, String>>,
+    RawBody(_): RawBody,
+) -> Result<(StatusCode, ())> {
+    let catalog_name = params
+        .get("catalog")
+        .cloned()
+        .unwrap_or("greptime".to_string());
+    let schema_name = params
+        .get("db")
+        .cloned()
+        .context(error::InvalidCompactArgumentSnafu {
+            err_msg: "db is not present",
+        })?;
+    let table_name = params
+        .get("table")
+        .cloned()
+        .context(error::InvalidCompactArgumentSnafu {
+            err_msg: "table is not present",
+        })?;
+
+    let region_number = params
+        .get("region")
+        .map(|v| {
+            v.parse::<u32>()
+                .ok()
+                .with_context(|| error::InvalidCompactArgumentSnafu {
+                    err_msg: format!("invalid region: {v}"),
+                })
+        })
+        .transpose()?;
+    let wait = params
+        .get("wait")
+        .map(|v| {
+            v.parse::<bool>()
+                .ok()
+                .with_context(|| error::InvalidCompactArgumentSnafu {
+                    err_msg: format!("invalid wait: {v}"),
+                })
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let stmt = CompactTable {
+        table_name: ObjectName(
+            [catalog_name, schema_name, table_name]
+                .into_iter()
+                .map(|ident| Ident::with_quote('"', ident))
+                .collect(),
+        ),
+        region_number,
+        wait,
+    };
+    let request = Request::Query(QueryRequest {
+        query: Some(GrpcQuery::Sql(stmt.to_string())),
+    });
+
+    grpc_handler.do_query(request, QueryContext::arc()).await?;
+    Ok((StatusCode::NO_CONTENT, ()))
+}
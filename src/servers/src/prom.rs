@@ -13,29 +13,31 @@
 // limitations under the License.
 
 //! prom supply the prometheus HTTP API Server compliance
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 
 use async_trait::async_trait;
 use axum::body::BoxBody;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::{routing, Form, Json, Router};
 use common_error::prelude::ErrorExt;
 use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::RecordBatches;
 use common_telemetry::info;
+use common_time::util::current_time_millis;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::scalars::ScalarVector;
 use datatypes::vectors::{Float64Vector, StringVector, TimestampMillisecondVector};
 use futures::FutureExt;
-use promql_parser::label::METRIC_NAME;
+use promql_parser::label::{MatchOp, Matchers, METRIC_NAME};
 use promql_parser::parser::{
-    AggregateExpr, BinaryExpr, Call, Expr as PromqlExpr, MatrixSelector, ParenExpr, SubqueryExpr,
-    UnaryExpr, VectorSelector,
+    token, AggregateExpr, BinaryExpr, Call, Expr as PromqlExpr, MatrixSelector, NumberLiteral,
+    ParenExpr, SubqueryExpr, UnaryExpr, ValueType, VectorSelector,
 };
-use query::parser::PromQuery;
+use query::parser::{PromQuery, QueryLanguageParser};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use snafu::{ensure, OptionExt, ResultExt};
@@ -48,7 +50,8 @@ use tower_http::trace::TraceLayer;
 
 use crate::auth::UserProviderRef;
 use crate::error::{
-    AlreadyStartedSnafu, CollectRecordbatchSnafu, InternalSnafu, Result, StartHttpSnafu,
+    AlreadyStartedSnafu, CollectRecordbatchSnafu, InternalSnafu, InvalidQuerySnafu, Result,
+    StartHttpSnafu,
 };
 use crate::http::authorize::HttpAuth;
 use crate::server::Server;
@@ -60,6 +63,29 @@ pub type PromHandlerRef = Arc<dyn PromHandler + Send + Sync>;
 #[async_trait]
 pub trait PromHandler {
     async fn do_query(&self, query: &PromQuery) -> Result<Output>;
+
+    /// Returns names of all metrics, which are tables in the default schema.
+    async fn metric_names(&self) -> Result<Vec<String>>;
+
+    /// Returns names of labels of the metric, which are primary key columns of the
+    /// table, or `None` if the metric doesn't exist.
+    async fn label_names(&self, metric: &str) -> Result<Option<Vec<String>>>;
+
+    /// Scans distinct values of `labels` in series selected by the `selector`.
+    async fn series_labels(&self, selector: &SeriesSelector, labels: &[String]) -> Result<Output>;
+}
+
+/// Selects series of a metric.
+#[derive(Debug, Clone)]
+pub struct SeriesSelector {
+    /// Name of the metric.
+    pub metric: String,
+    /// Label matchers, the metric name matcher is excluded.
+    pub matchers: Matchers,
+    /// Inclusive start of the time range in milliseconds.
+    pub start: Option<i64>,
+    /// Inclusive end of the time range in milliseconds.
+    pub end: Option<i64>,
 }
 
 /// PromServer represents PrometheusServer which handles the compliance with prometheus HTTP API
@@ -84,11 +110,17 @@ impl PromServer {
     }
 
     pub fn make_app(&self) -> Router {
-        // TODO(ruihang): implement format_query, series, labels, values, query_examplars and targets methods
+        // TODO(ruihang): implement format_query, query_examplars and targets methods
 
         let router = Router::new()
             .route("/query", routing::post(instant_query).get(instant_query))
             .route("/query_range", routing::post(range_query).get(range_query))
+            .route("/labels", routing::post(labels_query).get(labels_query))
+            .route(
+                "/label/:label_name/values",
+                routing::get(label_values_query),
+            )
+            .route("/series", routing::post(series_query).get(series_query))
             .with_state(self.query_handler.clone());
 
         Router::new()
@@ -157,20 +189,74 @@ impl Server for PromServer {
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct PromSeries {
     metric: HashMap<String, String>,
+    /// Samples of a range vector.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     values: Vec<(f64, String)>,
+    /// Sample of an instant vector.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<(f64, String)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PromQueryResult {
+    /// Result of `matrix` and `vector` types.
+    Series(Vec<PromSeries>),
+    /// Result of `scalar` type.
+    Scalar((f64, String)),
+}
+
+impl Default for PromQueryResult {
+    fn default() -> Self {
+        PromQueryResult::Series(Vec::new())
+    }
+}
+
+/// `resultType` of the query response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromResultType {
+    Matrix,
+    Vector,
+    Scalar,
+}
+
+impl PromResultType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PromResultType::Matrix => "matrix",
+            PromResultType::Vector => "vector",
+            PromResultType::Scalar => "scalar",
+        }
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct PromData {
     #[serde(rename = "resultType")]
     result_type: String,
-    result: Vec<PromSeries>,
+    result: PromQueryResult,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PromResponse {
+    PromData(PromData),
+    /// Names of labels or values of a label.
+    Labels(Vec<String>),
+    /// Label sets of series.
+    Series(Vec<BTreeMap<String, String>>),
+}
+
+impl Default for PromResponse {
+    fn default() -> Self {
+        PromResponse::PromData(PromData::default())
+    }
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct PromJsonResponse {
     status: String,
-    data: PromData,
+    data: PromResponse,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -188,14 +274,14 @@ impl PromJsonResponse {
     {
         Json(PromJsonResponse {
             status: "error".to_string(),
-            data: PromData::default(),
+            data: PromResponse::default(),
             error: Some(reason.into()),
             error_type: Some(error_type.into()),
             warnings: None,
         })
     }
 
-    pub fn success(data: PromData) -> Json<Self> {
+    pub fn success(data: PromResponse) -> Json<Self> {
         Json(PromJsonResponse {
             status: "success".to_string(),
             data,
@@ -205,18 +291,34 @@ impl PromJsonResponse {
         })
     }
 
+    /// Convert from the result of metadata APIs
+    fn from_metadata_result(result: Result<PromResponse>) -> Json<Self> {
+        match result {
+            Ok(data) => Self::success(data),
+            Err(err) => Self::error(err.status_code().to_string(), err.to_string()),
+        }
+    }
+
     /// Convert from `Result<Output>`
-    pub async fn from_query_result(result: Result<Output>, metric_name: String) -> Json<Self> {
+    pub async fn from_query_result(
+        result: Result<Output>,
+        metric_name: String,
+        result_type: PromResultType,
+    ) -> Json<Self> {
         let response: Result<Json<Self>> = try {
             let json = match result? {
-                Output::RecordBatches(batches) => {
-                    Self::success(Self::record_batches_to_data(batches, metric_name)?)
-                }
+                Output::RecordBatches(batches) => Self::success(PromResponse::PromData(
+                    Self::record_batches_to_data(batches, metric_name, result_type)?,
+                )),
                 Output::Stream(stream) => {
                     let record_batches = RecordBatches::try_collect(stream)
                         .await
                         .context(CollectRecordbatchSnafu)?;
-                    Self::success(Self::record_batches_to_data(record_batches, metric_name)?)
+                    Self::success(PromResponse::PromData(Self::record_batches_to_data(
+                        record_batches,
+                        metric_name,
+                        result_type,
+                    )?))
                 }
                 Output::AffectedRows(_) => Self::error(
                     "unexpected result",
@@ -234,10 +336,10 @@ impl PromJsonResponse {
                 if err.status_code() == StatusCode::TableNotFound
                     || err.status_code() == StatusCode::TableColumnNotFound
                 {
-                    Self::success(PromData {
-                        result_type: "matrix".to_string(),
+                    Self::success(PromResponse::PromData(PromData {
+                        result_type: result_type.as_str().to_string(),
                         ..Default::default()
-                    })
+                    }))
                 } else {
                     Self::error(err.status_code().to_string(), err.to_string())
                 }
@@ -245,7 +347,11 @@ impl PromJsonResponse {
         }
     }
 
-    fn record_batches_to_data(batches: RecordBatches, metric_name: String) -> Result<PromData> {
+    fn record_batches_to_data(
+        batches: RecordBatches,
+        metric_name: String,
+        result_type: PromResultType,
+    ) -> Result<PromData> {
         // infer semantic type of each column from schema.
         // TODO(ruihang): wish there is a better way to do this.
         let mut timestamp_column_index = None;
@@ -330,17 +436,42 @@ impl PromJsonResponse {
             }
         }
 
+        if result_type == PromResultType::Scalar {
+            // A scalar has no labels, so all values belong to one series.
+            let value = buffer
+                .into_values()
+                .next()
+                .and_then(|mut values| values.pop());
+            return Ok(PromData {
+                result_type: result_type.as_str().to_string(),
+                result: value.map(PromQueryResult::Scalar).unwrap_or_default(),
+            });
+        }
+
         let result = buffer
             .into_iter()
-            .map(|(tags, values)| PromSeries {
-                metric: tags.into_iter().collect(),
-                values,
+            .map(|(tags, mut values)| {
+                let metric = tags.into_iter().collect();
+                if result_type == PromResultType::Vector {
+                    // An instant query is evaluated at a single timestamp.
+                    PromSeries {
+                        metric,
+                        value: values.pop(),
+                        ..Default::default()
+                    }
+                } else {
+                    PromSeries {
+                        metric,
+                        values,
+                        ..Default::default()
+                    }
+                }
             })
             .collect();
 
         let data = PromData {
-            result_type: "matrix".to_string(),
-            result,
+            result_type: result_type.as_str().to_string(),
+            result: PromQueryResult::Series(result),
         };
 
         Ok(data)
@@ -349,20 +480,47 @@ impl PromJsonResponse {
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct InstantQuery {
-    query: String,
+    query: Option<String>,
     time: Option<String>,
     timeout: Option<String>,
 }
 
 #[axum_macros::debug_handler]
 pub async fn instant_query(
-    State(_handler): State<PromHandlerRef>,
-    Query(_params): Query<InstantQuery>,
+    State(handler): State<PromHandlerRef>,
+    Query(params): Query<InstantQuery>,
+    Form(form_params): Form<InstantQuery>,
 ) -> Json<PromJsonResponse> {
-    PromJsonResponse::error(
-        "not implemented",
-        "instant query api `/query` is not implemented. Use `/query_range` instead.",
-    )
+    let query = params.query.or(form_params.query).unwrap_or_default();
+    // Evaluates at the current time if `time` is absent, like Prometheus.
+    let time = params
+        .time
+        .or(form_params.time)
+        .unwrap_or_else(|| format!("{:.3}", current_time_millis() as f64 / 1000.0));
+
+    if let Some(value) = retrieve_scalar_literal(&query) {
+        let timestamp = match QueryLanguageParser::parse_promql_timestamp(&time) {
+            Ok(time) => time.duration_since(UNIX_EPOCH).unwrap_or_default(),
+            Err(e) => return PromJsonResponse::error(e.status_code().to_string(), e.to_string()),
+        };
+        return PromJsonResponse::success(PromResponse::PromData(PromData {
+            result_type: PromResultType::Scalar.as_str().to_string(),
+            result: PromQueryResult::Scalar((timestamp.as_secs_f64(), value.to_string())),
+        }));
+    }
+
+    // An instant query is a range query whose start and end are the same, the step
+    // doesn't matter.
+    let prom_query = PromQuery {
+        query,
+        start: time.clone(),
+        end: time,
+        step: "1s".to_string(),
+    };
+    let result = handler.do_query(&prom_query).await;
+    let metric_name = retrieve_metric_name(&prom_query.query).unwrap_or_default();
+    let result_type = retrieve_instant_result_type(&prom_query.query);
+    PromJsonResponse::from_query_result(result, metric_name, result_type).await
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -388,7 +546,320 @@ pub async fn range_query(
     };
     let result = handler.do_query(&prom_query).await;
     let metric_name = retrieve_metric_name(&prom_query.query).unwrap_or_default();
-    PromJsonResponse::from_query_result(result, metric_name).await
+    PromJsonResponse::from_query_result(result, metric_name, PromResultType::Matrix).await
+}
+
+/// Parameters of the metadata APIs. `match[]` may be repeated, so they are extracted as
+/// key-value pairs.
+#[derive(Debug, Default)]
+struct MetadataQuery {
+    matches: Vec<String>,
+    start: Option<String>,
+    end: Option<String>,
+}
+
+impl MetadataQuery {
+    fn new(params: Vec<(String, String)>, form_params: Vec<(String, String)>) -> MetadataQuery {
+        let mut query = MetadataQuery::default();
+        for (key, value) in params.into_iter().chain(form_params) {
+            match key.as_str() {
+                // Parameters of GET requests are extracted by both `Query` and `Form`.
+                "match[]" if !query.matches.contains(&value) => query.matches.push(value),
+                "start" if query.start.is_none() => query.start = Some(value),
+                "end" if query.end.is_none() => query.end = Some(value),
+                _ => {}
+            }
+        }
+        query
+    }
+
+    /// Returns true if the query selects all series regardless of time.
+    fn selects_all(&self) -> bool {
+        self.matches.is_empty() && self.start.is_none() && self.end.is_none()
+    }
+}
+
+/// A `match[]` series selector.
+struct MatchSelector {
+    /// Name of the metric, selects all metrics if it's absent.
+    metric: Option<String>,
+    matchers: Matchers,
+}
+
+fn parse_match_selector(selector: &str) -> Result<MatchSelector> {
+    let expr = promql_parser::parser::parse(selector)
+        .map_err(|reason| InvalidQuerySnafu { reason }.build())?;
+    let PromqlExpr::VectorSelector(VectorSelector { name, matchers, .. }) = expr else {
+        return InvalidQuerySnafu {
+            reason: format!("Expect a series selector, found: {selector}"),
+        }
+        .fail();
+    };
+
+    let mut metric = name;
+    let mut label_matchers = HashSet::new();
+    for matcher in matchers.matchers {
+        if matcher.name != METRIC_NAME {
+            label_matchers.insert(matcher);
+            continue;
+        }
+        ensure!(
+            matches!(matcher.op, MatchOp::Equal),
+            InvalidQuerySnafu {
+                reason: format!(
+                    "Only equal matcher is supported on {METRIC_NAME}, selector: {selector}"
+                ),
+            }
+        );
+        metric = Some(matcher.value);
+    }
+
+    Ok(MatchSelector {
+        metric,
+        matchers: Matchers {
+            matchers: label_matchers,
+        },
+    })
+}
+
+fn parse_time_millis(time: Option<&str>) -> Result<Option<i64>> {
+    time.map(|time| {
+        let time = QueryLanguageParser::parse_promql_timestamp(time).map_err(|e| {
+            InvalidQuerySnafu {
+                reason: e.to_string(),
+            }
+            .build()
+        })?;
+        Ok(time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as i64))
+    })
+    .transpose()
+}
+
+/// Returns label sets of series selected by `match[]` of the query, or series of all
+/// metrics if `match[]` is absent. Only the metric name and the `label` are kept in label
+/// sets if `label` is given.
+async fn select_series(
+    handler: &PromHandlerRef,
+    query: &MetadataQuery,
+    label: Option<&str>,
+) -> Result<BTreeSet<BTreeMap<String, String>>> {
+    let start = parse_time_millis(query.start.as_deref())?;
+    let end = parse_time_millis(query.end.as_deref())?;
+    let mut selectors = query
+        .matches
+        .iter()
+        .map(|selector| parse_match_selector(selector))
+        .collect::<Result<Vec<_>>>()?;
+    if selectors.is_empty() {
+        selectors.push(MatchSelector {
+            metric: None,
+            matchers: Matchers {
+                matchers: HashSet::new(),
+            },
+        });
+    }
+
+    let mut series = BTreeSet::new();
+    for selector in selectors {
+        let metrics = match selector.metric {
+            Some(metric) => vec![metric],
+            None => handler.metric_names().await?,
+        };
+        for metric in metrics {
+            let Some(label_names) = handler.label_names(&metric).await? else { continue };
+            // The metric has no series with labels to match.
+            if selector
+                .matchers
+                .matchers
+                .iter()
+                .any(|matcher| !label_names.contains(&matcher.name))
+            {
+                continue;
+            }
+            let labels = match label {
+                Some(METRIC_NAME) => Vec::new(),
+                Some(label) if label_names.iter().any(|name| name == label) => {
+                    vec![label.to_string()]
+                }
+                Some(_) => continue,
+                None => label_names,
+            };
+
+            let series_selector = SeriesSelector {
+                metric: metric.clone(),
+                matchers: selector.matchers.clone(),
+                start,
+                end,
+            };
+            let output = handler.series_labels(&series_selector, &labels).await?;
+            collect_series(output, &metric, &labels, &mut series).await?;
+        }
+    }
+    Ok(series)
+}
+
+async fn collect_series(
+    output: Output,
+    metric: &str,
+    labels: &[String],
+    series: &mut BTreeSet<BTreeMap<String, String>>,
+) -> Result<()> {
+    let batches = match output {
+        Output::RecordBatches(batches) => batches,
+        Output::Stream(stream) => RecordBatches::try_collect(stream)
+            .await
+            .context(CollectRecordbatchSnafu)?,
+        Output::AffectedRows(_) => {
+            return InternalSnafu {
+                err_msg: "expected data result, but got affected rows",
+            }
+            .fail()
+        }
+    };
+
+    for batch in batches.iter() {
+        let columns = labels
+            .iter()
+            .map(|label| {
+                batch.column_by_name(label).context(InternalSnafu {
+                    err_msg: format!("no label column {label} found"),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for row_index in 0..batch.num_rows() {
+            let mut label_set = BTreeMap::new();
+            label_set.insert(METRIC_NAME.to_string(), metric.to_string());
+            for (label, column) in labels.iter().zip(columns.iter()) {
+                let value = column.get(row_index);
+                // Prometheus treats empty labels as absent.
+                if !value.is_null() {
+                    let value = value.to_string();
+                    if !value.is_empty() {
+                        label_set.insert(label.to_string(), value);
+                    }
+                }
+            }
+            series.insert(label_set);
+        }
+    }
+    Ok(())
+}
+
+#[axum_macros::debug_handler]
+pub async fn labels_query(
+    State(handler): State<PromHandlerRef>,
+    Query(params): Query<Vec<(String, String)>>,
+    Form(form_params): Form<Vec<(String, String)>>,
+) -> Json<PromJsonResponse> {
+    let query = MetadataQuery::new(params, form_params);
+    let result: Result<PromResponse> = try {
+        let mut labels = BTreeSet::new();
+        if query.selects_all() {
+            // Labels of all metrics are known from the catalog.
+            labels.insert(METRIC_NAME.to_string());
+            for metric in handler.metric_names().await? {
+                labels.extend(handler.label_names(&metric).await?.unwrap_or_default());
+            }
+        } else {
+            for label_set in select_series(&handler, &query, None).await? {
+                labels.extend(label_set.into_keys());
+            }
+        }
+        PromResponse::Labels(labels.into_iter().collect())
+    };
+    PromJsonResponse::from_metadata_result(result)
+}
+
+#[axum_macros::debug_handler]
+pub async fn label_values_query(
+    State(handler): State<PromHandlerRef>,
+    Path(label_name): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    Form(form_params): Form<Vec<(String, String)>>,
+) -> Json<PromJsonResponse> {
+    let query = MetadataQuery::new(params, form_params);
+    let result: Result<PromResponse> = try {
+        let values = if label_name == METRIC_NAME && query.selects_all() {
+            handler.metric_names().await?.into_iter().collect()
+        } else {
+            select_series(&handler, &query, Some(&label_name))
+                .await?
+                .into_iter()
+                .filter_map(|mut label_set| label_set.remove(&label_name))
+                .collect::<BTreeSet<_>>()
+        };
+        PromResponse::Labels(values.into_iter().collect())
+    };
+    PromJsonResponse::from_metadata_result(result)
+}
+
+#[axum_macros::debug_handler]
+pub async fn series_query(
+    State(handler): State<PromHandlerRef>,
+    Query(params): Query<Vec<(String, String)>>,
+    Form(form_params): Form<Vec<(String, String)>>,
+) -> Json<PromJsonResponse> {
+    let query = MetadataQuery::new(params, form_params);
+    if query.matches.is_empty() {
+        return PromJsonResponse::error(
+            StatusCode::InvalidArguments.to_string(),
+            "no match[] parameter provided",
+        );
+    }
+    let result: Result<PromResponse> = try {
+        let series = select_series(&handler, &query, None).await?;
+        PromResponse::Series(series.into_iter().collect())
+    };
+    PromJsonResponse::from_metadata_result(result)
+}
+
+/// Returns the `resultType` of an instant query by the type of the value the query
+/// evaluates to, e.g. `1 + 1`, `time()` and `scalar(up)` evaluate to scalars.
+fn retrieve_instant_result_type(promql: &str) -> PromResultType {
+    match promql_parser::parser::parse(promql).map(|expr| expr.value_type()) {
+        Ok(ValueType::Scalar) => PromResultType::Scalar,
+        _ => PromResultType::Vector,
+    }
+}
+
+/// Returns the value of the query if it only consists of number literals, which is
+/// evaluated without reading any data.
+fn retrieve_scalar_literal(promql: &str) -> Option<f64> {
+    let promql_expr = promql_parser::parser::parse(promql).ok()?;
+    promql_expr_to_scalar(promql_expr)
+}
+
+fn promql_expr_to_scalar(expr: PromqlExpr) -> Option<f64> {
+    match expr {
+        PromqlExpr::NumberLiteral(NumberLiteral { val }) => Some(val),
+        PromqlExpr::Paren(ParenExpr { expr }) => promql_expr_to_scalar(*expr),
+        PromqlExpr::Unary(UnaryExpr { expr }) => promql_expr_to_scalar(*expr).map(|val| -val),
+        PromqlExpr::Binary(BinaryExpr { lhs, rhs, op, .. }) => {
+            let lhs = promql_expr_to_scalar(*lhs)?;
+            let rhs = promql_expr_to_scalar(*rhs)?;
+            // Comparisons between scalars always have the `bool` modifier.
+            let to_f64 = |b: bool| if b { 1.0 } else { 0.0 };
+            match op.id() {
+                token::T_ADD => Some(lhs + rhs),
+                token::T_SUB => Some(lhs - rhs),
+                token::T_MUL => Some(lhs * rhs),
+                token::T_DIV => Some(lhs / rhs),
+                token::T_MOD => Some(lhs % rhs),
+                token::T_POW => Some(lhs.powf(rhs)),
+                token::T_ATAN2 => Some(lhs.atan2(rhs)),
+                token::T_EQLC => Some(to_f64(lhs == rhs)),
+                token::T_NEQ => Some(to_f64(lhs != rhs)),
+                token::T_GTR => Some(to_f64(lhs > rhs)),
+                token::T_LSS => Some(to_f64(lhs < rhs)),
+                token::T_GTE => Some(to_f64(lhs >= rhs)),
+                token::T_LTE => Some(to_f64(lhs <= rhs)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
 }
 
 fn retrieve_metric_name(promql: &str) -> Option<String> {
@@ -422,3 +893,168 @@ fn promql_expr_to_metric_name(expr: PromqlExpr) -> Option<String> {
             .find_map(|e| promql_expr_to_metric_name(*e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use common_recordbatch::RecordBatch;
+    use datatypes::prelude::VectorRef;
+    use datatypes::schema::{ColumnSchema, Schema};
+
+    use super::*;
+
+    #[test]
+    fn test_retrieve_scalar_literal() {
+        assert_eq!(Some(1.5), retrieve_scalar_literal("1.5"));
+        assert_eq!(Some(-2.0), retrieve_scalar_literal("-(2)"));
+        assert_eq!(Some(7.0), retrieve_scalar_literal("1 + 2 * 3"));
+        assert_eq!(Some(8.0), retrieve_scalar_literal("2 ^ 3"));
+        assert_eq!(Some(1.0), retrieve_scalar_literal("2 > bool 1"));
+        assert_eq!(None, retrieve_scalar_literal("http_requests"));
+        assert_eq!(None, retrieve_scalar_literal("sum(http_requests)"));
+        assert_eq!(None, retrieve_scalar_literal("time()"));
+        assert_eq!(None, retrieve_scalar_literal("http_requests + 1"));
+    }
+
+    #[test]
+    fn test_retrieve_instant_result_type() {
+        for query in ["1", "-(2)", "1 + 1", "time()", "scalar(http_requests)"] {
+            assert_eq!(
+                PromResultType::Scalar,
+                retrieve_instant_result_type(query),
+                "{query}"
+            );
+        }
+        for query in [
+            "http_requests",
+            "sum(http_requests)",
+            "http_requests + 1",
+            "vector(1)",
+            "invalid(",
+        ] {
+            assert_eq!(
+                PromResultType::Vector,
+                retrieve_instant_result_type(query),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_metadata_query() {
+        let params = vec![
+            ("match[]".to_string(), "up".to_string()),
+            ("match[]".to_string(), "down".to_string()),
+            ("start".to_string(), "1".to_string()),
+        ];
+        let query = MetadataQuery::new(params.clone(), params);
+        assert_eq!(vec!["up", "down"], query.matches);
+        assert_eq!(Some("1"), query.start.as_deref());
+        assert!(query.end.is_none());
+        assert!(!query.selects_all());
+        assert!(MetadataQuery::new(Vec::new(), Vec::new()).selects_all());
+    }
+
+    #[test]
+    fn test_parse_match_selector() {
+        let selector = parse_match_selector(r#"up{job="api"}"#).unwrap();
+        assert_eq!(Some("up"), selector.metric.as_deref());
+        assert_eq!(1, selector.matchers.matchers.len());
+        assert!(selector.matchers.matchers.iter().all(|m| m.name == "job"));
+
+        let selector = parse_match_selector(r#"{__name__="up", job=~"api.*"}"#).unwrap();
+        assert_eq!(Some("up"), selector.metric.as_deref());
+        assert_eq!(1, selector.matchers.matchers.len());
+
+        let selector = parse_match_selector(r#"{job="api"}"#).unwrap();
+        assert!(selector.metric.is_none());
+
+        assert!(parse_match_selector("rate(up[5m])").is_err());
+        assert!(parse_match_selector(r#"{__name__=~"up.*"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_vector_result() {
+        let column_schemas = vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+            ColumnSchema::new(
+                "ts",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+        ];
+        let schema = Arc::new(Schema::new(column_schemas));
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec!["host1"])),
+            Arc::new(TimestampMillisecondVector::from_vec(vec![8000])),
+            Arc::new(Float64Vector::from_vec(vec![44.0])),
+        ];
+        let recordbatch = RecordBatch::new(schema.clone(), columns).unwrap();
+        let recordbatches = RecordBatches::try_new(schema, vec![recordbatch]).unwrap();
+
+        let Json(resp) = PromJsonResponse::from_query_result(
+            Ok(Output::RecordBatches(recordbatches)),
+            "cpu_usage".to_string(),
+            PromResultType::Vector,
+        )
+        .await;
+        assert_eq!(
+            serde_json::json!({
+                "status": "success",
+                "data": {
+                    "resultType": "vector",
+                    "result": [{
+                        "metric": {"__name__": "cpu_usage", "host": "host1"},
+                        "value": [8.0, "44"],
+                    }],
+                },
+            }),
+            serde_json::to_value(&resp).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scalar_query_result() {
+        // Result of `time()`.
+        let column_schemas = vec![
+            ColumnSchema::new(
+                "time",
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                false,
+            ),
+            ColumnSchema::new("value", ConcreteDataType::float64_datatype(), true),
+        ];
+        let schema = Arc::new(Schema::new(column_schemas));
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(TimestampMillisecondVector::from_vec(vec![8000])),
+            Arc::new(Float64Vector::from_vec(vec![8.0])),
+        ];
+        let recordbatch = RecordBatch::new(schema.clone(), columns).unwrap();
+        let recordbatches = RecordBatches::try_new(schema, vec![recordbatch]).unwrap();
+
+        let Json(resp) = PromJsonResponse::from_query_result(
+            Ok(Output::RecordBatches(recordbatches)),
+            String::new(),
+            PromResultType::Scalar,
+        )
+        .await;
+        let json = serde_json::to_string(&resp).unwrap();
+        assert_eq!(
+            r#"{"status":"success","data":{"resultType":"scalar","result":[8.0,"8"]}}"#,
+            json
+        );
+    }
+
+    #[test]
+    fn test_scalar_result() {
+        let Json(resp) = PromJsonResponse::success(PromResponse::PromData(PromData {
+            result_type: PromResultType::Scalar.as_str().to_string(),
+            result: PromQueryResult::Scalar((8.0, "1".to_string())),
+        }));
+        let json = serde_json::to_string(&resp).unwrap();
+        assert_eq!(
+            r#"{"status":"success","data":{"resultType":"scalar","result":[8.0,"1"]}}"#,
+            json
+        );
+    }
+}
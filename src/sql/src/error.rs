@@ -18,6 +18,7 @@ use common_error::prelude::*;
 use common_time::timestamp::TimeUnit;
 use common_time::Timestamp;
 use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
 use snafu::Location;
 use sqlparser::parser::ParserError;
 use sqlparser::tokenizer::TokenizerError;
@@ -145,6 +146,15 @@ pub enum Error {
         statement: String,
         location: Location,
     },
+
+    #[snafu(display("Unable to convert data type {:?} to SQL data type", data_type))]
+    ConvertToSqlDataType {
+        data_type: ConcreteDataType,
+        location: Location,
+    },
+
+    #[snafu(display("Unable to convert value {:?} to SQL value", value))]
+    ConvertToSqlValue { value: Value, location: Location },
 }
 
 impl ErrorExt for Error {
@@ -175,6 +185,7 @@ impl ErrorExt for Error {
             SerializeColumnDefaultConstraint { source, .. } => source.status_code(),
             ConvertToGrpcDataType { source, .. } => source.status_code(),
             ConvertToDfStatement { .. } => StatusCode::Internal,
+            ConvertToSqlDataType { .. } | ConvertToSqlValue { .. } => StatusCode::Unsupported,
         }
     }
 
@@ -19,7 +19,7 @@ use sqlparser::parser::{Parser, ParserError};
 use sqlparser::tokenizer::{Token, TokenWithLocation};
 
 use crate::error::{self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result, SyntaxSnafu};
-use crate::parsers::tql_parser;
+use crate::parsers::{compact_parser, snapshot_parser, tql_parser};
 use crate::statements::describe::DescribeTable;
 use crate::statements::drop::DropTable;
 use crate::statements::explain::Explain;
@@ -115,12 +115,28 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::COPY => self.parse_copy(),
 
+                    Keyword::TRUNCATE => self.parse_truncate(),
+
                     Keyword::NoKeyword
                         if w.value.to_uppercase() == tql_parser::TQL && w.quote_style.is_none() =>
                     {
                         self.parse_tql()
                     }
 
+                    Keyword::NoKeyword
+                        if w.value.to_uppercase() == compact_parser::COMPACT
+                            && w.quote_style.is_none() =>
+                    {
+                        self.parse_compact()
+                    }
+
+                    Keyword::NoKeyword
+                        if w.value.to_uppercase() == snapshot_parser::RESTORE
+                            && w.quote_style.is_none() =>
+                    {
+                        self.parse_restore()
+                    }
+
                     // todo(hl) support more statements.
                     _ => self.unsupported(self.peek_token_as_string()),
                 }
@@ -174,9 +190,7 @@ impl<'a> ParserContext<'a> {
                 name: table_name.to_string(),
             }
         );
-        Ok(Statement::ShowCreateTable(ShowCreateTable {
-            table_name: table_name.to_string(),
-        }))
+        Ok(Statement::ShowCreateTable(ShowCreateTable { table_name }))
     }
 
     fn parse_show_tables(&mut self) -> Result<Statement> {
@@ -290,6 +304,9 @@ impl<'a> ParserContext<'a> {
 
     fn parse_drop(&mut self) -> Result<Statement> {
         self.parser.next_token();
+        if self.consume_token(snapshot_parser::SNAPSHOT) {
+            return self.parse_drop_snapshot();
+        }
         if !self.matches_keyword(Keyword::TABLE) {
             return self.unsupported(self.peek_token_as_string());
         }
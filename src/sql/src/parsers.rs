@@ -13,9 +13,12 @@
 // limitations under the License.
 
 mod alter_parser;
+pub(crate) mod compact_parser;
 pub(crate) mod copy_parser;
 pub(crate) mod create_parser;
 pub(crate) mod delete_parser;
 pub(crate) mod insert_parser;
 pub(crate) mod query_parser;
+pub(crate) mod snapshot_parser;
 pub(crate) mod tql_parser;
+pub(crate) mod truncate_parser;
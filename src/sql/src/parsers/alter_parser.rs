@@ -14,7 +14,8 @@
 
 use snafu::ResultExt;
 use sqlparser::keywords::Keyword;
-use sqlparser::parser::ParserError;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
@@ -64,10 +65,27 @@ impl<'a> ParserContext<'a> {
                 }
             };
             AlterTableOperation::RenameTable { new_table_name }
+        } else if self.consume_token("MODIFY") {
+            let _ = self.parser.parse_keyword(Keyword::COLUMN);
+            let column_def = self.parser.parse_column_def()?;
+            AlterTableOperation::ModifyColumn { column_def }
+        } else if self.matches_keyword(Keyword::SET) {
+            let options = self.parser.parse_options(Keyword::SET)?;
+            AlterTableOperation::SetTableOptions { options }
+        } else if self.consume_token("UNSET") {
+            self.parser.expect_token(&Token::LParen)?;
+            let keys = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?
+                .into_iter()
+                .map(|key| key.value)
+                .collect();
+            self.parser.expect_token(&Token::RParen)?;
+            AlterTableOperation::UnsetTableOptions { keys }
         } else {
             return Err(ParserError::ParserError(format!(
-                "expect keyword ADD or DROP or RENAME after ALTER TABLE, found {}",
-                parser.peek_token()
+                "expect keyword ADD, DROP, MODIFY, RENAME, SET or UNSET after ALTER TABLE, found {}",
+                self.parser.peek_token()
             )));
         };
         Ok(AlterTable::new(table_name, alter_operation))
@@ -144,13 +162,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_alter_modify_column() {
+        let sql = "ALTER TABLE my_metric_1 MODIFY COLUMN a BIGINT DEFAULT 0";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        assert_matches!(statement, Statement::Alter { .. });
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+
+                let alter_operation = alter_table.alter_operation();
+                assert_matches!(alter_operation, AlterTableOperation::ModifyColumn { .. });
+                match alter_operation {
+                    AlterTableOperation::ModifyColumn { column_def } => {
+                        assert_eq!("a", column_def.name.value);
+                        assert_eq!(DataType::BigInt(None), column_def.data_type);
+                        assert!(column_def
+                            .options
+                            .iter()
+                            .any(|o| matches!(o.option, ColumnOption::Default(_))));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        // The keyword COLUMN is optional.
+        let sql = "ALTER TABLE my_metric_1 MODIFY a DOUBLE";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+    }
+
+    #[test]
+    fn test_parse_alter_table_options() {
+        let sql = "ALTER TABLE my_metric_1 SET ('ttl'='7d', write_buffer_size='1MB')";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+                match alter_table.alter_operation() {
+                    AlterTableOperation::SetTableOptions { options } => {
+                        let options: Vec<_> = options
+                            .iter()
+                            .map(|option| (option.name.value.as_str(), option.value.to_string()))
+                            .collect();
+                        assert_eq!(
+                            vec![
+                                ("ttl", "'7d'".to_string()),
+                                ("write_buffer_size", "'1MB'".to_string())
+                            ],
+                            options
+                        );
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 UNSET ('ttl', write_buffer_size)";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => match alter_table.alter_operation() {
+                AlterTableOperation::UnsetTableOptions { keys } => {
+                    assert_eq!(&["ttl", "write_buffer_size"], &keys[..]);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 UNSET ttl";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
     #[test]
     fn test_parse_alter_rename_table() {
         let sql = "ALTER TABLE test_table table_t";
         let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap_err();
         assert!(result
             .to_string()
-            .contains("expect keyword ADD or DROP or RENAME after ALTER TABLE"));
+            .contains("expect keyword ADD, DROP, MODIFY, RENAME, SET or UNSET after ALTER TABLE"));
 
         let sql = "ALTER TABLE test_table RENAME table_t";
         let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
@@ -0,0 +1,130 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, ResultExt};
+use sqlparser::keywords::Keyword;
+
+use crate::error::{self, InvalidTableNameSnafu, Result};
+use crate::parser::ParserContext;
+use crate::statements::compact::CompactTable;
+use crate::statements::statement::Statement;
+
+pub const COMPACT: &str = "COMPACT";
+const REGION: &str = "REGION";
+const WAIT: &str = "WAIT";
+
+/// COMPACT extension parser:
+/// - COMPACT TABLE <table> [REGION <region number>] [WAIT]
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_compact(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        self.parser
+            .expect_keyword(Keyword::TABLE)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        let table_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a table name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        ensure!(
+            !table_name.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: table_name.to_string()
+            }
+        );
+
+        let region_number = if self.consume_token(REGION) {
+            let actual = self.peek_token_as_string();
+            let region_number = self
+                .parser
+                .parse_literal_uint()
+                .ok()
+                .and_then(|n| u32::try_from(n).ok())
+                .with_context(|| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a region number",
+                    actual,
+                })?;
+            Some(region_number)
+        } else {
+            None
+        };
+        let wait = self.consume_token(WAIT);
+
+        Ok(Statement::CompactTable(CompactTable {
+            table_name,
+            region_number,
+            wait,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse_compact(sql: &str) -> CompactTable {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        match result.remove(0) {
+            Statement::CompactTable(compact) => compact,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_compact_table() {
+        let compact = parse_compact("COMPACT TABLE my_table");
+        assert_eq!("my_table", compact.table_name.to_string());
+        assert_eq!(None, compact.region_number);
+        assert!(!compact.wait);
+        assert_eq!("COMPACT TABLE my_table", compact.to_string());
+
+        let compact = parse_compact("compact table public.my_table region 2 wait;");
+        assert_eq!("public.my_table", compact.table_name.to_string());
+        assert_eq!(Some(2), compact.region_number);
+        assert!(compact.wait);
+        assert_eq!(
+            "COMPACT TABLE public.my_table REGION 2 WAIT",
+            compact.to_string()
+        );
+
+        let compact = parse_compact("COMPACT TABLE my_table WAIT");
+        assert_eq!(None, compact.region_number);
+        assert!(compact.wait);
+    }
+
+    #[test]
+    fn test_parse_compact_table_error() {
+        for sql in [
+            "COMPACT my_table",
+            "COMPACT TABLE",
+            "COMPACT TABLE my_table REGION",
+            "COMPACT TABLE my_table REGION -1",
+            "COMPACT TABLE my_table REGION 4294967296",
+            "COMPACT TABLE my_table NOW",
+        ] {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+}
@@ -12,24 +12,121 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use snafu::ResultExt;
-use sqlparser::ast::ObjectName;
+use sqlparser::ast::{ObjectName, Value};
 use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::copy::{CopyTable, CopyTableArgument, Format};
+use crate::statements::copy::{
+    CopyDatabase, CopyDatabaseArgument, CopyQueryTo, CopyTable, CopyTableArgument, Format,
+    COPY_FORMAT_KEY, COPY_PATTERN_KEY,
+};
+use crate::statements::query::Query;
 use crate::statements::statement::Statement;
 use crate::util::parse_option_string;
 
 // COPY tbl TO 'output.parquet';
+// COPY DATABASE db TO '/path/to/backup/';
+// COPY (SELECT * FROM tbl WHERE ts > 0) TO 'output.csv' WITH (FORMAT = 'csv');
 impl<'a> ParserContext<'a> {
     pub(crate) fn parse_copy(&mut self) -> Result<Statement> {
         self.parser.next_token();
+        if self.parser.parse_keyword(Keyword::DATABASE) {
+            let copy_database = self.parse_copy_database()?;
+            return Ok(Statement::CopyDatabase(copy_database));
+        }
+        if self.parser.consume_token(&Token::LParen) {
+            let copy_query_to = self.parse_copy_query_to()?;
+            return Ok(Statement::CopyQueryTo(copy_query_to));
+        }
         let copy_table = self.parse_copy_table()?;
         Ok(Statement::Copy(copy_table))
     }
 
+    fn parse_copy_database(&mut self) -> Result<CopyDatabase> {
+        let database_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a database name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        let to = if self.parser.parse_keyword(Keyword::TO) {
+            true
+        } else {
+            self.parser
+                .expect_keyword(Keyword::FROM)
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            false
+        };
+
+        let location =
+            self.parser
+                .parse_literal_string()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a directory",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        let with = self.parse_copy_with_options()?;
+        // Validates the format.
+        let _ = parse_format(&with)?;
+        let connection = self.parse_copy_connection_options()?;
+
+        let argument = CopyDatabaseArgument {
+            database_name,
+            with,
+            connection,
+            location,
+        };
+        Ok(if to {
+            CopyDatabase::To(argument)
+        } else {
+            CopyDatabase::From(argument)
+        })
+    }
+
+    fn parse_copy_query_to(&mut self) -> Result<CopyQueryTo> {
+        let query = self
+            .parser
+            .parse_query()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        self.parser
+            .expect_token(&Token::RParen)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        self.parser
+            .expect_keyword(Keyword::TO)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        let location =
+            self.parser
+                .parse_literal_string()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a file name",
+                    actual: self.peek_token_as_string(),
+                })?;
+
+        let with = self.parse_copy_with_options()?;
+        let format = parse_format(&with)?;
+        let connection = self.parse_copy_connection_options()?;
+
+        Ok(CopyQueryTo {
+            query: Box::new(Query::try_from(query)?),
+            format,
+            with,
+            connection,
+            location,
+        })
+    }
+
     fn parse_copy_table(&mut self) -> Result<CopyTable> {
         let table_name =
             self.parser
@@ -60,49 +157,15 @@ impl<'a> ParserContext<'a> {
                     actual: self.peek_token_as_string(),
                 })?;
 
-        let options = self
-            .parser
-            .parse_options(Keyword::WITH)
-            .context(error::SyntaxSnafu { sql: self.sql })?;
-
-        // default format is parquet
-        let mut format = Format::Parquet;
-        let mut pattern = None;
-        for option in options {
-            match option.name.value.to_ascii_uppercase().as_str() {
-                "FORMAT" => {
-                    if let Some(fmt_str) = parse_option_string(option.value) {
-                        format = Format::try_from(fmt_str)?;
-                    }
-                }
-                "PATTERN" => {
-                    if let Some(v) = parse_option_string(option.value) {
-                        pattern = Some(v);
-                    }
-                }
-                //TODO: throws warnings?
-                _ => (),
-            }
-        }
-
-        let connection_options = self
-            .parser
-            .parse_options(Keyword::CONNECTION)
-            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let with = self.parse_copy_with_options()?;
+        let format = parse_format(&with)?;
+        let pattern = with.get(COPY_PATTERN_KEY).cloned();
 
-        let connection = connection_options
-            .into_iter()
-            .filter_map(|option| {
-                if let Some(v) = parse_option_string(option.value) {
-                    Some((option.name.value.to_uppercase(), v))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let connection = self.parse_copy_connection_options()?;
         Ok(CopyTableArgument {
             table_name,
             format,
+            with,
             pattern,
             connection,
             location,
@@ -119,27 +182,29 @@ impl<'a> ParserContext<'a> {
                     actual: self.peek_token_as_string(),
                 })?;
 
-        let options = self
-            .parser
-            .parse_options(Keyword::WITH)
-            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let with = self.parse_copy_with_options()?;
+        let format = parse_format(&with)?;
 
-        // default format is parquet
-        let mut format = Format::Parquet;
-        for option in options {
-            if option.name.value.eq_ignore_ascii_case("FORMAT") {
-                if let Some(fmt_str) = parse_option_string(option.value) {
-                    format = Format::try_from(fmt_str)?;
-                }
-            }
-        }
+        let connection = self.parse_copy_connection_options()?;
 
+        Ok(CopyTableArgument {
+            table_name,
+            format,
+            with,
+            connection,
+            pattern: None,
+            location,
+        })
+    }
+
+    /// Parses options in `CONNECTION (...)`, keys are converted to upper case.
+    fn parse_copy_connection_options(&mut self) -> Result<HashMap<String, String>> {
         let connection_options = self
             .parser
             .parse_options(Keyword::CONNECTION)
             .context(error::SyntaxSnafu { sql: self.sql })?;
 
-        let connection = connection_options
+        Ok(connection_options
             .into_iter()
             .filter_map(|option| {
                 if let Some(v) = parse_option_string(option.value) {
@@ -148,18 +213,38 @@ impl<'a> ParserContext<'a> {
                     None
                 }
             })
-            .collect();
+            .collect())
+    }
 
-        Ok(CopyTableArgument {
-            table_name,
-            format,
-            connection,
-            pattern: None,
-            location,
-        })
+    /// Parses options in `WITH (...)`, keys are converted to upper case.
+    fn parse_copy_with_options(&mut self) -> Result<HashMap<String, String>> {
+        let options = self
+            .parser
+            .parse_options(Keyword::WITH)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        Ok(options
+            .into_iter()
+            .map(|option| {
+                let value = match option.value {
+                    Value::SingleQuotedString(v) | Value::DoubleQuotedString(v) => v,
+                    // Options like `HAS_HEADER = false` are not quoted.
+                    v => v.to_string(),
+                };
+                (option.name.value.to_uppercase(), value)
+            })
+            .collect())
     }
 }
 
+/// Returns the format in options, the default format is parquet.
+fn parse_format(with: &HashMap<String, String>) -> Result<Format> {
+    with.get(COPY_FORMAT_KEY)
+        .map(|format| Format::try_from(format.clone()))
+        .transpose()
+        .map(|format| format.unwrap_or(Format::Parquet))
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -168,6 +253,7 @@ mod tests {
     use sqlparser::dialect::GenericDialect;
 
     use super::*;
+    use crate::statements::copy::{COPY_END_TIME_KEY, COPY_START_TIME_KEY};
 
     #[test]
     fn test_parse_copy_table() {
@@ -330,6 +416,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_copy_table_with_text_format() {
+        let sql = "COPY tbl FROM 's3://bucket/data/' WITH (FORMAT = 'csv', HAS_HEADER = false, DELIMITER = '|', COMPRESSION_TYPE = 'gzip', pattern = '.*csv.gz')";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Copy(CopyTable::From(copy_table)) = result.remove(0) else { unreachable!() };
+        assert_eq!(Format::Csv, copy_table.format);
+        assert_eq!(Some(".*csv.gz".to_string()), copy_table.pattern);
+        let expected_with = [
+            ("FORMAT", "csv"),
+            ("HAS_HEADER", "false"),
+            ("DELIMITER", "|"),
+            ("COMPRESSION_TYPE", "gzip"),
+            ("PATTERN", ".*csv.gz"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<HashMap<_, _>>();
+        assert_eq!(expected_with, copy_table.with);
+
+        let sql = "COPY tbl TO 'tbl.json' WITH (FORMAT = 'ndjson')";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Copy(CopyTable::To(copy_table)) = result.remove(0) else { unreachable!() };
+        assert_eq!(Format::Json, copy_table.format);
+    }
+
+    #[test]
+    fn test_parse_copy_database() {
+        let sql = "COPY DATABASE catalog0.schema0 TO 's3://bucket/backup/' WITH (FORMAT = 'csv') CONNECTION (FOO='Bar')";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::CopyDatabase(CopyDatabase::To(copy_database)) = result.remove(0) else { unreachable!() };
+        assert_eq!("catalog0.schema0", copy_database.database_name.to_string());
+        assert_eq!("s3://bucket/backup/", copy_database.location);
+        assert_eq!(
+            HashMap::from([("FORMAT".to_string(), "csv".to_string())]),
+            copy_database.with
+        );
+        assert_eq!(
+            HashMap::from([("FOO".to_string(), "Bar".to_string())]),
+            copy_database.connection
+        );
+
+        let sql = "COPY DATABASE schema0 FROM '/tmp/backup/'";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::CopyDatabase(CopyDatabase::From(copy_database)) = result.remove(0) else { unreachable!() };
+        assert_eq!("schema0", copy_database.database_name.to_string());
+        assert!(copy_database.with.is_empty());
+
+        let sql = "COPY DATABASE schema0 TO '/tmp/backup/' WITH (FORMAT = 'orc')";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_copy_query_to() {
+        let sql = "COPY (SELECT * FROM tbl WHERE ts >= 1000) TO 'tbl.csv' WITH (FORMAT = 'csv') CONNECTION (FOO='Bar')";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::CopyQueryTo(copy_query_to) = result.remove(0) else { unreachable!() };
+        assert_eq!(
+            "SELECT * FROM tbl WHERE ts >= 1000",
+            copy_query_to.query.inner.to_string()
+        );
+        assert_eq!(Format::Csv, copy_query_to.format);
+        assert_eq!("tbl.csv", copy_query_to.location);
+        assert_eq!(
+            HashMap::from([("FOO".to_string(), "Bar".to_string())]),
+            copy_query_to.connection
+        );
+
+        let sql = "COPY tbl TO 'tbl.parquet' WITH (START_TIME = '2023-04-01 00:00:00', END_TIME = 1680393600000)";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::Copy(CopyTable::To(copy_table)) = result.remove(0) else { unreachable!() };
+        assert_eq!(
+            Some(&"2023-04-01 00:00:00".to_string()),
+            copy_table.with.get(COPY_START_TIME_KEY)
+        );
+        assert_eq!(
+            Some(&"1680393600000".to_string()),
+            copy_table.with.get(COPY_END_TIME_KEY)
+        );
+
+        // Only exporting is supported.
+        let sql = "COPY (SELECT * FROM tbl) FROM 'tbl.csv'";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+        let sql = "COPY (SELECT * FROM tbl TO 'tbl.csv'";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
     #[test]
     fn test_parse_copy_table_with_unsupopoted_format() {
         let results = [
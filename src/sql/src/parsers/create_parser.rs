@@ -31,6 +31,7 @@ use crate::error::{
     SyntaxSnafu,
 };
 use crate::parser::ParserContext;
+use crate::parsers::snapshot_parser;
 use crate::statements::create::{
     CreateDatabase, CreateExternalTable, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
 };
@@ -47,6 +48,9 @@ static THAN: Lazy<Token> = Lazy::new(|| Token::make_keyword("THAN"));
 /// Parses create [table] statement
 impl<'a> ParserContext<'a> {
     pub(crate) fn parse_create(&mut self) -> Result<Statement> {
+        if self.consume_token(snapshot_parser::SNAPSHOT) {
+            return self.parse_create_snapshot();
+        }
         match self.parser.peek_token().token {
             Token::Word(w) => match w.keyword {
                 Keyword::TABLE => self.parse_create_table(),
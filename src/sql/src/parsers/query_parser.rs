@@ -13,21 +13,93 @@
 // limitations under the License.
 
 use snafu::prelude::*;
+use sqlparser::ast::{Query as SpQuery, SetExpr, TableAlias, TableFactor};
+use sqlparser::keywords::Keyword;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::query::Query;
+use crate::statements::query::{AsOf, Query};
 use crate::statements::statement::Statement;
 
 impl<'a> ParserContext<'a> {
     /// Parses select and it's variants.
     pub(crate) fn parse_query(&mut self) -> Result<Statement> {
-        let spquery = self
+        let mut spquery = self
             .parser
             .parse_query()
             .context(error::SyntaxSnafu { sql: self.sql })?;
+        let as_of = self.parse_as_of(&mut spquery)?;
 
-        Ok(Statement::Query(Box::new(Query::try_from(spquery)?)))
+        let mut query = Query::try_from(spquery)?;
+        query.as_of = as_of;
+        Ok(Statement::Query(Box::new(query)))
+    }
+
+    /// Parses the optional `AS OF SEQUENCE <sequence>` or `AS OF TIMESTAMP '<time>'`
+    /// clause at the end of the query.
+    fn parse_as_of(&mut self, query: &mut SpQuery) -> Result<Option<AsOf>> {
+        if !self.parser.parse_keywords(&[Keyword::AS, Keyword::OF]) && !self.take_of_alias(query) {
+            return Ok(None);
+        }
+
+        if self.parser.parse_keyword(Keyword::SEQUENCE) {
+            let sequence = self
+                .parser
+                .parse_literal_uint()
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            Ok(Some(AsOf::Sequence(sequence)))
+        } else if self.parser.parse_keyword(Keyword::TIMESTAMP) {
+            let time = self
+                .parser
+                .parse_literal_string()
+                .context(error::SyntaxSnafu { sql: self.sql })?;
+            Ok(Some(AsOf::Timestamp(time)))
+        } else {
+            self.expected("SEQUENCE or TIMESTAMP", self.parser.peek_token())
+        }
+    }
+
+    /// The parser takes `OF` as the alias of the last table in `FROM t AS OF SEQUENCE 1`.
+    /// Removes the alias and returns true if the query ends like this.
+    fn take_of_alias(&mut self, query: &mut SpQuery) -> bool {
+        if !self.matches_keyword(Keyword::SEQUENCE) && !self.matches_keyword(Keyword::TIMESTAMP) {
+            return false;
+        }
+        let Some(alias) = last_table_alias(query) else { return false };
+        let is_of = alias.as_ref().map_or(false, |alias| {
+            alias.name.quote_style.is_none()
+                && alias.name.value.eq_ignore_ascii_case("OF")
+                && alias.columns.is_empty()
+        });
+        if !is_of {
+            return false;
+        }
+
+        // Also checks the alias follows `AS`, as `FROM t OF SEQUENCE 1` isn't valid.
+        self.parser.prev_token();
+        self.parser.prev_token();
+        if self.parser.parse_keywords(&[Keyword::AS, Keyword::OF]) {
+            *alias = None;
+            true
+        } else {
+            self.parser.next_token();
+            self.parser.next_token();
+            false
+        }
+    }
+}
+
+/// Returns the alias of the last table in the `FROM` clause of the query.
+fn last_table_alias(query: &mut SpQuery) -> Option<&mut Option<TableAlias>> {
+    let SetExpr::Select(select) = query.body.as_mut() else { return None };
+    let table = select.from.last_mut()?;
+    let relation = match table.joins.last_mut() {
+        Some(join) => &mut join.relation,
+        None => &mut table.relation,
+    };
+    match relation {
+        TableFactor::Table { alias, .. } | TableFactor::Derived { alias, .. } => Some(alias),
+        _ => None,
     }
 }
 
@@ -35,7 +107,7 @@ impl<'a> ParserContext<'a> {
 mod tests {
     use sqlparser::dialect::GenericDialect;
 
-    use crate::parser::ParserContext;
+    use super::*;
 
     #[test]
     pub fn test_parse_query() {
@@ -47,6 +119,64 @@ mod tests {
         let _ = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
     }
 
+    fn parse_as_of(sql: &str) -> Option<AsOf> {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        match result.remove(0) {
+            Statement::Query(query) => {
+                // The `OF` alias is removed.
+                assert!(!query.inner.to_string().contains("OF"), "{}", query.inner);
+                query.as_of
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_as_of() {
+        assert_eq!(None, parse_as_of("SELECT * FROM t"));
+        assert_eq!(
+            Some(AsOf::Sequence(10)),
+            parse_as_of("SELECT * FROM t AS OF SEQUENCE 10")
+        );
+        assert_eq!(
+            Some(AsOf::Sequence(10)),
+            parse_as_of("select * from t as of sequence 10;")
+        );
+        assert_eq!(
+            Some(AsOf::Timestamp("2023-01-01 00:00:00".to_string())),
+            parse_as_of("SELECT * FROM t WHERE a > 1 AS OF TIMESTAMP '2023-01-01 00:00:00'")
+        );
+        assert_eq!(
+            Some(AsOf::Sequence(1)),
+            parse_as_of("SELECT * FROM t1 x, t2 y ORDER BY x.a LIMIT 10 AS OF SEQUENCE 1")
+        );
+        assert_eq!(
+            Some(AsOf::Sequence(1)),
+            parse_as_of("SELECT * FROM t1 JOIN t2 ON t1.a = t2.a AS OF SEQUENCE 1")
+        );
+        assert_eq!(
+            "AS OF TIMESTAMP '2023-01-01'",
+            AsOf::Timestamp("2023-01-01".to_string()).to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_parse_invalid_as_of() {
+        for sql in [
+            "SELECT * FROM t AS OF SEQUENCE",
+            "SELECT * FROM t AS OF SEQUENCE -1",
+            "SELECT * FROM t AS OF TIMESTAMP 1",
+            "SELECT * FROM t AS OF VERSION 1",
+            "SELECT * FROM t OF SEQUENCE 1",
+        ] {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+
     #[test]
     pub fn test_parse_invalid_query() {
         let sql = "SELECT * FROM table_1 WHERE";
@@ -0,0 +1,187 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, ResultExt};
+use sqlparser::ast::{Ident, ObjectName};
+use sqlparser::keywords::Keyword;
+
+use crate::error::{self, InvalidTableNameSnafu, Result};
+use crate::parser::ParserContext;
+use crate::statements::snapshot::{CreateSnapshot, DropSnapshot, RestoreTable};
+use crate::statements::statement::Statement;
+
+pub(crate) const SNAPSHOT: &str = "SNAPSHOT";
+pub(crate) const RESTORE: &str = "RESTORE";
+
+/// Snapshot parser:
+/// - CREATE SNAPSHOT <name> ON [TABLE] <table>
+/// - DROP SNAPSHOT <name> ON [TABLE] <table>
+/// - RESTORE TABLE <table> FROM [TABLE] <table> SNAPSHOT <name>
+impl<'a> ParserContext<'a> {
+    /// Parses CREATE SNAPSHOT after the `SNAPSHOT` keyword is consumed.
+    pub(crate) fn parse_create_snapshot(&mut self) -> Result<Statement> {
+        let (snapshot_name, table_name) = self.parse_snapshot_on_table()?;
+        Ok(Statement::CreateSnapshot(CreateSnapshot {
+            snapshot_name,
+            table_name,
+        }))
+    }
+
+    /// Parses DROP SNAPSHOT after the `SNAPSHOT` keyword is consumed.
+    pub(crate) fn parse_drop_snapshot(&mut self) -> Result<Statement> {
+        let (snapshot_name, table_name) = self.parse_snapshot_on_table()?;
+        Ok(Statement::DropSnapshot(DropSnapshot {
+            snapshot_name,
+            table_name,
+        }))
+    }
+
+    pub(crate) fn parse_restore(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        self.parser
+            .expect_keyword(Keyword::TABLE)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let table_name = self.parse_snapshot_table_name()?;
+
+        self.parser
+            .expect_keyword(Keyword::FROM)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let source_table_name = self.parse_snapshot_table_name()?;
+
+        if !self.consume_token(SNAPSHOT) {
+            return self.expected(SNAPSHOT, self.parser.peek_token());
+        }
+        let snapshot_name = self.parse_snapshot_name()?;
+
+        Ok(Statement::RestoreTable(RestoreTable {
+            table_name,
+            source_table_name,
+            snapshot_name,
+        }))
+    }
+
+    fn parse_snapshot_on_table(&mut self) -> Result<(Ident, ObjectName)> {
+        let snapshot_name = self.parse_snapshot_name()?;
+        self.parser
+            .expect_keyword(Keyword::ON)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+        let table_name = self.parse_snapshot_table_name()?;
+
+        Ok((snapshot_name, table_name))
+    }
+
+    fn parse_snapshot_name(&mut self) -> Result<Ident> {
+        self.parser
+            .parse_identifier()
+            .with_context(|_| error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a snapshot name",
+                actual: self.peek_token_as_string(),
+            })
+    }
+
+    fn parse_snapshot_table_name(&mut self) -> Result<ObjectName> {
+        let table_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a table name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        ensure!(
+            !table_name.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: table_name.to_string()
+            }
+        );
+        Ok(table_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        result.remove(0)
+    }
+
+    #[test]
+    fn test_parse_create_drop_snapshot() {
+        match parse("CREATE SNAPSHOT s1 ON TABLE public.my_table") {
+            Statement::CreateSnapshot(create) => {
+                assert_eq!("s1", create.snapshot_name.value);
+                assert_eq!("public.my_table", create.table_name.to_string());
+                assert_eq!("CREATE SNAPSHOT s1 ON public.my_table", create.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        match parse("drop snapshot s1 on my_table;") {
+            Statement::DropSnapshot(drop) => {
+                assert_eq!("s1", drop.snapshot_name.value);
+                assert_eq!("my_table", drop.table_name.to_string());
+                assert_eq!("DROP SNAPSHOT s1 ON my_table", drop.to_string());
+            }
+            _ => unreachable!(),
+        }
+
+        for sql in [
+            "CREATE SNAPSHOT",
+            "CREATE SNAPSHOT s1",
+            "CREATE SNAPSHOT s1 my_table",
+            "DROP SNAPSHOT s1 ON",
+            "CREATE SNAPSHOT s1 ON my_table now",
+        ] {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_restore_table() {
+        match parse("RESTORE TABLE my_table_bak FROM my_table SNAPSHOT s1") {
+            Statement::RestoreTable(restore) => {
+                assert_eq!("my_table_bak", restore.table_name.to_string());
+                assert_eq!("my_table", restore.source_table_name.to_string());
+                assert_eq!("s1", restore.snapshot_name.value);
+                assert_eq!(
+                    "RESTORE TABLE my_table_bak FROM my_table SNAPSHOT s1",
+                    restore.to_string()
+                );
+            }
+            _ => unreachable!(),
+        }
+
+        for sql in [
+            "RESTORE my_table_bak FROM my_table SNAPSHOT s1",
+            "RESTORE TABLE my_table_bak FROM my_table",
+            "RESTORE TABLE my_table_bak my_table SNAPSHOT s1",
+        ] {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+}
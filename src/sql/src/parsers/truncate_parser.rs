@@ -0,0 +1,79 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, ResultExt};
+use sqlparser::keywords::Keyword;
+
+use crate::error::{self, InvalidTableNameSnafu, Result};
+use crate::parser::ParserContext;
+use crate::statements::statement::Statement;
+use crate::statements::truncate::TruncateTable;
+
+/// TRUNCATE parser:
+/// - TRUNCATE [TABLE] <table>
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_truncate(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let _ = self.parser.parse_keyword(Keyword::TABLE);
+
+        let table_name =
+            self.parser
+                .parse_object_name()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a table name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        ensure!(
+            !table_name.0.is_empty(),
+            InvalidTableNameSnafu {
+                name: table_name.to_string()
+            }
+        );
+
+        Ok(Statement::TruncateTable(TruncateTable { table_name }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_truncate_table() {
+        for (sql, expected) in [
+            ("TRUNCATE TABLE my_table", "my_table"),
+            ("truncate public.my_table;", "public.my_table"),
+        ] {
+            let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+            assert_eq!(1, result.len());
+            match result.remove(0) {
+                Statement::TruncateTable(truncate) => {
+                    assert_eq!(expected, truncate.table_name.to_string());
+                    assert_eq!(format!("TRUNCATE TABLE {expected}"), truncate.to_string());
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        for sql in ["TRUNCATE", "TRUNCATE TABLE", "TRUNCATE TABLE my_table now"] {
+            assert!(
+                ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err(),
+                "{sql}"
+            );
+        }
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod alter;
+pub mod compact;
 pub mod copy;
 pub mod create;
 pub mod delete;
@@ -22,8 +23,10 @@ pub mod explain;
 pub mod insert;
 pub mod query;
 pub mod show;
+pub mod snapshot;
 pub mod statement;
 pub mod tql;
+pub mod truncate;
 
 use std::str::FromStr;
 
@@ -35,9 +38,12 @@ use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema};
 use datatypes::types::TimestampType;
 use datatypes::value::Value;
 use snafu::{ensure, OptionExt, ResultExt};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
 
 use crate::ast::{
-    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, Value as SqlValue,
+    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, Ident, TimezoneInfo,
+    Value as SqlValue,
 };
 use crate::error::{
     self, ColumnTypeMismatchSnafu, ConvertToGrpcDataTypeSnafu, InvalidSqlValueSnafu,
@@ -257,6 +263,43 @@ pub fn column_def_to_schema(column_def: &ColumnDef, is_time_index: bool) -> Resu
         })
 }
 
+/// Create a `ColumnDef` from `ColumnSchema`, the inverse of [column_def_to_schema].
+///
+/// The time index constraint is not a column option, so it is not included.
+pub fn column_schema_to_column_def(column_schema: &ColumnSchema) -> Result<ColumnDef> {
+    let mut options = Vec::with_capacity(2);
+    let null_option = if column_schema.is_nullable() {
+        ColumnOption::Null
+    } else {
+        ColumnOption::NotNull
+    };
+    options.push(ColumnOptionDef {
+        name: None,
+        option: null_option,
+    });
+
+    if let Some(default_constraint) = column_schema.default_constraint() {
+        let expr = match default_constraint {
+            ColumnDefaultConstraint::Value(v) => Expr::Value(value_to_sql_value(v)?),
+            ColumnDefaultConstraint::Function(func) => Parser::new(&GenericDialect {})
+                .try_with_sql(func)
+                .and_then(|mut parser| parser.parse_expr())
+                .context(error::SyntaxSnafu { sql: func })?,
+        };
+        options.push(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::Default(expr),
+        });
+    }
+
+    Ok(ColumnDef {
+        name: Ident::with_quote('"', &column_schema.name),
+        data_type: concrete_data_type_to_sql_data_type(&column_schema.data_type)?,
+        collation: None,
+        options,
+    })
+}
+
 /// Convert `ColumnDef` in sqlparser to `ColumnDef` in gRPC proto.
 pub fn sql_column_def_to_grpc_column_def(col: &ColumnDef) -> Result<api::v1::ColumnDef> {
     let name = col.name.value.clone();
@@ -324,6 +367,65 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
     }
 }
 
+/// Converts a `ConcreteDataType` to the `DataType` in sqlparser, the inverse of
+/// [sql_data_type_to_concrete_data_type].
+pub fn concrete_data_type_to_sql_data_type(data_type: &ConcreteDataType) -> Result<SqlDataType> {
+    match data_type {
+        ConcreteDataType::Int64(_) => Ok(SqlDataType::BigInt(None)),
+        ConcreteDataType::UInt64(_) => Ok(SqlDataType::UnsignedBigInt(None)),
+        ConcreteDataType::Int32(_) => Ok(SqlDataType::Int(None)),
+        ConcreteDataType::UInt32(_) => Ok(SqlDataType::UnsignedInt(None)),
+        ConcreteDataType::Int16(_) => Ok(SqlDataType::SmallInt(None)),
+        ConcreteDataType::UInt16(_) => Ok(SqlDataType::UnsignedSmallInt(None)),
+        ConcreteDataType::Int8(_) => Ok(SqlDataType::TinyInt(None)),
+        ConcreteDataType::UInt8(_) => Ok(SqlDataType::UnsignedTinyInt(None)),
+        ConcreteDataType::String(_) => Ok(SqlDataType::String),
+        ConcreteDataType::Float32(_) => Ok(SqlDataType::Float(None)),
+        ConcreteDataType::Float64(_) => Ok(SqlDataType::Double),
+        ConcreteDataType::Boolean(_) => Ok(SqlDataType::Boolean),
+        ConcreteDataType::Date(_) => Ok(SqlDataType::Date),
+        ConcreteDataType::DateTime(_) => Ok(SqlDataType::Datetime(None)),
+        ConcreteDataType::Timestamp(ts_type) => Ok(SqlDataType::Timestamp(
+            Some(ts_type.precision()),
+            TimezoneInfo::None,
+        )),
+        ConcreteDataType::Binary(_) => Ok(SqlDataType::Varbinary(None)),
+        ConcreteDataType::Null(_) | ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_) => {
+            error::ConvertToSqlDataTypeSnafu {
+                data_type: data_type.clone(),
+            }
+            .fail()
+        }
+    }
+}
+
+/// Converts a `Value` to the `Value` in sqlparser, which can be converted back by
+/// [sql_value_to_value].
+pub fn value_to_sql_value(val: &Value) -> Result<SqlValue> {
+    Ok(match val {
+        Value::Null => SqlValue::Null,
+        Value::Boolean(b) => SqlValue::Boolean(*b),
+        Value::UInt8(_)
+        | Value::UInt16(_)
+        | Value::UInt32(_)
+        | Value::UInt64(_)
+        | Value::Int8(_)
+        | Value::Int16(_)
+        | Value::Int32(_)
+        | Value::Int64(_)
+        | Value::Float32(_)
+        | Value::Float64(_) => SqlValue::Number(val.to_string(), false),
+        Value::String(s) => SqlValue::SingleQuotedString(s.as_utf8().to_string()),
+        Value::Binary(_) => SqlValue::HexStringLiteral(val.to_string()),
+        Value::Date(d) => SqlValue::SingleQuotedString(d.to_string()),
+        Value::DateTime(d) => SqlValue::SingleQuotedString(d.to_string()),
+        Value::Timestamp(ts) => SqlValue::SingleQuotedString(ts.to_iso8601_string()),
+        Value::List(_) => {
+            return error::ConvertToSqlValueSnafu { value: val.clone() }.fail();
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -689,6 +791,58 @@ mod tests {
         assert!(!column_schema.is_time_index());
     }
 
+    #[test]
+    pub fn test_column_schema_to_column_def() {
+        let column_schema = ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_microsecond_datatype(),
+            false,
+        )
+        .with_default_constraint(Some(ColumnDefaultConstraint::Function(
+            "current_timestamp()".to_string(),
+        )))
+        .unwrap();
+        let column_def = column_schema_to_column_def(&column_schema).unwrap();
+        assert_eq!(
+            r#""ts" TIMESTAMP(6) NOT NULL DEFAULT current_timestamp()"#,
+            column_def.to_string()
+        );
+        let new_schema = column_def_to_schema(&column_def, false).unwrap();
+        assert_eq!(column_schema, new_schema);
+
+        let column_schema = ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true)
+            .with_default_constraint(Some(ColumnDefaultConstraint::Value(Value::from(1.5f64))))
+            .unwrap();
+        let column_def = column_schema_to_column_def(&column_schema).unwrap();
+        assert_eq!(r#""cpu" DOUBLE NULL DEFAULT 1.5"#, column_def.to_string());
+        let new_schema = column_def_to_schema(&column_def, false).unwrap();
+        assert_eq!(column_schema, new_schema);
+
+        let column_schema = ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_millisecond_datatype(),
+            false,
+        )
+        .with_default_constraint(Some(ColumnDefaultConstraint::Value(Value::Timestamp(
+            Timestamp::new_millisecond(1645459261000),
+        ))))
+        .unwrap();
+        let column_def = column_schema_to_column_def(&column_schema).unwrap();
+        assert_eq!(
+            r#""ts" TIMESTAMP(3) NOT NULL DEFAULT '2022-02-21 16:01:01+0000'"#,
+            column_def.to_string()
+        );
+        let new_schema = column_def_to_schema(&column_def, false).unwrap();
+        assert_eq!(column_schema, new_schema);
+
+        let column_schema = ColumnSchema::new(
+            "list",
+            ConcreteDataType::list_datatype(ConcreteDataType::int32_datatype()),
+            true,
+        );
+        assert!(column_schema_to_column_def(&column_schema).is_err());
+    }
+
     #[test]
     pub fn test_parse_placeholder_value() {
         assert!(sql_value_to_value(
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sqlparser::ast::{ColumnDef, Ident, ObjectName, TableConstraint};
+use sqlparser::ast::{ColumnDef, Ident, ObjectName, SqlOption, TableConstraint};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlterTable {
@@ -45,6 +45,12 @@ pub enum AlterTableOperation {
     AddColumn { column_def: ColumnDef },
     /// `DROP COLUMN <name>`
     DropColumn { name: Ident },
+    /// `MODIFY [ COLUMN ] <column_def>`
+    ModifyColumn { column_def: ColumnDef },
     /// `RENAME <new_table_name>`
     RenameTable { new_table_name: String },
+    /// `SET ( <name> = <value> [, ...] )`
+    SetTableOptions { options: Vec<SqlOption> },
+    /// `UNSET ( <name> [, ...] )`
+    UnsetTableOptions { keys: Vec<String> },
 }
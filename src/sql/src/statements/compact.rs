@@ -0,0 +1,40 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use sqlparser::ast::ObjectName;
+
+/// COMPACT TABLE statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactTable {
+    pub table_name: ObjectName,
+    /// Compacts all regions of the table if absent.
+    pub region_number: Option<u32>,
+    /// Waits until the compaction is done.
+    pub wait: bool,
+}
+
+impl Display for CompactTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "COMPACT TABLE {}", self.table_name)?;
+        if let Some(region_number) = self.region_number {
+            write!(f, " REGION {region_number}")?;
+        }
+        if self.wait {
+            write!(f, " WAIT")?;
+        }
+        Ok(())
+    }
+}
@@ -17,6 +17,14 @@ use std::collections::HashMap;
 use sqlparser::ast::ObjectName;
 
 use crate::error::{self, Result};
+use crate::statements::query::Query;
+
+pub const COPY_FORMAT_KEY: &str = "FORMAT";
+pub const COPY_PATTERN_KEY: &str = "PATTERN";
+/// Only rows whose time index is greater than or equal to this time are exported.
+pub const COPY_START_TIME_KEY: &str = "START_TIME";
+/// Only rows whose time index is less than this time are exported.
+pub const COPY_END_TIME_KEY: &str = "END_TIME";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CopyTable {
@@ -28,24 +36,60 @@ pub enum CopyTable {
 pub struct CopyTableArgument {
     pub table_name: ObjectName,
     pub format: Format,
+    /// Options in `WITH (...)`, keys are in upper case.
+    pub with: HashMap<String, String>,
     pub connection: HashMap<String, String>,
     pub pattern: Option<String>,
     /// Copy tbl [To|From] 'location'.
     pub location: String,
 }
 
+/// `COPY DATABASE` exports all tables in a database to a directory, one sub directory
+/// per table, or imports the tables from such a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyDatabase {
+    To(CopyDatabaseArgument),
+    From(CopyDatabaseArgument),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyDatabaseArgument {
+    pub database_name: ObjectName,
+    /// Options in `WITH (...)`, keys are in upper case.
+    pub with: HashMap<String, String>,
+    pub connection: HashMap<String, String>,
+    /// Copy database [To|From] 'location', the location is a directory.
+    pub location: String,
+}
+
+/// `COPY (SELECT ...) TO 'location'` exports the result of the query to a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyQueryTo {
+    pub query: Box<Query>,
+    pub format: Format,
+    /// Options in `WITH (...)`, keys are in upper case.
+    pub with: HashMap<String, String>,
+    pub connection: HashMap<String, String>,
+    pub location: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Format {
     Parquet,
+    Csv,
+    /// Newline delimited json.
+    Json,
 }
 
 impl TryFrom<String> for Format {
     type Error = error::Error;
 
     fn try_from(name: String) -> Result<Self> {
-        if name.eq_ignore_ascii_case("PARQUET") {
-            return Ok(Format::Parquet);
+        match name.to_uppercase().as_str() {
+            "PARQUET" => Ok(Format::Parquet),
+            "CSV" => Ok(Format::Csv),
+            "JSON" | "NDJSON" => Ok(Format::Json),
+            _ => error::UnsupportedCopyFormatOptionSnafu { name }.fail(),
         }
-        error::UnsupportedCopyFormatOptionSnafu { name }.fail()
     }
 }
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
 
 use crate::ast::{ColumnDef, Ident, ObjectName, SqlOption, TableConstraint, Value as SqlValue};
 
@@ -34,18 +37,93 @@ pub struct CreateTable {
     pub partitions: Option<Partitions>,
 }
 
+impl Display for CreateTable {
+    /// Formats the statement as a SQL that can be parsed back to the same [CreateTable],
+    /// except the `table_id` that is assigned by the catalog.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE TABLE ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        writeln!(f, "{} (", self.name)?;
+
+        let columns = self.columns.iter().map(|column| format!("  {column}"));
+        let constraints = self
+            .constraints
+            .iter()
+            .map(|constraint| format!("  {}", format_table_constraint(constraint)));
+        writeln!(f, "{}", columns.chain(constraints).join(",\n"))?;
+        writeln!(f, ")")?;
+
+        if let Some(partitions) = &self.partitions {
+            writeln!(f, "{partitions}")?;
+        }
+        write!(f, "ENGINE={}", self.engine)?;
+
+        if !self.options.is_empty() {
+            let options = self
+                .options
+                .iter()
+                .map(|option| format!("  {option}"))
+                .join(",\n");
+            write!(f, "\nWITH(\n{options}\n)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the `TIME INDEX` constraint in our own syntax, other constraints are formatted
+/// by sqlparser.
+fn format_table_constraint(constraint: &TableConstraint) -> String {
+    match constraint {
+        TableConstraint::Unique {
+            name: Some(name),
+            columns,
+            is_primary: false,
+        } if name.value == TIME_INDEX => format!("TIME INDEX ({})", columns.iter().join(", ")),
+        _ => constraint.to_string(),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Partitions {
     pub column_list: Vec<Ident>,
     pub entries: Vec<PartitionEntry>,
 }
 
+impl Display for Partitions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "PARTITION BY RANGE COLUMNS ({}) (",
+            self.column_list.iter().join(", ")
+        )?;
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| format!("  {entry}"))
+            .join(",\n");
+        write!(f, "{entries}\n)")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct PartitionEntry {
     pub name: Ident,
     pub value_list: Vec<SqlValue>,
 }
 
+impl Display for PartitionEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PARTITION {} VALUES LESS THAN ({})",
+            self.name,
+            self.value_list.iter().join(", ")
+        )
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CreateDatabase {
     pub name: ObjectName,
@@ -62,3 +140,121 @@ pub struct CreateExternalTable {
     /// Table options in `WITH`.
     pub options: HashMap<String, String>,
 }
+
+impl Display for CreateExternalTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE EXTERNAL TABLE {}", self.name)?;
+        if !self.columns.is_empty() || !self.constraints.is_empty() {
+            let columns = self.columns.iter().map(|column| format!("  {column}"));
+            let constraints = self
+                .constraints
+                .iter()
+                .map(|constraint| format!("  {}", format_table_constraint(constraint)));
+            write!(f, " (\n{}\n)", columns.chain(constraints).join(",\n"))?;
+        }
+
+        if !self.options.is_empty() {
+            // Sorts the options to get a stable output.
+            let options = self
+                .options
+                .iter()
+                .sorted_by(|a, b| a.0.cmp(b.0))
+                .map(|(k, v)| format!("  {k} = {}", SqlValue::SingleQuotedString(v.clone())))
+                .join(",\n");
+            write!(f, "\nWITH(\n{options}\n)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+    use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
+
+    fn parse_create_table(sql: &str) -> CreateTable {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        match stmts.pop().unwrap() {
+            Statement::CreateTable(c) => c,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_display_create_table() {
+        let sql = r"create table if not exists demo(
+                             host string,
+                             ts timestamp,
+                             cpu double default 0,
+                             memory double,
+                             TIME INDEX (ts),
+                             PRIMARY KEY(host)
+                       )
+                       PARTITION BY RANGE COLUMNS (host) (
+                         PARTITION r0 VALUES LESS THAN ('a'),
+                         PARTITION r1 VALUES LESS THAN (MAXVALUE)
+                       )
+                       engine=mito
+                       with(regions=1, ttl='7d');
+         ";
+        let create_table = parse_create_table(sql);
+        let new_sql = create_table.to_string();
+        assert_eq!(
+            r#"CREATE TABLE IF NOT EXISTS demo (
+  host STRING,
+  ts TIMESTAMP,
+  cpu DOUBLE DEFAULT 0,
+  memory DOUBLE,
+  TIME INDEX (ts),
+  PRIMARY KEY (host)
+)
+PARTITION BY RANGE COLUMNS (host) (
+  PARTITION r0 VALUES LESS THAN ('a'),
+  PARTITION r1 VALUES LESS THAN (MAXVALUE)
+)
+ENGINE=mito
+WITH(
+  regions = 1,
+  ttl = '7d'
+)"#,
+            new_sql
+        );
+
+        assert_eq!(create_table, parse_create_table(&new_sql));
+    }
+
+    #[test]
+    fn test_display_create_external_table() {
+        let sql = r"create external table city (
+                host string,
+                ts int64,
+                TIME INDEX (ts)
+            ) with(location='/var/data/city.csv', format='csv');";
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let Statement::CreateExternalTable(create_table) = stmts.pop().unwrap() else { unreachable!() };
+
+        let new_sql = create_table.to_string();
+        assert_eq!(
+            r#"CREATE EXTERNAL TABLE city (
+  host STRING,
+  ts int64,
+  TIME INDEX (ts)
+)
+WITH(
+  FORMAT = 'csv',
+  LOCATION = '/var/data/city.csv'
+)"#,
+            new_sql
+        );
+
+        let mut stmts = ParserContext::create_with_dialect(&new_sql, &GenericDialect {}).unwrap();
+        assert_eq!(
+            Statement::CreateExternalTable(create_table),
+            stmts.pop().unwrap()
+        );
+    }
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+
 use datatypes::prelude::ConcreteDataType;
 use sqlparser::ast::Query as SpQuery;
 
@@ -22,6 +24,26 @@ use crate::error::Error;
 pub struct Query {
     pub inner: SpQuery,
     pub param_types: Vec<ConcreteDataType>,
+    /// Version of tables to read, `None` to read the latest version.
+    pub as_of: Option<AsOf>,
+}
+
+/// The `AS OF` clause of a query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsOf {
+    /// `AS OF SEQUENCE <sequence>`
+    Sequence(u64),
+    /// `AS OF TIMESTAMP '<time>'`, the time is the commit time of rows.
+    Timestamp(String),
+}
+
+impl fmt::Display for AsOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsOf::Sequence(sequence) => write!(f, "AS OF SEQUENCE {sequence}"),
+            AsOf::Timestamp(time) => write!(f, "AS OF TIMESTAMP '{time}'"),
+        }
+    }
 }
 
 /// Automatically converts from sqlparser Query instance to SqlQuery.
@@ -32,6 +54,7 @@ impl TryFrom<SpQuery> for Query {
         Ok(Query {
             inner: q,
             param_types: vec![],
+            as_of: None,
         })
     }
 }
@@ -0,0 +1,69 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use sqlparser::ast::{Ident, ObjectName};
+
+/// CREATE SNAPSHOT statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateSnapshot {
+    pub snapshot_name: Ident,
+    pub table_name: ObjectName,
+}
+
+impl Display for CreateSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CREATE SNAPSHOT {} ON {}",
+            self.snapshot_name, self.table_name
+        )
+    }
+}
+
+/// DROP SNAPSHOT statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropSnapshot {
+    pub snapshot_name: Ident,
+    pub table_name: ObjectName,
+}
+
+impl Display for DropSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DROP SNAPSHOT {} ON {}",
+            self.snapshot_name, self.table_name
+        )
+    }
+}
+
+/// RESTORE TABLE statement, restores a read-only table from the snapshot of another table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreTable {
+    pub table_name: ObjectName,
+    pub source_table_name: ObjectName,
+    pub snapshot_name: Ident,
+}
+
+impl Display for RestoreTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RESTORE TABLE {} FROM {} SNAPSHOT {}",
+            self.table_name, self.source_table_name, self.snapshot_name
+        )
+    }
+}
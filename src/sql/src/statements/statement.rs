@@ -17,7 +17,8 @@ use sqlparser::ast::Statement as SpStatement;
 
 use crate::error::{ConvertToDfStatementSnafu, Error};
 use crate::statements::alter::AlterTable;
-use crate::statements::copy::CopyTable;
+use crate::statements::compact::CompactTable;
+use crate::statements::copy::{CopyDatabase, CopyQueryTo, CopyTable};
 use crate::statements::create::{CreateDatabase, CreateExternalTable, CreateTable};
 use crate::statements::delete::Delete;
 use crate::statements::describe::DescribeTable;
@@ -26,7 +27,9 @@ use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
 use crate::statements::query::Query;
 use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
+use crate::statements::snapshot::{CreateSnapshot, DropSnapshot, RestoreTable};
 use crate::statements::tql::Tql;
+use crate::statements::truncate::TruncateTable;
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[allow(clippy::large_enum_variant)]
@@ -61,7 +64,21 @@ pub enum Statement {
     Use(String),
     // COPY
     Copy(CopyTable),
+    // COPY DATABASE
+    CopyDatabase(CopyDatabase),
+    // COPY (query) TO
+    CopyQueryTo(CopyQueryTo),
     Tql(Tql),
+    // COMPACT TABLE
+    CompactTable(CompactTable),
+    // TRUNCATE TABLE
+    TruncateTable(TruncateTable),
+    // CREATE SNAPSHOT
+    CreateSnapshot(CreateSnapshot),
+    // DROP SNAPSHOT
+    DropSnapshot(DropSnapshot),
+    // RESTORE TABLE
+    RestoreTable(RestoreTable),
 }
 
 /// Comment hints from SQL.
@@ -12,21 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_telemetry::debug;
 use common_time::range::TimestampRange;
+use datafusion_common::{Column, ScalarValue};
+use datatypes::arrow::array::ArrayRef;
+use datatypes::data_type::DataType;
 use snafu::ResultExt;
 use store_api::storage::{Chunk, ChunkReader, SchemaRef, SequenceNumber};
-use table::predicate::{Predicate, TimeRangePredicateBuilder};
+use table::predicate::{Predicate, PruningStatistics, TimeRangePredicateBuilder};
 
 use crate::error::{self, Error, Result};
 use crate::memtable::{IterContext, MemtableRef};
-use crate::read::{Batch, BoxedBatchReader, DedupReader, MergeReaderBuilder};
+use crate::read::{
+    Batch, BoxedBatchReader, DedupReader, MergeReaderBuilder, SequenceReader, TombstoneFilter,
+    TombstoneReader,
+};
 use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchemaRef};
-use crate::sst::{AccessLayerRef, FileHandle, LevelMetas, ReadOptions};
+use crate::sst::{bloom_filter, AccessLayerRef, FileHandle, LevelMetas, ReadOptions};
+use crate::tombstone::RangeTombstonesRef;
 
 /// Chunk reader implementation.
 // Now we use async-trait to implement the chunk reader, which is easier to implement than
@@ -84,6 +92,10 @@ pub struct ChunkReaderBuilder {
     iter_ctx: IterContext,
     memtables: Vec<MemtableRef>,
     files_to_read: Vec<FileHandle>,
+    ordered: bool,
+    range_tombstones: RangeTombstonesRef,
+    /// Whether to remove rows invisible at the sequence from SSTs.
+    filter_by_sequence: bool,
 }
 
 impl ChunkReaderBuilder {
@@ -96,6 +108,9 @@ impl ChunkReaderBuilder {
             iter_ctx: IterContext::default(),
             memtables: Vec::new(),
             files_to_read: Vec::new(),
+            ordered: true,
+            range_tombstones: Default::default(),
+            filter_by_sequence: false,
         }
     }
 
@@ -125,6 +140,32 @@ impl ChunkReaderBuilder {
         self
     }
 
+    /// Sets whether to remove rows with duplicate keys and deleted rows, defaults to true.
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.iter_ctx.dedup = dedup;
+        self
+    }
+
+    /// Sets whether rows should be sorted by key, defaults to true. Sources are concatenated
+    /// instead of merged if false, which is only valid if rows are not deduped.
+    pub fn ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Sets whether to filter rows by the visible sequence, which is required to read a
+    /// sequence older than the committed sequence.
+    pub fn filter_by_sequence(mut self, filter_by_sequence: bool) -> Self {
+        self.filter_by_sequence = filter_by_sequence;
+        self
+    }
+
+    /// Sets range tombstones to remove rows they delete.
+    pub fn range_tombstones(mut self, range_tombstones: RangeTombstonesRef) -> Self {
+        self.range_tombstones = range_tombstones;
+        self
+    }
+
     pub fn pick_memtables(mut self, memtables: MemtableRef) -> Self {
         self.memtables.push(memtables);
         self
@@ -158,6 +199,27 @@ impl ChunkReaderBuilder {
             time_range_predicate
         );
 
+        let predicate = Predicate::new(self.filters);
+        // Pruning by field columns before dedup may skip the latest version or the delete of
+        // a key, then an older version matching the filters would be read. So only filters on
+        // row key columns, including the time index, can prune files, row groups and pages if
+        // rows are deduped.
+        let predicate = if self.iter_ctx.dedup {
+            let row_key_columns: HashSet<_> =
+                self.schema.row_key_columns().map(|c| c.name()).collect();
+            predicate.retain_columns(|name| row_key_columns.contains(name))
+        } else {
+            predicate
+        };
+        let equal_values = bloom_filter::collect_equal_values(&predicate);
+        let file_matches = predicate.prune_with_stats(
+            self.schema.user_schema(),
+            &FilePruningStatistics {
+                schema: self.schema.user_schema(),
+                files: &self.files_to_read,
+            },
+        );
+
         let schema = Arc::new(
             ProjectedSchema::new(self.schema, self.projection)
                 .context(error::InvalidProjectionSnafu)?,
@@ -176,10 +238,10 @@ impl ChunkReaderBuilder {
         let read_opts = ReadOptions {
             batch_size: self.iter_ctx.batch_size,
             projected_schema: schema.clone(),
-            predicate: Predicate::new(self.filters),
+            predicate,
             time_range: time_range_predicate,
         };
-        for file in &self.files_to_read {
+        for (file, matches) in self.files_to_read.iter().zip(file_matches) {
             if !Self::file_in_range(file, time_range_predicate) {
                 debug!(
                     "Skip file {:?}, predicate: {:?}",
@@ -187,15 +249,51 @@ impl ChunkReaderBuilder {
                 );
                 continue;
             }
+            if !matches {
+                debug!("Skip file {:?} by column statistics", file);
+                continue;
+            }
+            if !bloom_filter::file_may_match(file.bloom_filters(), &equal_values) {
+                debug!("Skip file {:?} by bloom filters", file);
+                continue;
+            }
             let reader = self.sst_layer.read_sst(file.clone(), &read_opts).await?;
 
             reader_builder = reader_builder.push_batch_reader(reader);
         }
 
-        let reader = reader_builder.build();
-        let reader = DedupReader::new(schema.clone(), reader);
+        debug_assert!(self.ordered || !self.iter_ctx.dedup);
+        let reader: BoxedBatchReader = if self.ordered {
+            Box::new(reader_builder.build())
+        } else {
+            Box::new(reader_builder.build_chain())
+        };
+        let reader: BoxedBatchReader = if self.filter_by_sequence {
+            Box::new(SequenceReader::new(
+                schema.clone(),
+                self.iter_ctx.visible_sequence,
+                reader,
+            ))
+        } else {
+            reader
+        };
+        let filter = TombstoneFilter::new(
+            schema.schema_to_read(),
+            &self.range_tombstones,
+            self.iter_ctx.visible_sequence,
+        );
+        let reader: BoxedBatchReader = if filter.is_empty() {
+            reader
+        } else {
+            Box::new(TombstoneReader::new(schema.clone(), filter, reader))
+        };
+        let reader: BoxedBatchReader = if self.iter_ctx.dedup {
+            Box::new(DedupReader::new(schema.clone(), reader))
+        } else {
+            reader
+        };
 
-        Ok(ChunkReaderImpl::new(schema, Box::new(reader)))
+        Ok(ChunkReaderImpl::new(schema, reader))
     }
 
     /// Build time range predicate from schema and filters.
@@ -216,3 +314,166 @@ impl ChunkReaderBuilder {
         file_ts_range.intersects(&predicate)
     }
 }
+
+/// Statistics of row key columns in files, each file is a container to prune.
+struct FilePruningStatistics<'a> {
+    schema: &'a SchemaRef,
+    files: &'a [FileHandle],
+}
+
+impl<'a> FilePruningStatistics<'a> {
+    /// Returns min or max values of the `column` in each file, files without statistics
+    /// of the column have null values. Returns `None` if no file has statistics.
+    fn values_of(&self, column: &Column, min: bool) -> Option<ArrayRef> {
+        let data_type = &self.schema.column_schema_by_name(&column.name)?.data_type;
+        let null = ScalarValue::try_from(&data_type.as_arrow_type()).ok()?;
+
+        let mut has_stats = false;
+        let values = self
+            .files
+            .iter()
+            .map(|file| {
+                file.column_stats()
+                    .iter()
+                    .find(|stats| stats.name == column.name)
+                    .and_then(|stats| {
+                        let value = if min { &stats.min } else { &stats.max };
+                        value.try_to_scalar_value(data_type).ok()
+                    })
+                    .map(|value| {
+                        has_stats = true;
+                        value
+                    })
+                    .unwrap_or_else(|| null.clone())
+            })
+            .collect::<Vec<_>>();
+        if !has_stats {
+            return None;
+        }
+
+        ScalarValue::iter_to_array(values).ok()
+    }
+}
+
+impl<'a> PruningStatistics for FilePruningStatistics<'a> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.values_of(column, true)
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.values_of(column, false)
+    }
+
+    fn num_containers(&self) -> usize {
+        self.files.len()
+    }
+
+    fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_time::Timestamp;
+    use datafusion_expr::{col, lit};
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::value::Value;
+    use store_api::storage::consts;
+
+    use super::*;
+    use crate::file_purger::noop::new_noop_file_purger;
+    use crate::memtable::tests as memtable_tests;
+    use crate::metadata::RegionMetadata;
+    use crate::sst::bloom_filter::{hash_bytes, BloomFilter};
+    use crate::sst::{ColumnStats, FileBloomFilter, FileId, FileMeta};
+    use crate::test_util::access_layer_util::MockAccessLayer;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
+
+    fn new_file_handle(column_stats: Vec<ColumnStats>) -> FileHandle {
+        FileHandle::new(
+            FileMeta {
+                region_id: 0,
+                file_id: FileId::random(),
+                time_range: Some((
+                    Timestamp::new_millisecond(0),
+                    Timestamp::new_millisecond(1000),
+                )),
+                level: 0,
+                file_size: 0,
+                column_stats,
+                tombstone_sequence: None,
+                bloom_filters: vec![],
+            },
+            Arc::new(MockAccessLayer {}),
+            new_noop_file_purger(),
+        )
+    }
+
+    fn version_stats(min: u64, max: u64) -> Vec<ColumnStats> {
+        vec![ColumnStats {
+            name: consts::VERSION_COLUMN_NAME.to_string(),
+            min: Value::UInt64(min),
+            max: Value::UInt64(max),
+        }]
+    }
+
+    #[test]
+    fn test_prune_files_by_column_stats() {
+        let schema = memtable_tests::schema_for_test();
+        let files = vec![
+            new_file_handle(version_stats(1, 2)),
+            new_file_handle(version_stats(4, 5)),
+            new_file_handle(vec![]),
+        ];
+        let stats = FilePruningStatistics {
+            schema: schema.user_schema(),
+            files: &files,
+        };
+
+        let predicate = Predicate::new(vec![Expr::from(
+            col(consts::VERSION_COLUMN_NAME).gt(lit(3u64)),
+        )]);
+        assert_eq!(
+            vec![false, true, true],
+            predicate.prune_with_stats(schema.user_schema(), &stats)
+        );
+
+        // Files without statistics of the column are never skipped.
+        let predicate = Predicate::new(vec![Expr::from(col("v0").gt(lit(3u64)))]);
+        assert_eq!(
+            vec![true, true, true],
+            predicate.prune_with_stats(schema.user_schema(), &stats)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skip_files_by_bloom_filters() {
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+        let file = FileHandle::new(
+            FileMeta {
+                bloom_filters: vec![FileBloomFilter {
+                    column: "host".to_string(),
+                    filter: BloomFilter::from_hashes([hash_bytes(b"a")].into_iter()),
+                }],
+                ..new_file_handle(vec![]).meta()
+            },
+            Arc::new(MockAccessLayer {}),
+            new_noop_file_purger(),
+        );
+
+        // The mock access layer panics if the file is read.
+        let mut reader =
+            ChunkReaderBuilder::new(metadata.schema().clone(), Arc::new(MockAccessLayer {}))
+                .filters(vec![Expr::from(col("host").eq(lit("b")))])
+                .pick_ssts(&[file])
+                .build()
+                .await
+                .unwrap();
+        assert!(reader.next_chunk().await.unwrap().is_none());
+    }
+}
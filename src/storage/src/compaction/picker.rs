@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,14 +22,16 @@ use common_telemetry::{debug, error, info};
 use common_time::Timestamp;
 use snafu::ResultExt;
 use store_api::logstore::LogStore;
+use store_api::storage::CompactionStrategy;
 
 use crate::compaction::scheduler::CompactionRequestImpl;
-use crate::compaction::strategy::{SimpleTimeWindowStrategy, StrategyRef};
-use crate::compaction::task::{CompactionTask, CompactionTaskImpl};
+use crate::compaction::strategy::{SimpleTimeWindowStrategy, StrategyRef, TwcsStrategy};
+use crate::compaction::task::{CompactionOutput, CompactionTask, CompactionTaskImpl};
 use crate::error::TtlCalculationSnafu;
 use crate::scheduler::Request;
 use crate::sst::{FileHandle, Level};
-use crate::version::LevelMetasRef;
+use crate::tombstone::RangeTombstone;
+use crate::version::{LevelMetasRef, Version};
 
 /// Picker picks input SST files and builds the compaction task.
 /// Different compaction strategy may implement different pickers.
@@ -54,8 +58,10 @@ impl PickerContext {
     }
 }
 
-/// L0 -> L1 compaction based on time windows.
+/// L0 -> L1 compaction based on time windows, and L1 -> L1 compaction if the region
+/// uses [CompactionStrategy::Twcs].
 pub struct SimplePicker<S> {
+    /// Strategy for regions using [CompactionStrategy::Simple].
     strategy: StrategyRef,
     _phantom_data: PhantomData<S>,
 }
@@ -91,6 +97,15 @@ impl<S> SimplePicker<S> {
         }
         Ok(expired_ssts)
     }
+
+    fn strategy(&self, compaction_strategy: CompactionStrategy) -> StrategyRef {
+        match compaction_strategy {
+            CompactionStrategy::Simple => self.strategy.clone(),
+            CompactionStrategy::Twcs {
+                max_files_in_window,
+            } => Arc::new(TwcsStrategy::new(max_files_in_window)),
+        }
+    }
 }
 
 impl<S: LogStore> Picker for SimplePicker<S> {
@@ -119,34 +134,105 @@ impl<S: LogStore> Picker for SimplePicker<S> {
             expired_ssts.iter().for_each(|f| f.mark_compacting(true));
         }
 
-        let ctx = &PickerContext::with(req.compaction_time_window);
+        let strategy = self.strategy(req.compaction_strategy);
+        // Inputs of different levels never overlap, so outputs of all levels are compacted
+        // in one task. Unless the window is configured, each level infers its own window
+        // from its files: level 0 from the span of the whole level, and level 1 from the
+        // span of each file written by previous compactions.
+        let ctx = PickerContext::with(req.compaction_time_window);
+        let mut compaction_time_window = req.compaction_time_window;
+        let mut outputs = vec![];
         for level_num in 0..levels.level_num() {
             let level = levels.level(level_num as u8);
-            let (compaction_time_window, outputs) = self.strategy.pick(ctx, level);
+            let (time_window, level_outputs) = strategy.pick(&ctx, level);
 
-            if outputs.is_empty() {
+            if level_outputs.is_empty() {
                 debug!("No SST file can be compacted at level {}", level_num);
                 continue;
             }
 
             debug!(
                 "Found SST files to compact {:?} on level: {}",
-                outputs, level_num
+                level_outputs, level_num
             );
-            return Ok(Some(CompactionTaskImpl {
-                schema: req.schema(),
-                sst_layer: req.sst_layer.clone(),
-                outputs,
-                writer: req.writer.clone(),
-                shared_data: req.shared.clone(),
-                wal: req.wal.clone(),
-                manifest: req.manifest.clone(),
-                expired_ssts,
-                sst_write_buffer_size: req.sst_write_buffer_size,
-                compaction_time_window,
-            }));
+            compaction_time_window = compaction_time_window.or(time_window);
+            outputs.extend(level_outputs);
+        }
+
+        if outputs.is_empty() {
+            return Ok(None);
         }
 
-        Ok(None)
+        let version = req.shared.version_control.current();
+        let range_tombstones = version.range_tombstones().clone();
+        let tombstones_to_remove = applied_tombstones(&version, &outputs, &expired_ssts);
+        // Dedup keeps only the latest row of each key, and tombstones delete rows flushed
+        // before them, so reads before these sequences can't see removed rows any more.
+        let min_readable_sequence = (!req.shared.append_mode() || !range_tombstones.is_empty())
+            .then(|| {
+                range_tombstones
+                    .iter()
+                    .map(|tombstone| tombstone.sequence)
+                    .fold(version.flushed_sequence(), cmp::max)
+            });
+
+        Ok(Some(CompactionTaskImpl {
+            schema: req.schema(),
+            sst_layer: req.sst_layer.clone(),
+            outputs,
+            writer: req.writer.clone(),
+            shared_data: req.shared.clone(),
+            wal: req.wal.clone(),
+            manifest: req.manifest.clone(),
+            expired_ssts,
+            sst_write_buffer_size: req.sst_write_buffer_size,
+            sst_compression: req.sst_compression,
+            compaction_time_window,
+            range_tombstones,
+            tombstones_to_remove,
+            min_readable_sequence,
+        }))
     }
 }
+
+/// Returns persisted range tombstones that only overlap SSTs to compact or expire, or SSTs
+/// that don't contain rows they delete. They are no longer needed after compaction as the
+/// outputs don't contain rows they delete either.
+///
+/// A tombstone without time range overlaps all SSTs, so it is removed once all SSTs written
+/// before it have been rewritten by compactions.
+///
+/// Unflushed tombstones are kept as their WAL entries would be replayed, and rows flushed
+/// later are always newer than these tombstones.
+fn applied_tombstones(
+    version: &Version,
+    outputs: &[CompactionOutput],
+    expired_ssts: &[FileHandle],
+) -> Vec<RangeTombstone> {
+    let inputs: HashSet<_> = outputs
+        .iter()
+        .flat_map(|output| &output.inputs)
+        .chain(expired_ssts)
+        .map(FileHandle::file_id)
+        .collect();
+    let remaining: Vec<_> = version
+        .ssts()
+        .levels()
+        .iter()
+        .flat_map(|level| level.files())
+        .filter(|file| !inputs.contains(&file.file_id()))
+        .collect();
+
+    version
+        .range_tombstones()
+        .iter()
+        .filter(|tombstone| {
+            tombstone.sequence <= version.flushed_sequence()
+                && remaining.iter().all(|file| {
+                    !tombstone.may_overlap(file.time_range())
+                        || tombstone.sequence <= file.tombstone_sequence().unwrap_or_default()
+                })
+        })
+        .cloned()
+        .collect()
+}
@@ -18,7 +18,7 @@ use std::time::Duration;
 use common_base::readable_size::ReadableSize;
 use common_telemetry::{debug, error, info};
 use store_api::logstore::LogStore;
-use store_api::storage::RegionId;
+use store_api::storage::{CompactionStrategy, RegionId, SstCompression};
 use tokio::sync::oneshot::Sender;
 use tokio::sync::Notify;
 
@@ -60,6 +60,8 @@ pub struct CompactionRequestImpl<S: LogStore> {
     pub wal: Wal<S>,
     pub ttl: Option<Duration>,
     pub compaction_time_window: Option<i64>,
+    pub compaction_strategy: CompactionStrategy,
+    pub sst_compression: SstCompression,
     /// Compaction result sender.
     pub sender: Option<Sender<Result<()>>>,
 
@@ -67,6 +67,80 @@ impl Strategy for SimpleTimeWindowStrategy {
     }
 }
 
+/// TwcsStrategy (time-window compaction strategy) compacts level 0 like
+/// [SimpleTimeWindowStrategy], and also compacts files in level 1 within the same time window
+/// into one file once there are more than `max_files_in_window` files in that window.
+///
+/// Files in level 1 that span multiple time windows are left untouched since a compaction
+/// only reads rows in the window of the output file while removing all the input files.
+pub struct TwcsStrategy {
+    max_files_in_window: usize,
+}
+
+impl TwcsStrategy {
+    pub fn new(max_files_in_window: usize) -> Self {
+        Self {
+            max_files_in_window,
+        }
+    }
+
+    fn pick_windows(
+        &self,
+        ctx: &PickerContext,
+        level: &LevelMeta,
+    ) -> (Option<i64>, Vec<CompactionOutput>) {
+        let files = find_compactable_files(level);
+        if files.len() <= self.max_files_in_window {
+            return (None, vec![]);
+        }
+        // Inferring the window from the span of the whole level would merge all files in a
+        // long-retention region into a few huge files, so the window is inferred from the span
+        // of each file, which is produced by a level 0 compaction with that window.
+        let time_bucket = ctx
+            .compaction_time_window()
+            .unwrap_or_else(|| infer_file_time_bucket(&files));
+
+        let mut windows: HashMap<i64, Vec<FileHandle>> = HashMap::new();
+        for file in files {
+            let Some((start, end)) = file.time_range() else { continue };
+            let bounds = file_time_bucket_span(
+                start.convert_to(TimeUnit::Second).unwrap().value(),
+                end.convert_to(TimeUnit::Second).unwrap().value(),
+                time_bucket,
+            );
+            if let [bound] = bounds.as_slice() {
+                windows.entry(*bound).or_default().push(file);
+            }
+        }
+
+        let outputs = windows
+            .into_iter()
+            .filter(|(_, files)| files.len() > self.max_files_in_window)
+            .map(|(bound, files)| CompactionOutput {
+                output_level: 1,
+                bucket_bound: bound,
+                bucket: time_bucket,
+                inputs: files,
+            })
+            .collect::<Vec<_>>();
+        debug!(
+            "Level 1 file bucket: {}, windows to compact: {:?}",
+            time_bucket, outputs
+        );
+        (Some(time_bucket), outputs)
+    }
+}
+
+impl Strategy for TwcsStrategy {
+    fn pick(&self, ctx: &PickerContext, level: &LevelMeta) -> (Option<i64>, Vec<CompactionOutput>) {
+        match level.level() {
+            0 => SimpleTimeWindowStrategy {}.pick(ctx, level),
+            1 => self.pick_windows(ctx, level),
+            _ => (None, vec![]),
+        }
+    }
+}
+
 /// Finds files that can be compacted in given level.
 /// Currently they're files that is not currently under compaction.
 #[inline]
@@ -146,6 +220,25 @@ fn infer_time_bucket(files: &[FileHandle]) -> i64 {
         .unwrap_or_else(|| *TIME_BUCKETS.last().unwrap()) // safety: TIME_BUCKETS cannot be empty.
 }
 
+/// Infers the time bucket of files that are already partitioned by a time bucket, by finding
+/// the minimum bucket that covers the time span of every file.
+fn infer_file_time_bucket(files: &[FileHandle]) -> i64 {
+    files
+        .iter()
+        .filter_map(|f| f.time_range().as_ref())
+        .map(|(start, end)| {
+            // safety: Convert whatever timestamp into seconds will not cause overflow.
+            let start_sec = start.convert_to(TimeUnit::Second).unwrap().value();
+            let end_sec = end.convert_to(TimeUnit::Second).unwrap().value();
+            end_sec
+                .checked_sub(start_sec)
+                .map(fit_time_bucket)
+                .unwrap_or_else(|| *TIME_BUCKETS.last().unwrap())
+        })
+        .max()
+        .unwrap_or(TIME_BUCKETS[0])
+}
+
 /// A set of predefined time buckets.
 const TIME_BUCKETS: [i64; 7] = [
     60 * 60,                 // one hour
@@ -175,7 +268,7 @@ mod tests {
 
     use super::*;
     use crate::file_purger::noop::new_noop_file_purger;
-    use crate::sst::{FileId, FileMeta};
+    use crate::sst::{FileId, FileMeta, LevelMetas};
 
     #[test]
     fn test_time_bucket_span() {
@@ -246,6 +339,9 @@ mod tests {
                 )),
                 level: 0,
                 file_size: 0,
+                column_stats: vec![],
+                tombstone_sequence: None,
+                bloom_filters: vec![],
             },
             layer,
             file_purger,
@@ -319,4 +415,101 @@ mod tests {
             &expected,
         );
     }
+
+    fn new_level1_metas(input: &[(FileId, i64, i64)]) -> LevelMetas {
+        let layer = Arc::new(crate::test_util::access_layer_util::MockAccessLayer {});
+        let files = input.iter().map(|(file_id, start, end)| FileMeta {
+            region_id: 0,
+            file_id: *file_id,
+            time_range: Some((
+                Timestamp::new_millisecond(*start),
+                Timestamp::new_millisecond(*end),
+            )),
+            level: 1,
+            file_size: 0,
+            column_stats: vec![],
+            tombstone_sequence: None,
+            bloom_filters: vec![],
+        });
+        LevelMetas::new(layer, new_noop_file_purger()).merge(files, std::iter::empty())
+    }
+
+    fn output_file_ids(output: &CompactionOutput) -> HashSet<FileId> {
+        output.inputs.iter().map(|f| f.file_id()).collect()
+    }
+
+    #[test]
+    fn test_twcs_pick_level1_windows() {
+        let file_ids = (0..6).map(|_| FileId::random()).collect::<Vec<_>>();
+        let hour_millis = TIME_BUCKETS[0] * 1000;
+        let metas = new_level1_metas(&[
+            // three files in window [0, 1h)
+            (file_ids[0], 0, 1000),
+            (file_ids[1], 1000, 2000),
+            (file_ids[2], 2000, hour_millis - 1),
+            // two files in window [1h, 2h)
+            (file_ids[3], hour_millis, hour_millis + 1000),
+            (file_ids[4], hour_millis + 1000, hour_millis + 2000),
+            // a file across windows is never compacted
+            (file_ids[5], 1000, hour_millis + 1000),
+        ]);
+
+        let strategy = TwcsStrategy::new(2);
+        let ctx = PickerContext::with(Some(TIME_BUCKETS[0]));
+        let (time_window, outputs) = strategy.pick(&ctx, metas.level(1));
+        assert_eq!(Some(TIME_BUCKETS[0]), time_window);
+        assert_eq!(1, outputs.len());
+        assert_eq!(1, outputs[0].output_level);
+        assert_eq!(0, outputs[0].bucket_bound);
+        assert_eq!(TIME_BUCKETS[0], outputs[0].bucket);
+        assert_eq!(
+            file_ids[..3].iter().copied().collect::<HashSet<_>>(),
+            output_file_ids(&outputs[0])
+        );
+
+        // Files under compaction are skipped.
+        metas
+            .level(1)
+            .files()
+            .filter(|f| f.file_id() == file_ids[0])
+            .for_each(|f| f.mark_compacting(true));
+        let (_, outputs) = strategy.pick(&ctx, metas.level(1));
+        assert!(outputs.is_empty());
+
+        // Simple strategy never compacts level 1.
+        let (_, outputs) = SimpleTimeWindowStrategy {}.pick(&ctx, metas.level(1));
+        assert!(outputs.is_empty());
+    }
+
+    #[test]
+    fn test_twcs_infer_level1_window() {
+        let file_ids = (0..4).map(|_| FileId::random()).collect::<Vec<_>>();
+        let day_millis = TIME_BUCKETS[3] * 1000;
+        let metas = new_level1_metas(&[
+            (file_ids[0], 0, 1000),
+            (file_ids[1], 1000, day_millis - 1),
+            (file_ids[2], day_millis, day_millis + 1000),
+            (
+                file_ids[3],
+                TIME_BUCKETS[5] * 1000,
+                TIME_BUCKETS[5] * 1000 + 1000,
+            ),
+        ]);
+
+        // The window is inferred from the span of each file rather than the whole level.
+        let strategy = TwcsStrategy::new(1);
+        let (time_window, outputs) = strategy.pick(&PickerContext::with(None), metas.level(1));
+        assert_eq!(Some(TIME_BUCKETS[3]), time_window);
+        assert_eq!(1, outputs.len());
+        assert_eq!(0, outputs[0].bucket_bound);
+        assert_eq!(
+            file_ids[..2].iter().copied().collect::<HashSet<_>>(),
+            output_file_ids(&outputs[0])
+        );
+
+        // No need to compact if the level has only a few files.
+        let strategy = TwcsStrategy::new(4);
+        let (_, outputs) = strategy.pick(&PickerContext::with(None), metas.level(1));
+        assert!(outputs.is_empty());
+    }
 }
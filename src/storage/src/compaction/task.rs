@@ -18,10 +18,10 @@ use std::fmt::{Debug, Formatter};
 use common_base::readable_size::ReadableSize;
 use common_telemetry::{debug, error};
 use store_api::logstore::LogStore;
-use store_api::storage::RegionId;
+use store_api::storage::{RegionId, SequenceNumber, SstCompression};
 
 use crate::compaction::writer::build_sst_reader;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::manifest::action::RegionEdit;
 use crate::manifest::region::RegionManifest;
 use crate::region::{RegionWriterRef, SharedDataRef};
@@ -29,6 +29,7 @@ use crate::schema::RegionSchemaRef;
 use crate::sst::{
     AccessLayerRef, FileHandle, FileId, FileMeta, Level, Source, SstInfo, WriteOptions,
 };
+use crate::tombstone::{RangeTombstone, RangeTombstonesRef};
 use crate::wal::Wal;
 
 const MAX_PARALLEL_COMPACTION: usize = 8;
@@ -48,7 +49,15 @@ pub struct CompactionTaskImpl<S: LogStore> {
     pub manifest: RegionManifest,
     pub expired_ssts: Vec<FileHandle>,
     pub sst_write_buffer_size: ReadableSize,
+    pub sst_compression: SstCompression,
     pub compaction_time_window: Option<i64>,
+    /// Range tombstones to apply to the outputs.
+    pub range_tombstones: RangeTombstonesRef,
+    /// Range tombstones to remove from the region after compaction.
+    pub tombstones_to_remove: Vec<RangeTombstone>,
+    /// Min sequence that reads could still use after compaction, if compaction removes
+    /// rows visible to older sequences.
+    pub min_readable_sequence: Option<SequenceNumber>,
 }
 
 impl<S: LogStore> Debug for CompactionTaskImpl<S> {
@@ -75,12 +84,24 @@ impl<S: LogStore> CompactionTaskImpl<S> {
             let schema = self.schema.clone();
             let sst_layer = self.sst_layer.clone();
             let sst_write_buffer_size = self.sst_write_buffer_size;
+            let sst_compression = self.sst_compression;
+            let range_tombstones = self.range_tombstones.clone();
+            // Rows in append mode are kept as is, but still sorted so the output is ordered.
+            let dedup = !self.shared_data.append_mode();
             compacted_inputs.extend(output.inputs.iter().map(FileHandle::meta));
 
             // TODO(hl): Maybe spawn to runtime to exploit in-job parallelism.
             futs.push(async move {
                 output
-                    .build(region_id, schema, sst_layer, sst_write_buffer_size)
+                    .build(
+                        region_id,
+                        schema,
+                        sst_layer,
+                        sst_write_buffer_size,
+                        sst_compression,
+                        dedup,
+                        range_tombstones,
+                    )
                     .await
             });
         }
@@ -116,6 +137,9 @@ impl<S: LogStore> CompactionTaskImpl<S> {
             flushed_sequence: None,
             files_to_add: Vec::from_iter(output.into_iter()),
             files_to_remove: Vec::from_iter(input.into_iter()),
+            tombstones_to_add: vec![],
+            tombstones_to_remove: self.tombstones_to_remove.clone(),
+            min_readable_sequence: self.min_readable_sequence,
         };
         debug!(
             "Compacted region: {}, region edit: {:?}",
@@ -147,12 +171,20 @@ impl<S: LogStore> CompactionTask for CompactionTaskImpl<S> {
             e
         })?;
         compacted.extend(self.expired_ssts.iter().map(FileHandle::meta));
-        self.write_manifest_and_apply(output, compacted)
-            .await
-            .map_err(|e| {
-                error!(e; "Failed to update region manifest: {}", self.shared_data.name());
-                e
-            })
+        let output_ids = output.iter().map(|file| file.file_id).collect::<Vec<_>>();
+        let result = self.write_manifest_and_apply(output, compacted).await;
+        if let Err(Error::StaleRegionEdit { .. }) = &result {
+            // The region is truncated during compaction, so the outputs are useless.
+            for file_id in output_ids {
+                if let Err(e) = self.sst_layer.delete_sst(file_id).await {
+                    error!(e; "Failed to delete compaction output {}", file_id);
+                }
+            }
+        }
+        result.map_err(|e| {
+            error!(e; "Failed to update region manifest: {}", self.shared_data.name());
+            e
+        })
     }
 }
 
@@ -177,19 +209,40 @@ impl CompactionOutput {
         schema: RegionSchemaRef,
         sst_layer: AccessLayerRef,
         sst_write_buffer_size: ReadableSize,
+        sst_compression: SstCompression,
+        dedup: bool,
+        range_tombstones: RangeTombstonesRef,
     ) -> Result<Option<FileMeta>> {
+        // All tombstones of the region are applied to the output, tombstones older than
+        // rows of all inputs don't delete rows in the output either.
+        let tombstone_sequence = self
+            .inputs
+            .iter()
+            .map(|file| file.tombstone_sequence().unwrap_or_default())
+            .min()
+            .unwrap_or_default()
+            .max(
+                range_tombstones
+                    .iter()
+                    .map(|tombstone| tombstone.sequence)
+                    .max()
+                    .unwrap_or_default(),
+            );
         let reader = build_sst_reader(
             schema,
             sst_layer.clone(),
             &self.inputs,
             self.bucket_bound,
             self.bucket_bound + self.bucket,
+            dedup,
+            range_tombstones,
         )
         .await?;
 
         let output_file_id = FileId::random();
         let opts = WriteOptions {
             sst_write_buffer_size,
+            compression: sst_compression,
         };
 
         Ok(sst_layer
@@ -199,6 +252,8 @@ impl CompactionOutput {
                 |SstInfo {
                      time_range,
                      file_size,
+                     column_stats,
+                     bloom_filters,
                      ..
                  }| FileMeta {
                     region_id,
@@ -206,6 +261,9 @@ impl CompactionOutput {
                     time_range,
                     level: self.output_level,
                     file_size,
+                    column_stats,
+                    tombstone_sequence: Some(tombstone_sequence),
+                    bloom_filters,
                 },
             ))
     }
@@ -20,14 +20,18 @@ use crate::chunk::{ChunkReaderBuilder, ChunkReaderImpl};
 use crate::error;
 use crate::schema::RegionSchemaRef;
 use crate::sst::{AccessLayerRef, FileHandle};
+use crate::tombstone::RangeTombstonesRef;
 
-/// Builds an SST reader that only reads rows within given time range.
+/// Builds an SST reader that only reads rows within given time range. Rows with duplicate
+/// keys are kept unless `dedup` is true, and rows deleted by `range_tombstones` are removed.
 pub(crate) async fn build_sst_reader(
     schema: RegionSchemaRef,
     sst_layer: AccessLayerRef,
     files: &[FileHandle],
     lower_sec_inclusive: i64,
     upper_sec_exclusive: i64,
+    dedup: bool,
+    range_tombstones: RangeTombstonesRef,
 ) -> error::Result<ChunkReaderImpl> {
     // TODO(hl): Schemas in different SSTs may differ, thus we should infer
     // timestamp column name from Parquet metadata.
@@ -45,6 +49,8 @@ pub(crate) async fn build_sst_reader(
             upper_sec_exclusive,
             &ts_col_name,
         )])
+        .dedup(dedup)
+        .range_tombstones(range_tombstones)
         .build()
         .await
 }
@@ -238,6 +244,9 @@ mod tests {
                 time_range,
                 level: 0,
                 file_size,
+                column_stats: vec![],
+                tombstone_sequence: None,
+                bloom_filters: vec![],
             },
             Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
             new_noop_file_purger(),
@@ -260,6 +269,8 @@ mod tests {
             files,
             lower_sec_inclusive,
             upper_sec_exclusive,
+            true,
+            Default::default(),
         )
         .await
         .unwrap();
@@ -339,9 +350,17 @@ mod tests {
         sst_layer: AccessLayerRef,
     ) -> Vec<i64> {
         let mut timestamps = vec![];
-        let mut reader = build_sst_reader(schema, sst_layer, files, i64::MIN, i64::MAX)
-            .await
-            .unwrap();
+        let mut reader = build_sst_reader(
+            schema,
+            sst_layer,
+            files,
+            i64::MIN,
+            i64::MAX,
+            true,
+            Default::default(),
+        )
+        .await
+        .unwrap();
         while let Some(chunk) = reader.next_chunk().await.unwrap() {
             let ts = chunk.columns[0]
                 .as_any()
@@ -403,18 +422,43 @@ mod tests {
         let sst_layer = Arc::new(FsAccessLayer::new("./", object_store.clone()));
         let input_files = vec![file2, file1];
 
-        let reader1 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 0, 3)
-            .await
-            .unwrap();
-        let reader2 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 3, 6)
-            .await
-            .unwrap();
-        let reader3 = build_sst_reader(schema.clone(), sst_layer.clone(), &input_files, 6, 10)
-            .await
-            .unwrap();
+        let reader1 = build_sst_reader(
+            schema.clone(),
+            sst_layer.clone(),
+            &input_files,
+            0,
+            3,
+            true,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        let reader2 = build_sst_reader(
+            schema.clone(),
+            sst_layer.clone(),
+            &input_files,
+            3,
+            6,
+            true,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        let reader3 = build_sst_reader(
+            schema.clone(),
+            sst_layer.clone(),
+            &input_files,
+            6,
+            10,
+            true,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         let opts = WriteOptions {
             sst_write_buffer_size: ReadableSize::mb(8),
+            ..Default::default()
         };
         let s1 = ParquetWriter::new(
             &output_file_ids[0].as_parquet(),
@@ -478,6 +522,9 @@ mod tests {
                         level: 1,
                         time_range: None,
                         file_size: 0,
+                        column_stats: vec![],
+                        tombstone_sequence: None,
+                        bloom_filters: vec![],
                     },
                     Arc::new(crate::test_util::access_layer_util::MockAccessLayer {}),
                     new_noop_file_purger(),
@@ -17,6 +17,19 @@
 use std::time::Duration;
 
 use common_base::readable_size::ReadableSize;
+use serde::{Deserialize, Serialize};
+
+/// Type of memtables of regions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemtableType {
+    /// Stores rows in a btree ordered by row keys.
+    #[default]
+    #[serde(rename = "btree")]
+    BTree,
+    /// Groups rows by series and stores rows of each series in columnar buffers.
+    #[serde(rename = "time_series")]
+    TimeSeries,
+}
 
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
@@ -26,6 +39,14 @@ pub struct EngineConfig {
     pub max_files_in_l0: usize,
     pub max_purge_tasks: usize,
     pub sst_write_buffer_size: ReadableSize,
+    /// Max memory of memtables in all regions, `None` for unlimited. Largest regions are
+    /// flushed once memtables use 7/8 of it, and writes stall once it's used up.
+    pub global_write_buffer_size: Option<ReadableSize>,
+    /// Max time a write stalls for the global write buffer, the write is rejected
+    /// after that. Writes are rejected without stalling if it's zero.
+    pub write_stall_timeout: Duration,
+    /// Type of memtables of regions.
+    pub memtable_type: MemtableType,
 }
 
 impl Default for EngineConfig {
@@ -37,6 +58,9 @@ impl Default for EngineConfig {
             max_files_in_l0: 8,
             max_purge_tasks: 32,
             sst_write_buffer_size: ReadableSize::mb(8),
+            global_write_buffer_size: Some(ReadableSize::gb(1)),
+            write_stall_timeout: Duration::from_secs(10),
+            memtable_type: MemtableType::default(),
         }
     }
 }
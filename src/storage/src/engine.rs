@@ -23,7 +23,8 @@ use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::manifest::Manifest;
 use store_api::storage::{
-    CreateOptions, EngineContext, OpenOptions, Region, RegionDescriptor, StorageEngine,
+    CompactionStrategy, CreateOptions, EngineContext, OpenOptions, Region, RegionDescriptor,
+    SstCompression, StorageEngine,
 };
 
 use crate::background::JobPoolImpl;
@@ -33,11 +34,12 @@ use crate::error::{self, Error, Result};
 use crate::file_purger::{FilePurgeHandler, FilePurgerRef};
 use crate::flush::{FlushSchedulerImpl, FlushSchedulerRef, FlushStrategyRef, SizeBasedStrategy};
 use crate::manifest::region::RegionManifest;
-use crate::memtable::{DefaultMemtableBuilder, MemtableBuilderRef};
+use crate::memtable::{self, MemtableBuilderRef};
 use crate::metadata::RegionMetadata;
 use crate::region::{RegionImpl, StoreConfig};
 use crate::scheduler::{LocalScheduler, SchedulerConfig};
 use crate::sst::FsAccessLayer;
+use crate::write_buffer::{WriteBufferManager, WriteBufferManagerRef};
 
 /// [StorageEngine] implementation.
 pub struct EngineImpl<S: LogStore> {
@@ -227,6 +229,7 @@ struct EngineInner<S: LogStore> {
     flush_strategy: FlushStrategyRef,
     compaction_scheduler: CompactionSchedulerRef<S>,
     file_purger: FilePurgerRef,
+    write_buffer_manager: WriteBufferManagerRef,
     config: Arc<EngineConfig>,
 }
 
@@ -246,15 +249,20 @@ impl<S: LogStore> EngineInner<S> {
             },
             FilePurgeHandler,
         ));
+        let write_buffer_manager = Arc::new(WriteBufferManager::new(
+            config.global_write_buffer_size.map(|size| size.0 as usize),
+            config.write_stall_timeout,
+        ));
         Self {
             object_store,
             log_store,
             regions: RwLock::new(Default::default()),
-            memtable_builder: Arc::new(DefaultMemtableBuilder::default()),
+            memtable_builder: memtable::new_memtable_builder(config.memtable_type),
             flush_scheduler,
             flush_strategy: Arc::new(SizeBasedStrategy::default()),
             compaction_scheduler,
             file_purger,
+            write_buffer_manager,
             config: Arc::new(config),
         }
     }
@@ -283,6 +291,10 @@ impl<S: LogStore> EngineInner<S> {
     }
 
     async fn open_region(&self, name: &str, opts: &OpenOptions) -> Result<Option<RegionImpl<S>>> {
+        if opts.snapshot.is_some() {
+            return self.open_region_at_snapshot(name, opts).await;
+        }
+
         // We can wait until the state of the slot has been changed to ready, but this will
         // make the code more complicate, so we just return the error here.
         if let Some(slot) = self.get_or_occupy_slot(name, RegionSlot::Opening) {
@@ -299,8 +311,12 @@ impl<S: LogStore> EngineInner<S> {
                 &self.config,
                 opts.ttl,
                 opts.compaction_time_window,
+                opts.compaction_strategy,
+                opts.sst_compression,
+                opts.append_mode,
             )
             .await?;
+        store_config.manifest.start().await?;
 
         let region = match RegionImpl::open(name.to_string(), store_config, opts).await? {
             None => return Ok(None),
@@ -315,6 +331,38 @@ impl<S: LogStore> EngineInner<S> {
         Ok(Some(region))
     }
 
+    /// Opens a read-only region at the snapshot in `opts`.
+    ///
+    /// The region isn't put into the region map as it has the same name as the region it's
+    /// opened from.
+    async fn open_region_at_snapshot(
+        &self,
+        name: &str,
+        opts: &OpenOptions,
+    ) -> Result<Option<RegionImpl<S>>> {
+        // The manifest isn't started so the read-only region never does checkpoints.
+        let store_config = self
+            .region_store_config(
+                &opts.parent_dir,
+                opts.write_buffer_size,
+                name,
+                &self.config,
+                opts.ttl,
+                opts.compaction_time_window,
+                opts.compaction_strategy,
+                opts.sst_compression,
+                opts.append_mode,
+            )
+            .await?;
+
+        let region = RegionImpl::open(name.to_string(), store_config, opts).await?;
+        debug!(
+            "Storage engine open region {} at snapshot {:?}",
+            name, opts.snapshot
+        );
+        Ok(region)
+    }
+
     async fn create_region(
         &self,
         descriptor: RegionDescriptor,
@@ -342,8 +390,12 @@ impl<S: LogStore> EngineInner<S> {
                 &self.config,
                 opts.ttl,
                 opts.compaction_time_window,
+                opts.compaction_strategy,
+                opts.sst_compression,
+                opts.append_mode,
             )
             .await?;
+        store_config.manifest.start().await?;
 
         let region = RegionImpl::create(metadata, store_config).await?;
 
@@ -363,6 +415,7 @@ impl<S: LogStore> EngineInner<S> {
         slot.get_ready_region()
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn region_store_config(
         &self,
         parent_dir: &str,
@@ -371,6 +424,9 @@ impl<S: LogStore> EngineInner<S> {
         config: &EngineConfig,
         ttl: Option<Duration>,
         compaction_time_window: Option<i64>,
+        compaction_strategy: CompactionStrategy,
+        sst_compression: SstCompression,
+        append_mode: bool,
     ) -> Result<StoreConfig<S>> {
         let parent_dir = util::normalize_dir(parent_dir);
 
@@ -383,7 +439,6 @@ impl<S: LogStore> EngineInner<S> {
             config.manifest_checkpoint_margin,
             config.manifest_gc_duration,
         );
-        manifest.start().await?;
 
         let flush_strategy = write_buffer_size
             .map(|size| Arc::new(SizeBasedStrategy::new(size)) as Arc<_>)
@@ -401,6 +456,10 @@ impl<S: LogStore> EngineInner<S> {
             file_purger: self.file_purger.clone(),
             ttl,
             compaction_time_window,
+            compaction_strategy,
+            sst_compression,
+            append_mode,
+            write_buffer_manager: self.write_buffer_manager.clone(),
         })
     }
 }
@@ -18,6 +18,7 @@ use std::str::Utf8Error;
 
 use common_error::prelude::*;
 use common_runtime::error::Error as RuntimeError;
+use common_time::Timestamp;
 use datatypes::arrow::error::ArrowError;
 use datatypes::prelude::ConcreteDataType;
 use serde_json::error::Error as JsonError;
@@ -252,6 +253,12 @@ pub enum Error {
         source: datatypes::error::Error,
     },
 
+    #[snafu(display("Failed to build vectors of memtable, source: {}", source))]
+    BuildMemtableVector {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display("Failed to build batch, {}", msg))]
     BuildBatch { msg: String, location: Location },
 
@@ -321,6 +328,21 @@ pub enum Error {
     #[snafu(display("Failed to read column {}, no proper default value for it", column))]
     NoDefaultToRead { column: String, location: Location },
 
+    #[snafu(display(
+        "Failed to cast column {} from {:?} to {:?}, source: {}",
+        column,
+        from,
+        to,
+        source
+    ))]
+    CastColumn {
+        column: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display(
         "Failed to convert arrow chunk to batch, name: {}, source: {}",
         name,
@@ -401,6 +423,78 @@ pub enum Error {
     #[snafu(display("More columns than expected in the request"))]
     MoreColumnThanExpected { location: Location },
 
+    #[snafu(display("Cannot delete rows from region {} in append mode", region))]
+    DeleteInAppendMode { region: String, location: Location },
+
+    #[snafu(display("Invalid range to delete, {}", msg))]
+    InvalidDeleteRange { msg: String, location: Location },
+
+    #[snafu(display(
+        "Files to remove from region {} are already removed by a truncation",
+        region
+    ))]
+    StaleRegionEdit { region: String, location: Location },
+
+    #[snafu(display(
+        "Invalid snapshot name {}, only alphanumeric characters, '_' and '-' are allowed",
+        name
+    ))]
+    InvalidSnapshotName { name: String, location: Location },
+
+    #[snafu(display("Snapshot {} of region {} already exists", name, region))]
+    SnapshotExists {
+        region: String,
+        name: String,
+        location: Location,
+    },
+
+    #[snafu(display("Snapshot {} of region {} not found", name, region))]
+    SnapshotNotFound {
+        region: String,
+        name: String,
+        location: Location,
+    },
+
+    #[snafu(display("Region {} is opened at a snapshot and is read-only", region))]
+    ReadOnlyRegion { region: String, location: Location },
+
+    #[snafu(display(
+        "Unknown sequence committed at {}, commit times of data before restarting are not kept",
+        time.to_iso8601_string()
+    ))]
+    CommitTimeUnavailable { time: Timestamp, location: Location },
+
+    #[snafu(display(
+        "Sequence {} is not readable, compaction has removed rows visible to sequences before {}",
+        sequence,
+        min_readable_sequence
+    ))]
+    SequenceNotReadable {
+        sequence: SequenceNumber,
+        min_readable_sequence: SequenceNumber,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Write buffer is full, usage: {} bytes, limit: {} bytes, region: {}",
+        usage,
+        limit,
+        region
+    ))]
+    WriteBufferFull {
+        usage: usize,
+        limit: usize,
+        region: String,
+        location: Location,
+    },
+
+    #[snafu(display("Invalid row key to get, expect {} values, given: {}", expect, given))]
+    InvalidRowKey {
+        expect: usize,
+        given: usize,
+        location: Location,
+    },
+
     #[snafu(display("Failed to decode parquet file time range, msg: {}", msg))]
     DecodeParquetTimeRange { msg: String, location: Location },
 
@@ -468,9 +562,21 @@ impl ErrorExt for Error {
             | TypeMismatch { .. }
             | HasNull { .. }
             | UnequalLengths { .. }
-            | MoreColumnThanExpected { .. } => StatusCode::InvalidArguments,
+            | MoreColumnThanExpected { .. }
+            | InvalidDeleteRange { .. }
+            | InvalidRowKey { .. }
+            | InvalidSnapshotName { .. }
+            | SnapshotExists { .. }
+            | SnapshotNotFound { .. }
+            | CommitTimeUnavailable { .. }
+            | SequenceNotReadable { .. } => StatusCode::InvalidArguments,
+
+            DeleteInAppendMode { .. } | ReadOnlyRegion { .. } => StatusCode::Unsupported,
+
+            WriteBufferFull { .. } => StatusCode::StorageBusy,
 
             Utf8 { .. }
+            | StaleRegionEdit { .. }
             | EncodeJson { .. }
             | DecodeJson { .. }
             | JoinTask { .. }
@@ -488,6 +594,7 @@ impl ErrorExt for Error {
             | CompatRead { .. }
             | CreateDefaultToRead { .. }
             | NoDefaultToRead { .. }
+            | CastColumn { .. }
             | NewRecordBatch { .. }
             | BatchCorrupted { .. }
             | DecodeArrow { .. }
@@ -514,7 +621,7 @@ impl ErrorExt for Error {
             InvalidAlterRequest { source, .. } | InvalidRegionDesc { source, .. } => {
                 source.status_code()
             }
-            PushBatch { source, .. } => source.status_code(),
+            PushBatch { source, .. } | BuildMemtableVector { source, .. } => source.status_code(),
             CreateDefault { source, .. } => source.status_code(),
             ConvertChunk { source, .. } => source.status_code(),
             MarkWalObsolete { source, .. } => source.status_code(),
@@ -160,6 +160,9 @@ mod tests {
                     time_range: None,
                     level: 0,
                     file_size: sst_info.file_size,
+                    column_stats: vec![],
+                    tombstone_sequence: None,
+                    bloom_filters: vec![],
                 },
                 layer.clone(),
                 file_purger,
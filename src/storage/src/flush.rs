@@ -20,7 +20,7 @@ use async_trait::async_trait;
 use common_telemetry::logging;
 use store_api::logstore::LogStore;
 use store_api::storage::consts::WRITE_ROW_GROUP_SIZE;
-use store_api::storage::SequenceNumber;
+use store_api::storage::{SequenceNumber, SstCompression};
 
 use crate::background::{Context, Job, JobHandle, JobPoolRef};
 use crate::config::EngineConfig;
@@ -177,6 +177,8 @@ pub struct FlushJob<S: LogStore> {
     pub on_success: Option<FlushCallback>,
     /// Storage engine config
     pub engine_config: Arc<EngineConfig>,
+    /// Compression codec of SST files to write.
+    pub sst_compression: SstCompression,
 }
 
 impl<S: LogStore> FlushJob<S> {
@@ -186,6 +188,9 @@ impl<S: LogStore> FlushJob<S> {
         }
 
         let region_id = self.shared.id();
+        // Flushes are serialized, so rows in the memtables are newer than the flushed
+        // sequence and not deleted by tombstones up to the next sequence.
+        let tombstone_sequence = self.shared.version_control.current().flushed_sequence() + 1;
         let mut futures = Vec::with_capacity(self.memtables.len());
         let iter_ctx = IterContext {
             for_flush: true,
@@ -206,6 +211,7 @@ impl<S: LogStore> FlushJob<S> {
             let sst_layer = self.sst_layer.clone();
             let write_options = WriteOptions {
                 sst_write_buffer_size: self.engine_config.sst_write_buffer_size,
+                compression: self.sst_compression,
             };
             futures.push(async move {
                 Ok(sst_layer
@@ -215,6 +221,8 @@ impl<S: LogStore> FlushJob<S> {
                         |SstInfo {
                              time_range,
                              file_size,
+                             column_stats,
+                             bloom_filters,
                              ..
                          }| FileMeta {
                             region_id,
@@ -222,6 +230,9 @@ impl<S: LogStore> FlushJob<S> {
                             time_range,
                             level: 0,
                             file_size,
+                            column_stats,
+                            tombstone_sequence: Some(tombstone_sequence),
+                            bloom_filters,
                         },
                     ))
             });
@@ -238,11 +249,27 @@ impl<S: LogStore> FlushJob<S> {
     }
 
     async fn write_manifest_and_apply(&mut self, file_metas: &[FileMeta]) -> Result<()> {
+        // Range tombstones written after last flush are persisted to the manifest, as the
+        // WAL that contains them would be obsoleted.
+        let version = self.shared.version_control.current();
+        let prev_flushed_sequence = version.flushed_sequence();
+        let tombstones_to_add = version
+            .range_tombstones()
+            .iter()
+            .filter(|tombstone| {
+                tombstone.sequence > prev_flushed_sequence
+                    && tombstone.sequence <= self.flush_sequence
+            })
+            .cloned()
+            .collect();
         let edit = RegionEdit {
-            region_version: self.shared.version_control.metadata().version(),
+            region_version: version.metadata().version(),
             flushed_sequence: Some(self.flush_sequence),
             files_to_add: file_metas.to_vec(),
             files_to_remove: Vec::default(),
+            tombstones_to_add,
+            tombstones_to_remove: Vec::default(),
+            min_readable_sequence: None,
         };
 
         self.writer
@@ -25,6 +25,7 @@ mod flush;
 pub mod manifest;
 pub mod memtable;
 pub mod metadata;
+mod metrics;
 pub mod proto;
 pub mod read;
 pub mod region;
@@ -35,9 +36,12 @@ pub mod sst;
 mod sync;
 #[cfg(test)]
 mod test_util;
+mod timeline;
+pub mod tombstone;
 mod version;
 mod wal;
 pub mod write_batch;
+pub mod write_buffer;
 
 pub use engine::EngineImpl;
 mod file_purger;
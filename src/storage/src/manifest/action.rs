@@ -29,6 +29,7 @@ use crate::error::{
 use crate::manifest::helper;
 use crate::metadata::{ColumnFamilyMetadata, ColumnMetadata, VersionNumber};
 use crate::sst::{FileId, FileMeta};
+use crate::tombstone::RangeTombstone;
 
 /// Minimal data that could be used to persist and recover [RegionMetadata](crate::metadata::RegionMetadata).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -79,6 +80,15 @@ pub struct RegionEdit {
     pub flushed_sequence: Option<SequenceNumber>,
     pub files_to_add: Vec<FileMeta>,
     pub files_to_remove: Vec<FileMeta>,
+    /// Range tombstones persisted by flush.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tombstones_to_add: Vec<RangeTombstone>,
+    /// Range tombstones that compaction has applied to all SSTs they overlap.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tombstones_to_remove: Vec<RangeTombstone>,
+    /// Min sequence that reads could still use after compaction removed old versions of rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_readable_sequence: Option<SequenceNumber>,
 }
 
 /// The region version checkpoint
@@ -87,6 +97,10 @@ pub struct RegionVersion {
     pub manifest_version: ManifestVersion,
     pub flushed_sequence: Option<SequenceNumber>,
     pub files: HashMap<FileId, FileMeta>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub range_tombstones: Vec<RangeTombstone>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_readable_sequence: Option<SequenceNumber>,
 }
 
 /// The region manifest data checkpoint
@@ -132,6 +146,13 @@ impl RegionManifestDataBuilder {
             for file in edit.files_to_remove {
                 version.files.remove(&file.file_id);
             }
+            version.range_tombstones.extend(edit.tombstones_to_add);
+            version
+                .range_tombstones
+                .retain(|tombstone| !edit.tombstones_to_remove.contains(tombstone));
+            version.min_readable_sequence = version
+                .min_readable_sequence
+                .max(edit.min_readable_sequence);
         } else {
             self.version = Some(RegionVersion {
                 manifest_version,
@@ -141,6 +162,8 @@ impl RegionManifestDataBuilder {
                     .into_iter()
                     .map(|f| (f.file_id, f))
                     .collect(),
+                range_tombstones: edit.tombstones_to_add,
+                min_readable_sequence: edit.min_readable_sequence,
             });
         }
     }
@@ -186,6 +209,32 @@ impl Checkpoint for RegionCheckpoint {
     }
 }
 
+/// A named snapshot of the region, which keeps SSTs in its version from being purged.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RegionSnapshot {
+    pub name: String,
+    /// Metadata and version of the region when the snapshot is taken.
+    pub data: RegionManifestData,
+}
+
+impl RegionSnapshot {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        json::to_vec(self).context(error::EncodeJsonSnafu)
+    }
+
+    pub fn decode(bs: &[u8]) -> Result<Self> {
+        json::from_slice(bs).context(DecodeJsonSnafu)
+    }
+
+    /// Returns ids of SSTs in the snapshot.
+    pub fn file_ids(&self) -> impl Iterator<Item = FileId> + '_ {
+        self.data
+            .version
+            .iter()
+            .flat_map(|version| version.files.keys().copied())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum RegionMetaAction {
     Protocol(ProtocolAction),
@@ -289,7 +338,10 @@ impl MetaAction for RegionMetaActionList {
 #[cfg(test)]
 mod tests {
     use common_telemetry::logging;
+    use common_time::Timestamp;
     use datatypes::type_id::LogicalTypeId;
+    use datatypes::value::Value;
+    use store_api::storage::DeleteRange;
 
     use super::*;
     use crate::manifest::test_utils;
@@ -359,9 +411,23 @@ mod tests {
             time_range: None,
             level: 0,
             file_size: 1024,
+            column_stats: vec![],
+            tombstone_sequence: None,
+            bloom_filters: vec![],
         }
     }
 
+    fn mock_tombstone(sequence: SequenceNumber) -> RangeTombstone {
+        RangeTombstone::new(
+            sequence,
+            DeleteRange {
+                keys: vec![("k1".to_string(), Value::from("a"))],
+                start: Some(Timestamp::new_millisecond(1000)),
+                end: None,
+            },
+        )
+    }
+
     #[test]
     fn test_region_manifest_builder() {
         let desc = RegionDescBuilder::new("test_region_manifest_builder")
@@ -377,6 +443,7 @@ mod tests {
             metadata: RawRegionMetadata::from(&region_metadata),
         });
         let files = vec![mock_file_meta(), mock_file_meta()];
+        let tombstones = vec![mock_tombstone(98), mock_tombstone(99)];
         builder.apply_edit(
             84,
             RegionEdit {
@@ -384,6 +451,9 @@ mod tests {
                 flushed_sequence: Some(99),
                 files_to_add: files.clone(),
                 files_to_remove: vec![],
+                tombstones_to_add: tombstones.clone(),
+                tombstones_to_remove: vec![],
+                min_readable_sequence: None,
             },
         );
         builder.apply_edit(
@@ -393,6 +463,9 @@ mod tests {
                 flushed_sequence: Some(100),
                 files_to_add: vec![],
                 files_to_remove: vec![files[0].clone()],
+                tombstones_to_add: vec![],
+                tombstones_to_remove: vec![tombstones[0].clone()],
+                min_readable_sequence: Some(99),
             },
         );
 
@@ -405,6 +478,8 @@ mod tests {
                 manifest_version: 85,
                 flushed_sequence: Some(100),
                 files: files[1..].iter().map(|f| (f.file_id, f.clone())).collect(),
+                range_tombstones: tombstones[1..].to_vec(),
+                min_readable_sequence: Some(99),
             })
         );
     }
@@ -425,6 +500,8 @@ mod tests {
                         .into_iter()
                         .map(|f| (f.file_id, f))
                         .collect(),
+                    range_tombstones: vec![mock_tombstone(99)],
+                    min_readable_sequence: Some(99),
                 }),
             }),
         };
@@ -130,6 +130,25 @@ impl<S: 'static + Checkpoint<Error = Error>, M: 'static + MetaAction<Error = Err
     pub(crate) fn manifest_store(&self) -> &Arc<ManifestObjectStore> {
         self.inner.manifest_store()
     }
+
+    /// Saves a named snapshot beside the manifest. Snapshots aren't removed by checkpoints.
+    pub async fn save_snapshot(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        self.manifest_store().save_snapshot(name, bytes).await
+    }
+
+    /// Loads the named snapshot, returns `None` if it doesn't exist.
+    pub async fn load_snapshot(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        self.manifest_store().load_snapshot(name).await
+    }
+
+    pub async fn delete_snapshot(&self, name: &str) -> Result<()> {
+        self.manifest_store().delete_snapshot(name).await
+    }
+
+    /// Returns names of all snapshots in ascending order.
+    pub async fn list_snapshots(&self) -> Result<Vec<String>> {
+        self.manifest_store().list_snapshots().await
+    }
 }
 
 #[async_trait]
@@ -14,6 +14,7 @@
 
 //! Region manifest impl
 use std::any::Any;
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -30,6 +31,7 @@ use crate::error::{ManifestCheckpointSnafu, Result};
 use crate::manifest::action::*;
 use crate::manifest::checkpoint::Checkpointer;
 use crate::manifest::ManifestImpl;
+use crate::sst::FileId;
 
 pub type RegionManifest = ManifestImpl<RegionCheckpoint, RegionMetaActionList>;
 
@@ -167,6 +169,26 @@ impl RegionManifest {
             }
         }
     }
+
+    /// Loads all named snapshots of the region.
+    pub(crate) async fn load_snapshots(&self) -> Result<Vec<RegionSnapshot>> {
+        let mut snapshots = Vec::new();
+        for name in self.list_snapshots().await? {
+            if let Some(bytes) = self.load_snapshot(&name).await? {
+                snapshots.push(RegionSnapshot::decode(&bytes)?);
+            }
+        }
+        Ok(snapshots)
+    }
+
+    /// Returns ids of SSTs in all snapshots of the region.
+    pub(crate) async fn snapshot_file_ids(&self) -> Result<HashSet<FileId>> {
+        let snapshots = self.load_snapshots().await?;
+        Ok(snapshots
+            .iter()
+            .flat_map(|snapshot| snapshot.file_ids())
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -359,6 +381,7 @@ mod tests {
                 manifest_version: 1,
                 flushed_sequence: Some(3),
                 files,
+                ..
             }),
         }) if files.len() == 2 &&
                          files.contains_key(&file_ids[0]) &&
@@ -413,6 +436,7 @@ mod tests {
                 manifest_version: 1,
                 flushed_sequence: Some(3),
                 files,
+                ..
             }),
         }) if files.len() == 2 &&
                          files.contains_key(&file_ids[0]) &&
@@ -435,6 +459,7 @@ mod tests {
                 manifest_version: 4,
                 flushed_sequence: Some(201),
                 files,
+                ..
             }),
         }) if files.len() == 1 &&
                          files.contains_key(&new_file) &&
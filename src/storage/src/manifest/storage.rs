@@ -36,6 +36,8 @@ lazy_static! {
 }
 
 const LAST_CHECKPOINT_FILE: &str = "_last_checkpoint";
+const SNAPSHOT_DIR: &str = "snapshots/";
+const SNAPSHOT_FILE_SUFFIX: &str = ".json";
 
 #[inline]
 pub fn delta_file(version: ManifestVersion) -> String {
@@ -123,6 +125,66 @@ impl ManifestObjectStore {
     pub(crate) fn path(&self) -> &str {
         &self.path
     }
+
+    #[inline]
+    fn snapshot_dir(&self) -> String {
+        format!("{}{}", self.path, SNAPSHOT_DIR)
+    }
+
+    #[inline]
+    fn snapshot_file_path(&self, name: &str) -> String {
+        format!("{}{}{}", self.snapshot_dir(), name, SNAPSHOT_FILE_SUFFIX)
+    }
+
+    /// Saves the named snapshot, overwriting the existing one with the same name.
+    pub async fn save_snapshot(&self, name: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.snapshot_file_path(name);
+        self.object_store
+            .write(&path, bytes.to_vec())
+            .await
+            .context(WriteObjectSnafu { path })
+    }
+
+    /// Loads the named snapshot, returns `None` if it doesn't exist.
+    pub async fn load_snapshot(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.snapshot_file_path(name);
+        match self.object_store.read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context(ReadObjectSnafu { path }),
+        }
+    }
+
+    pub async fn delete_snapshot(&self, name: &str) -> Result<()> {
+        let path = self.snapshot_file_path(name);
+        self.object_store
+            .delete(&path)
+            .await
+            .context(DeleteObjectSnafu { path })
+    }
+
+    /// Returns names of all snapshots.
+    pub async fn list_snapshots(&self) -> Result<Vec<String>> {
+        let dir = self.snapshot_dir();
+        let streamer = match self.object_store.list(&dir).await {
+            Ok(streamer) => streamer,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context(ListObjectsSnafu { path: dir }),
+        };
+
+        let mut names: Vec<_> = streamer
+            .try_filter_map(|e| async move {
+                Ok(e.name()
+                    .strip_suffix(SNAPSHOT_FILE_SUFFIX)
+                    .map(|name| name.to_string()))
+            })
+            .try_collect()
+            .await
+            .context(ListObjectsSnafu { path: &dir })?;
+        names.sort_unstable();
+
+        Ok(names)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -422,4 +484,43 @@ mod tests {
         let mut it = log_store.scan(0, 11).await.unwrap();
         assert!(it.next_log().await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_manifest_snapshots() {
+        let tmp_dir = create_temp_dir("test_manifest_snapshots");
+        let mut builder = Fs::default();
+        builder.root(&tmp_dir.path().to_string_lossy());
+        let object_store = ObjectStore::new(builder).unwrap().finish();
+
+        let log_store = ManifestObjectStore::new("/", object_store);
+        assert!(log_store.list_snapshots().await.unwrap().is_empty());
+        assert!(log_store.load_snapshot("s1").await.unwrap().is_none());
+
+        log_store.save(0, "hello, 0".as_bytes()).await.unwrap();
+        log_store
+            .save_snapshot("s2", "snapshot 2".as_bytes())
+            .await
+            .unwrap();
+        log_store
+            .save_snapshot("s1", "snapshot 1".as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(
+            vec!["s1".to_string(), "s2".to_string()],
+            log_store.list_snapshots().await.unwrap()
+        );
+        assert_eq!(
+            "snapshot 1".as_bytes(),
+            log_store.load_snapshot("s1").await.unwrap().unwrap()
+        );
+
+        // Snapshots are kept after deleting logs.
+        log_store.delete_until(11).await.unwrap();
+        log_store.delete_snapshot("s1").await.unwrap();
+        assert_eq!(
+            vec!["s2".to_string()],
+            log_store.list_snapshots().await.unwrap()
+        );
+        assert!(log_store.load_snapshot("s1").await.unwrap().is_none());
+    }
 }
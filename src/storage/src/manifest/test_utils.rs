@@ -59,6 +59,9 @@ pub fn build_region_edit(
                 time_range: None,
                 level: 0,
                 file_size: DEFAULT_TEST_FILE_SIZE,
+                column_stats: vec![],
+                tombstone_sequence: None,
+                bloom_filters: vec![],
             })
             .collect(),
         files_to_remove: files_to_remove
@@ -69,7 +72,13 @@ pub fn build_region_edit(
                 time_range: None,
                 level: 0,
                 file_size: DEFAULT_TEST_FILE_SIZE,
+                column_stats: vec![],
+                tombstone_sequence: None,
+                bloom_filters: vec![],
             })
             .collect(),
+        tombstones_to_add: vec![],
+        tombstones_to_remove: vec![],
+        min_readable_sequence: None,
     }
 }
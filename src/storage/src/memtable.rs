@@ -16,17 +16,21 @@ mod btree;
 mod inserter;
 #[cfg(test)]
 pub mod tests;
+mod time_series;
 mod version;
 
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
+use datatypes::value::Value;
 use datatypes::vectors::VectorRef;
 use store_api::storage::{consts, OpType, SequenceNumber};
 
+use crate::config::MemtableType;
 use crate::error::Result;
 use crate::memtable::btree::BTreeMemtable;
 pub use crate::memtable::inserter::Inserter;
+use crate::memtable::time_series::TimeSeriesMemtable;
 pub use crate::memtable::version::MemtableVersion;
 use crate::read::Batch;
 use crate::schema::{ProjectedSchemaRef, RegionSchemaRef};
@@ -51,6 +55,13 @@ pub trait Memtable: Send + Sync + std::fmt::Debug {
     /// Iterates the memtable.
     fn iter(&self, ctx: &IterContext) -> Result<BoxedBatchIterator>;
 
+    /// Gets the latest visible row whose row key equals to `row_key`, the row may be
+    /// a deletion.
+    ///
+    /// `row_key` should follow the row key of the memtable's schema. Returns a [Batch]
+    /// with only one row, ignores `ctx.batch_size` and `ctx.for_flush`.
+    fn get(&self, ctx: &IterContext, row_key: &[Value]) -> Result<Option<Batch>>;
+
     /// Returns the estimated bytes allocated by this memtable from heap. Result
     /// of this method may be larger than the estimated based on [`num_rows`] because
     /// of the implementor's pre-alloc behavior.
@@ -77,6 +88,10 @@ pub struct IterContext {
     /// Returns all rows, ignores sequence visibility and key duplication.
     pub for_flush: bool,
 
+    /// Only returns the latest visible row of each key. Otherwise returns all visible
+    /// rows, which is used by regions in append mode.
+    pub dedup: bool,
+
     /// Schema the reader expect to read.
     ///
     /// Set to `None` to read all columns.
@@ -90,6 +105,7 @@ impl Default for IterContext {
             // All data in memory is visible by default.
             visible_sequence: SequenceNumber::MAX,
             for_flush: false,
+            dedup: true,
             projected_schema: None,
         }
     }
@@ -171,3 +187,24 @@ impl MemtableBuilder for DefaultMemtableBuilder {
         Arc::new(BTreeMemtable::new(id, schema))
     }
 }
+
+/// Builds memtables that group rows by series and store them in columnar buffers.
+#[derive(Debug, Default)]
+pub struct TimeSeriesMemtableBuilder {
+    memtable_id: AtomicU32,
+}
+
+impl MemtableBuilder for TimeSeriesMemtableBuilder {
+    fn build(&self, schema: RegionSchemaRef) -> MemtableRef {
+        let id = self.memtable_id.fetch_add(1, Ordering::Relaxed);
+        Arc::new(TimeSeriesMemtable::new(id, schema))
+    }
+}
+
+/// Returns the builder of memtables of the `memtable_type`.
+pub fn new_memtable_builder(memtable_type: MemtableType) -> MemtableBuilderRef {
+    match memtable_type {
+        MemtableType::BTree => Arc::new(DefaultMemtableBuilder::default()),
+        MemtableType::TimeSeries => Arc::new(TimeSeriesMemtableBuilder::default()),
+    }
+}
@@ -100,6 +100,35 @@ impl Memtable for BTreeMemtable {
         Ok(Box::new(iter))
     }
 
+    fn get(&self, ctx: &IterContext, row_key: &[Value]) -> Result<Option<Batch>> {
+        let projected_schema = ctx
+            .projected_schema
+            .clone()
+            .unwrap_or_else(|| Arc::new(ProjectedSchema::no_projection(self.schema.clone())));
+        let adapter = ReadAdapter::new(self.schema.store_schema().clone(), projected_schema)?;
+
+        let map = self.map.read().unwrap();
+        // Sequence, index_in_batch and op_type are ordered in desc order, so the first
+        // entry after the seek key is the latest visible entry of the row key.
+        let seek_key = InnerKey {
+            row_key: row_key.to_vec(),
+            sequence: ctx.visible_sequence,
+            index_in_batch: usize::MAX,
+            op_type: OpType::Put,
+        };
+        let Some((inner_key, row_value)) = map
+            .range((Bound::Included(&seek_key), Bound::Unbounded))
+            .next()
+            .filter(|(inner_key, _)| inner_key.is_row_key_equal(&seek_key))
+            else { return Ok(None) };
+
+        let (keys, sequences, op_types, values) =
+            collect_iter(std::iter::once((inner_key, row_value)), 1);
+        let batch = build_batch(&self.schema, &adapter, keys, sequences, op_types, values)?;
+
+        Ok(Some(batch))
+    }
+
     fn bytes_allocated(&self) -> usize {
         self.estimated_bytes.load(AtomicOrdering::Relaxed)
     }
@@ -170,6 +199,10 @@ impl BTreeIterator {
 
         let (keys, sequences, op_types, values) = if self.ctx.for_flush {
             collect_iter(iter, self.ctx.batch_size)
+        } else if !self.ctx.dedup {
+            let visible_sequence = self.ctx.visible_sequence;
+            let iter = iter.filter(|(k, _)| k.is_visible(visible_sequence));
+            collect_iter(iter, self.ctx.batch_size)
         } else {
             let iter = MapIterWrapper::new(iter, self.ctx.visible_sequence);
             collect_iter(iter, self.ctx.batch_size)
@@ -178,43 +211,60 @@ impl BTreeIterator {
         if keys.is_empty() {
             return Ok(None);
         }
+        // Other rows with the same row key are skipped only if we dedup rows.
+        let skip_same_key = self.ctx.dedup && !self.ctx.for_flush;
         self.last_key = keys.last().map(|k| {
             let mut last_key = (*k).clone();
-            last_key.reset_for_seek();
+            if skip_same_key {
+                last_key.reset_for_seek();
+            }
             last_key
         });
 
-        let key_data_types = self
-            .schema
-            .row_key_columns()
-            .map(|column_meta| column_meta.desc.data_type.clone());
-        let value_data_types = self
-            .schema
-            .field_columns()
-            .map(|column_meta| column_meta.desc.data_type.clone());
-
-        let key_columns = rows_to_vectors(
-            key_data_types,
-            self.adapter.source_key_needed(),
-            keys.as_slice(),
-        );
-        let field_columns = rows_to_vectors(
-            value_data_types,
-            self.adapter.source_value_needed(),
-            values.as_slice(),
-        );
-
-        let batch = self.adapter.batch_from_parts(
-            key_columns,
-            field_columns,
-            Arc::new(sequences),
-            Arc::new(op_types),
+        let batch = build_batch(
+            &self.schema,
+            &self.adapter,
+            keys,
+            sequences,
+            op_types,
+            values,
         )?;
 
         Ok(Some(batch))
     }
 }
 
+/// Builds a [Batch] in the schema of the `adapter` from rows collected by [collect_iter].
+fn build_batch(
+    schema: &RegionSchemaRef,
+    adapter: &ReadAdapter,
+    keys: Vec<&InnerKey>,
+    sequences: UInt64Vector,
+    op_types: UInt8Vector,
+    values: Vec<&RowValue>,
+) -> Result<Batch> {
+    let key_data_types = schema
+        .row_key_columns()
+        .map(|column_meta| column_meta.desc.data_type.clone());
+    let value_data_types = schema
+        .field_columns()
+        .map(|column_meta| column_meta.desc.data_type.clone());
+
+    let key_columns = rows_to_vectors(key_data_types, adapter.source_key_needed(), keys.as_slice());
+    let field_columns = rows_to_vectors(
+        value_data_types,
+        adapter.source_value_needed(),
+        values.as_slice(),
+    );
+
+    adapter.batch_from_parts(
+        key_columns,
+        field_columns,
+        Arc::new(sequences),
+        Arc::new(op_types),
+    )
+}
+
 fn collect_iter<'a, I: Iterator<Item = (&'a InnerKey, &'a RowValue)>>(
     iter: I,
     batch_size: usize,
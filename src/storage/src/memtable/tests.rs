@@ -171,7 +171,10 @@ impl Default for MemtableTester {
 impl MemtableTester {
     fn new() -> MemtableTester {
         let schema = schema_for_test();
-        let builders = vec![Arc::new(DefaultMemtableBuilder::default()) as _];
+        let builders = vec![
+            Arc::new(DefaultMemtableBuilder::default()) as _,
+            Arc::new(TimeSeriesMemtableBuilder::default()) as _,
+        ];
 
         MemtableTester { schema, builders }
     }
@@ -409,6 +412,53 @@ fn test_duplicate_key_across_batch() {
     });
 }
 
+#[test]
+fn test_iter_without_dedup() {
+    let tester = MemtableTester::default();
+    tester.run_testcase(|ctx| {
+        write_kvs(
+            &*ctx.memtable,
+            10, // sequence
+            OpType::Put,
+            &[(1000, 1), (2000, 1)],             // keys
+            &[(Some(1), None), (Some(2), None)], // values
+        );
+        write_kvs(
+            &*ctx.memtable,
+            11, // sequence
+            OpType::Put,
+            &[(1000, 1)],        // keys
+            &[(Some(11), None)], // values
+        );
+        write_kvs(
+            &*ctx.memtable,
+            12, // sequence
+            OpType::Put,
+            &[(2000, 1)],        // keys
+            &[(Some(12), None)], // values
+        );
+
+        let batch_sizes = [1, 2, 3, 4];
+        for batch_size in batch_sizes {
+            let iter_ctx = IterContext {
+                batch_size,
+                visible_sequence: 11,
+                dedup: false,
+                ..Default::default()
+            };
+
+            let mut iter = ctx.memtable.iter(&iter_ctx).unwrap();
+            check_iter_content(
+                &mut *iter,
+                &[(1000, 1), (1000, 1), (2000, 1)],       // keys
+                &[11, 10, 10],                            // sequences
+                &[OpType::Put, OpType::Put, OpType::Put], // op_types
+                &[(Some(11), None), (Some(1), None), (Some(2), None)], // values
+            );
+        }
+    });
+}
+
 #[test]
 fn test_duplicate_key_in_batch() {
     let tester = MemtableTester::default();
@@ -473,6 +523,7 @@ fn test_sequence_visibility() {
                 batch_size: 1,
                 visible_sequence: 9,
                 for_flush: false,
+                dedup: true,
                 projected_schema: None,
             };
 
@@ -491,6 +542,7 @@ fn test_sequence_visibility() {
                 batch_size: 1,
                 visible_sequence: 10,
                 for_flush: false,
+                dedup: true,
                 projected_schema: None,
             };
 
@@ -509,6 +561,7 @@ fn test_sequence_visibility() {
                 batch_size: 1,
                 visible_sequence: 11,
                 for_flush: false,
+                dedup: true,
                 projected_schema: None,
             };
 
@@ -592,3 +645,64 @@ fn test_memtable_projection() {
         assert_eq!(op_types, *batch.column(4));
     });
 }
+
+#[test]
+fn test_memtable_get() {
+    let tester = MemtableTester::default();
+    tester.run_testcase(|ctx| {
+        write_kvs(
+            &*ctx.memtable,
+            10, // sequence
+            OpType::Put,
+            &[(1000, 1), (1001, 2)],             // keys
+            &[(Some(1), None), (Some(2), None)], // values
+        );
+        write_kvs(
+            &*ctx.memtable,
+            11, // sequence
+            OpType::Put,
+            &[(1000, 1)],        // keys
+            &[(Some(11), None)], // values
+        );
+        write_kvs(
+            &*ctx.memtable,
+            12, // sequence
+            OpType::Delete,
+            &[(1001, 2)],    // keys
+            &[(None, None)], // values
+        );
+
+        let row_key = |ts: i64, version: u64| {
+            vec![
+                Value::from(TimestampMillisecond::from(ts)),
+                Value::from(version),
+            ]
+        };
+        let get = |visible_sequence, row_key: &[Value]| {
+            let iter_ctx = IterContext {
+                visible_sequence,
+                ..Default::default()
+            };
+            ctx.memtable.get(&iter_ctx, row_key).unwrap()
+        };
+
+        let batch = get(SequenceNumber::MAX, &row_key(1000, 1)).unwrap();
+        check_batch_valid(&batch);
+        assert_eq!(1, batch.num_rows());
+        assert_eq!(Value::from(11u64), batch.column(2).get(0));
+        assert_eq!(Value::from(11u64), batch.column(4).get(0));
+
+        let batch = get(10, &row_key(1000, 1)).unwrap();
+        assert_eq!(Value::from(1u64), batch.column(2).get(0));
+        assert!(get(9, &row_key(1000, 1)).is_none());
+
+        // The latest entry of the key is a deletion.
+        let batch = get(SequenceNumber::MAX, &row_key(1001, 2)).unwrap();
+        assert_eq!(Value::from(OpType::Delete.as_u8()), batch.column(5).get(0));
+        let batch = get(11, &row_key(1001, 2)).unwrap();
+        assert_eq!(Value::from(OpType::Put.as_u8()), batch.column(5).get(0));
+
+        assert!(get(SequenceNumber::MAX, &row_key(1000, 2)).is_none());
+        assert!(get(SequenceNumber::MAX, &row_key(1002, 1)).is_none());
+    });
+}
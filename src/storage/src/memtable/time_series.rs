@@ -0,0 +1,862 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A memtable that groups rows by series and stores rows of each series in columnar
+//! buffers.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Bound, Range};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use datatypes::data_type::DataType;
+use datatypes::prelude::*;
+use datatypes::vectors::{UInt32Vector, UInt64Vector, UInt8Vector};
+use snafu::ResultExt;
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::{self, Result};
+use crate::memtable::{
+    BatchIterator, BoxedBatchIterator, IterContext, KeyValues, Memtable, MemtableId, RowOrdering,
+};
+use crate::read::Batch;
+use crate::schema::compat::ReadAdapter;
+use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchema, RegionSchemaRef};
+
+/// Values of the row key columns before the timestamp, which identify a series.
+type SeriesKey = Vec<Value>;
+type SeriesRef = Arc<Mutex<Series>>;
+type RwLockSeriesMap = RwLock<BTreeMap<SeriesKey, SeriesRef>>;
+
+/// A memtable that groups rows by series.
+///
+/// Rows of a series are appended to columnar buffers in write order, and are sorted
+/// lazily when the series is read. Only rows written since the last read are sorted, and
+/// then merged with rows sorted before.
+pub struct TimeSeriesMemtable {
+    id: MemtableId,
+    schema: RegionSchemaRef,
+    series: Arc<RwLockSeriesMap>,
+    num_rows: AtomicUsize,
+    estimated_bytes: AtomicUsize,
+}
+
+impl TimeSeriesMemtable {
+    pub fn new(id: MemtableId, schema: RegionSchemaRef) -> TimeSeriesMemtable {
+        TimeSeriesMemtable {
+            id,
+            schema,
+            series: Arc::new(RwLock::new(BTreeMap::new())),
+            num_rows: AtomicUsize::new(0),
+            estimated_bytes: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl fmt::Debug for TimeSeriesMemtable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let num_series = self.series.read().unwrap().len();
+
+        f.debug_struct("TimeSeriesMemtable")
+            .field("id", &self.id)
+            .field("schema", &self.schema)
+            .field("series", &num_series)
+            .field("rows", &self.num_rows)
+            .field("estimated_bytes", &self.estimated_bytes)
+            .finish()
+    }
+}
+
+impl Memtable for TimeSeriesMemtable {
+    fn id(&self) -> MemtableId {
+        self.id
+    }
+
+    fn schema(&self) -> RegionSchemaRef {
+        self.schema.clone()
+    }
+
+    fn write(&self, kvs: &KeyValues) -> Result<()> {
+        self.estimated_bytes
+            .fetch_add(kvs.estimated_memory_size(), AtomicOrdering::Relaxed);
+
+        let series_key_len = self.schema.timestamp_key_index();
+        let mut map = self.series.write().unwrap();
+        let mut start = 0;
+        // Rows of the same series are usually adjacent, so we push them together.
+        while start < kvs.len() {
+            let key: SeriesKey = kvs.keys[..series_key_len]
+                .iter()
+                .map(|vector| vector.get(start))
+                .collect();
+            let end = (start + 1..kvs.len())
+                .find(|row| !is_row_of_series(&key, kvs, *row))
+                .unwrap_or(kvs.len());
+
+            let series = map.entry(key).or_default();
+            series
+                .lock()
+                .unwrap()
+                .push_rows(&self.schema, kvs, start..end)?;
+            self.num_rows
+                .fetch_add(end - start, AtomicOrdering::Relaxed);
+            start = end;
+        }
+
+        Ok(())
+    }
+
+    fn iter(&self, ctx: &IterContext) -> Result<BoxedBatchIterator> {
+        assert!(ctx.batch_size > 0);
+
+        let iter = TimeSeriesIterator::new(ctx.clone(), self.schema.clone(), self.series.clone())?;
+
+        Ok(Box::new(iter))
+    }
+
+    fn get(&self, ctx: &IterContext, row_key: &[Value]) -> Result<Option<Batch>> {
+        let projected_schema = ctx
+            .projected_schema
+            .clone()
+            .unwrap_or_else(|| Arc::new(ProjectedSchema::no_projection(self.schema.clone())));
+        let adapter = ReadAdapter::new(self.schema.store_schema().clone(), projected_schema)?;
+
+        let series_key_len = self.schema.timestamp_key_index();
+        let (series_key, keys) = row_key.split_at(series_key_len);
+        let Some(series) = self.series.read().unwrap().get(series_key).cloned()
+            else { return Ok(None) };
+        let values = series.lock().unwrap().sorted_values()?;
+
+        // Rows of the same key are ordered by sequence in desc order, so the first visible
+        // row is the latest.
+        let Some(row) = (values.lower_bound(keys)..values.len())
+            .take_while(|row| values.compare_key(*row, keys) == Ordering::Equal)
+            .find(|row| values.sequences[*row] <= ctx.visible_sequence)
+            else { return Ok(None) };
+
+        let part = SeriesRows {
+            key: series_key.to_vec(),
+            values,
+            rows: vec![row as u32],
+        };
+        let batch = build_batch(&self.schema, &adapter, &[part])?;
+
+        Ok(Some(batch))
+    }
+
+    fn bytes_allocated(&self) -> usize {
+        self.estimated_bytes.load(AtomicOrdering::Relaxed)
+    }
+
+    fn num_rows(&self) -> usize {
+        self.num_rows.load(AtomicOrdering::Relaxed)
+    }
+}
+
+/// Returns true if the series key of the `row` in `kvs` equals to `key`.
+fn is_row_of_series(key: &[Value], kvs: &KeyValues, row: usize) -> bool {
+    key.iter()
+        .zip(&kvs.keys)
+        .all(|(value, vector)| value.as_value_ref() == vector.get_ref(row))
+}
+
+/// Rows of a series.
+#[derive(Default)]
+struct Series {
+    /// Rows written since the series was last read, in write order.
+    active: Option<ValuesBuilder>,
+    /// Rows sorted by [Values::compare_rows].
+    sorted: Option<Arc<Values>>,
+}
+
+impl Series {
+    fn push_rows(
+        &mut self,
+        schema: &RegionSchema,
+        kvs: &KeyValues,
+        rows: Range<usize>,
+    ) -> Result<()> {
+        self.active
+            .get_or_insert_with(|| ValuesBuilder::new(schema, rows.len()))
+            .push_rows(schema, kvs, rows)
+    }
+
+    /// Merges active rows into sorted rows, and returns all sorted rows of the series.
+    fn sorted_values(&mut self) -> Result<Arc<Values>> {
+        if let Some(mut active) = self.active.take() {
+            let active = active.finish().sort()?;
+            let values = match self.sorted.take() {
+                Some(sorted) => sorted.merge(&active)?,
+                None => active,
+            };
+            self.sorted = Some(Arc::new(values));
+        }
+
+        Ok(self.sorted.clone().unwrap_or_default())
+    }
+}
+
+/// Builders of rows in a series.
+struct ValuesBuilder {
+    /// Number of key columns in `columns`.
+    num_keys: usize,
+    /// Builders of the timestamp, version (if enabled) and field columns.
+    columns: Vec<Box<dyn MutableVector>>,
+    sequences: Vec<SequenceNumber>,
+    indices_in_batch: Vec<usize>,
+    op_types: Vec<OpType>,
+}
+
+impl ValuesBuilder {
+    fn new(schema: &RegionSchema, capacity: usize) -> ValuesBuilder {
+        let columns = schema
+            .row_key_columns()
+            .skip(schema.timestamp_key_index())
+            .chain(schema.field_columns())
+            .map(|column| column.desc.data_type.create_mutable_vector(capacity))
+            .collect();
+
+        ValuesBuilder {
+            num_keys: schema.num_row_key_columns() - schema.timestamp_key_index(),
+            columns,
+            sequences: Vec::with_capacity(capacity),
+            indices_in_batch: Vec::with_capacity(capacity),
+            op_types: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn push_rows(
+        &mut self,
+        schema: &RegionSchema,
+        kvs: &KeyValues,
+        rows: Range<usize>,
+    ) -> Result<()> {
+        let vectors = kvs.keys[schema.timestamp_key_index()..]
+            .iter()
+            .chain(&kvs.values);
+        for (builder, vector) in self.columns.iter_mut().zip(vectors) {
+            if vector.is_const() {
+                for row in rows.clone() {
+                    builder.push_value_ref(vector.get_ref(row));
+                }
+            } else {
+                builder
+                    .extend_slice_of(&**vector, rows.start, rows.len())
+                    .context(error::BuildMemtableVectorSnafu)?;
+            }
+        }
+        self.sequences
+            .extend(std::iter::repeat(kvs.sequence).take(rows.len()));
+        self.op_types
+            .extend(std::iter::repeat(kvs.op_type).take(rows.len()));
+        self.indices_in_batch
+            .extend(rows.map(|row| kvs.start_index_in_batch + row));
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Values {
+        Values {
+            num_keys: self.num_keys,
+            columns: self
+                .columns
+                .iter_mut()
+                .map(|builder| builder.to_vector())
+                .collect(),
+            sequences: std::mem::take(&mut self.sequences),
+            indices_in_batch: std::mem::take(&mut self.indices_in_batch),
+            op_types: std::mem::take(&mut self.op_types),
+        }
+    }
+}
+
+/// Rows of a series in columnar format.
+#[derive(Debug, Default)]
+struct Values {
+    /// Number of key columns in `columns`.
+    num_keys: usize,
+    /// The timestamp, version (if enabled) and field columns.
+    columns: Vec<VectorRef>,
+    sequences: Vec<SequenceNumber>,
+    indices_in_batch: Vec<usize>,
+    op_types: Vec<OpType>,
+}
+
+impl Values {
+    fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Returns rows of `self` followed by rows of `other`.
+    fn concat(&self, other: &Values) -> Result<Values> {
+        let columns = self
+            .columns
+            .iter()
+            .zip(&other.columns)
+            .map(|(left, right)| {
+                let mut builder = left
+                    .data_type()
+                    .create_mutable_vector(left.len() + right.len());
+                builder.extend_slice_of(&**left, 0, left.len())?;
+                builder.extend_slice_of(&**right, 0, right.len())?;
+                Ok(builder.to_vector())
+            })
+            .collect::<datatypes::error::Result<_>>()
+            .context(error::BuildMemtableVectorSnafu)?;
+
+        Ok(Values {
+            num_keys: self.num_keys,
+            columns,
+            sequences: [&self.sequences[..], &other.sequences].concat(),
+            indices_in_batch: [&self.indices_in_batch[..], &other.indices_in_batch].concat(),
+            op_types: [&self.op_types[..], &other.op_types].concat(),
+        })
+    }
+
+    /// Returns rows of sorted `self` and sorted `other` ordered by [Values::compare_rows],
+    /// without sorting all rows again.
+    fn merge(&self, other: &Values) -> Result<Values> {
+        let values = self.concat(other)?;
+        let (mut left, mut right) = (0, self.len());
+        let mut rows = Vec::with_capacity(values.len());
+        while left < self.len() && right < values.len() {
+            if values.compare_rows(left, right) == Ordering::Greater {
+                rows.push(right as u32);
+                right += 1;
+            } else {
+                rows.push(left as u32);
+                left += 1;
+            }
+        }
+        rows.extend(left as u32..self.len() as u32);
+        rows.extend(right as u32..values.len() as u32);
+
+        // New rows usually have larger timestamps, so they are often already in order.
+        if rows.iter().enumerate().all(|(i, row)| i as u32 == *row) {
+            return Ok(values);
+        }
+        values.take(&rows)
+    }
+
+    /// Returns rows ordered by (key asc, sequence desc, index_in_batch desc, op_type desc),
+    /// the same order as the [BTreeMemtable](crate::memtable::btree::BTreeMemtable).
+    fn sort(self) -> Result<Values> {
+        let is_sorted =
+            (1..self.len()).all(|row| self.compare_rows(row - 1, row) != Ordering::Greater);
+        if is_sorted {
+            return Ok(self);
+        }
+
+        let mut rows = (0..self.len() as u32).collect::<Vec<_>>();
+        rows.sort_unstable_by(|left, right| self.compare_rows(*left as usize, *right as usize));
+        self.take(&rows)
+    }
+
+    fn take(&self, rows: &[u32]) -> Result<Values> {
+        let indices = UInt32Vector::from_slice(rows);
+        let columns = self
+            .columns
+            .iter()
+            .map(|vector| vector.take(&indices))
+            .collect::<datatypes::error::Result<_>>()
+            .context(error::BuildMemtableVectorSnafu)?;
+
+        Ok(Values {
+            num_keys: self.num_keys,
+            columns,
+            sequences: rows
+                .iter()
+                .map(|row| self.sequences[*row as usize])
+                .collect(),
+            indices_in_batch: rows
+                .iter()
+                .map(|row| self.indices_in_batch[*row as usize])
+                .collect(),
+            op_types: rows
+                .iter()
+                .map(|row| self.op_types[*row as usize])
+                .collect(),
+        })
+    }
+
+    fn compare_rows(&self, left: usize, right: usize) -> Ordering {
+        self.columns[..self.num_keys]
+            .iter()
+            .map(|vector| vector.get_ref(left).cmp(&vector.get_ref(right)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.sequences[right].cmp(&self.sequences[left]))
+            .then_with(|| self.indices_in_batch[right].cmp(&self.indices_in_batch[left]))
+            .then_with(|| self.op_types[right].cmp(&self.op_types[left]))
+    }
+
+    /// Compares the key of the `row` with `keys`.
+    fn compare_key(&self, row: usize, keys: &[Value]) -> Ordering {
+        self.columns[..self.num_keys]
+            .iter()
+            .zip(keys)
+            .map(|(vector, key)| vector.get_ref(row).cmp(&key.as_value_ref()))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    fn is_key_equal(&self, left: usize, right: usize) -> bool {
+        self.columns[..self.num_keys]
+            .iter()
+            .all(|vector| vector.get_ref(left) == vector.get_ref(right))
+    }
+
+    /// Returns the first row whose key is not less than `keys`.
+    fn lower_bound(&self, keys: &[Value]) -> usize {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.compare_key(mid, keys) == Ordering::Less {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Returns rows to read under `ctx`. Unless reading for flush or without dedup, only the
+    /// latest visible row of each key is returned.
+    fn rows_to_read(&self, ctx: &IterContext) -> Vec<u32> {
+        if ctx.for_flush {
+            return (0..self.len() as u32).collect();
+        }
+
+        let mut rows = Vec::new();
+        let mut last_row = None;
+        for (row, sequence) in self.sequences.iter().enumerate() {
+            if *sequence > ctx.visible_sequence {
+                continue;
+            }
+            if ctx.dedup && last_row.map_or(false, |last_row| self.is_key_equal(last_row, row)) {
+                continue;
+            }
+            rows.push(row as u32);
+            last_row = Some(row);
+        }
+        rows
+    }
+}
+
+/// Rows to read from a series.
+struct SeriesRows {
+    key: SeriesKey,
+    values: Arc<Values>,
+    rows: Vec<u32>,
+}
+
+struct TimeSeriesIterator {
+    ctx: IterContext,
+    /// Schema of this memtable.
+    schema: RegionSchemaRef,
+    /// Projected schema that user expect to read.
+    projected_schema: ProjectedSchemaRef,
+    adapter: ReadAdapter,
+    series: Arc<RwLockSeriesMap>,
+    /// Key of the last series fetched from the map.
+    last_key: Option<SeriesKey>,
+    /// Rows of the current series and the number of rows already read.
+    current: Option<(SeriesRows, usize)>,
+}
+
+impl BatchIterator for TimeSeriesIterator {
+    fn schema(&self) -> ProjectedSchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn ordering(&self) -> RowOrdering {
+        RowOrdering::Key
+    }
+}
+
+impl Iterator for TimeSeriesIterator {
+    type Item = Result<Batch>;
+
+    fn next(&mut self) -> Option<Result<Batch>> {
+        self.next_batch().transpose()
+    }
+}
+
+impl TimeSeriesIterator {
+    fn new(
+        ctx: IterContext,
+        schema: RegionSchemaRef,
+        series: Arc<RwLockSeriesMap>,
+    ) -> Result<TimeSeriesIterator> {
+        let projected_schema = ctx
+            .projected_schema
+            .clone()
+            .unwrap_or_else(|| Arc::new(ProjectedSchema::no_projection(schema.clone())));
+        let adapter = ReadAdapter::new(schema.store_schema().clone(), projected_schema.clone())?;
+
+        Ok(TimeSeriesIterator {
+            ctx,
+            schema,
+            projected_schema,
+            adapter,
+            series,
+            last_key: None,
+            current: None,
+        })
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let mut parts = Vec::new();
+        let mut num_rows = 0;
+        while num_rows < self.ctx.batch_size {
+            if self
+                .current
+                .as_ref()
+                .map_or(true, |(series, offset)| *offset == series.rows.len())
+            {
+                self.current = self.next_series()?.map(|series| (series, 0));
+            }
+            let Some((series, offset)) = &mut self.current else { break };
+
+            let len = (self.ctx.batch_size - num_rows).min(series.rows.len() - *offset);
+            parts.push(SeriesRows {
+                key: series.key.clone(),
+                values: series.values.clone(),
+                rows: series.rows[*offset..*offset + len].to_vec(),
+            });
+            *offset += len;
+            num_rows += len;
+        }
+
+        if parts.is_empty() {
+            return Ok(None);
+        }
+
+        let batch = build_batch(&self.schema, &self.adapter, &parts)?;
+
+        Ok(Some(batch))
+    }
+
+    /// Fetches the next series that has rows to read.
+    fn next_series(&mut self) -> Result<Option<SeriesRows>> {
+        loop {
+            let (key, series) = {
+                let map = self.series.read().unwrap();
+                let mut iter = if let Some(last_key) = &self.last_key {
+                    map.range((Bound::Excluded(last_key), Bound::Unbounded))
+                } else {
+                    map.range(..)
+                };
+                let Some((key, series)) = iter.next() else { return Ok(None) };
+                (key.clone(), series.clone())
+            };
+            self.last_key = Some(key.clone());
+
+            let values = series.lock().unwrap().sorted_values()?;
+            let rows = values.rows_to_read(&self.ctx);
+            if !rows.is_empty() {
+                return Ok(Some(SeriesRows { key, values, rows }));
+            }
+        }
+    }
+}
+
+/// Builds a [Batch] in the schema of the `adapter` from rows of series.
+fn build_batch(
+    schema: &RegionSchema,
+    adapter: &ReadAdapter,
+    parts: &[SeriesRows],
+) -> Result<Batch> {
+    let num_rows = parts.iter().map(|part| part.rows.len()).sum();
+    let series_key_len = schema.timestamp_key_index();
+    let num_row_key_columns = schema.num_row_key_columns();
+    let needed = adapter
+        .source_key_needed()
+        .iter()
+        .chain(adapter.source_value_needed());
+
+    let mut key_columns = Vec::with_capacity(num_row_key_columns);
+    let mut field_columns = Vec::with_capacity(schema.num_field_columns());
+    let columns = schema.row_key_columns().chain(schema.field_columns());
+    for (idx, (column, needed)) in columns.zip(needed).enumerate() {
+        if !*needed {
+            continue;
+        }
+
+        let data_type = &column.desc.data_type;
+        let vector = if idx < series_key_len {
+            let mut builder = data_type.create_mutable_vector(num_rows);
+            for part in parts {
+                for _ in 0..part.rows.len() {
+                    builder.push_value_ref(part.key[idx].as_value_ref());
+                }
+            }
+            builder.to_vector()
+        } else {
+            take_column(parts, idx - series_key_len, data_type, num_rows)?
+        };
+
+        if idx < num_row_key_columns {
+            key_columns.push(vector);
+        } else {
+            field_columns.push(vector);
+        }
+    }
+
+    let sequences = UInt64Vector::from_values(parts.iter().flat_map(|part| {
+        part.rows
+            .iter()
+            .map(|row| part.values.sequences[*row as usize])
+    }));
+    let op_types = UInt8Vector::from_values(parts.iter().flat_map(|part| {
+        part.rows
+            .iter()
+            .map(|row| part.values.op_types[*row as usize].as_u8())
+    }));
+
+    adapter.batch_from_parts(
+        key_columns,
+        field_columns,
+        Arc::new(sequences),
+        Arc::new(op_types),
+    )
+}
+
+/// Takes rows of the `column` in [Values] from all `parts`.
+fn take_column(
+    parts: &[SeriesRows],
+    column: usize,
+    data_type: &ConcreteDataType,
+    num_rows: usize,
+) -> Result<VectorRef> {
+    let take = |part: &SeriesRows| {
+        part.values.columns[column]
+            .take(&UInt32Vector::from_slice(&part.rows))
+            .context(error::BuildMemtableVectorSnafu)
+    };
+    if let [part] = parts {
+        return take(part);
+    }
+
+    let mut builder = data_type.create_mutable_vector(num_rows);
+    for part in parts {
+        let vector = take(part)?;
+        builder
+            .extend_slice_of(&*vector, 0, vector.len())
+            .context(error::BuildMemtableVectorSnafu)?;
+    }
+    Ok(builder.to_vector())
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::vectors::{StringVector, TimestampMillisecondVector};
+
+    use super::*;
+    use crate::metadata::RegionMetadata;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
+
+    fn schema_with_tag() -> RegionSchemaRef {
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, false))
+            .push_field_column(("v0", LogicalTypeId::UInt64, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+
+        metadata.schema().clone()
+    }
+
+    fn write_rows(memtable: &dyn Memtable, sequence: SequenceNumber, rows: &[(&str, i64, u64)]) {
+        let kvs = KeyValues {
+            sequence,
+            op_type: OpType::Put,
+            start_index_in_batch: 0,
+            keys: vec![
+                Arc::new(StringVector::from(
+                    rows.iter().map(|row| row.0).collect::<Vec<_>>(),
+                )) as VectorRef,
+                Arc::new(TimestampMillisecondVector::from_vec(
+                    rows.iter().map(|row| row.1).collect(),
+                )),
+            ],
+            values: vec![Arc::new(UInt64Vector::from_vec(
+                rows.iter().map(|row| row.2).collect(),
+            )) as VectorRef],
+        };
+        memtable.write(&kvs).unwrap();
+    }
+
+    /// Reads (host, ts, v0, sequence) of all rows and the number of rows in each batch.
+    fn read_rows(
+        memtable: &dyn Memtable,
+        ctx: &IterContext,
+    ) -> (Vec<(String, i64, u64, u64)>, Vec<usize>) {
+        let mut rows = Vec::new();
+        let mut batch_sizes = Vec::new();
+        for batch in memtable.iter(ctx).unwrap() {
+            let batch = batch.unwrap();
+            batch_sizes.push(batch.num_rows());
+            for i in 0..batch.num_rows() {
+                let Value::String(host) = batch.column(0).get(i) else { unreachable!() };
+                let Value::Timestamp(ts) = batch.column(1).get(i) else { unreachable!() };
+                let Value::UInt64(v0) = batch.column(2).get(i) else { unreachable!() };
+                let Value::UInt64(sequence) = batch.column(3).get(i) else { unreachable!() };
+                rows.push((host.as_utf8().to_string(), ts.value(), v0, sequence));
+            }
+        }
+        (rows, batch_sizes)
+    }
+
+    #[test]
+    fn test_series_order() {
+        let memtable = TimeSeriesMemtable::new(0, schema_with_tag());
+        write_rows(
+            &memtable,
+            1,
+            &[("b", 2, 1), ("a", 1, 2), ("b", 1, 3), ("a", 1, 4)],
+        );
+        write_rows(&memtable, 2, &[("a", 2, 5), ("b", 1, 6)]);
+        assert_eq!(6, memtable.num_rows());
+
+        let ctx = IterContext {
+            batch_size: 3,
+            ..Default::default()
+        };
+        let (rows, batch_sizes) = read_rows(&memtable, &ctx);
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 4, 1),
+                ("a".to_string(), 2, 5, 2),
+                ("b".to_string(), 1, 6, 2),
+                ("b".to_string(), 2, 1, 1),
+            ],
+            rows
+        );
+        // Batches may contain rows from multiple series.
+        assert_eq!(vec![3, 1], batch_sizes);
+
+        let ctx = IterContext {
+            visible_sequence: 1,
+            ..Default::default()
+        };
+        let (rows, _) = read_rows(&memtable, &ctx);
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 4, 1),
+                ("b".to_string(), 1, 3, 1),
+                ("b".to_string(), 2, 1, 1),
+            ],
+            rows
+        );
+
+        // Flush reads all rows, including duplicate and invisible ones.
+        let ctx = IterContext {
+            visible_sequence: 1,
+            for_flush: true,
+            ..Default::default()
+        };
+        let (rows, _) = read_rows(&memtable, &ctx);
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 4, 1),
+                ("a".to_string(), 1, 2, 1),
+                ("a".to_string(), 2, 5, 2),
+                ("b".to_string(), 1, 6, 2),
+                ("b".to_string(), 1, 3, 1),
+                ("b".to_string(), 2, 1, 1),
+            ],
+            rows
+        );
+
+        // Reading without dedup returns all visible rows.
+        let ctx = IterContext {
+            visible_sequence: 1,
+            dedup: false,
+            ..Default::default()
+        };
+        let (rows, _) = read_rows(&memtable, &ctx);
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 4, 1),
+                ("a".to_string(), 1, 2, 1),
+                ("b".to_string(), 1, 3, 1),
+                ("b".to_string(), 2, 1, 1),
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn test_read_after_each_write() {
+        let memtable = TimeSeriesMemtable::new(0, schema_with_tag());
+        let ctx = IterContext {
+            for_flush: true,
+            ..Default::default()
+        };
+        write_rows(&memtable, 1, &[("a", 3, 1), ("a", 1, 2)]);
+        assert_eq!(2, read_rows(&memtable, &ctx).0.len());
+        // Rows are appended after the sorted rows.
+        write_rows(&memtable, 2, &[("a", 5, 3), ("a", 4, 4)]);
+        assert_eq!(4, read_rows(&memtable, &ctx).0.len());
+        // Rows are interleaved with the sorted rows.
+        write_rows(&memtable, 3, &[("a", 2, 5), ("a", 1, 6), ("a", 6, 7)]);
+
+        let (rows, _) = read_rows(&memtable, &ctx);
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1, 6, 3),
+                ("a".to_string(), 1, 2, 1),
+                ("a".to_string(), 2, 5, 3),
+                ("a".to_string(), 3, 1, 1),
+                ("a".to_string(), 4, 4, 2),
+                ("a".to_string(), 5, 3, 2),
+                ("a".to_string(), 6, 7, 3),
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn test_get_from_series() {
+        let memtable = TimeSeriesMemtable::new(0, schema_with_tag());
+        write_rows(&memtable, 1, &[("b", 2, 1), ("a", 1, 2), ("b", 1, 3)]);
+        // Reads the memtable so the rows above are sorted before the next write.
+        assert_eq!(3, read_rows(&memtable, &IterContext::default()).0.len());
+        write_rows(&memtable, 2, &[("b", 1, 6)]);
+
+        let get = |visible_sequence, host: &str, ts: i64| {
+            let ctx = IterContext {
+                visible_sequence,
+                ..Default::default()
+            };
+            let row_key = [
+                Value::from(host),
+                Value::Timestamp(common_time::Timestamp::new_millisecond(ts)),
+            ];
+            memtable
+                .get(&ctx, &row_key)
+                .unwrap()
+                .map(|batch| batch.column(2).get(0))
+        };
+
+        assert_eq!(Some(Value::UInt64(6)), get(2, "b", 1));
+        assert_eq!(Some(Value::UInt64(3)), get(1, "b", 1));
+        assert_eq!(Some(Value::UInt64(2)), get(2, "a", 1));
+        assert_eq!(None, get(2, "a", 2));
+        assert_eq!(None, get(2, "c", 1));
+    }
+}
@@ -26,8 +26,8 @@ use store_api::storage::consts::{self, ReservedColumnId};
 use store_api::storage::{
     AddColumn, AlterOperation, AlterRequest, ColumnDescriptor, ColumnDescriptorBuilder,
     ColumnDescriptorBuilderError, ColumnFamilyDescriptor, ColumnFamilyDescriptorBuilder,
-    ColumnFamilyId, ColumnId, RegionDescriptor, RegionDescriptorBuilder, RegionId, RegionMeta,
-    RowKeyDescriptor, RowKeyDescriptorBuilder, Schema, SchemaRef,
+    ColumnFamilyId, ColumnId, ModifyColumn, RegionDescriptor, RegionDescriptorBuilder, RegionId,
+    RegionMeta, RowKeyDescriptor, RowKeyDescriptorBuilder, Schema, SchemaRef,
 };
 
 use crate::manifest::action::{RawColumnFamiliesMetadata, RawColumnsMetadata, RawRegionMetadata};
@@ -83,6 +83,34 @@ pub enum Error {
     #[snafu(display("Failed to drop column {} as it is an internal column", name))]
     DropInternalColumn { name: String },
 
+    #[snafu(display("Failed to modify column as there is no column named {}", name))]
+    ModifyAbsentColumn { name: String },
+
+    #[snafu(display("Failed to modify column {} as it is not a value column", name))]
+    ModifyNonValueColumn { name: String },
+
+    #[snafu(display(
+        "Failed to modify column {} from type {:?} to type {:?} as the new type is not wider",
+        name,
+        from,
+        to
+    ))]
+    ModifyColumnType {
+        name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+    },
+
+    #[snafu(display("Failed to modify nullable column {} to non null", name))]
+    ModifyNullableToNonNull { name: String },
+
+    #[snafu(display("Invalid default constraint for column {}, source: {}", name, source))]
+    ModifyColumnDefault {
+        name: String,
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     // End of variants for validating `AlterRequest`.
     #[snafu(display("Failed to convert to column schema, source: {}", source))]
     ToColumnSchema {
@@ -250,6 +278,11 @@ impl RegionMetadata {
                     self.validate_drop_column(name)?;
                 }
             }
+            AlterOperation::ModifyColumns { columns } => {
+                for col in columns {
+                    self.validate_modify_column(col)?;
+                }
+            }
         }
 
         Ok(())
@@ -315,6 +348,39 @@ impl RegionMetadata {
         Ok(())
     }
 
+    fn validate_modify_column(&self, modify_column: &ModifyColumn) -> Result<()> {
+        let name = &modify_column.name;
+        let column = self
+            .columns
+            .iter_user_columns()
+            .find(|column| column.name() == name)
+            .context(ModifyAbsentColumnSnafu { name })?;
+        ensure!(
+            !self.schema.store_schema().is_key_column(name),
+            ModifyNonValueColumnSnafu { name }
+        );
+        // Old data is casted to the new type while reading, so the new type must be wider.
+        ensure!(
+            column.desc.data_type.can_widen_to(&modify_column.data_type),
+            ModifyColumnTypeSnafu {
+                name,
+                from: column.desc.data_type.clone(),
+                to: modify_column.data_type.clone(),
+            }
+        );
+        ensure!(
+            modify_column.is_nullable || !column.desc.is_nullable(),
+            ModifyNullableToNonNullSnafu { name }
+        );
+        if let Some(constraint) = &modify_column.default_constraint {
+            constraint
+                .validate(&modify_column.data_type, modify_column.is_nullable)
+                .context(ModifyColumnDefaultSnafu { name })?;
+        }
+
+        Ok(())
+    }
+
     fn to_descriptor(&self) -> RegionDescriptor {
         let row_key = self.columns.to_row_key_descriptor();
         let mut builder = RegionDescriptorBuilder::default()
@@ -1395,6 +1461,88 @@ mod tests {
         metadata.validate_alter(&req).unwrap();
     }
 
+    fn new_modify_column(name: &str, data_type: ConcreteDataType) -> ModifyColumn {
+        ModifyColumn {
+            name: name.to_string(),
+            data_type,
+            is_nullable: true,
+            default_constraint: None,
+        }
+    }
+
+    #[test]
+    fn test_alter_metadata_modify_columns() {
+        let metadata: RegionMetadata = RegionDescBuilder::new("region-modify")
+            .enable_version_column(false)
+            .timestamp(("ts", LogicalTypeId::TimestampMillisecond, false))
+            .push_key_column(("k0", LogicalTypeId::Int32, false))
+            .push_field_column(("v0", LogicalTypeId::Float32, true))
+            .push_field_column(("v1", LogicalTypeId::Int32, false))
+            .build()
+            .try_into()
+            .unwrap();
+
+        let validate = |column: ModifyColumn| {
+            metadata.validate_alter(&AlterRequest {
+                operation: AlterOperation::ModifyColumns {
+                    columns: vec![column],
+                },
+                version: 0,
+            })
+        };
+        let err = validate(new_modify_column("v2", ConcreteDataType::int64_datatype()));
+        assert!(matches!(err, Err(Error::ModifyAbsentColumn { .. })));
+        let err = validate(new_modify_column("k0", ConcreteDataType::int64_datatype()));
+        assert!(matches!(err, Err(Error::ModifyNonValueColumn { .. })));
+        let err = validate(new_modify_column("ts", ConcreteDataType::int64_datatype()));
+        assert!(matches!(err, Err(Error::ModifyNonValueColumn { .. })));
+        let err = validate(new_modify_column("v0", ConcreteDataType::int64_datatype()));
+        assert!(matches!(err, Err(Error::ModifyColumnType { .. })));
+        let mut column = new_modify_column("v0", ConcreteDataType::float64_datatype());
+        column.is_nullable = false;
+        let err = validate(column);
+        assert!(matches!(err, Err(Error::ModifyNullableToNonNull { .. })));
+        let mut column = new_modify_column("v1", ConcreteDataType::int64_datatype());
+        column.default_constraint = Some(ColumnDefaultConstraint::Value(Value::from("a")));
+        let err = validate(column);
+        assert!(matches!(err, Err(Error::ModifyColumnDefault { .. })));
+
+        let mut v1 = new_modify_column("v1", ConcreteDataType::int64_datatype());
+        v1.default_constraint = Some(ColumnDefaultConstraint::Value(Value::Int64(1)));
+        let req = AlterRequest {
+            operation: AlterOperation::ModifyColumns {
+                columns: vec![
+                    new_modify_column("v0", ConcreteDataType::float64_datatype()),
+                    v1,
+                ],
+            },
+            version: 0,
+        };
+        metadata.validate_alter(&req).unwrap();
+        let altered = metadata.alter(&req).unwrap();
+
+        assert_eq!(1, altered.version());
+        let fields: Vec<_> = altered.columns.iter_field_columns().collect();
+        assert_eq!(2, fields.len());
+        let old_fields: Vec<_> = metadata.columns.iter_field_columns().collect();
+        for (field, old_field) in fields.iter().zip(old_fields) {
+            // Column ids are kept.
+            assert_eq!(old_field.id(), field.id());
+            assert_eq!(old_field.name(), field.name());
+        }
+        assert_eq!(
+            ConcreteDataType::float64_datatype(),
+            fields[0].desc.data_type
+        );
+        assert_eq!(ConcreteDataType::int64_datatype(), fields[1].desc.data_type);
+        // v1 becomes nullable.
+        assert!(fields[1].desc.is_nullable());
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(Value::Int64(1))),
+            fields[1].desc.default_constraint()
+        );
+    }
+
     #[test]
     fn test_column_metadata_conversion() {
         let desc = ColumnDescriptorBuilder::new(123, "test", ConcreteDataType::int32_datatype())
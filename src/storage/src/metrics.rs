@@ -0,0 +1,28 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage metrics
+
+/// Bytes of memtables in all regions.
+pub const WRITE_BUFFER_BYTES: &str = "storage.write_buffer.bytes";
+/// Bytes of mutable memtables in all regions.
+pub const WRITE_BUFFER_MUTABLE_BYTES: &str = "storage.write_buffer.mutable_bytes";
+/// Counter of flushes triggered by the global write buffer.
+pub const WRITE_BUFFER_FLUSH_TOTAL: &str = "storage.write_buffer.flush_total";
+/// Counter of writes stalled by the global write buffer.
+pub const WRITE_STALL_TOTAL: &str = "storage.write_stall.total";
+/// Elapsed time of stalled writes.
+pub const WRITE_STALL_ELAPSED: &str = "storage.write_stall.elapsed";
+/// Counter of writes rejected by the global write buffer.
+pub const WRITE_REJECT_TOTAL: &str = "storage.write_reject.total";
@@ -14,19 +14,25 @@
 
 //! Common structs and utilities for read.
 
+mod chain;
 mod dedup;
 mod merge;
+mod sequence;
+mod tombstone;
 
 use std::cmp::Ordering;
 
 use async_trait::async_trait;
+pub use chain::ChainReader;
 use common_base::BitVec;
 use datatypes::data_type::DataType;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::vectors::{BooleanVector, MutableVector, VectorRef};
 pub use dedup::DedupReader;
 pub use merge::{MergeReader, MergeReaderBuilder};
+pub use sequence::SequenceReader;
 use snafu::{ensure, ResultExt};
+pub use tombstone::{TombstoneFilter, TombstoneReader};
 
 use crate::error::{self, Result};
 
@@ -258,3 +264,10 @@ pub trait BatchReader: Send {
 
 /// Pointer to [BatchReader].
 pub type BoxedBatchReader = Box<dyn BatchReader>;
+
+#[async_trait]
+impl<T: BatchReader + ?Sized> BatchReader for Box<T> {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        (**self).next_batch().await
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::read::merge::Source;
+use crate::read::{Batch, BatchReader};
+
+/// A reader that concatenates batches from its sources, one source after another.
+///
+/// Unlike [MergeReader](crate::read::MergeReader), rows from different sources are not
+/// sorted, so it is only used to read regions that don't dedup rows.
+pub struct ChainReader {
+    sources: VecDeque<Source>,
+}
+
+impl ChainReader {
+    pub(super) fn new(sources: Vec<Source>) -> ChainReader {
+        ChainReader {
+            sources: sources.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchReader for ChainReader {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        while let Some(source) = self.sources.front_mut() {
+            if let Some(batch) = source.next_non_empty_batch().await? {
+                return Ok(Some(batch));
+            }
+            // Drops the exhausted source.
+            self.sources.pop_front();
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::MergeReaderBuilder;
+    use crate::test_util::read_util;
+
+    #[tokio::test]
+    async fn test_chain_reader_empty() {
+        let schema = read_util::new_projected_schema();
+        let mut reader = MergeReaderBuilder::new(schema).build_chain();
+
+        assert!(reader.next_batch().await.unwrap().is_none());
+        // Call next_batch() again is allowed.
+        assert!(reader.next_batch().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chain_reader_concat_sources() {
+        let schema = read_util::new_projected_schema();
+        let mut reader = MergeReaderBuilder::new(schema)
+            .push_batch_iter(read_util::build_boxed_iter(&[
+                &[(1000, Some(1)), (1001, Some(2))],
+                &[],
+                &[(1000, Some(3))],
+            ]))
+            .push_batch_reader(read_util::build_boxed_reader(&[]))
+            .push_batch_reader(read_util::build_boxed_reader(&[&[
+                (999, Some(4)),
+                (1000, Some(5)),
+            ]]))
+            .build_chain();
+
+        let result = read_util::collect_kv_batch(&mut reader).await;
+        let expect = [
+            (1000, Some(1)),
+            (1001, Some(2)),
+            (1000, Some(3)),
+            (999, Some(4)),
+            (1000, Some(5)),
+        ];
+        assert_eq!(&expect, &result[..]);
+    }
+}
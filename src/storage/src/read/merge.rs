@@ -63,11 +63,11 @@ use store_api::storage::consts;
 
 use crate::error::Result;
 use crate::memtable::BoxedBatchIterator;
-use crate::read::{Batch, BatchBuilder, BatchOp, BatchReader, BoxedBatchReader};
+use crate::read::{Batch, BatchBuilder, BatchOp, BatchReader, BoxedBatchReader, ChainReader};
 use crate::schema::{ProjectedSchema, ProjectedSchemaRef};
 
 /// Batch data source.
-enum Source {
+pub(super) enum Source {
     // To avoid the overhead of async-trait (typically a heap allocation), wraps the
     // BatchIterator into an enum instead of converting the iterator into a BatchReader.
     Iter(BoxedBatchIterator),
@@ -83,7 +83,7 @@ impl Source {
     }
 
     /// Fetch next non empty batch.
-    async fn next_non_empty_batch(&mut self) -> Result<Option<Batch>> {
+    pub(super) async fn next_non_empty_batch(&mut self) -> Result<Option<Batch>> {
         while let Some(batch) = self.next_batch().await? {
             if !batch.is_empty() {
                 return Ok(Some(batch));
@@ -448,6 +448,12 @@ impl MergeReaderBuilder {
         self
     }
 
+    /// Builds a [ChainReader] that reads the sources one after another without merging
+    /// them, so rows from different sources are unordered.
+    pub fn build_chain(self) -> ChainReader {
+        ChainReader::new(self.sources)
+    }
+
     pub fn build(self) -> MergeReader {
         let num_sources = self.sources.len();
         let column_schemas = self.schema.schema_to_read().schema().column_schemas();
@@ -0,0 +1,91 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use datatypes::value::Value;
+use datatypes::vectors::BooleanVector;
+use store_api::storage::SequenceNumber;
+
+use crate::error::Result;
+use crate::read::{Batch, BatchOp, BatchReader};
+use crate::schema::ProjectedSchemaRef;
+
+/// A reader that removes rows whose sequences are greater than the visible sequence from
+/// the inner reader.
+///
+/// Memtables already skip such rows, but SSTs don't, so reading an old sequence requires
+/// this reader.
+pub struct SequenceReader<R> {
+    schema: ProjectedSchemaRef,
+    visible_sequence: SequenceNumber,
+    reader: R,
+}
+
+impl<R> SequenceReader<R> {
+    pub fn new(schema: ProjectedSchemaRef, visible_sequence: SequenceNumber, reader: R) -> Self {
+        SequenceReader {
+            schema,
+            visible_sequence,
+            reader,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: BatchReader> BatchReader for SequenceReader<R> {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let sequence_index = self.schema.schema_to_read().sequence_index();
+        while let Some(batch) = self.reader.next_batch().await? {
+            let sequences = batch.column(sequence_index);
+            let selected = BooleanVector::from_iterator((0..batch.num_rows()).map(|row| {
+                matches!(sequences.get(row), Value::UInt64(sequence) if sequence <= self.visible_sequence)
+            }));
+            let filtered = self.schema.filter(&batch, &selected)?;
+            // Skip empty batch.
+            if !filtered.is_empty() {
+                return Ok(Some(filtered));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use store_api::storage::OpType;
+
+    use super::*;
+    use crate::test_util::read_util;
+
+    #[tokio::test]
+    async fn test_sequence_reader() {
+        let schema = read_util::new_projected_schema();
+        let reader = read_util::build_full_vec_reader(&[
+            // key, value, sequence, op_type
+            &[
+                (100, 1, 10, OpType::Put),
+                (101, 1, 20, OpType::Put),
+                (102, 1, 15, OpType::Put),
+            ],
+            &[(103, 1, 30, OpType::Put)],
+            &[(104, 1, 5, OpType::Put)],
+        ]);
+        let mut reader = SequenceReader::new(schema, 15, reader);
+
+        let result = read_util::collect_kv_batch(&mut reader).await;
+        let expect = [(100, Some(1)), (102, Some(1)), (104, Some(1))];
+        assert_eq!(&expect, &result[..]);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use datatypes::value::Value;
+use datatypes::vectors::BooleanVector;
+use store_api::storage::SequenceNumber;
+
+use crate::error::Result;
+use crate::read::{Batch, BatchOp, BatchReader};
+use crate::schema::{ProjectedSchemaRef, StoreSchema};
+use crate::tombstone::RangeTombstone;
+
+/// Finds rows deleted by range tombstones in batches of a [StoreSchema].
+#[derive(Debug, Default)]
+pub struct TombstoneFilter {
+    /// Visible tombstones and the indices and values of their key columns.
+    tombstones: Vec<(RangeTombstone, Vec<(usize, Value)>)>,
+    timestamp_index: usize,
+    sequence_index: usize,
+}
+
+impl TombstoneFilter {
+    /// Creates a filter that applies `tombstones` whose sequences are not greater than
+    /// `visible_sequence` to batches of the `schema`.
+    pub fn new(
+        schema: &StoreSchema,
+        tombstones: &[RangeTombstone],
+        visible_sequence: SequenceNumber,
+    ) -> TombstoneFilter {
+        let Some(timestamp_index) = schema.schema().timestamp_index() else { return TombstoneFilter::default() };
+
+        let tombstones = tombstones
+            .iter()
+            .filter(|tombstone| tombstone.sequence <= visible_sequence)
+            .filter_map(|tombstone| {
+                // Key columns are never removed, so a tombstone always finds its key columns
+                // in the region.
+                let keys = tombstone
+                    .range
+                    .keys
+                    .iter()
+                    .map(|(name, value)| {
+                        let index = schema
+                            .row_key_indices()
+                            .find(|index| schema.column_name(*index) == name)?;
+                        Some((index, value.clone()))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some((tombstone.clone(), keys))
+            })
+            .collect();
+
+        TombstoneFilter {
+            tombstones,
+            timestamp_index,
+            sequence_index: schema.sequence_index(),
+        }
+    }
+
+    /// Returns true if there is no tombstone to apply.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    /// Returns true if the `row` of the `batch` is deleted by any tombstone.
+    pub fn is_deleted(&self, batch: &Batch, row: usize) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let Value::Timestamp(ts) = batch.column(self.timestamp_index).get(row) else { return false };
+        let Value::UInt64(sequence) = batch.column(self.sequence_index).get(row) else { return false };
+        self.tombstones.iter().any(|(tombstone, keys)| {
+            sequence < tombstone.sequence
+                && tombstone.range.contains_timestamp(&ts)
+                && keys
+                    .iter()
+                    .all(|(index, value)| batch.column(*index).get(row) == *value)
+        })
+    }
+}
+
+/// A reader that removes rows deleted by range tombstones from the inner reader.
+pub struct TombstoneReader<R> {
+    schema: ProjectedSchemaRef,
+    filter: TombstoneFilter,
+    reader: R,
+}
+
+impl<R> TombstoneReader<R> {
+    pub fn new(schema: ProjectedSchemaRef, filter: TombstoneFilter, reader: R) -> Self {
+        TombstoneReader {
+            schema,
+            filter,
+            reader,
+        }
+    }
+}
+
+#[async_trait]
+impl<R: BatchReader> BatchReader for TombstoneReader<R> {
+    async fn next_batch(&mut self) -> Result<Option<Batch>> {
+        while let Some(batch) = self.reader.next_batch().await? {
+            let selected = BooleanVector::from_iterator(
+                (0..batch.num_rows()).map(|row| !self.filter.is_deleted(&batch, row)),
+            );
+            let filtered = self.schema.filter(&batch, &selected)?;
+            // Skip empty batch.
+            if !filtered.is_empty() {
+                return Ok(Some(filtered));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_time::Timestamp;
+    use store_api::storage::{DeleteRange, OpType};
+
+    use super::*;
+    use crate::test_util::read_util;
+
+    fn new_tombstone(sequence: SequenceNumber, start: i64, end: i64) -> RangeTombstone {
+        RangeTombstone::new(
+            sequence,
+            DeleteRange {
+                keys: vec![],
+                start: Some(Timestamp::new_millisecond(start)),
+                end: Some(Timestamp::new_millisecond(end)),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tombstone_reader() {
+        let schema = read_util::new_projected_schema();
+        let reader = read_util::build_full_vec_reader(&[
+            // key, value, sequence, op_type
+            &[
+                (100, 1, 10, OpType::Put),
+                (101, 1, 20, OpType::Put),
+                (102, 1, 10, OpType::Put),
+            ],
+            &[(103, 1, 10, OpType::Put), (200, 1, 10, OpType::Put)],
+        ]);
+        let tombstones = [
+            // Deletes 100, but 101 is written after the tombstone.
+            new_tombstone(15, 100, 102),
+            // Invisible to the reader.
+            new_tombstone(30, 103, 104),
+            // Written before 200.
+            new_tombstone(5, 200, 201),
+        ];
+        let filter = TombstoneFilter::new(schema.schema_to_read(), &tombstones, 20);
+        let mut reader = TombstoneReader::new(schema, filter, reader);
+
+        let result = read_util::collect_kv_batch(&mut reader).await;
+        let expect = [
+            (101, Some(1)),
+            (102, Some(1)),
+            (103, Some(1)),
+            (200, Some(1)),
+        ];
+        assert_eq!(&expect, &result[..]);
+    }
+}
@@ -18,26 +18,28 @@ mod writer;
 
 use std::collections::BTreeMap;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, Weak};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use common_telemetry::logging;
-use snafu::ResultExt;
+use common_time::util::current_time_millis;
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::logstore::LogStore;
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AlterRequest, FlushContext, OpenOptions, ReadContext, Region, RegionId, SequenceNumber,
-    WriteContext, WriteResponse,
+    AlterOptions, AlterRequest, CompactionStrategy, FlushContext, OpType, OpenOptions, ReadContext,
+    Region, RegionId, SequenceNumber, SstCompression, WriteContext, WriteResponse,
 };
 
 use crate::compaction::CompactionSchedulerRef;
 use crate::config::EngineConfig;
 use crate::error::{self, Error, Result};
 use crate::file_purger::FilePurgerRef;
-use crate::flush::{FlushSchedulerRef, FlushStrategyRef};
+use crate::flush::{FlushSchedulerRef, FlushStrategyRef, SizeBasedStrategy};
 use crate::manifest::action::{
-    RawRegionMetadata, RegionChange, RegionCheckpoint, RegionMetaAction, RegionMetaActionList,
+    RawRegionMetadata, RegionChange, RegionCheckpoint, RegionManifestData, RegionMetaAction,
+    RegionMetaActionList, RegionSnapshot,
 };
 use crate::manifest::region::RegionManifest;
 use crate::memtable::MemtableBuilderRef;
@@ -45,12 +47,13 @@ use crate::metadata::{RegionMetaImpl, RegionMetadata, RegionMetadataRef};
 pub use crate::region::writer::{AlterContext, RegionWriter, RegionWriterRef, WriterContext};
 use crate::schema::compat::CompatWrite;
 use crate::snapshot::SnapshotImpl;
-use crate::sst::AccessLayerRef;
+use crate::sst::{AccessLayerRef, PinnedFiles, PinnedFilesRef};
 use crate::version::{
     Version, VersionControl, VersionControlRef, VersionEdit, INIT_COMMITTED_SEQUENCE,
 };
 use crate::wal::Wal;
 use crate::write_batch::WriteBatch;
+use crate::write_buffer::{BufferedRegion, WriteBufferManagerRef};
 
 /// [Region] implementation.
 pub struct RegionImpl<S: LogStore> {
@@ -100,6 +103,21 @@ impl<S: LogStore> Region for RegionImpl<S> {
     }
 
     async fn write(&self, ctx: &WriteContext, mut request: WriteBatch) -> Result<WriteResponse> {
+        // Rows are not deduped in append mode, so a deletion can't hide the rows it deletes.
+        let shared = &self.inner.shared;
+        let payload = request.payload();
+        ensure!(
+            !shared.append_mode
+                || (payload.delete_ranges.is_empty()
+                    && payload
+                        .mutations
+                        .iter()
+                        .all(|mutation| mutation.op_type == OpType::Put)),
+            error::DeleteInAppendModeSnafu {
+                region: shared.name(),
+            }
+        );
+
         // Compat the schema of the write batch outside of the write lock.
         self.inner.compat_write_batch(&mut request)?;
 
@@ -122,6 +140,10 @@ impl<S: LogStore> Region for RegionImpl<S> {
         self.inner.alter(request).await
     }
 
+    async fn alter_options(&self, options: AlterOptions) -> Result<()> {
+        self.inner.alter_options(options).await
+    }
+
     async fn close(&self) -> Result<()> {
         self.inner.close().await
     }
@@ -139,6 +161,28 @@ impl<S: LogStore> Region for RegionImpl<S> {
     async fn flush(&self, ctx: &FlushContext) -> Result<()> {
         self.inner.flush(ctx).await
     }
+
+    async fn compact(&self, ctx: &store_api::storage::CompactContext) -> Result<()> {
+        // Manual compactions compact all files in level 0 regardless of the file number.
+        self.inner
+            .compact(CompactContext {
+                wait: ctx.wait,
+                max_files_in_l0: 0,
+            })
+            .await
+    }
+
+    async fn truncate(&self) -> Result<()> {
+        self.inner.truncate().await
+    }
+
+    async fn save_snapshot(&self, name: &str) -> Result<()> {
+        self.inner.save_snapshot(name).await
+    }
+
+    async fn drop_snapshot(&self, name: &str) -> Result<bool> {
+        self.inner.drop_snapshot(name).await
+    }
 }
 
 /// Storage related config for region.
@@ -157,6 +201,10 @@ pub struct StoreConfig<S: LogStore> {
     pub file_purger: FilePurgerRef,
     pub ttl: Option<Duration>,
     pub compaction_time_window: Option<i64>,
+    pub compaction_strategy: CompactionStrategy,
+    pub sst_compression: SstCompression,
+    pub append_mode: bool,
+    pub write_buffer_manager: WriteBufferManagerRef,
 }
 
 pub type RecoverdMetadata = (SequenceNumber, (ManifestVersion, RawRegionMetadata));
@@ -203,20 +251,28 @@ impl<S: LogStore> RegionImpl<S> {
         let mutable_memtable = store_config
             .memtable_builder
             .build(metadata.schema().clone());
+        // A new region has no snapshot.
+        let pinned_files = PinnedFilesRef::default();
         let version = Version::with_manifest_version(
             metadata,
             manifest_version,
             mutable_memtable,
             store_config.sst_layer.clone(),
             store_config.file_purger.clone(),
+            pinned_files.clone(),
         );
-        let region = RegionImpl::new(version, store_config);
+        let region = RegionImpl::new(version, store_config, pinned_files, false);
 
         Ok(region)
     }
 
     /// Create a new region without persisting manifest.
-    fn new(version: Version, store_config: StoreConfig<S>) -> RegionImpl<S> {
+    fn new(
+        version: Version,
+        store_config: StoreConfig<S>,
+        pinned_files: PinnedFilesRef,
+        read_only: bool,
+    ) -> RegionImpl<S> {
         let metadata = version.metadata();
         let id = metadata.id();
         let name = metadata.name().to_string();
@@ -228,21 +284,42 @@ impl<S: LogStore> RegionImpl<S> {
                 id,
                 name,
                 version_control: Arc::new(version_control),
+                append_mode: store_config.append_mode,
+                read_only,
+                write_buffer_manager: store_config.write_buffer_manager,
             }),
             writer: Arc::new(RegionWriter::new(
                 store_config.memtable_builder,
                 store_config.engine_config.clone(),
                 store_config.ttl,
                 store_config.compaction_time_window,
+                store_config.compaction_strategy,
+                store_config.sst_compression,
             )),
             wal,
-            flush_strategy: store_config.flush_strategy,
+            flush_strategy: RwLock::new(store_config.flush_strategy),
             flush_scheduler: store_config.flush_scheduler,
             compaction_scheduler: store_config.compaction_scheduler,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
+            pinned_files,
         });
 
+        RegionImpl::with_inner(inner)
+    }
+
+    fn with_inner(inner: Arc<RegionInner<S>>) -> RegionImpl<S> {
+        // Read-only regions never write memtables.
+        if !inner.shared.read_only {
+            let region: Weak<dyn BufferedRegion> = Arc::downgrade(&inner) as _;
+            inner
+                .shared
+                .write_buffer_manager
+                .register(inner.shared.id, region);
+            // Memtables may be replayed before registering.
+            inner.shared.update_write_buffer_usage();
+        }
+
         RegionImpl { inner }
     }
 
@@ -254,12 +331,23 @@ impl<S: LogStore> RegionImpl<S> {
         store_config: StoreConfig<S>,
         opts: &OpenOptions,
     ) -> Result<Option<RegionImpl<S>>> {
+        if let Some(snapshot_name) = &opts.snapshot {
+            return Self::open_at_snapshot(name, snapshot_name, store_config)
+                .await
+                .map(Some);
+        }
+
+        // SSTs in snapshots must be pinned before replaying edits that remove them.
+        let pinned_files = Arc::new(PinnedFiles::default());
+        pinned_files.set(store_config.manifest.snapshot_file_ids().await?);
+
         // Load version meta data from manifest.
         let (version, mut recovered_metadata) = match Self::recover_from_manifest(
             &store_config.manifest,
             &store_config.memtable_builder,
             &store_config.sst_layer,
             &store_config.file_purger,
+            &pinned_files,
         )
         .await?
         {
@@ -275,6 +363,8 @@ impl<S: LogStore> RegionImpl<S> {
         let metadata = version.metadata().clone();
         let flushed_sequence = version.flushed_sequence();
         let version_control = Arc::new(VersionControl::with_version(version));
+        // Commit times of flushed data are unknown, the replay records the rest.
+        version_control.commit_timeline().reset(flushed_sequence);
 
         let recovered_metadata_after_flushed =
             recovered_metadata.split_off(&(flushed_sequence + 1));
@@ -308,6 +398,9 @@ impl<S: LogStore> RegionImpl<S> {
             id: metadata.id(),
             name,
             version_control,
+            append_mode: store_config.append_mode,
+            read_only: false,
+            write_buffer_manager: store_config.write_buffer_manager,
         });
         let compaction_time_window = store_config
             .compaction_time_window
@@ -317,6 +410,8 @@ impl<S: LogStore> RegionImpl<S> {
             store_config.engine_config.clone(),
             store_config.ttl,
             compaction_time_window,
+            store_config.compaction_strategy,
+            store_config.sst_compression,
         ));
         let writer_ctx = WriterContext {
             shared: &shared,
@@ -332,6 +427,11 @@ impl<S: LogStore> RegionImpl<S> {
         writer
             .replay(recovered_metadata_after_flushed, writer_ctx)
             .await?;
+        // All recovered data is committed before now.
+        let version_control = &shared.version_control;
+        version_control
+            .commit_timeline()
+            .record(current_time_millis(), version_control.committed_sequence());
 
         // Try to do a manifest checkpoint on opening
         if store_config.engine_config.manifest_checkpoint_on_startup {
@@ -343,14 +443,73 @@ impl<S: LogStore> RegionImpl<S> {
             shared,
             writer,
             wal,
-            flush_strategy: store_config.flush_strategy,
+            flush_strategy: RwLock::new(store_config.flush_strategy),
             flush_scheduler: store_config.flush_scheduler,
             compaction_scheduler: store_config.compaction_scheduler,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
+            pinned_files,
         });
 
-        Ok(Some(RegionImpl { inner }))
+        Ok(Some(RegionImpl::with_inner(inner)))
+    }
+
+    /// Open the region read-only at the named snapshot, so rows written after the snapshot is
+    /// taken are invisible. The WAL and the manifest of the region are left untouched.
+    async fn open_at_snapshot(
+        name: String,
+        snapshot_name: &str,
+        store_config: StoreConfig<S>,
+    ) -> Result<RegionImpl<S>> {
+        ensure!(
+            is_valid_snapshot_name(snapshot_name),
+            error::InvalidSnapshotNameSnafu {
+                name: snapshot_name
+            }
+        );
+        let bytes = store_config
+            .manifest
+            .load_snapshot(snapshot_name)
+            .await?
+            .with_context(|| error::SnapshotNotFoundSnafu {
+                region: &name,
+                name: snapshot_name,
+            })?;
+        let snapshot = RegionSnapshot::decode(&bytes)?;
+        let manifest_version = snapshot
+            .data
+            .version
+            .as_ref()
+            .map(|v| v.manifest_version)
+            .unwrap_or(manifest::MIN_VERSION);
+        // Files of the snapshot are never removed from the read-only region.
+        let pinned_files = PinnedFilesRef::default();
+        let version = Self::create_version_with_data(
+            snapshot.data,
+            manifest_version,
+            &store_config.memtable_builder,
+            &store_config.sst_layer,
+            &store_config.file_purger,
+            &pinned_files,
+        )?;
+
+        logging::info!(
+            "Open region {} at snapshot {}, manifest_version: {}",
+            name,
+            snapshot_name,
+            manifest_version
+        );
+
+        let flushed_sequence = version.flushed_sequence();
+        let region = RegionImpl::new(version, store_config, pinned_files, true);
+        let version_control = region.inner.version_control();
+        version_control.set_committed_sequence(flushed_sequence);
+        version_control.commit_timeline().reset(flushed_sequence);
+        version_control
+            .commit_timeline()
+            .record(current_time_millis(), flushed_sequence);
+
+        Ok(region)
     }
 
     /// Get ID of this region.
@@ -363,15 +522,31 @@ impl<S: LogStore> RegionImpl<S> {
         memtable_builder: &MemtableBuilderRef,
         sst_layer: &AccessLayerRef,
         file_purger: &FilePurgerRef,
+        pinned_files: &PinnedFilesRef,
     ) -> Result<Option<Version>> {
-        if checkpoint.checkpoint.is_none() {
-            return Ok(None);
-        }
-        // Safety: it's safe to unwrap here, checking it above.
-        let s = checkpoint.checkpoint.unwrap();
+        let Some(data) = checkpoint.checkpoint else { return Ok(None) };
+
+        Self::create_version_with_data(
+            data,
+            checkpoint.last_version,
+            memtable_builder,
+            sst_layer,
+            file_purger,
+            pinned_files,
+        )
+        .map(Some)
+    }
 
-        let region = s.metadata.name.clone();
-        let region_metadata: RegionMetadata = s
+    fn create_version_with_data(
+        data: RegionManifestData,
+        manifest_version: ManifestVersion,
+        memtable_builder: &MemtableBuilderRef,
+        sst_layer: &AccessLayerRef,
+        file_purger: &FilePurgerRef,
+        pinned_files: &PinnedFilesRef,
+    ) -> Result<Version> {
+        let region = data.metadata.name.clone();
+        let region_metadata: RegionMetadata = data
             .metadata
             .try_into()
             .context(error::InvalidRawRegionSnafu { region })?;
@@ -379,21 +554,24 @@ impl<S: LogStore> RegionImpl<S> {
         let memtable = memtable_builder.build(region_metadata.schema().clone());
         let mut version = Version::with_manifest_version(
             Arc::new(region_metadata),
-            checkpoint.last_version,
+            manifest_version,
             memtable,
             sst_layer.clone(),
             file_purger.clone(),
+            pinned_files.clone(),
         );
 
-        if let Some(v) = s.version {
+        if let Some(v) = data.version {
             version.apply_checkpoint(
                 v.flushed_sequence,
                 v.manifest_version,
                 v.files.into_values(),
+                v.range_tombstones,
+                v.min_readable_sequence.unwrap_or_default(),
             );
         }
 
-        Ok(Some(version))
+        Ok(version)
     }
 
     async fn recover_from_manifest(
@@ -401,6 +579,7 @@ impl<S: LogStore> RegionImpl<S> {
         memtable_builder: &MemtableBuilderRef,
         sst_layer: &AccessLayerRef,
         file_purger: &FilePurgerRef,
+        pinned_files: &PinnedFilesRef,
     ) -> Result<(Option<Version>, RecoveredMetadataMap)> {
         let checkpoint = manifest.last_checkpoint().await?;
 
@@ -413,6 +592,7 @@ impl<S: LogStore> RegionImpl<S> {
                     memtable_builder,
                     sst_layer,
                     file_purger,
+                    pinned_files,
                 )?,
             )
         } else {
@@ -445,6 +625,7 @@ impl<S: LogStore> RegionImpl<S> {
                             memtable,
                             sst_layer.clone(),
                             file_purger.clone(),
+                            pinned_files.clone(),
                         ));
                         for (manifest_version, action) in actions.drain(..) {
                             version = Self::replay_edit(manifest_version, action, version);
@@ -490,6 +671,9 @@ impl<S: LogStore> RegionImpl<S> {
                 flushed_sequence: e.flushed_sequence,
                 manifest_version,
                 max_memtable_id: None,
+                tombstones_to_add: e.tombstones_to_add,
+                tombstones_to_remove: e.tombstones_to_remove,
+                min_readable_sequence: e.min_readable_sequence,
             };
             version.map(|mut v| {
                 v.apply_edit(edit);
@@ -526,9 +710,10 @@ impl<S: LogStore> RegionImpl<S> {
     // Replay metadata to inner.
     async fn replay_inner(&self, recovered_metadata: RecoveredMetadataMap) -> Result<()> {
         let inner = &self.inner;
+        let flush_strategy = inner.flush_strategy();
         let writer_ctx = WriterContext {
             shared: &inner.shared,
-            flush_strategy: &inner.flush_strategy,
+            flush_strategy: &flush_strategy,
             flush_scheduler: &inner.flush_scheduler,
             compaction_scheduler: &inner.compaction_scheduler,
             sst_layer: &inner.sst_layer,
@@ -550,6 +735,11 @@ pub struct SharedData {
     name: String,
     // TODO(yingwen): Maybe no need to use Arc for version control.
     pub version_control: VersionControlRef,
+    /// Whether the region only appends rows, so readers don't dedup rows by key.
+    append_mode: bool,
+    /// Whether the region is opened at a snapshot, which rejects any modification.
+    read_only: bool,
+    write_buffer_manager: WriteBufferManagerRef,
 }
 
 impl SharedData {
@@ -562,6 +752,27 @@ impl SharedData {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    #[inline]
+    pub fn append_mode(&self) -> bool {
+        self.append_mode
+    }
+
+    #[inline]
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Reports memory used by memtables of the region to the write buffer manager.
+    pub(crate) fn update_write_buffer_usage(&self) {
+        let current = self.version_control.current();
+        let memtables = current.memtables();
+        self.write_buffer_manager.update(
+            self.id,
+            memtables.mutable_bytes_allocated(),
+            memtables.total_bytes_allocated(),
+        );
+    }
 }
 
 pub type SharedDataRef = Arc<SharedData>;
@@ -570,11 +781,25 @@ struct RegionInner<S: LogStore> {
     shared: SharedDataRef,
     writer: RegionWriterRef,
     wal: Wal<S>,
-    flush_strategy: FlushStrategyRef,
+    /// Strategy to trigger flush, which could be replaced by altering options of the region.
+    flush_strategy: RwLock<FlushStrategyRef>,
     flush_scheduler: FlushSchedulerRef,
     compaction_scheduler: CompactionSchedulerRef<S>,
     sst_layer: AccessLayerRef,
     manifest: RegionManifest,
+    /// SSTs kept by snapshots of the region.
+    pinned_files: PinnedFilesRef,
+}
+
+#[async_trait]
+impl<S: LogStore> BufferedRegion for RegionInner<S> {
+    fn name(&self) -> &str {
+        self.shared.name()
+    }
+
+    async fn schedule_flush(&self) -> Result<()> {
+        self.flush(&FlushContext { wait: false }).await
+    }
 }
 
 impl<S: LogStore> RegionInner<S> {
@@ -583,6 +808,21 @@ impl<S: LogStore> RegionInner<S> {
         &self.shared.version_control
     }
 
+    fn ensure_writable(&self) -> Result<()> {
+        ensure!(
+            !self.shared.read_only,
+            error::ReadOnlyRegionSnafu {
+                region: &self.shared.name,
+            }
+        );
+        Ok(())
+    }
+
+    #[inline]
+    fn flush_strategy(&self) -> FlushStrategyRef {
+        self.flush_strategy.read().unwrap().clone()
+    }
+
     fn in_memory_metadata(&self) -> RegionMetaImpl {
         let metadata = self.version_control().metadata();
 
@@ -593,7 +833,13 @@ impl<S: LogStore> RegionInner<S> {
         let version = self.version_control().current();
         let sequence = self.version_control().committed_sequence();
 
-        SnapshotImpl::new(version, sequence, self.sst_layer.clone())
+        SnapshotImpl::new(
+            version,
+            sequence,
+            self.sst_layer.clone(),
+            self.shared.append_mode,
+            self.version_control().commit_timeline().clone(),
+        )
     }
 
     fn compat_write_batch(&self, request: &mut WriteBatch) -> Result<()> {
@@ -607,9 +853,17 @@ impl<S: LogStore> RegionInner<S> {
 
     /// Write to writer directly.
     async fn write(&self, ctx: &WriteContext, request: WriteBatch) -> Result<WriteResponse> {
+        self.ensure_writable()?;
+        // Waits before acquiring the write lock, which flushes of the region also need.
+        self.shared
+            .write_buffer_manager
+            .wait_for_room(&self.shared.name)
+            .await?;
+
+        let flush_strategy = self.flush_strategy();
         let writer_ctx = WriterContext {
             shared: &self.shared,
-            flush_strategy: &self.flush_strategy,
+            flush_strategy: &flush_strategy,
             flush_scheduler: &self.flush_scheduler,
             compaction_scheduler: &self.compaction_scheduler,
             sst_layer: &self.sst_layer,
@@ -623,6 +877,8 @@ impl<S: LogStore> RegionInner<S> {
     }
 
     async fn alter(&self, request: AlterRequest) -> Result<()> {
+        self.ensure_writable()?;
+
         logging::info!(
             "Alter region {}, name: {}, request: {:?}",
             self.shared.id,
@@ -639,15 +895,52 @@ impl<S: LogStore> RegionInner<S> {
         self.writer.alter(alter_ctx, request).await
     }
 
+    async fn alter_options(&self, options: AlterOptions) -> Result<()> {
+        self.ensure_writable()?;
+
+        logging::info!(
+            "Alter options of region {}, name: {}, options: {:?}",
+            self.shared.id,
+            self.shared.name,
+            options
+        );
+
+        let flush_strategy: FlushStrategyRef = Arc::new(
+            options
+                .write_buffer_size
+                .map(SizeBasedStrategy::new)
+                .unwrap_or_default(),
+        );
+        *self.flush_strategy.write().unwrap() = flush_strategy;
+
+        self.writer
+            .alter_options(
+                options.ttl,
+                options.compaction_time_window,
+                options.compaction_strategy,
+                options.sst_compression,
+            )
+            .await
+    }
+
     async fn close(&self) -> Result<()> {
         self.writer.close().await?;
+        if self.shared.read_only {
+            // A read-only region is never registered to the write buffer manager, and it may
+            // have the same id as the writable region. The manifest isn't started either.
+            return Ok(());
+        }
+        self.shared.write_buffer_manager.unregister(self.shared.id);
         self.manifest.stop().await
     }
 
     async fn flush(&self, ctx: &FlushContext) -> Result<()> {
+        self.ensure_writable()?;
+
+        let flush_strategy = self.flush_strategy();
         let writer_ctx = WriterContext {
             shared: &self.shared,
-            flush_strategy: &self.flush_strategy,
+            flush_strategy: &flush_strategy,
             flush_scheduler: &self.flush_scheduler,
             compaction_scheduler: &self.compaction_scheduler,
             sst_layer: &self.sst_layer,
@@ -660,9 +953,12 @@ impl<S: LogStore> RegionInner<S> {
 
     /// Compact the region manually.
     async fn compact(&self, ctx: CompactContext) -> Result<()> {
+        self.ensure_writable()?;
+
+        let flush_strategy = self.flush_strategy();
         let writer_ctx = WriterContext {
             shared: &self.shared,
-            flush_strategy: &self.flush_strategy,
+            flush_strategy: &flush_strategy,
             flush_scheduler: &self.flush_scheduler,
             compaction_scheduler: &self.compaction_scheduler,
             sst_layer: &self.sst_layer,
@@ -672,4 +968,66 @@ impl<S: LogStore> RegionInner<S> {
         };
         self.writer.compact(writer_ctx, ctx).await
     }
+
+    async fn truncate(&self) -> Result<()> {
+        self.ensure_writable()?;
+
+        logging::info!(
+            "Truncate region {}, name: {}",
+            self.shared.id,
+            self.shared.name
+        );
+
+        self.writer
+            .truncate(&self.wal, &self.shared, &self.manifest)
+            .await
+    }
+
+    async fn save_snapshot(&self, name: &str) -> Result<()> {
+        self.ensure_writable()?;
+        ensure!(
+            is_valid_snapshot_name(name),
+            error::InvalidSnapshotNameSnafu { name }
+        );
+
+        logging::info!(
+            "Save snapshot {} of region {}, name: {}",
+            name,
+            self.shared.id,
+            self.shared.name
+        );
+
+        // Flushes the memtables first so the snapshot contains rows written before.
+        self.flush(&FlushContext::default()).await?;
+        self.writer
+            .save_snapshot(name, &self.shared, &self.manifest, &self.pinned_files)
+            .await
+    }
+
+    async fn drop_snapshot(&self, name: &str) -> Result<bool> {
+        self.ensure_writable()?;
+        ensure!(
+            is_valid_snapshot_name(name),
+            error::InvalidSnapshotNameSnafu { name }
+        );
+
+        logging::info!(
+            "Drop snapshot {} of region {}, name: {}",
+            name,
+            self.shared.id,
+            self.shared.name
+        );
+
+        self.writer
+            .drop_snapshot(name, &self.shared, &self.manifest, &self.pinned_files)
+            .await
+    }
+}
+
+/// Returns true if `name` could be used as the name of a snapshot, which is also a file name.
+fn is_valid_snapshot_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
 }
@@ -15,9 +15,11 @@
 //! Region tests.
 
 mod alter;
+mod append;
 mod basic;
 mod close;
 mod compact;
+mod delete_range;
 mod flush;
 mod projection;
 
@@ -28,6 +30,7 @@ use common_test_util::temp_dir::create_temp_dir;
 use datatypes::prelude::{ScalarVector, WrapperType};
 use datatypes::timestamp::TimestampMillisecond;
 use datatypes::type_id::LogicalTypeId;
+use datatypes::value::Value;
 use datatypes::vectors::{Int64Vector, TimestampMillisecondVector, VectorRef};
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use log_store::NoopLogStore;
@@ -35,7 +38,8 @@ use object_store::services::Fs;
 use object_store::ObjectStore;
 use store_api::manifest::MAX_VERSION;
 use store_api::storage::{
-    consts, Chunk, ChunkReader, RegionMeta, ScanRequest, SequenceNumber, Snapshot, WriteRequest,
+    consts, Chunk, ChunkReader, DeleteRange, GetRequest, RegionMeta, ScanRequest, SequenceNumber,
+    Snapshot, WriteRequest,
 };
 
 use super::*;
@@ -146,6 +150,36 @@ impl<S: LogStore> TesterBase<S> {
         dst
     }
 
+    /// Scan data by the request.
+    pub async fn scan(&self, request: ScanRequest) -> Result<Vec<(i64, Option<i64>)>> {
+        let snapshot = self.region.snapshot(&self.read_ctx).unwrap();
+
+        let mut reader = snapshot.scan(&self.read_ctx, request).await?.reader;
+        let mut dst = Vec::new();
+        while let Some(chunk) = reader.next_chunk().await? {
+            let chunk = reader.project_chunk(chunk);
+            append_chunk_to(&chunk, &mut dst);
+        }
+
+        Ok(dst)
+    }
+
+    /// Get the row by the key (timestamp).
+    pub async fn get(&self, key: i64) -> Option<(i64, Option<i64>)> {
+        let snapshot = self.region.snapshot(&self.read_ctx).unwrap();
+
+        let request = GetRequest {
+            row_key: vec![Value::from(TimestampMillisecond::from(key))],
+            ..Default::default()
+        };
+        let chunk = snapshot.get(&self.read_ctx, request).await.unwrap().chunk?;
+
+        let mut dst = Vec::new();
+        append_chunk_to(&chunk, &mut dst);
+        assert_eq!(1, dst.len());
+        dst.pop()
+    }
+
     pub fn committed_sequence(&self) -> SequenceNumber {
         self.region.committed_sequence()
     }
@@ -161,6 +195,14 @@ impl<S: LogStore> TesterBase<S> {
         self.region.write(&self.write_ctx, batch).await.unwrap()
     }
 
+    /// Delete rows in the range.
+    pub async fn delete_range(&self, range: DeleteRange) -> WriteResponse {
+        let mut batch = new_write_batch_for_test(false);
+        batch.delete_range(range).unwrap();
+
+        self.region.write(&self.write_ctx, batch).await.unwrap()
+    }
+
     /// Returns a reader to scan all data.
     pub async fn full_scan_reader(&self) -> ChunkReaderImpl {
         let snapshot = self.region.snapshot(&self.read_ctx).unwrap();
@@ -284,6 +326,8 @@ async fn test_new_region() {
     let region = RegionImpl::new(
         Version::new(Arc::new(metadata), placeholder_memtable),
         store_config,
+        Default::default(),
+        false,
     );
 
     let expect_schema = schema_util::new_schema_ref(
@@ -329,6 +373,7 @@ async fn test_recover_region_manifets() {
         &memtable_builder,
         &sst_layer,
         &file_purger,
+        &Default::default(),
     )
     .await
     .unwrap()
@@ -376,6 +421,7 @@ async fn test_recover_region_manifets() {
         &memtable_builder,
         &sst_layer,
         &file_purger,
+        &Default::default(),
     )
     .await
     .unwrap();
@@ -403,6 +449,7 @@ async fn test_recover_region_manifets() {
         &memtable_builder,
         &sst_layer,
         &file_purger,
+        &Default::default(),
     )
     .await
     .unwrap();
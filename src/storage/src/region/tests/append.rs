@@ -0,0 +1,90 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for regions in append mode.
+
+use common_test_util::temp_dir::create_temp_dir;
+use datatypes::timestamp::TimestampMillisecond;
+use log_store::raft_engine::log_store::RaftEngineLogStore;
+use store_api::storage::{FlushContext, Region};
+
+use crate::error::Error;
+use crate::region::tests::{self, FileTesterBase};
+use crate::region::RegionImpl;
+use crate::test_util::config_util;
+
+const REGION_NAME: &str = "region-append-0";
+
+async fn create_region_for_append(store_dir: &str) -> RegionImpl<RaftEngineLogStore> {
+    let metadata = tests::new_metadata(REGION_NAME, false);
+
+    let mut store_config = config_util::new_store_config(REGION_NAME, store_dir).await;
+    store_config.append_mode = true;
+
+    RegionImpl::create(metadata, store_config).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_append_mode_keeps_duplicate_rows() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = create_temp_dir("append-mode");
+    let store_dir = dir.path().to_str().unwrap();
+    let tester = FileTesterBase::with_region(create_region_for_append(store_dir).await);
+
+    tester.put(&[(1000, Some(1)), (1001, Some(2))]).await;
+    tester.put(&[(1000, Some(3))]).await;
+    // Rows in a memtable are still sorted by key.
+    assert_eq!(
+        vec![(1000, Some(3)), (1000, Some(1)), (1001, Some(2))],
+        tester.full_scan().await
+    );
+
+    tester.region.flush(&FlushContext::default()).await.unwrap();
+    tester.put(&[(999, Some(4)), (1000, Some(5))]).await;
+    // Sources are concatenated without merging.
+    assert_eq!(
+        vec![
+            (999, Some(4)),
+            (1000, Some(5)),
+            (1000, Some(3)),
+            (1000, Some(1)),
+            (1001, Some(2)),
+        ],
+        tester.full_scan().await
+    );
+
+    // Get still returns the latest row of the key.
+    assert_eq!(Some((1000, Some(5))), tester.get(1000).await);
+}
+
+#[tokio::test]
+async fn test_append_mode_rejects_delete() {
+    let dir = create_temp_dir("append-mode-delete");
+    let store_dir = dir.path().to_str().unwrap();
+    let tester = FileTesterBase::with_region(create_region_for_append(store_dir).await);
+    tester.put(&[(1000, Some(1))]).await;
+
+    let mut batch = tests::new_write_batch_for_test(false);
+    let keys = tests::new_delete_data(&[TimestampMillisecond::from(1000)]);
+    batch.delete(keys).unwrap();
+    let err = tester
+        .region
+        .write(&tester.write_ctx, batch)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::DeleteInAppendMode { .. }), "{err:?}");
+
+    assert_eq!(vec![(1000, Some(1))], tester.full_scan().await);
+}
@@ -19,6 +19,7 @@ use common_test_util::temp_dir::create_temp_dir;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use store_api::storage::{OpenOptions, SequenceNumber, WriteResponse};
 
+use crate::config::MemtableType;
 use crate::error::Result;
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::RegionImpl;
@@ -31,10 +32,13 @@ async fn create_region_for_basic(
     region_name: &str,
     store_dir: &str,
     enable_version_column: bool,
+    memtable_type: MemtableType,
 ) -> RegionImpl<RaftEngineLogStore> {
     let metadata = tests::new_metadata(region_name, enable_version_column);
 
-    let store_config = config_util::new_store_config(region_name, store_dir).await;
+    let store_config =
+        config_util::new_store_config_with_memtable_type(region_name, store_dir, memtable_type)
+            .await;
 
     RegionImpl::create(metadata, store_config).await.unwrap()
 }
@@ -43,16 +47,26 @@ async fn create_region_for_basic(
 struct Tester {
     region_name: String,
     store_dir: String,
+    memtable_type: MemtableType,
     base: Option<FileTesterBase>,
 }
 
 impl Tester {
     async fn new(region_name: &str, store_dir: &str) -> Tester {
-        let region = create_region_for_basic(region_name, store_dir, false).await;
+        Self::with_memtable_type(region_name, store_dir, MemtableType::default()).await
+    }
+
+    async fn with_memtable_type(
+        region_name: &str,
+        store_dir: &str,
+        memtable_type: MemtableType,
+    ) -> Tester {
+        let region = create_region_for_basic(region_name, store_dir, false, memtable_type).await;
 
         Tester {
             region_name: region_name.to_string(),
             store_dir: store_dir.to_string(),
+            memtable_type,
             base: Some(FileTesterBase::with_region(region)),
         }
     }
@@ -61,6 +75,7 @@ impl Tester {
         Tester {
             region_name: region_name.to_string(),
             store_dir: store_dir.to_string(),
+            memtable_type: MemtableType::default(),
             base: None,
         }
     }
@@ -78,7 +93,12 @@ impl Tester {
 
         self.base = None;
         // Reopen the region.
-        let store_config = config_util::new_store_config(&self.region_name, &self.store_dir).await;
+        let store_config = config_util::new_store_config_with_memtable_type(
+            &self.region_name,
+            &self.store_dir,
+            self.memtable_type,
+        )
+        .await;
         let opts = OpenOptions::default();
         let region = RegionImpl::open(self.region_name.clone(), store_config, &opts).await?;
         match region {
@@ -118,11 +138,10 @@ impl Tester {
     }
 }
 
-#[tokio::test]
-async fn test_simple_put_scan() {
+async fn simple_put_scan(memtable_type: MemtableType) {
     let dir = create_temp_dir("put-scan");
     let store_dir = dir.path().to_str().unwrap();
-    let tester = Tester::new(REGION_NAME, store_dir).await;
+    let tester = Tester::with_memtable_type(REGION_NAME, store_dir, memtable_type).await;
 
     let data = vec![
         (1000, Some(100)),
@@ -138,6 +157,16 @@ async fn test_simple_put_scan() {
     assert_eq!(data, output);
 }
 
+#[tokio::test]
+async fn test_simple_put_scan() {
+    simple_put_scan(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_simple_put_scan_time_series() {
+    simple_put_scan(MemtableType::TimeSeries).await;
+}
+
 #[tokio::test]
 async fn test_sequence_increase() {
     let dir = create_temp_dir("sequence");
@@ -153,13 +182,12 @@ async fn test_sequence_increase() {
     }
 }
 
-#[tokio::test]
-async fn test_reopen() {
+async fn reopen(memtable_type: MemtableType) {
     common_telemetry::logging::init_default_ut_logging();
 
     let dir = create_temp_dir("reopen");
     let store_dir = dir.path().to_str().unwrap();
-    let mut tester = Tester::new(REGION_NAME, store_dir).await;
+    let mut tester = Tester::with_memtable_type(REGION_NAME, store_dir, memtable_type).await;
 
     let mut all_data = Vec::new();
     // Reopen region multiple times.
@@ -182,6 +210,16 @@ async fn test_reopen() {
     }
 }
 
+#[tokio::test]
+async fn test_reopen() {
+    reopen(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_reopen_time_series() {
+    reopen(MemtableType::TimeSeries).await;
+}
+
 #[tokio::test]
 async fn test_open_empty() {
     let dir = create_temp_dir("open-empty");
@@ -213,12 +251,11 @@ async fn test_scan_different_batch() {
     }
 }
 
-#[tokio::test]
-async fn test_put_delete_scan() {
+async fn put_delete_scan(memtable_type: MemtableType) {
     common_telemetry::init_default_ut_logging();
     let dir = create_temp_dir("put-delete-scan");
     let store_dir = dir.path().to_str().unwrap();
-    let mut tester = Tester::new(REGION_NAME, store_dir).await;
+    let mut tester = Tester::with_memtable_type(REGION_NAME, store_dir, memtable_type).await;
 
     let data = vec![
         (1000, Some(100)),
@@ -244,6 +281,16 @@ async fn test_put_delete_scan() {
     assert_eq!(expect, output);
 }
 
+#[tokio::test]
+async fn test_put_delete_scan() {
+    put_delete_scan(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_put_delete_scan_time_series() {
+    put_delete_scan(MemtableType::TimeSeries).await;
+}
+
 #[tokio::test]
 async fn test_put_delete_absent_key() {
     let dir = create_temp_dir("put-delete-scan");
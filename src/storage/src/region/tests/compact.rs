@@ -23,12 +23,12 @@ use common_test_util::temp_dir::create_temp_dir;
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use object_store::services::{Fs, S3};
 use object_store::ObjectStore;
-use store_api::storage::{FlushContext, Region, WriteResponse};
+use store_api::storage::{FlushContext, Region, ScanRequest, WriteResponse};
 use tokio::sync::Notify;
 
 use crate::compaction::{CompactionHandler, SimplePicker};
 use crate::config::EngineConfig;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::file_purger::{FilePurgeHandler, FilePurgeRequest};
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::{CompactContext, FlushStrategyRef, RegionImpl};
@@ -280,3 +280,64 @@ async fn test_compact_during_read_on_s3() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_read_as_of_after_compaction() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("compact_read_as_of");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let tester = CompactionTester::new(
+        store_dir,
+        EngineConfig {
+            max_files_in_l0: 100,
+            ..Default::default()
+        },
+        Arc::new(FlushSwitch::default()),
+        None,
+    )
+    .await;
+
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    let sequence1 = tester.base().committed_sequence();
+    tester.flush(None).await;
+    tester.put(&[(2000, Some(201))]).await;
+    let sequence2 = tester.base().committed_sequence();
+    tester.flush(None).await;
+
+    let scan_at_sequence = |sequence| ScanRequest {
+        sequence: Some(sequence),
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(200))],
+        tester
+            .base()
+            .scan(scan_at_sequence(sequence1))
+            .await
+            .unwrap()
+    );
+
+    // Compaction only keeps the latest row of each key, so reads before the
+    // flushed sequence are rejected.
+    tester.compact().await;
+    let err = tester
+        .base()
+        .scan(scan_at_sequence(sequence1))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::SequenceNotReadable { .. }),
+        "unexpected error: {err}"
+    );
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(201))],
+        tester
+            .base()
+            .scan(scan_at_sequence(sequence2))
+            .await
+            .unwrap()
+    );
+
+    tester.clean_up().await;
+}
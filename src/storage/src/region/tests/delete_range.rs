@@ -0,0 +1,182 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Region range tombstone tests.
+
+use std::sync::Arc;
+
+use common_test_util::temp_dir::create_temp_dir;
+use common_time::Timestamp;
+use log_store::raft_engine::log_store::RaftEngineLogStore;
+use store_api::storage::{CompactionStrategy, DeleteRange, FlushContext, OpenOptions, Region};
+
+use crate::compaction::{CompactionHandler, SimplePicker};
+use crate::config::EngineConfig;
+use crate::region::tests::{self, FileTesterBase};
+use crate::region::{CompactContext, RegionImpl, StoreConfig};
+use crate::scheduler::{LocalScheduler, SchedulerConfig};
+use crate::test_util::config_util;
+use crate::test_util::flush_switch::FlushSwitch;
+
+const REGION_NAME: &str = "region-delete-range-0";
+
+/// Returns the store config that compacts the region by TWCS in one hour windows.
+async fn new_store_config(store_dir: &str) -> StoreConfig<RaftEngineLogStore> {
+    let mut store_config = config_util::new_store_config(REGION_NAME, store_dir).await;
+    // Disable auto-flush and auto-compaction.
+    store_config.flush_strategy = Arc::new(FlushSwitch::default());
+    store_config.engine_config = Arc::new(EngineConfig {
+        max_files_in_l0: 100,
+        ..Default::default()
+    });
+    let handler = CompactionHandler::new(SimplePicker::default());
+    store_config.compaction_scheduler =
+        Arc::new(LocalScheduler::new(SchedulerConfig::default(), handler));
+    store_config.compaction_time_window = Some(3600);
+    store_config.compaction_strategy = CompactionStrategy::Twcs {
+        max_files_in_window: 1,
+    };
+
+    store_config
+}
+
+/// Tester for deleting rows by range tombstones.
+struct DeleteRangeTester {
+    base: Option<FileTesterBase>,
+    store_dir: String,
+}
+
+impl DeleteRangeTester {
+    async fn new(store_dir: &str) -> DeleteRangeTester {
+        let metadata = tests::new_metadata(REGION_NAME, false);
+        let store_config = new_store_config(store_dir).await;
+        let region = RegionImpl::create(metadata, store_config).await.unwrap();
+
+        DeleteRangeTester {
+            base: Some(FileTesterBase::with_region(region)),
+            store_dir: store_dir.to_string(),
+        }
+    }
+
+    async fn reopen(&mut self) {
+        // Close the old region.
+        if let Some(base) = self.base.as_ref() {
+            base.close().await;
+        }
+        self.base = None;
+        // Reopen the region.
+        let store_config = new_store_config(&self.store_dir).await;
+        let opts = OpenOptions::default();
+        let region = RegionImpl::open(REGION_NAME.to_string(), store_config, &opts)
+            .await
+            .unwrap()
+            .unwrap();
+        self.base = Some(FileTesterBase::with_region(region));
+    }
+
+    #[inline]
+    fn base(&self) -> &FileTesterBase {
+        self.base.as_ref().unwrap()
+    }
+
+    async fn put(&self, keys: &[i64]) {
+        let data: Vec<_> = keys.iter().map(|key| (*key, Some(*key))).collect();
+        self.base().put(&data).await;
+    }
+
+    async fn full_scan_keys(&self) -> Vec<i64> {
+        self.base()
+            .full_scan()
+            .await
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    async fn flush(&self) {
+        self.base()
+            .region
+            .flush(&FlushContext::default())
+            .await
+            .unwrap();
+    }
+
+    async fn compact(&self) {
+        // Compacts files in level 0 and windows of level 1 that have too many files.
+        self.base()
+            .region
+            .compact(CompactContext {
+                wait: true,
+                max_files_in_l0: 0,
+            })
+            .await
+            .unwrap();
+    }
+
+    fn num_range_tombstones(&self) -> usize {
+        let version = self.base().region.inner.version_control().current();
+        version.range_tombstones().len()
+    }
+}
+
+#[tokio::test]
+async fn test_delete_range_until_compacted() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("delete-range");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let mut tester = DeleteRangeTester::new(store_dir).await;
+    // Level 1 has one file in each window after compaction.
+    tester.put(&[0, 1000, 3_600_000]).await;
+    tester.flush().await;
+    tester.compact().await;
+
+    // Deletes rows since 1000 without an end.
+    tester
+        .base()
+        .delete_range(DeleteRange {
+            start: Some(Timestamp::new_millisecond(1000)),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(vec![0], tester.full_scan_keys().await);
+    // The tombstone is replayed from the WAL.
+    tester.reopen().await;
+    assert_eq!(vec![0], tester.full_scan_keys().await);
+
+    // Rows written after the tombstone are visible.
+    tester.put(&[2000, 3_601_000]).await;
+    tester.flush().await;
+    // The tombstone is persisted to the manifest.
+    tester.reopen().await;
+    assert_eq!(vec![0, 2000, 3_601_000], tester.full_scan_keys().await);
+    assert_eq!(1, tester.num_range_tombstones());
+
+    // Files in level 1 written before the tombstone aren't compacted yet.
+    tester.compact().await;
+    assert_eq!(1, tester.num_range_tombstones());
+    assert_eq!(vec![0, 2000, 3_601_000], tester.full_scan_keys().await);
+
+    // Both windows of level 1 are compacted, so no SST contains rows the tombstone deletes.
+    tester.put(&[500, 3_602_000]).await;
+    tester.flush().await;
+    tester.compact().await;
+    assert_eq!(0, tester.num_range_tombstones());
+    let expect = vec![0, 500, 2000, 3_601_000, 3_602_000];
+    assert_eq!(expect, tester.full_scan_keys().await);
+
+    tester.reopen().await;
+    assert_eq!(0, tester.num_range_tombstones());
+    assert_eq!(expect, tester.full_scan_keys().await);
+}
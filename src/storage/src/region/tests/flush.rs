@@ -16,11 +16,21 @@
 
 use std::sync::Arc;
 
+use common_query::logical_plan::Expr;
 use common_test_util::temp_dir::create_temp_dir;
+use common_time::util::current_time_millis;
+use common_time::Timestamp;
+use datafusion_expr::{col, lit};
 use log_store::raft_engine::log_store::RaftEngineLogStore;
-use store_api::storage::{FlushContext, OpenOptions, Region, WriteResponse};
+use object_store::services::Fs;
+use object_store::ObjectStore;
+use store_api::storage::{
+    AlterOptions, FlushContext, OpenOptions, Region, ScanRequest, WriteResponse,
+};
 
+use crate::config::MemtableType;
 use crate::engine;
+use crate::error::Result;
 use crate::flush::FlushStrategyRef;
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::RegionImpl;
@@ -34,10 +44,13 @@ async fn create_region_for_flush(
     store_dir: &str,
     enable_version_column: bool,
     flush_strategy: FlushStrategyRef,
+    memtable_type: MemtableType,
 ) -> RegionImpl<RaftEngineLogStore> {
     let metadata = tests::new_metadata(REGION_NAME, enable_version_column);
 
-    let mut store_config = config_util::new_store_config(REGION_NAME, store_dir).await;
+    let mut store_config =
+        config_util::new_store_config_with_memtable_type(REGION_NAME, store_dir, memtable_type)
+            .await;
     store_config.flush_strategy = flush_strategy;
 
     RegionImpl::create(metadata, store_config).await.unwrap()
@@ -48,16 +61,27 @@ struct FlushTester {
     base: Option<FileTesterBase>,
     store_dir: String,
     flush_strategy: FlushStrategyRef,
+    memtable_type: MemtableType,
 }
 
 impl FlushTester {
     async fn new(store_dir: &str, flush_strategy: FlushStrategyRef) -> FlushTester {
-        let region = create_region_for_flush(store_dir, false, flush_strategy.clone()).await;
+        Self::with_memtable_type(store_dir, flush_strategy, MemtableType::default()).await
+    }
+
+    async fn with_memtable_type(
+        store_dir: &str,
+        flush_strategy: FlushStrategyRef,
+        memtable_type: MemtableType,
+    ) -> FlushTester {
+        let region =
+            create_region_for_flush(store_dir, false, flush_strategy.clone(), memtable_type).await;
 
         FlushTester {
             base: Some(FileTesterBase::with_region(region)),
             store_dir: store_dir.to_string(),
             flush_strategy: flush_strategy.clone(),
+            memtable_type,
         }
     }
 
@@ -68,7 +92,12 @@ impl FlushTester {
         }
         self.base = None;
         // Reopen the region.
-        let mut store_config = config_util::new_store_config(REGION_NAME, &self.store_dir).await;
+        let mut store_config = config_util::new_store_config_with_memtable_type(
+            REGION_NAME,
+            &self.store_dir,
+            self.memtable_type,
+        )
+        .await;
         store_config.flush_strategy = self.flush_strategy.clone();
         let opts = OpenOptions::default();
         let region = RegionImpl::open(REGION_NAME.to_string(), store_config, &opts)
@@ -91,6 +120,10 @@ impl FlushTester {
         self.base().full_scan().await
     }
 
+    async fn get(&self, key: i64) -> Option<(i64, Option<i64>)> {
+        self.base().get(key).await
+    }
+
     async fn flush(&self, wait: Option<bool>) {
         let ctx = wait.map(|wait| FlushContext { wait }).unwrap_or_default();
         self.base().region.flush(&ctx).await.unwrap();
@@ -125,6 +158,38 @@ async fn test_flush_and_stall() {
     assert!(has_parquet_file(&sst_dir));
 }
 
+#[tokio::test]
+async fn test_alter_write_buffer_size() {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = create_temp_dir("alter-write-buffer-size");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::new(store_dir, flush_switch).await;
+
+    let data = [(1000, Some(100))];
+    tester.put(&data).await;
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(!has_parquet_file(&sst_dir));
+
+    // A tiny write buffer makes the region flush on next write.
+    tester
+        .base()
+        .region
+        .alter_options(AlterOptions {
+            write_buffer_size: Some(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    tester.put(&data).await;
+    // Put another data to wait until the last flush done.
+    tester.put(&data).await;
+
+    assert!(has_parquet_file(&sst_dir));
+}
+
 #[tokio::test]
 async fn test_manual_flush() {
     common_telemetry::init_default_ut_logging();
@@ -176,15 +241,14 @@ async fn test_flush_empty() {
     assert_eq!(expect, output);
 }
 
-#[tokio::test]
-async fn test_read_after_flush() {
+async fn read_after_flush(memtable_type: MemtableType) {
     common_telemetry::init_default_ut_logging();
 
     let dir = create_temp_dir("read-flush");
     let store_dir = dir.path().to_str().unwrap();
 
     let flush_switch = Arc::new(FlushSwitch::default());
-    let tester = FlushTester::new(store_dir, flush_switch.clone()).await;
+    let tester = FlushTester::with_memtable_type(store_dir, flush_switch, memtable_type).await;
 
     // Put elements so we have content to flush.
     tester.put(&[(1000, Some(100))]).await;
@@ -211,12 +275,21 @@ async fn test_read_after_flush() {
 }
 
 #[tokio::test]
-async fn test_merge_read_after_flush() {
+async fn test_read_after_flush() {
+    read_after_flush(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_read_after_flush_time_series() {
+    read_after_flush(MemtableType::TimeSeries).await;
+}
+
+async fn merge_read_after_flush(memtable_type: MemtableType) {
     let dir = create_temp_dir("merge-read-flush");
     let store_dir = dir.path().to_str().unwrap();
 
     let flush_switch = Arc::new(FlushSwitch::default());
-    let tester = FlushTester::new(store_dir, flush_switch.clone()).await;
+    let tester = FlushTester::with_memtable_type(store_dir, flush_switch, memtable_type).await;
 
     // Put elements so we have content to flush (In SST1).
     tester.put(&[(3000, Some(300))]).await;
@@ -251,3 +324,236 @@ async fn test_merge_read_after_flush() {
     let output = tester.full_scan().await;
     assert_eq!(expect, output);
 }
+
+#[tokio::test]
+async fn test_merge_read_after_flush() {
+    merge_read_after_flush(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_merge_read_after_flush_time_series() {
+    merge_read_after_flush(MemtableType::TimeSeries).await;
+}
+
+#[tokio::test]
+async fn test_filter_field_after_overwrite() {
+    let dir = create_temp_dir("filter-field-overwrite");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::new(store_dir, flush_switch.clone()).await;
+
+    // In SST1.
+    tester.put(&[(1000, Some(100)), (2000, Some(100))]).await;
+    tester.flush(None).await;
+
+    // Overwrite and delete rows (In SST2).
+    tester.put(&[(1000, Some(101))]).await;
+    tester.base().delete(&[2000]).await;
+    tester.flush(None).await;
+
+    // SST2 doesn't contain the filtered value, but it can't be pruned, otherwise the old
+    // values in SST1 come back.
+    let request = ScanRequest {
+        filters: vec![Expr::from(col("v0").eq(lit(100i64)))],
+        ..Default::default()
+    };
+    let output = tester.base().scan(request).await.unwrap();
+    assert_eq!(vec![(1000, Some(101))], output);
+}
+
+async fn get_after_flush(memtable_type: MemtableType) {
+    common_telemetry::init_default_ut_logging();
+
+    let dir = create_temp_dir("get-after-flush");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let tester = FlushTester::with_memtable_type(store_dir, flush_switch, memtable_type).await;
+
+    // In SST1.
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    tester.flush(None).await;
+    // In SST2.
+    tester.put(&[(2000, Some(201)), (3000, Some(300))]).await;
+    tester.flush(None).await;
+    // In memtable.
+    tester.put(&[(3000, Some(301))]).await;
+    tester.base().delete(&[1000]).await;
+
+    assert_eq!(None, tester.get(1000).await);
+    assert_eq!(Some((2000, Some(201))), tester.get(2000).await);
+    assert_eq!(Some((3000, Some(301))), tester.get(3000).await);
+    assert_eq!(None, tester.get(4000).await);
+
+    // Reopen
+    let mut tester = tester;
+    tester.reopen().await;
+    tester.flush(None).await;
+
+    assert_eq!(None, tester.get(1000).await);
+    assert_eq!(Some((2000, Some(201))), tester.get(2000).await);
+    assert_eq!(Some((3000, Some(301))), tester.get(3000).await);
+}
+
+#[tokio::test]
+async fn test_get_after_flush() {
+    get_after_flush(MemtableType::BTree).await;
+}
+
+#[tokio::test]
+async fn test_get_after_flush_time_series() {
+    get_after_flush(MemtableType::TimeSeries).await;
+}
+
+#[tokio::test]
+async fn test_truncate() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("truncate");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let mut tester = FlushTester::new(store_dir, flush_switch).await;
+
+    // Rows in both SSTs and memtables.
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    tester.flush(None).await;
+    tester.put(&[(3000, Some(300))]).await;
+    assert_eq!(3, tester.full_scan().await.len());
+
+    tester.base().region.truncate().await.unwrap();
+    assert!(tester.full_scan().await.is_empty());
+    assert_eq!(None, tester.get(1000).await);
+    assert_eq!(0, tester.base().region.disk_usage_bytes());
+
+    // The table is still writable.
+    tester.put(&[(4000, Some(400))]).await;
+    assert_eq!(vec![(4000, Some(400))], tester.full_scan().await);
+
+    // Truncated rows are not replayed from the WAL.
+    tester.reopen().await;
+    assert_eq!(vec![(4000, Some(400))], tester.full_scan().await);
+}
+
+/// Opens the region read-only at the snapshot. The region uses a log store under `log_dir`
+/// since the log store under `store_dir` is still used by the region opened for write.
+async fn open_at_snapshot(
+    store_dir: &str,
+    log_dir: &str,
+    snapshot: &str,
+) -> Result<RegionImpl<RaftEngineLogStore>> {
+    let mut builder = Fs::default();
+    builder.root(store_dir);
+    let object_store = ObjectStore::new(builder).unwrap().finish();
+    let store_config =
+        config_util::new_store_config_with_object_store(REGION_NAME, log_dir, object_store).await;
+    let opts = OpenOptions {
+        snapshot: Some(snapshot.to_string()),
+        ..Default::default()
+    };
+
+    RegionImpl::open(REGION_NAME.to_string(), store_config, &opts)
+        .await
+        .map(Option::unwrap)
+}
+
+#[tokio::test]
+async fn test_snapshot() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("snapshot");
+    let store_dir = dir.path().to_str().unwrap();
+    let log_dir = create_temp_dir("snapshot-log");
+    let log_dir = log_dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let mut tester = FlushTester::new(store_dir, flush_switch).await;
+
+    // Rows in memtables are flushed before saving the snapshot.
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    let region = &tester.base().region;
+    region.save_snapshot("s1").await.unwrap();
+    assert!(region.save_snapshot("s1").await.is_err());
+    assert!(region.save_snapshot("../s2").await.is_err());
+
+    // Truncation removes SSTs in the snapshot from the region.
+    tester.put(&[(3000, Some(300))]).await;
+    tester.base().region.truncate().await.unwrap();
+    tester.reopen().await;
+    assert!(tester.full_scan().await.is_empty());
+
+    let snapshot_region = open_at_snapshot(store_dir, log_dir, "s1").await.unwrap();
+    let snapshot_base = FileTesterBase::with_region(snapshot_region);
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(200))],
+        snapshot_base.full_scan().await
+    );
+    assert!(snapshot_base.try_put(&[(4000, Some(400))]).await.is_err());
+    assert!(snapshot_base
+        .region
+        .flush(&FlushContext::default())
+        .await
+        .is_err());
+
+    let region = &tester.base().region;
+    assert!(region.drop_snapshot("s1").await.unwrap());
+    assert!(!region.drop_snapshot("s1").await.unwrap());
+    assert!(open_at_snapshot(store_dir, log_dir, "s1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_read_as_of() {
+    common_telemetry::init_default_ut_logging();
+    let dir = create_temp_dir("read-as-of");
+    let store_dir = dir.path().to_str().unwrap();
+
+    let flush_switch = Arc::new(FlushSwitch::default());
+    let mut tester = FlushTester::new(store_dir, flush_switch).await;
+
+    // Overwritten rows are in different SSTs and the memtable.
+    tester.put(&[(1000, Some(100)), (2000, Some(200))]).await;
+    let sequence1 = tester.base().committed_sequence();
+    tester.flush(None).await;
+    tester.put(&[(2000, Some(201))]).await;
+    let sequence2 = tester.base().committed_sequence();
+    tester.put(&[(3000, Some(300))]).await;
+    tester.flush(None).await;
+    tester.put(&[(1000, Some(101))]).await;
+
+    let scan_at_sequence = |sequence| ScanRequest {
+        sequence: Some(sequence),
+        ..Default::default()
+    };
+    let scan_at_time = |millis| ScanRequest {
+        commit_time: Some(Timestamp::new_millisecond(millis)),
+        ..Default::default()
+    };
+    let base = tester.base();
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(200))],
+        base.scan(scan_at_sequence(sequence1)).await.unwrap()
+    );
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(201))],
+        base.scan(scan_at_sequence(sequence2)).await.unwrap()
+    );
+    // Nothing is committed before the region is created.
+    assert!(base.scan(scan_at_time(0)).await.unwrap().is_empty());
+    let future = current_time_millis() + 3_600_000;
+    assert_eq!(
+        tester.full_scan().await,
+        base.scan(scan_at_time(future)).await.unwrap()
+    );
+
+    // Commit times of flushed data are lost after reopen.
+    tester.reopen().await;
+    let base = tester.base();
+    assert_eq!(
+        vec![(1000, Some(100)), (2000, Some(201))],
+        base.scan(scan_at_sequence(sequence2)).await.unwrap()
+    );
+    assert!(base.scan(scan_at_time(0)).await.is_err());
+    assert_eq!(
+        vec![(1000, Some(101)), (2000, Some(201)), (3000, Some(300))],
+        base.scan(scan_at_time(future)).await.unwrap()
+    );
+}
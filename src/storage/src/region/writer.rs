@@ -19,11 +19,15 @@ use common_base::readable_size::ReadableSize;
 use common_error::prelude::BoxedError;
 use common_telemetry::tracing::log::{debug, info};
 use common_telemetry::{error, logging};
+use common_time::util::current_time_millis;
 use futures::TryStreamExt;
 use snafu::{ensure, ResultExt};
 use store_api::logstore::LogStore;
 use store_api::manifest::{Manifest, ManifestVersion, MetaAction};
-use store_api::storage::{AlterRequest, FlushContext, SequenceNumber, WriteContext, WriteResponse};
+use store_api::storage::{
+    AlterRequest, CompactionStrategy, FlushContext, SequenceNumber, SstCompression, WriteContext,
+    WriteResponse,
+};
 use tokio::sync::{oneshot, Mutex};
 
 use crate::background::JobHandle;
@@ -32,7 +36,8 @@ use crate::config::EngineConfig;
 use crate::error::{self, Result};
 use crate::flush::{FlushCallback, FlushJob, FlushSchedulerRef, FlushStrategyRef};
 use crate::manifest::action::{
-    RawRegionMetadata, RegionChange, RegionEdit, RegionMetaAction, RegionMetaActionList,
+    RawRegionMetadata, RegionChange, RegionEdit, RegionManifestData, RegionMetaAction,
+    RegionMetaActionList, RegionSnapshot, RegionVersion,
 };
 use crate::memtable::{Inserter, MemtableBuilderRef, MemtableId, MemtableRef};
 use crate::metadata::RegionMetadataRef;
@@ -41,10 +46,11 @@ use crate::region::{
     CompactContext, RecoverdMetadata, RecoveredMetadataMap, RegionManifest, SharedDataRef,
 };
 use crate::schema::compat::CompatWrite;
-use crate::sst::AccessLayerRef;
+use crate::sst::{AccessLayerRef, FileHandle, PinnedFilesRef};
+use crate::tombstone::RangeTombstone;
 use crate::version::{VersionControl, VersionControlRef, VersionEdit, VersionRef};
 use crate::wal::Wal;
-use crate::write_batch::WriteBatch;
+use crate::write_batch::{Payload, WriteBatch};
 
 pub type RegionWriterRef = Arc<RegionWriter>;
 
@@ -69,6 +75,8 @@ impl RegionWriter {
         config: Arc<EngineConfig>,
         ttl: Option<Duration>,
         compaction_time_window: Option<i64>,
+        compaction_strategy: CompactionStrategy,
+        sst_compression: SstCompression,
     ) -> RegionWriter {
         RegionWriter {
             inner: Mutex::new(WriterInner::new(
@@ -76,6 +84,8 @@ impl RegionWriter {
                 config,
                 ttl,
                 compaction_time_window,
+                compaction_strategy,
+                sst_compression,
             )),
             version_mutex: Mutex::new(()),
         }
@@ -126,6 +136,18 @@ impl RegionWriter {
         let version_control = &shared.version_control;
         let prev_version = version_control.current_manifest_version();
 
+        // A truncation may remove input files of a running compaction, applying its edit would
+        // add rows in the truncated files back.
+        let current = version_control.current();
+        ensure!(
+            edit.files_to_remove
+                .iter()
+                .all(|file| current.ssts().contains_file(file)),
+            error::StaleRegionEditSnafu {
+                region: shared.name(),
+            }
+        );
+
         logging::debug!(
             "Write region edit: {:?} to manifest, prev_version: {}.",
             edit,
@@ -134,7 +156,10 @@ impl RegionWriter {
 
         let files_to_add = edit.files_to_add.clone();
         let files_to_remove = edit.files_to_remove.clone();
+        let tombstones_to_add = edit.tombstones_to_add.clone();
+        let tombstones_to_remove = edit.tombstones_to_remove.clone();
         let flushed_sequence = edit.flushed_sequence;
+        let min_readable_sequence = edit.min_readable_sequence;
 
         // Persist the meta action.
         let mut action_list = RegionMetaActionList::with_action(RegionMetaAction::Edit(edit));
@@ -152,11 +177,16 @@ impl RegionWriter {
             flushed_sequence,
             manifest_version,
             max_memtable_id,
+            tombstones_to_add,
+            tombstones_to_remove,
+            min_readable_sequence,
         };
 
         // We could tolerate failure during persisting manifest version to the WAL, since it won't
         // affect how we applying the edit to the version.
         version_control.apply_edit(version_edit);
+        // Flushed memtables are removed from the version.
+        shared.update_write_buffer_usage();
         // TODO(yingwen): We should set the flush handle to `None`, but we can't acquire
         // write lock here.
 
@@ -226,11 +256,210 @@ impl RegionWriter {
             manifest_version,
             new_mutable,
         );
+        alter_ctx.shared.update_write_buffer_usage();
 
         self.persist_manifest_version(alter_ctx.wal, version_control, manifest_version)
             .await
     }
 
+    /// Replaces the ttl, compaction time window and compaction strategy of the region,
+    /// which are used by subsequent compactions, and the compression codec of SST files
+    /// written by subsequent flushes and compactions.
+    pub async fn alter_options(
+        &self,
+        ttl: Option<Duration>,
+        compaction_time_window: Option<i64>,
+        compaction_strategy: CompactionStrategy,
+        sst_compression: SstCompression,
+    ) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        inner.ttl = ttl;
+        inner.compaction_time_window = compaction_time_window;
+        inner.compaction_strategy = compaction_strategy;
+        inner.sst_compression = sst_compression;
+
+        Ok(())
+    }
+
+    /// Truncates the region, removes all SSTs and rows in memtables and the WAL.
+    pub async fn truncate<S: LogStore>(
+        &self,
+        wal: &Wal<S>,
+        shared: &SharedDataRef,
+        manifest: &RegionManifest,
+    ) -> Result<()> {
+        // The write lock rejects writes and flushes during truncation.
+        let mut inner = self.inner.lock().await;
+
+        ensure!(!inner.is_closed(), error::ClosedRegionSnafu);
+
+        // Waits for the pending flush, otherwise it adds SSTs of truncated rows later.
+        if let Some(handle) = inner.flush_handle.take() {
+            handle.join().await?;
+        }
+
+        let _lock = self.version_mutex.lock().await;
+        let version_control = &shared.version_control;
+        let current = version_control.current();
+        let committed_sequence = version_control.committed_sequence();
+
+        let files_to_remove: Vec<_> = current
+            .ssts()
+            .levels()
+            .iter()
+            .flat_map(|level| level.files().map(FileHandle::meta))
+            .collect();
+        let tombstones_to_remove = current.range_tombstones().to_vec();
+        // Rows written before the truncation won't be replayed from the WAL, as they are
+        // treated as flushed.
+        let edit = RegionEdit {
+            region_version: current.metadata().version(),
+            flushed_sequence: Some(committed_sequence),
+            files_to_add: Vec::new(),
+            files_to_remove: files_to_remove.clone(),
+            tombstones_to_add: Vec::new(),
+            tombstones_to_remove: tombstones_to_remove.clone(),
+            min_readable_sequence: None,
+        };
+        logging::info!(
+            "Truncate region {}, committed_sequence: {}, files_to_remove: {}",
+            shared.name(),
+            committed_sequence,
+            files_to_remove.len()
+        );
+
+        let mut action_list = RegionMetaActionList::with_action(RegionMetaAction::Edit(edit));
+        action_list.set_prev_version(version_control.current_manifest_version());
+        let manifest_version = manifest.update(action_list).await?;
+        manifest.set_flushed_manifest_version(manifest_version);
+
+        // Removed SSTs are deleted by the file purger once no one reads them.
+        let version_edit = VersionEdit {
+            files_to_add: Vec::new(),
+            files_to_remove,
+            flushed_sequence: Some(committed_sequence),
+            manifest_version,
+            max_memtable_id: None,
+            tombstones_to_add: Vec::new(),
+            tombstones_to_remove,
+            min_readable_sequence: None,
+        };
+        let new_mutable = inner.alloc_memtable(version_control);
+        version_control.truncate(version_edit, new_mutable);
+        shared.update_write_buffer_usage();
+
+        wal.obsolete(committed_sequence).await?;
+
+        self.persist_manifest_version(wal, version_control, manifest_version)
+            .await
+    }
+
+    /// Saves the current version of the region as a named snapshot and pins its SSTs.
+    pub async fn save_snapshot(
+        &self,
+        name: &str,
+        shared: &SharedDataRef,
+        manifest: &RegionManifest,
+        pinned_files: &PinnedFilesRef,
+    ) -> Result<()> {
+        ensure!(
+            !self.inner.lock().await.is_closed(),
+            error::ClosedRegionSnafu
+        );
+
+        // Compactions can't remove SSTs of the version until they are pinned.
+        let _lock = self.version_mutex.lock().await;
+        ensure!(
+            manifest.load_snapshot(name).await?.is_none(),
+            error::SnapshotExistsSnafu {
+                region: shared.name(),
+                name,
+            }
+        );
+
+        let current = shared.version_control.current();
+        let files = current
+            .ssts()
+            .levels()
+            .iter()
+            .flat_map(|level| level.files().map(|file| (file.file_id(), file.meta())))
+            .collect();
+        let snapshot = RegionSnapshot {
+            name: name.to_string(),
+            data: RegionManifestData {
+                committed_sequence: current.flushed_sequence(),
+                metadata: current.metadata().as_ref().into(),
+                version: Some(RegionVersion {
+                    manifest_version: current.manifest_version(),
+                    flushed_sequence: Some(current.flushed_sequence()),
+                    files,
+                    range_tombstones: current.range_tombstones().to_vec(),
+                    min_readable_sequence: Some(current.min_readable_sequence()),
+                }),
+            },
+        };
+        manifest.save_snapshot(name, &snapshot.encode()?).await?;
+        pinned_files.set(manifest.snapshot_file_ids().await?);
+
+        logging::info!(
+            "Saved snapshot {} of region {}, manifest_version: {}, flushed_sequence: {}",
+            name,
+            shared.name(),
+            current.manifest_version(),
+            current.flushed_sequence()
+        );
+
+        Ok(())
+    }
+
+    /// Drops the named snapshot and purges its SSTs that are no longer used by the region or
+    /// other snapshots. Returns false if the snapshot doesn't exist.
+    pub async fn drop_snapshot(
+        &self,
+        name: &str,
+        shared: &SharedDataRef,
+        manifest: &RegionManifest,
+        pinned_files: &PinnedFilesRef,
+    ) -> Result<bool> {
+        ensure!(
+            !self.inner.lock().await.is_closed(),
+            error::ClosedRegionSnafu
+        );
+
+        // Compactions can't remove SSTs of the version while we are checking them.
+        let _lock = self.version_mutex.lock().await;
+        let Some(bytes) = manifest.load_snapshot(name).await? else { return Ok(false) };
+        let snapshot = RegionSnapshot::decode(&bytes)?;
+        manifest.delete_snapshot(name).await?;
+
+        let still_pinned = manifest.snapshot_file_ids().await?;
+        pinned_files.set(still_pinned.clone());
+
+        let current = shared.version_control.current();
+        let ssts = current.ssts();
+        let files_to_purge: Vec<_> = snapshot
+            .data
+            .version
+            .into_iter()
+            .flat_map(|version| version.files.into_values())
+            .filter(|file| !ssts.contains_file(file) && !still_pinned.contains(&file.file_id))
+            .collect();
+
+        logging::info!(
+            "Dropped snapshot {} of region {}, files_to_purge: {}",
+            name,
+            shared.name(),
+            files_to_purge.len()
+        );
+
+        ssts.purge_files(files_to_purge.into_iter());
+
+        Ok(true)
+    }
+
     /// Allocate a sequence and persist the manifest version using that sequence to the wal.
     ///
     /// This method should be protected by the `version_mutex`.
@@ -368,6 +597,8 @@ struct WriterInner {
     engine_config: Arc<EngineConfig>,
     ttl: Option<Duration>,
     compaction_time_window: Option<i64>,
+    compaction_strategy: CompactionStrategy,
+    sst_compression: SstCompression,
 }
 
 impl WriterInner {
@@ -376,6 +607,8 @@ impl WriterInner {
         engine_config: Arc<EngineConfig>,
         ttl: Option<Duration>,
         compaction_time_window: Option<i64>,
+        compaction_strategy: CompactionStrategy,
+        sst_compression: SstCompression,
     ) -> WriterInner {
         WriterInner {
             memtable_builder,
@@ -384,6 +617,8 @@ impl WriterInner {
             closed: false,
             ttl,
             compaction_time_window,
+            compaction_strategy,
+            sst_compression,
         }
     }
 
@@ -415,7 +650,11 @@ impl WriterInner {
         let next_sequence = committed_sequence + 1;
 
         let version = version_control.current();
-        let wal_header = WalHeader::with_last_manifest_version(version.manifest_version());
+        let commit_time = current_time_millis();
+        let wal_header = WalHeader {
+            commit_time,
+            ..WalHeader::with_last_manifest_version(version.manifest_version())
+        };
         writer_ctx
             .wal
             .write_to_wal(next_sequence, wal_header, Some(request.payload()))
@@ -424,10 +663,15 @@ impl WriterInner {
         // Insert batch into memtable.
         let mut inserter = Inserter::new(next_sequence);
         inserter.insert_memtable(request.payload(), version.mutable_memtable())?;
+        add_range_tombstones(version_control, next_sequence, request.payload());
 
         // Update committed_sequence to make current batch visible. The `&mut self` of WriterInner
         // guarantees the writer is exclusive.
         version_control.set_committed_sequence(next_sequence);
+        version_control
+            .commit_timeline()
+            .record(commit_time, next_sequence);
+        writer_ctx.shared.update_write_buffer_usage();
 
         Ok(WriteResponse {})
     }
@@ -453,7 +697,7 @@ impl WriterInner {
             // Read starts from the first entry after last flushed entry, so the start sequence
             // should be flushed_sequence + 1.
             let mut stream = writer_ctx.wal.read_from_wal(flushed_sequence + 1).await?;
-            while let Some((req_sequence, _header, payload)) = stream.try_next().await? {
+            while let Some((req_sequence, header, payload)) = stream.try_next().await? {
                 while let Some((sequence_before_alter, _)) = next_apply_metadata {
                     // There might be multiple metadata changes to be applied, so a loop is necessary.
                     if req_sequence > sequence_before_alter {
@@ -502,6 +746,15 @@ impl WriterInner {
                     // out of memory during replay, but we need to do it carefully to avoid dead lock.
                     let mut inserter = Inserter::new(last_sequence);
                     inserter.insert_memtable(&payload, version.mutable_memtable())?;
+                    add_range_tombstones(version_control, last_sequence, &payload);
+                    // Entries written by older versions don't have the commit time.
+                    if header.commit_time > 0 {
+                        version_control
+                            .commit_timeline()
+                            .record(header.commit_time, last_sequence);
+                    } else {
+                        version_control.commit_timeline().reset(last_sequence);
+                    }
                 }
             }
 
@@ -623,6 +876,7 @@ impl WriterInner {
         let new_mutable = self.alloc_memtable(version_control);
         // Freeze all mutable memtables so we can flush them later.
         version_control.freeze_mutable(new_mutable);
+        ctx.shared.update_write_buffer_usage();
 
         if let Some(flush_handle) = self.flush_handle.take() {
             // Previous flush job is incomplete, wait util it is finished (write stall).
@@ -653,6 +907,8 @@ impl WriterInner {
             &self.engine_config,
             self.ttl,
             self.compaction_time_window,
+            self.compaction_strategy,
+            self.sst_compression,
         );
 
         let flush_req = FlushJob {
@@ -667,6 +923,7 @@ impl WriterInner {
             manifest: ctx.manifest.clone(),
             on_success: cb,
             engine_config: self.engine_config.clone(),
+            sst_compression: self.sst_compression,
         };
 
         let flush_handle = ctx
@@ -694,6 +951,8 @@ impl WriterInner {
             wal: writer_ctx.wal.clone(),
             ttl: self.ttl,
             compaction_time_window: self.compaction_time_window,
+            compaction_strategy: self.compaction_strategy,
+            sst_compression: self.sst_compression,
             sender: None,
             sst_write_buffer_size,
         };
@@ -742,6 +1001,8 @@ impl WriterInner {
         config: &Arc<EngineConfig>,
         ttl: Option<Duration>,
         compaction_time_window: Option<i64>,
+        compaction_strategy: CompactionStrategy,
+        sst_compression: SstCompression,
     ) -> Option<FlushCallback> {
         let region_id = version.metadata().id();
         let compaction_request = CompactionRequestImpl {
@@ -753,6 +1014,8 @@ impl WriterInner {
             wal: ctx.wal.clone(),
             ttl,
             compaction_time_window,
+            compaction_strategy,
+            sst_compression,
             sender: None,
             sst_write_buffer_size: config.sst_write_buffer_size,
         };
@@ -826,3 +1089,20 @@ impl WriterInner {
         self.closed = true;
     }
 }
+
+/// Adds ranges to delete in the `payload` written at `sequence` to the version.
+fn add_range_tombstones(
+    version_control: &VersionControl,
+    sequence: SequenceNumber,
+    payload: &Payload,
+) {
+    if payload.delete_ranges.is_empty() {
+        return;
+    }
+
+    let tombstones = payload
+        .delete_ranges
+        .iter()
+        .map(|range| RangeTombstone::new(sequence, range.clone()));
+    version_control.add_range_tombstones(tombstones);
+}
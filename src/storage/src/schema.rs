@@ -19,6 +19,7 @@ mod store;
 
 pub use crate::schema::projected::{ProjectedSchema, ProjectedSchemaRef};
 pub use crate::schema::region::{RegionSchema, RegionSchemaRef};
+pub(crate) use crate::schema::store::row_key_end_of;
 pub use crate::schema::store::{StoreSchema, StoreSchemaRef};
 
 #[cfg(test)]
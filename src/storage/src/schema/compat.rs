@@ -15,7 +15,7 @@
 //! Utilities for resolving schema compatibility problems.
 
 use datatypes::arrow::record_batch::RecordBatch;
-use datatypes::schema::SchemaRef;
+use datatypes::schema::{ColumnSchema, SchemaRef};
 use datatypes::vectors::{Helper, VectorRef};
 use snafu::{ensure, OptionExt, ResultExt};
 
@@ -60,8 +60,13 @@ fn is_source_column_compatible(
         return Ok(false);
     }
 
+    // The type of a column might be widened by altering the column, values are
+    // casted to the new type while reading.
     ensure!(
-        source_column.desc.data_type == dest_column.desc.data_type,
+        source_column
+            .desc
+            .data_type
+            .can_widen_to(&dest_column.desc.data_type),
         error::CompatReadSnafu {
             reason: format!(
                 "could not read column {} from {:?} type as {:?} type",
@@ -282,7 +287,7 @@ impl ReadAdapter {
             .zip(column_schemas)
             .map(|(index_opt, column_schema)| {
                 if let Some(idx) = index_opt {
-                    Ok(source[*idx].clone())
+                    cast_column(&source[*idx], column_schema)
                 } else {
                     let vector = column_schema
                         .create_default_vector(num_rows)
@@ -301,12 +306,28 @@ impl ReadAdapter {
     }
 }
 
+/// Casts `vector` to the type of `column_schema` if their types are different.
+pub(crate) fn cast_column(vector: &VectorRef, column_schema: &ColumnSchema) -> Result<VectorRef> {
+    if vector.data_type() == column_schema.data_type {
+        return Ok(vector.clone());
+    }
+
+    vector
+        .cast(&column_schema.data_type)
+        .context(error::CastColumnSnafu {
+            column: &column_schema.name,
+            from: vector.data_type(),
+            to: column_schema.data_type.clone(),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::Schema;
+    use datatypes::vectors::Float64Vector;
     use store_api::storage::ColumnDescriptorBuilder;
 
     use super::*;
@@ -529,6 +550,38 @@ mod tests {
         check_batch_with_null_padding(&batch, &new_batch, &[2]);
     }
 
+    #[test]
+    fn test_compat_widened_column() {
+        // (k0, timestamp, v0, v1) with version 0.
+        let region_schema_old = Arc::new(schema_util::new_region_schema(0, 2));
+
+        let mut descriptor = descriptor_util::desc_with_field_columns(tests::REGION_NAME, 2);
+        // Widen v0 from int64 to float64, the column id is unchanged.
+        descriptor.default_cf.columns[0].data_type = ConcreteDataType::float64_datatype();
+        let metadata: RegionMetadata = descriptor.try_into().unwrap();
+        let region_schema_new = Arc::new(RegionSchema::new(metadata.columns, 1).unwrap());
+
+        let projected_schema = Arc::new(ProjectedSchema::no_projection(region_schema_new));
+        let source_schema = region_schema_old.store_schema().clone();
+        let adapter = ReadAdapter::new(source_schema, projected_schema).unwrap();
+
+        assert_eq!(&[true, true], adapter.source_key_needed());
+        assert_eq!(&[true, true], adapter.source_value_needed());
+
+        let batch = tests::new_batch_with_num_values(2);
+        let expect_v0: VectorRef = Arc::new(Float64Vector::from_slice([0.0, 0.0, 0.0]));
+        for new_batch in [
+            call_batch_from_parts(&adapter, &batch, 2),
+            call_arrow_chunk_to_batch(&adapter, &batch),
+        ] {
+            assert_eq!(batch.num_columns(), new_batch.num_columns());
+            assert_eq!(&expect_v0, new_batch.column(2));
+            for idx in [0, 1, 3, 4, 5] {
+                assert_eq!(batch.column(idx), new_batch.column(idx));
+            }
+        }
+    }
+
     #[inline]
     fn new_column_desc_builder() -> ColumnDescriptorBuilder {
         ColumnDescriptorBuilder::new(10, "test", ConcreteDataType::int32_datatype())
@@ -552,6 +605,27 @@ mod tests {
         assert!(!is_source_column_compatible(&source, &dest).unwrap());
     }
 
+    #[test]
+    fn test_read_column_with_different_type() {
+        let desc = new_column_desc_builder().build().unwrap();
+        let source = ColumnMetadata { cf_id: 1, desc };
+
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::int64_datatype())
+            .build()
+            .unwrap();
+        let dest = ColumnMetadata { cf_id: 1, desc };
+        // Int32 could be widened to Int64.
+        assert!(is_source_column_compatible(&source, &dest).unwrap());
+
+        // Int64 could not be read as Int32.
+        let err = is_source_column_compatible(&dest, &source).unwrap_err();
+        assert!(
+            matches!(err, Error::CompatRead { .. }),
+            "{err:?} is not CompatRead",
+        );
+    }
+
     #[test]
     fn test_nullable_column_read_by_not_null() {
         let desc = new_column_desc_builder().build().unwrap();
@@ -240,6 +240,12 @@ impl TryFrom<ArrowSchema> for StoreSchema {
     }
 }
 
+/// Returns the ending index of row key columns recorded in the metadata of `schema`, or
+/// `None` if the schema is not built by [StoreSchema].
+pub(crate) fn row_key_end_of(schema: &Schema) -> Option<usize> {
+    parse_index_from_metadata(schema.metadata(), ROW_KEY_END_KEY).ok()
+}
+
 fn parse_index_from_metadata(metadata: &HashMap<String, String>, key: &str) -> Result<usize> {
     let value = metadata
         .get(key)
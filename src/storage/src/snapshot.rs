@@ -13,16 +13,27 @@
 // limitations under the License.
 
 use std::cmp;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_query::logical_plan::{DfExpr, Expr};
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use datafusion_common::Column;
+use datatypes::value::Value;
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{
-    GetRequest, GetResponse, ReadContext, ScanRequest, ScanResponse, SchemaRef, SequenceNumber,
-    Snapshot,
+    Chunk, ChunkReader, GetRequest, GetResponse, OpType, ReadContext, ScanRequest, ScanResponse,
+    SchemaRef, SequenceNumber, Snapshot,
 };
 
 use crate::chunk::{ChunkReaderBuilder, ChunkReaderImpl};
-use crate::error::{Error, Result};
+use crate::error::{self, Error, Result};
+use crate::memtable::{IterContext, MemtableRef};
+use crate::read::{Batch, TombstoneFilter};
+use crate::schema::{ProjectedSchema, RegionSchema};
 use crate::sst::AccessLayerRef;
+use crate::timeline::CommitTimelineRef;
 use crate::version::VersionRef;
 
 /// [Snapshot] implementation.
@@ -31,6 +42,9 @@ pub struct SnapshotImpl {
     /// Max sequence number (inclusive) visible to user.
     visible_sequence: SequenceNumber,
     sst_layer: AccessLayerRef,
+    /// Whether to read rows without dedup.
+    append_mode: bool,
+    commit_timeline: CommitTimelineRef,
 }
 
 #[async_trait]
@@ -47,7 +61,8 @@ impl Snapshot for SnapshotImpl {
         ctx: &ReadContext,
         request: ScanRequest,
     ) -> Result<ScanResponse<ChunkReaderImpl>> {
-        let visible_sequence = self.sequence_to_read(request.sequence);
+        let request_sequence = self.sequence_of_request(request.sequence, request.commit_time)?;
+        let visible_sequence = self.sequence_to_read(request_sequence)?;
         let memtable_version = self.version.memtables();
 
         let mutables = memtable_version.mutable_memtable();
@@ -60,6 +75,10 @@ impl Snapshot for SnapshotImpl {
                 .filters(request.filters)
                 .batch_size(ctx.batch_size)
                 .visible_sequence(visible_sequence)
+                .filter_by_sequence(visible_sequence < self.visible_sequence)
+                .dedup(!self.append_mode)
+                .ordered(!self.append_mode)
+                .range_tombstones(self.version.range_tombstones().clone())
                 .pick_memtables(mutables.clone());
 
         for memtable in immutables {
@@ -71,8 +90,65 @@ impl Snapshot for SnapshotImpl {
         Ok(ScanResponse { reader })
     }
 
-    async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
-        unimplemented!()
+    async fn get(&self, ctx: &ReadContext, request: GetRequest) -> Result<GetResponse> {
+        let region_schema = self.version.schema();
+        ensure!(
+            request.row_key.len() == region_schema.num_row_key_columns(),
+            error::InvalidRowKeySnafu {
+                expect: region_schema.num_row_key_columns(),
+                given: request.row_key.len(),
+            }
+        );
+
+        let visible_sequence = self.sequence_to_read(request.sequence)?;
+        let projected_schema = Arc::new(
+            ProjectedSchema::new(region_schema.clone(), request.projection.clone())
+                .context(error::InvalidProjectionSnafu)?,
+        );
+        let iter_ctx = IterContext {
+            visible_sequence,
+            projected_schema: Some(projected_schema.clone()),
+            ..Default::default()
+        };
+
+        // Memtables created before adding a key column have a different row key, so we
+        // can't look up the key in them directly.
+        let memtable_version = self.version.memtables();
+        let (memtables, legacy_memtables): (Vec<_>, Vec<_>) =
+            std::iter::once(memtable_version.mutable_memtable())
+                .chain(memtable_version.immutable_memtables())
+                .cloned()
+                .partition(|memtable| is_same_row_key(&memtable.schema(), region_schema));
+
+        let sequence_index = projected_schema.schema_to_read().sequence_index();
+        let mut latest: Option<Batch> = None;
+        for memtable in &memtables {
+            let Some(batch) = memtable.get(&iter_ctx, &request.row_key)? else { continue };
+            if latest.as_ref().map_or(true, |latest| {
+                latest.column(sequence_index).get(0) < batch.column(sequence_index).get(0)
+            }) {
+                latest = Some(batch);
+            }
+        }
+        // Data in memtables with the latest row key is always newer than data in other
+        // memtables and SSTs, so we can return once we find the key.
+        if let Some(batch) = latest {
+            let op_type_index = projected_schema.schema_to_read().op_type_index();
+            let filter = TombstoneFilter::new(
+                projected_schema.schema_to_read(),
+                self.version.range_tombstones(),
+                visible_sequence,
+            );
+            let deleted = batch.column(op_type_index).get(0) == Value::from(OpType::Delete.as_u8())
+                || filter.is_deleted(&batch, 0);
+            let chunk = (!deleted).then(|| projected_schema.batch_to_chunk(&batch));
+            return Ok(GetResponse { chunk });
+        }
+
+        let chunk = self
+            .get_from_ssts(ctx, request, visible_sequence, legacy_memtables)
+            .await?;
+        Ok(GetResponse { chunk })
     }
 }
 
@@ -81,18 +157,117 @@ impl SnapshotImpl {
         version: VersionRef,
         visible_sequence: SequenceNumber,
         sst_layer: AccessLayerRef,
+        append_mode: bool,
+        commit_timeline: CommitTimelineRef,
     ) -> SnapshotImpl {
         SnapshotImpl {
             version,
             visible_sequence,
             sst_layer,
+            append_mode,
+            commit_timeline,
+        }
+    }
+
+    /// Returns the max sequence to read for the sequence and commit time of the request,
+    /// the smaller one wins if both are set.
+    fn sequence_of_request(
+        &self,
+        sequence: Option<SequenceNumber>,
+        commit_time: Option<Timestamp>,
+    ) -> Result<Option<SequenceNumber>> {
+        let Some(time) = commit_time else { return Ok(sequence) };
+
+        let second = time
+            .convert_to(TimeUnit::Second)
+            .context(error::CommitTimeUnavailableSnafu { time })?
+            .value();
+        let committed = self
+            .commit_timeline
+            .sequence_at(second)
+            .context(error::CommitTimeUnavailableSnafu { time })?;
+        Ok(Some(sequence.map_or(committed, |s| cmp::min(s, committed))))
+    }
+
+    /// Gets the row from SSTs and `memtables`, only reads SSTs whose time range contains the
+    /// timestamp of the key, and row groups whose key range may contain the key.
+    async fn get_from_ssts(
+        &self,
+        ctx: &ReadContext,
+        request: GetRequest,
+        visible_sequence: SequenceNumber,
+        memtables: Vec<MemtableRef>,
+    ) -> Result<Option<Chunk>> {
+        let region_schema = self.version.schema();
+        let mut builder = ChunkReaderBuilder::new(region_schema.clone(), self.sst_layer.clone())
+            .reserve_num_memtables(memtables.len())
+            .projection(request.projection)
+            .filters(row_key_filters(region_schema, &request.row_key))
+            .batch_size(ctx.batch_size)
+            .visible_sequence(visible_sequence)
+            .filter_by_sequence(visible_sequence < self.visible_sequence)
+            .range_tombstones(self.version.range_tombstones().clone());
+        for memtable in memtables {
+            builder = builder.pick_memtables(memtable);
+        }
+        let mut reader = builder.pick_all_ssts(self.version.ssts())?.build().await?;
+
+        // The reader has removed duplicate and deleted rows, and row key columns always
+        // come first in the chunk.
+        while let Some(chunk) = reader.next_chunk().await? {
+            let num_rows = chunk.columns.first().map(|v| v.len()).unwrap_or_default();
+            let found = (0..num_rows).find(|row| {
+                request
+                    .row_key
+                    .iter()
+                    .enumerate()
+                    .all(|(col, value)| chunk.columns[col].get(*row) == *value)
+            });
+            if let Some(row) = found {
+                let columns = chunk.columns.iter().map(|v| v.slice(row, 1)).collect();
+                return Ok(Some(reader.project_chunk(Chunk::new(columns))));
+            }
         }
+
+        Ok(None)
     }
 
-    #[inline]
-    fn sequence_to_read(&self, request_sequence: Option<SequenceNumber>) -> SequenceNumber {
-        request_sequence
+    /// Returns the max sequence to read, fails if compaction has removed rows visible to
+    /// the requested sequence.
+    fn sequence_to_read(&self, request_sequence: Option<SequenceNumber>) -> Result<SequenceNumber> {
+        let sequence = request_sequence
             .map(|s| cmp::min(s, self.visible_sequence))
-            .unwrap_or(self.visible_sequence)
+            .unwrap_or(self.visible_sequence);
+        let min_readable_sequence = self.version.min_readable_sequence();
+        ensure!(
+            sequence >= min_readable_sequence,
+            error::SequenceNotReadableSnafu {
+                sequence,
+                min_readable_sequence,
+            }
+        );
+
+        Ok(sequence)
     }
 }
+
+fn is_same_row_key(left: &RegionSchema, right: &RegionSchema) -> bool {
+    left.row_key_columns()
+        .map(|column| column.id())
+        .eq(right.row_key_columns().map(|column| column.id()))
+}
+
+/// Builds equal filters of the row key to prune SSTs and row groups.
+fn row_key_filters(schema: &RegionSchema, row_key: &[Value]) -> Vec<Expr> {
+    schema
+        .row_key_columns()
+        .zip(row_key)
+        // A null literal never equals to any value, so we skip it.
+        .filter(|(_, value)| !value.is_null())
+        .filter_map(|(column, value)| {
+            let scalar = value.try_to_scalar_value(&column.desc.data_type).ok()?;
+            let column = DfExpr::Column(Column::from_name(column.name()));
+            Some(Expr::from(column.eq(DfExpr::Literal(scalar))))
+        })
+        .collect()
+}
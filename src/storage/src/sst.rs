@@ -12,14 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod bloom_filter;
 pub(crate) mod parquet;
 mod stream_writer;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
 use common_base::readable_size::ReadableSize;
@@ -28,11 +30,12 @@ use common_telemetry::{debug, error};
 use common_time::range::TimestampRange;
 use common_time::Timestamp;
 use datatypes::schema::SchemaRef;
+use datatypes::value::Value;
 use futures_util::StreamExt;
 use object_store::{util, ObjectStore};
 use serde::{Deserialize, Deserializer, Serialize};
 use snafu::{ResultExt, Snafu};
-use store_api::storage::{ChunkReader, RegionId};
+use store_api::storage::{ChunkReader, RegionId, SequenceNumber, SstCompression};
 use table::predicate::Predicate;
 use uuid::Uuid;
 
@@ -51,6 +54,7 @@ pub const MAX_LEVEL: u8 = 2;
 
 pub type Level = u8;
 
+pub use crate::sst::bloom_filter::FileBloomFilter;
 pub use crate::sst::stream_writer::BufferedWriter;
 
 // We only has fixed number of level, so we use array to hold elements. This implementation
@@ -65,6 +69,7 @@ pub struct LevelMetas {
     levels: LevelMetaVec,
     sst_layer: AccessLayerRef,
     file_purger: FilePurgerRef,
+    pinned_files: PinnedFilesRef,
 }
 
 impl std::fmt::Debug for LevelMetas {
@@ -82,9 +87,16 @@ impl LevelMetas {
             levels: new_level_meta_vec(),
             sst_layer,
             file_purger,
+            pinned_files: PinnedFilesRef::default(),
         }
     }
 
+    /// Sets files that won't be purged after they are removed from the region.
+    pub fn with_pinned_files(mut self, pinned_files: PinnedFilesRef) -> LevelMetas {
+        self.pinned_files = pinned_files;
+        self
+    }
+
     /// Returns total level number.
     #[inline]
     pub fn level_num(&self) -> usize {
@@ -115,15 +127,53 @@ impl LevelMetas {
         for file in files_to_remove {
             let level = file.level;
             if let Some(removed_file) = merged.levels[level as usize].remove_file(file.file_id) {
-                removed_file.mark_deleted();
+                if !self.pinned_files.contains(&file.file_id) {
+                    removed_file.mark_deleted();
+                }
             }
         }
         merged
     }
 
+    /// Purges files that were removed from the region while they were pinned.
+    pub fn purge_files(&self, files: impl Iterator<Item = FileMeta>) {
+        for file in files {
+            // The purge is scheduled once the handle is dropped.
+            FileHandle::new(file, self.sst_layer.clone(), self.file_purger.clone()).mark_deleted();
+        }
+    }
+
     pub fn levels(&self) -> &[LevelMeta] {
         &self.levels
     }
+
+    /// Returns true if the file is in its level.
+    pub fn contains_file(&self, file: &FileMeta) -> bool {
+        self.levels
+            .get(file.level as usize)
+            .map(|level| level.files.contains_key(&file.file_id))
+            .unwrap_or(false)
+    }
+}
+
+/// Ids of SSTs kept by named snapshots of a region, which aren't purged even if they are
+/// removed from the region.
+#[derive(Debug, Default)]
+pub struct PinnedFiles {
+    files: RwLock<HashSet<FileId>>,
+}
+
+pub type PinnedFilesRef = Arc<PinnedFiles>;
+
+impl PinnedFiles {
+    pub fn contains(&self, file_id: &FileId) -> bool {
+        self.files.read().unwrap().contains(file_id)
+    }
+
+    /// Replaces all pinned files with `files`.
+    pub fn set(&self, files: HashSet<FileId>) {
+        *self.files.write().unwrap() = files;
+    }
 }
 
 /// Metadata of files in same SST level.
@@ -245,6 +295,21 @@ impl FileHandle {
         &self.inner.meta.time_range
     }
 
+    #[inline]
+    pub fn tombstone_sequence(&self) -> Option<SequenceNumber> {
+        self.inner.meta.tombstone_sequence
+    }
+
+    #[inline]
+    pub fn column_stats(&self) -> &[ColumnStats] {
+        &self.inner.meta.column_stats
+    }
+
+    #[inline]
+    pub fn bloom_filters(&self) -> &[FileBloomFilter] {
+        &self.inner.meta.bloom_filters
+    }
+
     /// Returns true if current file is under compaction.
     #[inline]
     pub fn compacting(&self) -> bool {
@@ -397,6 +462,31 @@ pub struct FileMeta {
     pub level: Level,
     /// Size of the file.
     pub file_size: u64,
+    /// Min/max values of row key columns in the file, empty if unknown.
+    pub column_stats: Vec<ColumnStats>,
+    /// Range tombstones with sequences up to it delete no rows of the file, as they were
+    /// applied when writing the file or rows in the file are newer. `None` if unknown.
+    pub tombstone_sequence: Option<SequenceNumber>,
+    /// Bloom filters of string row key columns in the file, empty if unknown.
+    pub bloom_filters: Vec<FileBloomFilter>,
+}
+
+/// Min/max values of a column in a sst file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnStats {
+    /// Name of the column.
+    pub name: String,
+    /// Min non-null value of the column.
+    pub min: Value,
+    /// Max non-null value of the column.
+    pub max: Value,
+}
+
+// Value doesn't implement Hash, and a file never has two stats for the same column.
+impl Hash for ColumnStats {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
 }
 
 fn deserialize_from_string<'de, D>(deserializer: D) -> std::result::Result<FileId, D::Error>
@@ -412,12 +502,15 @@ where
 pub struct WriteOptions {
     // TODO(yingwen): [flush] row group size.
     pub sst_write_buffer_size: ReadableSize,
+    /// Compression codec of the SST file.
+    pub compression: SstCompression,
 }
 
 impl Default for WriteOptions {
     fn default() -> Self {
         Self {
             sst_write_buffer_size: ReadableSize::mb(8),
+            compression: SstCompression::default(),
         }
     }
 }
@@ -438,6 +531,8 @@ pub struct SstInfo {
     pub time_range: Option<(Timestamp, Timestamp)>,
     pub file_size: u64,
     pub num_rows: usize,
+    pub column_stats: Vec<ColumnStats>,
+    pub bloom_filters: Vec<FileBloomFilter>,
 }
 
 /// SST access layer.
@@ -664,6 +759,9 @@ mod tests {
             time_range: None,
             level,
             file_size: 0,
+            column_stats: vec![],
+            tombstone_sequence: None,
+            bloom_filters: vec![],
         }
     }
 
@@ -0,0 +1,569 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bloom filters of row key columns in SST files.
+//!
+//! Filters of row groups are stored in the key-value metadata of the parquet file under
+//! [BLOOM_FILTERS_KEY], so they are loaded together with the footer and don't bloat the region
+//! manifest. A small [FileBloomFilter] of the whole file is stored in the manifest to skip files
+//! without reading them.
+
+use std::collections::HashSet;
+use std::hash::Hasher;
+
+use common_query::logical_plan::DfExpr;
+use common_telemetry::warn;
+use datafusion_common::ScalarValue;
+use datafusion_expr::{BinaryExpr, Operator};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::ColumnSchema;
+use datatypes::value::ValueRef;
+use parquet::file::metadata::ParquetMetaData;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use table::predicate::Predicate;
+use twox_hash::XxHash64;
+
+use crate::read::Batch;
+
+/// Key of the bloom filters in the key-value metadata of a SST file.
+pub(crate) const BLOOM_FILTERS_KEY: &str = "greptime:bloom_filters";
+
+/// Number of bits for each distinct value, the false positive rate is about 1%.
+const BITS_PER_VALUE: usize = 10;
+/// Number of bits to set for each value.
+const NUM_HASHES: u32 = 7;
+/// Max number of distinct values of a filter. No filter is built for a row group with more
+/// distinct values in the column, as such a filter would be large but rarely prunes anything.
+const MAX_DISTINCT_VALUES: usize = 64 * 1024;
+/// Max number of distinct values of a [FileBloomFilter], which is kept in the region manifest
+/// and must be small.
+const MAX_FILE_DISTINCT_VALUES: usize = 1024;
+
+/// A bloom filter of the distinct values in a column of a row group.
+///
+/// Values are hashed by [hash_bytes] before being added to the filter, so the filter is
+/// persistable and remains valid across restarts.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BloomFilter {
+    num_hashes: u32,
+    #[serde(
+        serialize_with = "serialize_words",
+        deserialize_with = "deserialize_words"
+    )]
+    words: Vec<u64>,
+}
+
+impl BloomFilter {
+    /// Builds a filter that contains all `hashes`, which are distinct hashes of values.
+    ///
+    /// The filter is sized by the number of hashes to keep the false positive rate.
+    pub fn from_hashes<I>(hashes: I) -> BloomFilter
+    where
+        I: ExactSizeIterator<Item = u64>,
+    {
+        let num_words = ((hashes.len() * BITS_PER_VALUE + 63) / 64).max(1);
+        let mut filter = BloomFilter {
+            num_hashes: NUM_HASHES,
+            words: vec![0; num_words],
+        };
+        for hash in hashes {
+            filter.insert_hash(hash);
+        }
+        filter
+    }
+
+    /// Returns false if `value` is definitely not in the filter.
+    pub fn may_contain(&self, value: &[u8]) -> bool {
+        self.may_contain_hash(hash_bytes(value))
+    }
+
+    fn insert_hash(&mut self, hash: u64) {
+        for bit in self.bit_indices(hash) {
+            self.words[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    fn may_contain_hash(&self, hash: u64) -> bool {
+        // A filter without any word is corrupted, so it can't filter anything.
+        self.words.is_empty()
+            || self
+                .bit_indices(hash)
+                .all(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Derives indices of bits for `hash` by double hashing.
+    fn bit_indices(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let num_bits = (self.words.len() * 64) as u64;
+        let (h1, h2) = (hash & u32::MAX as u64, hash >> 32);
+        (0..self.num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+}
+
+/// Encodes words as base64 of their little endian bytes, which is much more compact than a
+/// json array of numbers.
+fn serialize_words<S: Serializer>(words: &[u64], serializer: S) -> Result<S::Ok, S::Error> {
+    let bytes = words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect::<Vec<_>>();
+    serializer.serialize_str(&base64::encode(bytes))
+}
+
+fn deserialize_words<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u64>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    let bytes = base64::decode(encoded).map_err(serde::de::Error::custom)?;
+    if bytes.len() % 8 != 0 {
+        return Err(serde::de::Error::custom(format!(
+            "invalid length of bloom filter: {}",
+            bytes.len()
+        )));
+    }
+    Ok(bytes
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .collect())
+}
+
+/// Returns the hash of `value` to add to a [BloomFilter].
+pub fn hash_bytes(value: &[u8]) -> u64 {
+    let mut hasher = XxHash64::with_seed(0);
+    hasher.write(value);
+    hasher.finish()
+}
+
+/// A bloom filter of the distinct values in a column of a whole SST file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FileBloomFilter {
+    /// Name of the column.
+    pub column: String,
+    pub filter: BloomFilter,
+}
+
+/// Bloom filters of a column in all row groups of a SST file.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ColumnBloomFilters {
+    column: String,
+    /// Filter of each row group, `None` if the row group has too many distinct values.
+    row_groups: Vec<Option<BloomFilter>>,
+}
+
+/// Builds bloom filters of string and binary columns for each row group while writing a SST.
+pub(crate) struct BloomFiltersBuilder {
+    /// Max number of rows in a row group, must be the same as the parquet writer's.
+    row_group_size: usize,
+    /// Number of rows in the current row group.
+    num_rows: usize,
+    columns: Vec<ColumnBuilder>,
+}
+
+struct ColumnBuilder {
+    /// Index of the column in written batches.
+    index: usize,
+    /// Distinct hashes in the current row group, `None` if there are too many of them.
+    hashes: Option<HashSet<u64>>,
+    /// Distinct hashes in the file, `None` if there are too many of them.
+    file_hashes: Option<HashSet<u64>>,
+    filters: ColumnBloomFilters,
+}
+
+impl BloomFiltersBuilder {
+    /// Creates a builder for `column_schemas`, which must be the leading columns of batches
+    /// to write.
+    pub(crate) fn new(column_schemas: &[ColumnSchema], row_group_size: usize) -> Self {
+        let columns = column_schemas
+            .iter()
+            .enumerate()
+            .filter(|(_, column_schema)| {
+                matches!(
+                    column_schema.data_type,
+                    ConcreteDataType::String(_) | ConcreteDataType::Binary(_)
+                )
+            })
+            .map(|(index, column_schema)| ColumnBuilder {
+                index,
+                hashes: Some(HashSet::new()),
+                file_hashes: Some(HashSet::new()),
+                filters: ColumnBloomFilters {
+                    column: column_schema.name.clone(),
+                    row_groups: Vec::new(),
+                },
+            })
+            .collect();
+        BloomFiltersBuilder {
+            row_group_size,
+            num_rows: 0,
+            columns,
+        }
+    }
+
+    /// Adds values in the `batch`, which may span multiple row groups.
+    pub(crate) fn update(&mut self, batch: &Batch) {
+        if self.columns.is_empty() {
+            return;
+        }
+        let mut start = 0;
+        while start < batch.num_rows() {
+            let end = batch
+                .num_rows()
+                .min(start + self.row_group_size - self.num_rows);
+            for column in &mut self.columns {
+                if column.hashes.is_none() && column.file_hashes.is_none() {
+                    continue;
+                }
+                let vector = batch.column(column.index);
+                for i in start..end {
+                    let hash = match vector.get_ref(i) {
+                        ValueRef::String(s) => hash_bytes(s.as_bytes()),
+                        ValueRef::Binary(b) => hash_bytes(b),
+                        _ => continue,
+                    };
+                    for hashes in [&mut column.hashes, &mut column.file_hashes]
+                        .into_iter()
+                        .flatten()
+                    {
+                        hashes.insert(hash);
+                    }
+                }
+                if matches!(&column.hashes, Some(hashes) if hashes.len() > MAX_DISTINCT_VALUES) {
+                    column.hashes = None;
+                }
+                if matches!(&column.file_hashes, Some(hashes) if hashes.len() > MAX_FILE_DISTINCT_VALUES)
+                {
+                    column.file_hashes = None;
+                }
+            }
+            self.num_rows += end - start;
+            if self.num_rows == self.row_group_size {
+                self.finish_row_group();
+            }
+            start = end;
+        }
+    }
+
+    fn finish_row_group(&mut self) {
+        for column in &mut self.columns {
+            let filter = column
+                .hashes
+                .replace(HashSet::new())
+                .map(|hashes| BloomFilter::from_hashes(hashes.into_iter()));
+            column.filters.row_groups.push(filter);
+        }
+        self.num_rows = 0;
+    }
+
+    /// Returns the encoded filters of row groups to store under [BLOOM_FILTERS_KEY], or `None`
+    /// if no filter is built, and filters of the whole file.
+    pub(crate) fn finish(mut self) -> (Option<String>, Vec<FileBloomFilter>) {
+        if self.num_rows > 0 {
+            self.finish_row_group();
+        }
+        let mut file_filters = Vec::new();
+        let mut filters = Vec::with_capacity(self.columns.len());
+        for column in self.columns {
+            if let Some(hashes) = column.file_hashes {
+                file_filters.push(FileBloomFilter {
+                    column: column.filters.column.clone(),
+                    filter: BloomFilter::from_hashes(hashes.into_iter()),
+                });
+            }
+            if column.filters.row_groups.iter().any(Option::is_some) {
+                filters.push(column.filters);
+            }
+        }
+        if filters.is_empty() {
+            return (None, file_filters);
+        }
+        // Serializing plain structs to json never fails.
+        (Some(serde_json::to_string(&filters).unwrap()), file_filters)
+    }
+}
+
+/// Decodes bloom filters from the key-value metadata of a SST file. Filters are only used to
+/// prune row groups, so invalid filters are ignored.
+pub(crate) fn decode_bloom_filters(metadata: &ParquetMetaData) -> Vec<ColumnBloomFilters> {
+    let Some(encoded) = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kvs| kvs.iter().find(|kv| kv.key == BLOOM_FILTERS_KEY))
+        .and_then(|kv| kv.value.as_ref()) else { return Vec::new() };
+    match serde_json::from_str::<Vec<ColumnBloomFilters>>(encoded) {
+        Ok(filters) => filters
+            .into_iter()
+            .filter(|filters| filters.row_groups.len() == metadata.num_row_groups())
+            .collect(),
+        Err(e) => {
+            warn!("Failed to decode bloom filters, error: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Values that a column must equal to, extracted from filters like `col = 'a'` and
+/// `col IN ('a', 'b')`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct EqualValues {
+    column: String,
+    values: Vec<Vec<u8>>,
+}
+
+/// Collects [EqualValues] of string and binary literals from the `predicate`.
+pub(crate) fn collect_equal_values(predicate: &Predicate) -> Vec<EqualValues> {
+    let mut equal_values = Vec::new();
+    for expr in predicate.exprs() {
+        collect_equal_values_of_expr(expr.df_expr(), &mut equal_values);
+    }
+    equal_values
+}
+
+/// Collects [EqualValues] from conjunctions in `expr`.
+fn collect_equal_values_of_expr(expr: &DfExpr, output: &mut Vec<EqualValues>) {
+    match expr {
+        DfExpr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        }) => {
+            collect_equal_values_of_expr(left, output);
+            collect_equal_values_of_expr(right, output);
+        }
+        DfExpr::BinaryExpr(BinaryExpr {
+            left,
+            op: Operator::Eq,
+            right,
+        }) => {
+            let (column, value) = match (left.as_ref(), right.as_ref()) {
+                (DfExpr::Column(column), DfExpr::Literal(value))
+                | (DfExpr::Literal(value), DfExpr::Column(column)) => (column, value),
+                _ => return,
+            };
+            if let Some(value) = literal_bytes(value) {
+                output.push(EqualValues {
+                    column: column.name.clone(),
+                    values: vec![value],
+                });
+            }
+        }
+        DfExpr::InList {
+            expr,
+            list,
+            negated: false,
+        } => {
+            let DfExpr::Column(column) = expr.as_ref() else { return };
+            let values = list
+                .iter()
+                .map(|item| match item {
+                    DfExpr::Literal(value) => literal_bytes(value),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            if let Some(values) = values {
+                output.push(EqualValues {
+                    column: column.name.clone(),
+                    values,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn literal_bytes(value: &ScalarValue) -> Option<Vec<u8>> {
+    match value {
+        ScalarValue::Utf8(Some(s)) | ScalarValue::LargeUtf8(Some(s)) => Some(s.as_bytes().to_vec()),
+        ScalarValue::Binary(Some(b)) | ScalarValue::LargeBinary(Some(b)) => Some(b.clone()),
+        _ => None,
+    }
+}
+
+/// Returns false if `filters` prove that no row in the `row_group` matches `equal_values`.
+pub(crate) fn row_group_may_match(
+    filters: &[ColumnBloomFilters],
+    row_group: usize,
+    equal_values: &[EqualValues],
+) -> bool {
+    equal_values.iter().all(|equal| {
+        filters
+            .iter()
+            .find(|filters| filters.column == equal.column)
+            .and_then(|filters| filters.row_groups[row_group].as_ref())
+            .map_or(true, |filter| {
+                equal.values.iter().any(|value| filter.may_contain(value))
+            })
+    })
+}
+
+/// Returns false if `filters` of a file prove that no row in the file matches `equal_values`.
+pub(crate) fn file_may_match(filters: &[FileBloomFilter], equal_values: &[EqualValues]) -> bool {
+    equal_values.iter().all(|equal| {
+        filters
+            .iter()
+            .find(|filter| filter.column == equal.column)
+            .map_or(true, |filter| {
+                equal
+                    .values
+                    .iter()
+                    .any(|value| filter.filter.may_contain(value))
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_query::logical_plan::Expr;
+    use datafusion_expr::{col, lit};
+    use datatypes::vectors::{StringVector, UInt64Vector, VectorRef};
+
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter() {
+        let values = (0..1000).map(|i| format!("trace-{i}")).collect::<Vec<_>>();
+        let hashes = values
+            .iter()
+            .map(|v| hash_bytes(v.as_bytes()))
+            .collect::<HashSet<_>>();
+        let filter = BloomFilter::from_hashes(hashes.into_iter());
+        assert_eq!((1000 * BITS_PER_VALUE + 63) / 64, filter.words.len());
+
+        for value in &values {
+            assert!(filter.may_contain(value.as_bytes()));
+        }
+        let false_positives = (1000..11000)
+            .filter(|i| filter.may_contain(format!("trace-{i}").as_bytes()))
+            .count();
+        assert!(false_positives < 300, "false positives: {false_positives}");
+
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(filter, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_bloom_filter_size() {
+        let filter = BloomFilter::from_hashes(std::iter::empty());
+        assert_eq!(1, filter.words.len());
+        assert!(!filter.may_contain(b"a"));
+
+        // Filters grow with the number of distinct values, so they don't saturate.
+        let hashes = (0..1_000_000)
+            .map(|i| hash_bytes(format!("trace-{i}").as_bytes()))
+            .collect::<Vec<_>>();
+        let filter = BloomFilter::from_hashes(hashes.into_iter());
+        assert_eq!((1_000_000 * BITS_PER_VALUE + 63) / 64, filter.words.len());
+        let false_positives = (1_000_000..1_010_000)
+            .filter(|i| filter.may_contain(format!("trace-{i}").as_bytes()))
+            .count();
+        assert!(false_positives < 300, "false positives: {false_positives}");
+    }
+
+    fn new_batch(hosts: &[&str]) -> Batch {
+        let hosts: VectorRef = Arc::new(StringVector::from(hosts.to_vec()));
+        let values: VectorRef = Arc::new(UInt64Vector::from_vec(vec![0; hosts.len()]));
+        Batch::new(vec![hosts, values])
+    }
+
+    fn column_schemas() -> Vec<ColumnSchema> {
+        vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("v0", ConcreteDataType::uint64_datatype(), true),
+        ]
+    }
+
+    #[test]
+    fn test_build_bloom_filters_by_row_group() {
+        let mut builder = BloomFiltersBuilder::new(&column_schemas(), 3);
+        // Row groups: [a, b, c], [d, e, f], [g]
+        builder.update(&new_batch(&["a", "b"]));
+        builder.update(&new_batch(&["c", "d", "e", "f", "g"]));
+        let (encoded, file_filters) = builder.finish();
+        let filters: Vec<ColumnBloomFilters> = serde_json::from_str(&encoded.unwrap()).unwrap();
+        assert_eq!(1, filters.len());
+        assert_eq!("host", filters[0].column);
+        assert_eq!(3, filters[0].row_groups.len());
+
+        let equal_values = |value: &str| {
+            collect_equal_values(&Predicate::new(vec![Expr::from(
+                col("host").eq(lit(value)),
+            )]))
+        };
+        let matched_row_groups = |value: &str| {
+            let equal_values = equal_values(value);
+            (0..3)
+                .filter(|i| row_group_may_match(&filters, *i, &equal_values))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec![0], matched_row_groups("c"));
+        assert_eq!(vec![1], matched_row_groups("d"));
+        assert_eq!(vec![2], matched_row_groups("g"));
+        assert!(matched_row_groups("zzz").is_empty());
+        assert!(file_may_match(&file_filters, &equal_values("g")));
+        assert!(!file_may_match(&file_filters, &equal_values("zzz")));
+
+        // No filter is built without string or binary columns.
+        let mut builder = BloomFiltersBuilder::new(&column_schemas()[1..], 3);
+        builder.update(&Batch::new(vec![Arc::new(UInt64Vector::from_vec(vec![1]))]));
+        assert_eq!((None, vec![]), builder.finish());
+    }
+
+    #[test]
+    fn test_skip_bloom_filter_over_capacity() {
+        let num_rows = MAX_DISTINCT_VALUES + 1;
+        let mut builder = BloomFiltersBuilder::new(&column_schemas(), num_rows);
+        let hosts = (0..num_rows).map(|i| i.to_string()).collect::<Vec<_>>();
+        let hosts = hosts.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        builder.update(&new_batch(&hosts));
+        assert_eq!((None, vec![]), builder.finish());
+
+        // The filter of the file has a much smaller capacity.
+        let mut builder = BloomFiltersBuilder::new(&column_schemas(), num_rows);
+        builder.update(&new_batch(&hosts[..MAX_FILE_DISTINCT_VALUES + 1]));
+        let (encoded, file_filters) = builder.finish();
+        assert!(encoded.is_some());
+        assert!(file_filters.is_empty());
+    }
+
+    #[test]
+    fn test_collect_equal_values() {
+        let equal_values_of =
+            |expr: DfExpr| collect_equal_values(&Predicate::new(vec![Expr::from(expr)]));
+
+        assert_eq!(
+            vec![EqualValues {
+                column: "host".to_string(),
+                values: vec![b"a".to_vec()],
+            }],
+            equal_values_of(col("host").eq(lit("a")).and(col("v0").eq(lit(1u64))))
+        );
+        assert_eq!(
+            vec![EqualValues {
+                column: "host".to_string(),
+                values: vec![b"zzz".to_vec()],
+            }],
+            equal_values_of(lit("zzz").eq(col("host")))
+        );
+        assert_eq!(
+            vec![EqualValues {
+                column: "host".to_string(),
+                values: vec![b"c".to_vec(), b"b".to_vec()],
+            }],
+            equal_values_of(col("host").in_list(vec![lit("c"), lit("b")], false))
+        );
+
+        // Negated and disjunctive filters can't prune row groups.
+        assert!(equal_values_of(col("host").in_list(vec![lit("c")], true)).is_empty());
+        assert!(equal_values_of(col("host").eq(lit("c")).or(col("host").eq(lit("d")))).is_empty());
+    }
+}
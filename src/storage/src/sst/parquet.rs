@@ -35,26 +35,31 @@ use datatypes::arrow::array::BooleanArray;
 use datatypes::arrow::error::ArrowError;
 use datatypes::arrow::record_batch::RecordBatch;
 use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::ColumnSchema;
+use datatypes::value::Value;
+use datatypes::vectors::VectorRef;
 use futures_util::{Stream, StreamExt, TryStreamExt};
 use object_store::ObjectStore;
-use parquet::arrow::arrow_reader::{ArrowPredicate, RowFilter};
+use parquet::arrow::arrow_reader::{ArrowPredicate, ArrowReaderOptions, RowFilter};
 use parquet::arrow::{ParquetRecordBatchStreamBuilder, ProjectionMask};
-use parquet::basic::{Compression, Encoding, ZstdLevel};
+use parquet::basic::{Compression, Encoding, GzipLevel, ZstdLevel};
 use parquet::file::metadata::KeyValue;
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{WriterProperties, WriterPropertiesBuilder};
 use parquet::format::FileMetaData;
-use parquet::schema::types::SchemaDescriptor;
+use parquet::schema::types::{ColumnPath, SchemaDescriptor};
 use snafu::{OptionExt, ResultExt};
+use store_api::storage::{consts, SstCompression};
 use table::predicate::Predicate;
 use tokio::io::BufReader;
 
 use crate::error::{self, DecodeParquetTimeRangeSnafu, ReadObjectSnafu, ReadParquetSnafu, Result};
 use crate::read::{Batch, BatchReader};
 use crate::schema::compat::ReadAdapter;
-use crate::schema::{ProjectedSchemaRef, StoreSchema};
+use crate::schema::{row_key_end_of, ProjectedSchemaRef, StoreSchema};
 use crate::sst;
+use crate::sst::bloom_filter::{self, BloomFiltersBuilder, BLOOM_FILTERS_KEY};
 use crate::sst::stream_writer::BufferedWriter;
-use crate::sst::{FileHandle, Source, SstInfo};
+use crate::sst::{ColumnStats, FileHandle, Source, SstInfo};
 
 /// Parquet sst writer.
 pub struct ParquetWriter<'a> {
@@ -87,9 +92,7 @@ impl<'a> ParquetWriter<'a> {
         opts: &sst::WriteOptions,
     ) -> Result<Option<SstInfo>> {
         let schema = self.source.schema();
-        let writer_props = WriterProperties::builder()
-            .set_compression(Compression::ZSTD(ZstdLevel::default()))
-            .set_encoding(Encoding::PLAIN)
+        let writer_props = writer_properties_builder(&schema, opts.compression)
             .set_max_row_group_size(self.max_row_group_size)
             .set_key_value_metadata(extra_meta.map(|map| {
                 map.iter()
@@ -107,8 +110,21 @@ impl<'a> ParquetWriter<'a> {
         )
         .await?;
         let mut rows_written = 0;
+        let num_key_columns = row_key_end_of(&schema).unwrap_or(0);
+        let mut key_stats = schema.column_schemas()[..num_key_columns]
+            .iter()
+            .map(ColumnStatsBuilder::new)
+            .collect::<Vec<_>>();
+        let mut bloom_filters = BloomFiltersBuilder::new(
+            &schema.column_schemas()[..num_key_columns],
+            self.max_row_group_size,
+        );
 
         while let Some(batch) = self.source.next_batch().await? {
+            for (builder, vector) in key_stats.iter_mut().zip(batch.columns()) {
+                builder.update(vector);
+            }
+            bloom_filters.update(&batch);
             buffered_writer.write(&batch).await?;
             rows_written += batch.num_rows();
         }
@@ -124,18 +140,117 @@ impl<'a> ParquetWriter<'a> {
             return Ok(None);
         }
 
+        let (encoded_bloom_filters, bloom_filters) = bloom_filters.finish();
+        if let Some(encoded) = encoded_bloom_filters {
+            buffered_writer
+                .append_key_value_metadata(KeyValue::new(BLOOM_FILTERS_KEY.to_string(), encoded));
+        }
         let (file_meta, file_size) = buffered_writer.close().await?;
         let time_range = decode_timestamp_range(&file_meta, &schema).ok().flatten();
+        let column_stats = key_stats
+            .into_iter()
+            .filter_map(ColumnStatsBuilder::finish)
+            .collect();
 
         // object_store.write will make sure all bytes are written or an error is raised.
         Ok(Some(SstInfo {
             time_range,
             file_size,
             num_rows: rows_written,
+            column_stats,
+            bloom_filters,
         }))
     }
 }
 
+/// Builds [ColumnStats] of a column from all batches written to a SST.
+struct ColumnStatsBuilder {
+    name: String,
+    min_max: Option<(Value, Value)>,
+}
+
+impl ColumnStatsBuilder {
+    fn new(column_schema: &ColumnSchema) -> ColumnStatsBuilder {
+        ColumnStatsBuilder {
+            name: column_schema.name.clone(),
+            min_max: None,
+        }
+    }
+
+    /// Merges non-null values of the `vector` into the statistics.
+    fn update(&mut self, vector: &VectorRef) {
+        let mut min_max: Option<(usize, usize)> = None;
+        for i in 0..vector.len() {
+            let value = vector.get_ref(i);
+            if value.is_null() {
+                continue;
+            }
+            min_max = match min_max {
+                None => Some((i, i)),
+                Some((min, max)) => Some((
+                    if value < vector.get_ref(min) { i } else { min },
+                    if value > vector.get_ref(max) { i } else { max },
+                )),
+            };
+        }
+        let Some((min, max)) = min_max else { return };
+
+        let (min, max) = (vector.get(min), vector.get(max));
+        self.min_max = match self.min_max.take() {
+            None => Some((min, max)),
+            Some((old_min, old_max)) => Some((old_min.min(min), old_max.max(max))),
+        };
+    }
+
+    /// Returns the statistics, or `None` if all values are null.
+    fn finish(self) -> Option<ColumnStats> {
+        let (min, max) = self.min_max?;
+        Some(ColumnStats {
+            name: self.name,
+            min,
+            max,
+        })
+    }
+}
+
+/// Builds properties to write an SST file of `schema` with the `compression` codec.
+///
+/// String row key columns, which are usually tags with a few distinct values, are dictionary
+/// encoded. The time index and sequence columns are mostly increasing within a series, so
+/// they are delta encoded instead.
+fn writer_properties_builder(
+    schema: &datatypes::schema::Schema,
+    compression: SstCompression,
+) -> WriterPropertiesBuilder {
+    let mut builder = WriterProperties::builder()
+        .set_compression(parquet_compression(compression))
+        .set_encoding(Encoding::PLAIN);
+
+    let row_key_end = row_key_end_of(schema).unwrap_or(0);
+    let ts_index = schema.timestamp_index();
+    for (idx, column) in schema.column_schemas().iter().enumerate() {
+        let path = ColumnPath::from(column.name.as_str());
+        if Some(idx) == ts_index || column.name == consts::SEQUENCE_COLUMN_NAME {
+            builder = builder
+                .set_column_dictionary_enabled(path.clone(), false)
+                .set_column_encoding(path, Encoding::DELTA_BINARY_PACKED);
+        } else if idx < row_key_end && matches!(column.data_type, ConcreteDataType::String(_)) {
+            builder = builder.set_column_dictionary_enabled(path, true);
+        }
+    }
+    builder
+}
+
+fn parquet_compression(compression: SstCompression) -> Compression {
+    match compression {
+        SstCompression::Uncompressed => Compression::UNCOMPRESSED,
+        SstCompression::Snappy => Compression::SNAPPY,
+        SstCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+        SstCompression::Lz4 => Compression::LZ4_RAW,
+        SstCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+    }
+}
+
 fn decode_timestamp_range(
     file_meta: &FileMetaData,
     schema: &datatypes::schema::SchemaRef,
@@ -250,7 +365,9 @@ impl ParquetReader {
             .context(ReadObjectSnafu { path: &file_path })?
             .compat();
         let buf_reader = BufReader::new(reader);
-        let builder = ParquetRecordBatchStreamBuilder::new(buf_reader)
+        // The page index is only useful to prune pages by the predicate.
+        let options = ArrowReaderOptions::new().with_page_index(!self.predicate.exprs().is_empty());
+        let builder = ParquetRecordBatchStreamBuilder::new_with_options(buf_reader, options)
             .await
             .context(ReadParquetSnafu { file: &file_path })?;
         let arrow_schema = builder.schema().clone();
@@ -272,6 +389,22 @@ impl ParquetReader {
             .enumerate()
             .filter_map(|(idx, valid)| if valid { Some(idx) } else { None })
             .collect::<Vec<_>>();
+        let equal_values = bloom_filter::collect_equal_values(&self.predicate);
+        let pruned_row_groups = if equal_values.is_empty() {
+            pruned_row_groups
+        } else {
+            let filters = bloom_filter::decode_bloom_filters(builder.metadata());
+            pruned_row_groups
+                .into_iter()
+                .filter(|idx| bloom_filter::row_group_may_match(&filters, *idx, &equal_values))
+                .collect()
+        };
+
+        let row_selection = self.predicate.prune_pages(
+            store_schema.schema(),
+            builder.metadata(),
+            &pruned_row_groups,
+        );
 
         let parquet_schema_desc = builder.metadata().file_metadata().schema_descr_ptr();
 
@@ -279,6 +412,9 @@ impl ParquetReader {
         let mut builder = builder
             .with_projection(projection)
             .with_row_groups(pruned_row_groups);
+        if let Some(row_selection) = row_selection {
+            builder = builder.with_row_selection(row_selection);
+        }
 
         // if time range row filter is present, we can push down the filter to reduce rows to scan.
         if let Some(row_filter) = self.build_time_range_row_filter(&parquet_schema_desc) {
@@ -536,6 +672,7 @@ mod tests {
     use common_test_util::temp_dir::create_temp_dir;
     use datatypes::arrow::array::{Array, ArrayRef, UInt64Array, UInt8Array};
     use datatypes::prelude::{ScalarVector, Vector};
+    use datatypes::type_id::LogicalTypeId;
     use datatypes::types::{TimestampMillisecondType, TimestampType};
     use datatypes::vectors::TimestampMillisecondVector;
     use object_store::services::Fs;
@@ -546,8 +683,10 @@ mod tests {
     use crate::memtable::{
         tests as memtable_tests, DefaultMemtableBuilder, IterContext, MemtableBuilder,
     };
+    use crate::metadata::RegionMetadata;
     use crate::schema::ProjectedSchema;
     use crate::sst::{FileId, FileMeta};
+    use crate::test_util::descriptor_util::RegionDescBuilder;
 
     fn create_object_store(root: &str) -> ObjectStore {
         let mut builder = Fs::default();
@@ -591,10 +730,27 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, Source::Iter(iter), object_store.clone());
 
-        writer
+        let sst_info = writer
             .write_sst(&sst::WriteOptions::default())
             .await
+            .unwrap()
             .unwrap();
+        // Statistics of row key columns: timestamp, __version
+        assert_eq!(
+            vec![
+                ColumnStats {
+                    name: crate::test_util::TIMESTAMP_NAME.to_string(),
+                    min: Value::Timestamp(Timestamp::new_millisecond(1000)),
+                    max: Value::Timestamp(Timestamp::new_millisecond(2003)),
+                },
+                ColumnStats {
+                    name: consts::VERSION_COLUMN_NAME.to_string(),
+                    min: Value::UInt64(1),
+                    max: Value::UInt64(5),
+                },
+            ],
+            sst_info.column_stats
+        );
 
         // verify parquet file
         let reader = BufReader::new(object_store.reader(sst_file_name).await.unwrap().compat());
@@ -732,6 +888,9 @@ mod tests {
                 )),
                 level: 0,
                 file_size: 0,
+                column_stats: vec![],
+                tombstone_sequence: None,
+                bloom_filters: vec![],
             },
             layer,
             file_purger,
@@ -1016,4 +1175,41 @@ mod tests {
         check_unit_lossy(TimeUnit::Nanosecond, TimeUnit::Microsecond, true);
         check_unit_lossy(TimeUnit::Nanosecond, TimeUnit::Nanosecond, false);
     }
+
+    #[test]
+    fn test_writer_properties() {
+        let desc = RegionDescBuilder::new("region-0")
+            .timestamp(("ts", LogicalTypeId::TimestampMillisecond, false))
+            .push_key_column(("host", LogicalTypeId::String, true))
+            .push_key_column(("k1", LogicalTypeId::Int32, true))
+            .push_field_column(("v1", LogicalTypeId::String, true))
+            .build();
+        let metadata = RegionMetadata::try_from(desc).unwrap();
+        let schema = metadata.schema().store_schema().schema();
+
+        let props = writer_properties_builder(schema, SstCompression::Snappy).build();
+        let column_path = |name: &str| ColumnPath::from(name);
+        for name in ["ts", consts::SEQUENCE_COLUMN_NAME] {
+            assert!(!props.dictionary_enabled(&column_path(name)));
+            assert_eq!(
+                Some(Encoding::DELTA_BINARY_PACKED),
+                props.encoding(&column_path(name))
+            );
+        }
+        assert!(props.dictionary_enabled(&column_path("host")));
+        assert_eq!(Some(Encoding::PLAIN), props.encoding(&column_path("v1")));
+        assert_eq!(Compression::SNAPPY, props.compression(&column_path("v1")));
+
+        // Schema without row key metadata, e.g. schema of a table scan.
+        let schema = metadata.user_schema();
+        let props = writer_properties_builder(schema, SstCompression::default()).build();
+        assert_eq!(
+            Some(Encoding::DELTA_BINARY_PACKED),
+            props.encoding(&column_path("ts"))
+        );
+        assert_eq!(
+            Compression::ZSTD(ZstdLevel::default()),
+            props.compression(&column_path("host"))
+        );
+    }
 }
@@ -21,6 +21,7 @@ use bytes::{BufMut, BytesMut};
 use datatypes::schema::SchemaRef;
 use object_store::{ObjectStore, Writer};
 use parquet::arrow::ArrowWriter;
+use parquet::file::metadata::KeyValue;
 use parquet::file::properties::WriterProperties;
 use parquet::format::FileMetaData;
 use snafu::ResultExt;
@@ -128,6 +129,12 @@ impl BufferedWriter {
         Ok(())
     }
 
+    /// Appends key-value metadata to the footer of the file, which can be called after all
+    /// batches are written.
+    pub fn append_key_value_metadata(&mut self, kv_metadata: KeyValue) {
+        self.arrow_writer.append_key_value_metadata(kv_metadata);
+    }
+
     /// Abort writer.
     pub async fn abort(self) -> bool {
         // TODO(hl): Currently we can do nothing if file's parts have been uploaded to remote storage
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use log_store::raft_engine::log_store::RaftEngineLogStore;
 use log_store::LogConfig;
@@ -22,14 +23,16 @@ use store_api::manifest::Manifest;
 
 use crate::background::JobPoolImpl;
 use crate::compaction::noop::NoopCompactionScheduler;
+use crate::config::{EngineConfig, MemtableType};
 use crate::engine;
 use crate::file_purger::noop::NoopFilePurgeHandler;
 use crate::flush::{FlushSchedulerImpl, SizeBasedStrategy};
 use crate::manifest::region::RegionManifest;
-use crate::memtable::DefaultMemtableBuilder;
+use crate::memtable::{self, DefaultMemtableBuilder};
 use crate::region::StoreConfig;
 use crate::scheduler::{LocalScheduler, SchedulerConfig};
 use crate::sst::FsAccessLayer;
+use crate::write_buffer::WriteBufferManager;
 
 fn log_store_dir(store_dir: &str) -> String {
     format!("{store_dir}/logstore")
@@ -47,6 +50,22 @@ pub async fn new_store_config(
     new_store_config_with_object_store(region_name, store_dir, object_store).await
 }
 
+/// Create a new StoreConfig whose regions use memtables of `memtable_type`.
+pub async fn new_store_config_with_memtable_type(
+    region_name: &str,
+    store_dir: &str,
+    memtable_type: MemtableType,
+) -> StoreConfig<RaftEngineLogStore> {
+    let mut store_config = new_store_config(region_name, store_dir).await;
+    store_config.memtable_builder = memtable::new_memtable_builder(memtable_type);
+    store_config.engine_config = Arc::new(EngineConfig {
+        memtable_type,
+        ..Default::default()
+    });
+
+    store_config
+}
+
 /// Create a new StoreConfig with given object store.
 pub async fn new_store_config_with_object_store(
     region_name: &str,
@@ -84,5 +103,9 @@ pub async fn new_store_config_with_object_store(
         file_purger,
         ttl: None,
         compaction_time_window: None,
+        compaction_strategy: Default::default(),
+        sst_compression: Default::default(),
+        append_mode: false,
+        write_buffer_manager: Arc::new(WriteBufferManager::new(None, Duration::ZERO)),
     }
 }
@@ -0,0 +1,124 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commit times of sequences in a region.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use store_api::storage::SequenceNumber;
+
+/// Max number of points in a timeline, about one day if the region commits every second.
+const MAX_POINTS: usize = 86400;
+
+/// Records when sequences of a region are committed, to find the sequence to read for
+/// a commit time.
+///
+/// Commit times are kept at second precision, so reading at a time also returns data
+/// committed later in the same second.
+#[derive(Debug, Default)]
+pub struct CommitTimeline {
+    inner: Mutex<TimelineInner>,
+}
+
+pub type CommitTimelineRef = Arc<CommitTimeline>;
+
+#[derive(Debug, Default)]
+struct TimelineInner {
+    /// Sequences not greater than this were committed at unknown times.
+    base_sequence: SequenceNumber,
+    /// Commit time in seconds and the last sequence committed in that second, in
+    /// ascending order.
+    points: VecDeque<(i64, SequenceNumber)>,
+}
+
+impl CommitTimeline {
+    /// Records that the `sequence` is committed at `commit_time_millis`.
+    pub fn record(&self, commit_time_millis: i64, sequence: SequenceNumber) {
+        let second = commit_time_millis.div_euclid(1000);
+        let mut inner = self.inner.lock().unwrap();
+        match inner.points.back_mut() {
+            // The clock may go backward, we put the sequence to the last point in such case.
+            Some((last_second, last_sequence)) if *last_second >= second => {
+                *last_sequence = sequence.max(*last_sequence);
+            }
+            _ => inner.points.push_back((second, sequence)),
+        }
+
+        if inner.points.len() > MAX_POINTS {
+            if let Some((_, sequence)) = inner.points.pop_front() {
+                inner.base_sequence = sequence;
+            }
+        }
+    }
+
+    /// Forgets commit times of all sequences not greater than `base_sequence`.
+    pub fn reset(&self, base_sequence: SequenceNumber) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.base_sequence = base_sequence;
+        inner.points.clear();
+    }
+
+    /// Returns the last sequence committed at or before `second`, or `None` if the commit
+    /// time is unknown.
+    pub fn sequence_at(&self, second: i64) -> Option<SequenceNumber> {
+        let inner = self.inner.lock().unwrap();
+        let index = inner.points.partition_point(|(s, _)| *s <= second);
+        if index > 0 {
+            return Some(inner.points[index - 1].1);
+        }
+
+        // Nothing is committed before the first point if we know the commit times of
+        // all sequences.
+        (inner.base_sequence == 0).then_some(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_timeline() {
+        let timeline = CommitTimeline::default();
+        assert_eq!(Some(0), timeline.sequence_at(10));
+
+        timeline.record(10_000, 1);
+        timeline.record(10_500, 2);
+        timeline.record(12_000, 3);
+        // Clock goes backward.
+        timeline.record(11_000, 4);
+        assert_eq!(Some(0), timeline.sequence_at(9));
+        assert_eq!(Some(2), timeline.sequence_at(10));
+        assert_eq!(Some(2), timeline.sequence_at(11));
+        assert_eq!(Some(4), timeline.sequence_at(12));
+        assert_eq!(Some(4), timeline.sequence_at(100));
+
+        timeline.reset(4);
+        assert_eq!(None, timeline.sequence_at(12));
+        timeline.record(13_000, 5);
+        assert_eq!(None, timeline.sequence_at(12));
+        assert_eq!(Some(5), timeline.sequence_at(13));
+    }
+
+    #[test]
+    fn test_commit_timeline_evict() {
+        let timeline = CommitTimeline::default();
+        for i in 0..=MAX_POINTS {
+            timeline.record(i as i64 * 1000, i as u64 + 1);
+        }
+        assert_eq!(None, timeline.sequence_at(0));
+        assert_eq!(Some(2), timeline.sequence_at(1));
+    }
+}
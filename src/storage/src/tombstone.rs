@@ -0,0 +1,49 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Range tombstones that delete rows without enumerating their row keys.
+
+use std::sync::Arc;
+
+use common_time::Timestamp;
+use serde::{Deserialize, Serialize};
+use store_api::storage::{DeleteRange, SequenceNumber};
+
+/// A [DeleteRange] written to the region.
+///
+/// The tombstone only deletes rows written before it, whose sequences are less than
+/// the sequence of the tombstone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeTombstone {
+    /// Sequence of the write batch that contains the range.
+    pub sequence: SequenceNumber,
+    /// Rows to delete.
+    pub range: DeleteRange,
+}
+
+pub type RangeTombstonesRef = Arc<Vec<RangeTombstone>>;
+
+impl RangeTombstone {
+    pub fn new(sequence: SequenceNumber, range: DeleteRange) -> RangeTombstone {
+        RangeTombstone { sequence, range }
+    }
+
+    /// Returns true if the tombstone may delete rows of an SST with the inclusive
+    /// `time_range`.
+    pub fn may_overlap(&self, time_range: &Option<(Timestamp, Timestamp)>) -> bool {
+        // Files without time range may contain any timestamp.
+        let Some((start, end)) = time_range else { return true };
+        self.range.overlaps(start, end)
+    }
+}
@@ -32,8 +32,10 @@ use crate::file_purger::FilePurgerRef;
 use crate::memtable::{MemtableId, MemtableRef, MemtableVersion};
 use crate::metadata::RegionMetadataRef;
 use crate::schema::RegionSchemaRef;
-use crate::sst::{AccessLayerRef, FileMeta, LevelMetas};
+use crate::sst::{AccessLayerRef, FileMeta, LevelMetas, PinnedFilesRef};
 use crate::sync::CowCell;
+use crate::timeline::CommitTimelineRef;
+use crate::tombstone::{RangeTombstone, RangeTombstonesRef};
 pub const INIT_COMMITTED_SEQUENCE: u64 = 0;
 
 /// Controls version of in memory state for a region.
@@ -46,6 +48,8 @@ pub struct VersionControl {
     version: CowCell<Version>,
     /// Latest sequence that is committed and visible to user.
     committed_sequence: AtomicU64,
+    /// Commit times of sequences.
+    commit_timeline: CommitTimelineRef,
 }
 
 impl VersionControl {
@@ -54,6 +58,7 @@ impl VersionControl {
         VersionControl {
             version: CowCell::new(version),
             committed_sequence: AtomicU64::new(INIT_COMMITTED_SEQUENCE),
+            commit_timeline: CommitTimelineRef::default(),
         }
     }
 
@@ -89,6 +94,11 @@ impl VersionControl {
         self.committed_sequence.store(value, Ordering::Relaxed);
     }
 
+    #[inline]
+    pub fn commit_timeline(&self) -> &CommitTimelineRef {
+        &self.commit_timeline
+    }
+
     /// Freeze all mutable memtables.
     pub fn freeze_mutable(&self, new_memtable: MemtableRef) {
         let mut version_to_update = self.version.lock();
@@ -100,6 +110,22 @@ impl VersionControl {
         version_to_update.commit();
     }
 
+    /// Replaces all memtables with the empty `mutable` memtable and applies the [VersionEdit]
+    /// that removes all SSTs of the region.
+    pub fn truncate(&self, edit: VersionEdit, mutable: MemtableRef) {
+        let mut version_to_update = self.version.lock();
+        version_to_update.memtables = Arc::new(MemtableVersion::new(mutable));
+        version_to_update.apply_edit(edit);
+        version_to_update.commit();
+    }
+
+    /// Adds range tombstones written to the region.
+    pub fn add_range_tombstones(&self, tombstones: impl Iterator<Item = RangeTombstone>) {
+        let mut version_to_update = self.version.lock();
+        version_to_update.add_range_tombstones(tombstones);
+        version_to_update.commit();
+    }
+
     /// Apply [VersionEdit] to the version.
     pub fn apply_edit(&self, edit: VersionEdit) {
         let mut version_to_update = self.version.lock();
@@ -134,6 +160,9 @@ pub struct VersionEdit {
     pub flushed_sequence: Option<SequenceNumber>,
     pub manifest_version: ManifestVersion,
     pub max_memtable_id: Option<MemtableId>,
+    pub tombstones_to_add: Vec<RangeTombstone>,
+    pub tombstones_to_remove: Vec<RangeTombstone>,
+    pub min_readable_sequence: Option<SequenceNumber>,
 }
 
 pub type VersionControlRef = Arc<VersionControl>;
@@ -155,10 +184,16 @@ pub struct Version {
     memtables: MemtableVersionRef,
     /// SSTs of the region.
     ssts: LevelMetasRef,
+    /// Range tombstones that haven't been applied to all SSTs by compaction, including
+    /// tombstones that haven't been flushed.
+    range_tombstones: RangeTombstonesRef,
     /// Inclusive max sequence of flushed data.
     flushed_sequence: SequenceNumber,
     /// Current version of manifest.
     manifest_version: ManifestVersion,
+    /// Min sequence that reads could still use, data visible to older sequences may
+    /// have been removed by compaction.
+    min_readable_sequence: SequenceNumber,
     // TODO(yingwen): Maybe also store last sequence to this version when switching
     // version, so we can know the newest data can read from this version.
 }
@@ -172,7 +207,14 @@ impl Version {
             crate::scheduler::SchedulerConfig::default(),
             crate::file_purger::noop::NoopFilePurgeHandler,
         ));
-        Version::with_manifest_version(metadata, 0, memtable, sst_layer, file_purger)
+        Version::with_manifest_version(
+            metadata,
+            0,
+            memtable,
+            sst_layer,
+            file_purger,
+            Default::default(),
+        )
     }
 
     /// Create a new `Version` with given `metadata` and initial `manifest_version`.
@@ -182,13 +224,16 @@ impl Version {
         mutable_memtable: MemtableRef,
         sst_layer: AccessLayerRef,
         file_purger: FilePurgerRef,
+        pinned_files: PinnedFilesRef,
     ) -> Version {
         Version {
             metadata,
             memtables: Arc::new(MemtableVersion::new(mutable_memtable)),
-            ssts: Arc::new(LevelMetas::new(sst_layer, file_purger)),
+            ssts: Arc::new(LevelMetas::new(sst_layer, file_purger).with_pinned_files(pinned_files)),
+            range_tombstones: Arc::new(Vec::new()),
             flushed_sequence: 0,
             manifest_version,
+            min_readable_sequence: 0,
         }
     }
 
@@ -227,14 +272,28 @@ impl Version {
         self.flushed_sequence
     }
 
+    #[inline]
+    pub fn range_tombstones(&self) -> &RangeTombstonesRef {
+        &self.range_tombstones
+    }
+
+    #[inline]
+    pub fn min_readable_sequence(&self) -> SequenceNumber {
+        self.min_readable_sequence
+    }
+
     pub fn apply_checkpoint(
         &mut self,
         flushed_sequence: Option<SequenceNumber>,
         manifest_version: ManifestVersion,
         files: impl Iterator<Item = FileMeta>,
+        range_tombstones: Vec<RangeTombstone>,
+        min_readable_sequence: SequenceNumber,
     ) {
         self.flushed_sequence = flushed_sequence.unwrap_or(self.flushed_sequence);
         self.manifest_version = manifest_version;
+        self.min_readable_sequence = min_readable_sequence;
+        self.range_tombstones = Arc::new(range_tombstones);
         let ssts = self.ssts.merge(files, std::iter::empty());
         info!(
             "After applying checkpoint, region: {}, id: {}, flushed_sequence: {}, manifest_version: {}",
@@ -255,6 +314,9 @@ impl Version {
         if self.manifest_version < edit.manifest_version {
             self.manifest_version = edit.manifest_version;
         }
+        if let Some(min_readable_sequence) = edit.min_readable_sequence {
+            self.min_readable_sequence = self.min_readable_sequence.max(min_readable_sequence);
+        }
 
         if let Some(max_memtable_id) = edit.max_memtable_id {
             // Remove flushed memtables
@@ -263,6 +325,14 @@ impl Version {
             self.memtables = Arc::new(removed);
         }
 
+        // Tombstones in an edit of flush are already added by the writer, except during replay.
+        self.add_range_tombstones(edit.tombstones_to_add.into_iter());
+        if !edit.tombstones_to_remove.is_empty() {
+            let mut tombstones = self.range_tombstones.as_ref().clone();
+            tombstones.retain(|tombstone| !edit.tombstones_to_remove.contains(tombstone));
+            self.range_tombstones = Arc::new(tombstones);
+        }
+
         let handles_to_add = edit.files_to_add.into_iter();
         let merged_ssts = self
             .ssts
@@ -277,6 +347,20 @@ impl Version {
         self.ssts = Arc::new(merged_ssts);
     }
 
+    /// Adds `tombstones` that are not in the version yet.
+    pub fn add_range_tombstones(&mut self, tombstones: impl Iterator<Item = RangeTombstone>) {
+        let mut added = None;
+        for tombstone in tombstones {
+            let current = added.get_or_insert_with(|| self.range_tombstones.as_ref().clone());
+            if !current.contains(&tombstone) {
+                current.push(tombstone);
+            }
+        }
+        if let Some(tombstones) = added {
+            self.range_tombstones = Arc::new(tombstones);
+        }
+    }
+
     /// Updates metadata of the version.
     ///
     /// # Panics
@@ -331,4 +415,34 @@ mod tests {
         version_control.set_committed_sequence(12345);
         assert_eq!(12345, version_control.committed_sequence());
     }
+
+    #[test]
+    fn test_range_tombstones() {
+        let version_control = new_version_control();
+        let tombstones: Vec<_> = (1..=3)
+            .map(|sequence| RangeTombstone::new(sequence, Default::default()))
+            .collect();
+
+        version_control.add_range_tombstones(tombstones[..2].iter().cloned());
+        assert_eq!(
+            &tombstones[..2],
+            &version_control.current().range_tombstones()[..]
+        );
+
+        // Tombstones of a flushed edit have been added.
+        version_control.apply_edit(VersionEdit {
+            files_to_add: vec![],
+            files_to_remove: vec![],
+            flushed_sequence: Some(3),
+            manifest_version: 1,
+            max_memtable_id: None,
+            tombstones_to_add: tombstones[1..].to_vec(),
+            tombstones_to_remove: vec![tombstones[0].clone()],
+            min_readable_sequence: None,
+        });
+        assert_eq!(
+            &tombstones[1..],
+            &version_control.current().range_tombstones()[..]
+        );
+    }
 }
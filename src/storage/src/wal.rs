@@ -25,8 +25,8 @@ use store_api::storage::{RegionId, SequenceNumber};
 
 use crate::codec::{Decoder, Encoder};
 use crate::error::{
-    DecodeWalHeaderSnafu, EncodeWalHeaderSnafu, Error, MarkWalObsoleteSnafu, ReadWalSnafu, Result,
-    WalDataCorruptedSnafu, WriteWalSnafu,
+    DecodeJsonSnafu, DecodeWalHeaderSnafu, EncodeJsonSnafu, EncodeWalHeaderSnafu, Error,
+    MarkWalObsoleteSnafu, ReadWalSnafu, Result, WalDataCorruptedSnafu, WriteWalSnafu,
 };
 use crate::proto::wal::{self, WalHeader};
 use crate::write_batch::codec::{PayloadDecoder, PayloadEncoder};
@@ -108,6 +108,10 @@ impl<S: LogStore> Wal<S> {
     ) -> Result<Id> {
         if let Some(p) = payload {
             header.mutation_types = wal::gen_mutation_types(p);
+            if !p.delete_ranges.is_empty() {
+                header.delete_ranges =
+                    serde_json::to_vec(&p.delete_ranges).context(EncodeJsonSnafu)?;
+            }
         }
 
         let mut buf = vec![];
@@ -193,17 +197,21 @@ impl<S: LogStore> Wal<S> {
             }
         );
 
-        if header.mutation_types.is_empty() {
+        if header.mutation_types.is_empty() && header.delete_ranges.is_empty() {
             return Ok((seq_num, header, None));
         }
 
         let decoder = PayloadDecoder::new(&header.mutation_types);
-        let payload = decoder
+        let mut payload = decoder
             .decode(&input[data_pos..])
             .map_err(BoxedError::new)
             .context(ReadWalSnafu {
                 region_id: self.region_id(),
             })?;
+        if !header.delete_ranges.is_empty() {
+            payload.delete_ranges =
+                serde_json::from_slice(&header.delete_ranges).context(DecodeJsonSnafu)?;
+        }
 
         Ok((seq_num, header, Some(payload)))
     }
@@ -245,9 +253,13 @@ impl Decoder for WalHeaderDecoder {
 #[cfg(test)]
 mod tests {
     use common_test_util::temp_dir::create_temp_dir;
+    use common_time::Timestamp;
+    use datatypes::value::Value;
     use log_store::test_util;
+    use store_api::storage::{DeleteRange, WriteRequest};
 
     use super::*;
+    use crate::write_batch;
 
     #[tokio::test]
     pub async fn test_write_wal() {
@@ -288,11 +300,41 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn test_read_wal_delete_ranges() -> Result<()> {
+        let log_file_dir = create_temp_dir("wal_test");
+        let log_file_dir_path = log_file_dir.path().to_str().unwrap();
+        let log_store =
+            test_util::log_store_util::create_tmp_local_file_log_store(log_file_dir_path).await;
+        let wal = Wal::new(0, Arc::new(log_store));
+
+        let mut batch = write_batch::new_test_batch();
+        let range = DeleteRange {
+            keys: vec![("k1".to_string(), Value::UInt64(1))],
+            start: Some(Timestamp::new_millisecond(1000)),
+            end: None,
+        };
+        batch.delete_range(range).unwrap();
+        let header = WalHeader::with_last_manifest_version(111);
+        wal.write_to_wal(3, header, Some(batch.payload())).await?;
+
+        let mut stream = wal.read_from_wal(3).await?;
+        let (seq_num, header, payload) = stream.try_next().await?.unwrap();
+        assert_eq!(3, seq_num);
+        assert!(header.mutation_types.is_empty());
+        assert_eq!(batch.payload(), &payload.unwrap());
+        assert!(stream.try_next().await?.is_none());
+
+        Ok(())
+    }
+
     #[test]
     pub fn test_wal_header_codec() {
         let wal_header = WalHeader {
             last_manifest_version: 99999999,
             mutation_types: vec![],
+            delete_ranges: vec![],
+            commit_time: 1_680_000_000_000,
         };
 
         let mut buf: Vec<u8> = vec![];
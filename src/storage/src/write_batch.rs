@@ -21,12 +21,12 @@ use common_recordbatch::RecordBatch;
 use datatypes::schema::{ColumnSchema, SchemaRef};
 use datatypes::vectors::VectorRef;
 use snafu::{ensure, OptionExt, ResultExt};
-use store_api::storage::{OpType, WriteRequest};
+use store_api::storage::{DeleteRange, OpType, WriteRequest};
 
 use crate::error::{
     BatchMissingColumnSnafu, CreateDefaultSnafu, CreateRecordBatchSnafu, Error, HasNullSnafu,
-    MoreColumnThanExpectedSnafu, RequestTooLargeSnafu, Result, TypeMismatchSnafu,
-    UnequalLengthsSnafu, UnknownColumnSnafu,
+    InvalidDeleteRangeSnafu, MoreColumnThanExpectedSnafu, RequestTooLargeSnafu, Result,
+    TypeMismatchSnafu, UnequalLengthsSnafu, UnknownColumnSnafu,
 };
 
 /// Max number of updates in a write batch.
@@ -43,6 +43,8 @@ pub struct Payload {
     /// This schema doesn't contain internal columns.
     pub schema: SchemaRef,
     pub mutations: Vec<Mutation>,
+    /// Ranges to delete, they are stored in the WAL header instead of the arrow payload.
+    pub delete_ranges: Vec<DeleteRange>,
 }
 
 impl Payload {
@@ -51,13 +53,14 @@ impl Payload {
         Payload {
             schema,
             mutations: Vec::new(),
+            delete_ranges: Vec::new(),
         }
     }
 
-    /// Returns true if there is no mutation in the payload.
+    /// Returns true if there is no mutation or range to delete in the payload.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.mutations.is_empty()
+        self.mutations.is_empty() && self.delete_ranges.is_empty()
     }
 }
 
@@ -121,6 +124,14 @@ impl WriteRequest for WriteBatch {
 
         Ok(())
     }
+
+    fn delete_range(&mut self, range: DeleteRange) -> Result<()> {
+        self.validate_delete_range(&range)?;
+
+        self.payload.delete_ranges.push(range);
+
+        Ok(())
+    }
 }
 
 // WriteBatch pub methods.
@@ -227,6 +238,58 @@ impl WriteBatch {
         RecordBatch::new(self.schema().clone(), columns).context(CreateRecordBatchSnafu)
     }
 
+    /// Validates that `range` only specifies values of row key columns other than the
+    /// timestamp, and the time range is not empty.
+    fn validate_delete_range(&self, range: &DeleteRange) -> Result<()> {
+        let timestamp_index = self.schema().timestamp_index();
+        for (name, value) in &range.keys {
+            let index = self
+                .schema()
+                .column_index_by_name(name)
+                .context(UnknownColumnSnafu { name })?;
+            ensure!(
+                index < self.row_key_end && Some(index) != timestamp_index,
+                InvalidDeleteRangeSnafu {
+                    msg: format!("column {name} is not a tag column"),
+                }
+            );
+
+            let column_schema = &self.schema().column_schemas()[index];
+            if value.is_null() {
+                ensure!(
+                    column_schema.is_nullable(),
+                    HasNullSnafu {
+                        name: &column_schema.name,
+                    }
+                );
+            } else {
+                ensure!(
+                    value.data_type() == column_schema.data_type,
+                    TypeMismatchSnafu {
+                        name: &column_schema.name,
+                        expect: column_schema.data_type.clone(),
+                        given: value.data_type(),
+                    }
+                );
+            }
+        }
+
+        if let (Some(start), Some(end)) = (&range.start, &range.end) {
+            ensure!(
+                start < end,
+                InvalidDeleteRangeSnafu {
+                    msg: format!(
+                        "start {} is not less than end {}",
+                        start.to_iso8601_string(),
+                        end.to_iso8601_string()
+                    ),
+                }
+            );
+        }
+
+        Ok(())
+    }
+
     fn add_num_rows_to_mutate(&mut self, len: usize) -> Result<()> {
         let num_rows = self.num_rows_to_mutate + len;
         ensure!(
@@ -352,8 +415,10 @@ mod tests {
     use std::sync::Arc;
 
     use common_error::prelude::*;
+    use common_time::Timestamp;
     use datatypes::prelude::ScalarVector;
     use datatypes::type_id::LogicalTypeId;
+    use datatypes::value::Value;
     use datatypes::vectors::{
         BooleanVector, Int32Vector, Int64Vector, TimestampMillisecondVector, UInt64Vector,
     };
@@ -609,4 +674,65 @@ mod tests {
         );
         batch.delete(keys).unwrap();
     }
+
+    #[test]
+    fn test_write_batch_delete_range() {
+        let mut batch = new_test_batch();
+        batch.delete_range(DeleteRange::default()).unwrap();
+        let range = DeleteRange {
+            keys: vec![("k1".to_string(), Value::UInt64(1))],
+            start: Some(Timestamp::new_millisecond(0)),
+            end: Some(Timestamp::new_millisecond(1000)),
+        };
+        batch.delete_range(range.clone()).unwrap();
+        assert!(!batch.payload().is_empty());
+        assert!(batch.payload().mutations.is_empty());
+        assert_eq!(
+            &[DeleteRange::default(), range],
+            &batch.payload().delete_ranges[..]
+        );
+    }
+
+    #[test]
+    fn test_invalid_delete_range() {
+        let mut batch = new_test_batch();
+        let new_range = |name: &str, value: Value| DeleteRange {
+            keys: vec![(name.to_string(), value)],
+            ..Default::default()
+        };
+
+        let err = batch
+            .delete_range(new_range("k1", Value::Boolean(true)))
+            .unwrap_err();
+        check_err(err, "Type of column k1 does not match");
+        let err = batch
+            .delete_range(new_range("k1", Value::Null))
+            .unwrap_err();
+        check_err(err, "Column k1 is not null");
+        let err = batch
+            .delete_range(new_range(
+                "ts",
+                Value::Timestamp(Timestamp::new_millisecond(0)),
+            ))
+            .unwrap_err();
+        check_err(err, "column ts is not a tag column");
+        let err = batch
+            .delete_range(new_range("v1", Value::Boolean(true)))
+            .unwrap_err();
+        check_err(err, "column v1 is not a tag column");
+        let err = batch
+            .delete_range(new_range("v2", Value::Boolean(true)))
+            .unwrap_err();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+
+        let err = batch
+            .delete_range(DeleteRange {
+                keys: vec![],
+                start: Some(Timestamp::new_millisecond(1000)),
+                end: Some(Timestamp::new_second(1)),
+            })
+            .unwrap_err();
+        check_err(err, "is not less than end");
+        assert!(batch.payload().is_empty());
+    }
 }
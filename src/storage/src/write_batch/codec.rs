@@ -123,7 +123,11 @@ impl<'a> Decoder for PayloadDecoder<'a> {
             }
         );
 
-        Ok(Payload { schema, mutations })
+        Ok(Payload {
+            schema,
+            mutations,
+            delete_ranges: Vec::new(),
+        })
     }
 }
 
@@ -17,7 +17,7 @@ use datatypes::schema::{ColumnSchema, SchemaRef};
 use snafu::{ensure, ResultExt};
 
 use crate::error::{self, Result};
-use crate::schema::compat::CompatWrite;
+use crate::schema::compat::{self, CompatWrite};
 use crate::write_batch::{self, Mutation, WriteBatch};
 
 impl CompatWrite for WriteBatch {
@@ -71,7 +71,8 @@ impl CompatWrite for Mutation {
         let mut columns = Vec::with_capacity(dest_schema.num_columns());
         for column_schema in dest_schema.column_schemas() {
             if let Some(vector) = self.record_batch.column_by_name(&column_schema.name) {
-                columns.push(vector.clone());
+                // The column might be widened to another type after the mutation is created.
+                columns.push(compat::cast_column(vector, column_schema)?);
             } else {
                 // We need to fill the column by null or its default value.
                 let vector = write_batch::new_column_with_default_value(column_schema, num_rows)?;
@@ -104,7 +105,7 @@ mod tests {
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnDefaultConstraint, SchemaBuilder};
-    use datatypes::vectors::{Int32Vector, TimestampMillisecondVector, VectorRef};
+    use datatypes::vectors::{Int32Vector, Int64Vector, TimestampMillisecondVector, VectorRef};
     use store_api::storage::WriteRequest;
 
     use super::*;
@@ -171,6 +172,35 @@ mod tests {
         assert!(v0.only_null());
     }
 
+    #[test]
+    fn test_mutation_compat_write_widened_column() {
+        let mut put_data = new_put_data();
+        put_data.insert(
+            "v0".to_string(),
+            Arc::new(Int32Vector::from_slice([4, 5, 6])) as VectorRef,
+        );
+        let schema_old = new_test_schema(Some(None));
+        let mut batch = WriteBatch::new(schema_old.clone(), TEST_ROW_KEY_END);
+        batch.put(put_data).unwrap();
+
+        // Widen v0 from int32 to int64.
+        let mut column_schemas = schema_old.column_schemas().to_vec();
+        column_schemas[2] = ColumnSchema::new("v0", ConcreteDataType::int64_datatype(), true);
+        let schema = Arc::new(
+            SchemaBuilder::try_from(column_schemas)
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+
+        let mutation = &mut batch.payload.mutations[0];
+        mutation.compat_write(&schema).unwrap();
+
+        let v0 = mutation.record_batch.column_by_name("v0").unwrap();
+        let expect: VectorRef = Arc::new(Int64Vector::from_slice([4, 5, 6]));
+        assert_eq!(&expect, v0);
+    }
+
     #[test]
     fn test_write_batch_compat_write() {
         let schema_old = new_test_schema(None);
@@ -0,0 +1,365 @@
+// Copyright 2023 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Engine-wide write buffer that limits memory of memtables in all regions.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_telemetry::{logging, timer};
+use metrics::{gauge, increment_counter};
+use store_api::storage::RegionId;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::error::{self, Result};
+use crate::metrics::{
+    WRITE_BUFFER_BYTES, WRITE_BUFFER_FLUSH_TOTAL, WRITE_BUFFER_MUTABLE_BYTES, WRITE_REJECT_TOTAL,
+    WRITE_STALL_ELAPSED, WRITE_STALL_TOTAL,
+};
+
+/// A region that buffers writes in the write buffer.
+#[async_trait]
+pub trait BufferedRegion: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Freezes memtables of the region and schedules a flush without waiting for it.
+    async fn schedule_flush(&self) -> Result<()>;
+}
+
+/// Memory used by a region.
+struct RegionUsage {
+    region: Weak<dyn BufferedRegion>,
+    mutable_bytes: usize,
+    total_bytes: usize,
+}
+
+#[derive(Default)]
+struct Usage {
+    regions: HashMap<RegionId, RegionUsage>,
+    mutable_bytes: usize,
+    total_bytes: usize,
+}
+
+impl Usage {
+    fn update(&mut self, region_id: RegionId, mutable_bytes: usize, total_bytes: usize) {
+        let Some(region) = self.regions.get_mut(&region_id) else { return };
+        self.mutable_bytes = self.mutable_bytes + mutable_bytes - region.mutable_bytes;
+        self.total_bytes = self.total_bytes + total_bytes - region.total_bytes;
+        region.mutable_bytes = mutable_bytes;
+        region.total_bytes = total_bytes;
+    }
+
+    fn remove(&mut self, region_id: RegionId) {
+        if let Some(region) = self.regions.remove(&region_id) {
+            self.mutable_bytes -= region.mutable_bytes;
+            self.total_bytes -= region.total_bytes;
+        }
+    }
+
+    /// Picks the region with the largest mutable memtables to flush, and treats its
+    /// mutable memtables as frozen so the region won't be picked again before they
+    /// are frozen.
+    fn pick_region_to_flush(&mut self) -> Option<Arc<dyn BufferedRegion>> {
+        loop {
+            let (&region_id, usage) = self
+                .regions
+                .iter()
+                .max_by_key(|(_, usage)| usage.mutable_bytes)?;
+            if usage.mutable_bytes == 0 {
+                return None;
+            }
+
+            let Some(region) = usage.region.upgrade() else {
+                // The region is dropped without closing.
+                self.remove(region_id);
+                continue;
+            };
+            let total_bytes = usage.total_bytes;
+            self.update(region_id, 0, total_bytes);
+            return Some(region);
+        }
+    }
+}
+
+/// Tracks memory of memtables in all regions of the engine.
+///
+/// It flushes the largest regions once memtables use 7/8 of the write buffer, and
+/// stalls writes once memtables use up the write buffer. Stalled writes are rejected
+/// with a retryable error if flushes can't free memory in time.
+pub struct WriteBufferManager {
+    /// Max bytes of memtables, `None` for unlimited.
+    limit: Option<usize>,
+    /// Max time a write stalls.
+    stall_timeout: Duration,
+    usage: Mutex<Usage>,
+    /// Wakes stalled writes up once memory is released.
+    notify: Notify,
+}
+
+pub type WriteBufferManagerRef = Arc<WriteBufferManager>;
+
+impl WriteBufferManager {
+    pub fn new(limit: Option<usize>, stall_timeout: Duration) -> WriteBufferManager {
+        WriteBufferManager {
+            limit,
+            stall_timeout,
+            usage: Mutex::new(Usage::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Registers a region so the manager could flush it.
+    pub fn register(&self, region_id: RegionId, region: Weak<dyn BufferedRegion>) {
+        let mut usage = self.usage.lock().unwrap();
+        usage.remove(region_id);
+        usage.regions.insert(
+            region_id,
+            RegionUsage {
+                region,
+                mutable_bytes: 0,
+                total_bytes: 0,
+            },
+        );
+    }
+
+    /// Removes the region and releases its memory.
+    pub fn unregister(&self, region_id: RegionId) {
+        self.usage.lock().unwrap().remove(region_id);
+        self.notify.notify_waiters();
+    }
+
+    /// Updates memory used by memtables of the region, and flushes the largest region if
+    /// memtables use too much memory.
+    pub fn update(&self, region_id: RegionId, mutable_bytes: usize, total_bytes: usize) {
+        let released = {
+            let mut usage = self.usage.lock().unwrap();
+            let released = usage
+                .regions
+                .get(&region_id)
+                .map_or(false, |region| total_bytes < region.total_bytes);
+            usage.update(region_id, mutable_bytes, total_bytes);
+            released
+        };
+        if released {
+            self.notify.notify_waiters();
+        }
+
+        self.maybe_flush();
+    }
+
+    /// Returns bytes of memtables in all regions.
+    pub fn memory_usage(&self) -> usize {
+        self.usage.lock().unwrap().total_bytes
+    }
+
+    /// Returns true if memtables use up the write buffer.
+    pub fn is_full(&self) -> bool {
+        self.limit
+            .map_or(false, |limit| self.memory_usage() >= limit)
+    }
+
+    /// Waits until the write buffer isn't full before writing to the `region`.
+    ///
+    /// Returns an error if the write buffer is still full after the stall timeout.
+    pub async fn wait_for_room(&self, region: &str) -> Result<()> {
+        let Some(limit) = self.limit else { return Ok(()) };
+        if !self.is_full() {
+            return Ok(());
+        }
+        // Memtables may use up the write buffer before the usage reaches the flush limit
+        // if writes are large.
+        self.maybe_flush();
+
+        if !self.stall_timeout.is_zero() {
+            increment_counter!(WRITE_STALL_TOTAL);
+            let _timer = timer!(WRITE_STALL_ELAPSED);
+            logging::info!(
+                "Write stall, region: {}, memory_usage: {}, limit: {}",
+                region,
+                self.memory_usage(),
+                limit
+            );
+
+            let deadline = Instant::now() + self.stall_timeout;
+            loop {
+                // Registers the waiter before checking the usage so we won't miss any
+                // notification.
+                let notified = self.notify.notified();
+                if !self.is_full() {
+                    return Ok(());
+                }
+                if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        increment_counter!(WRITE_REJECT_TOTAL);
+        error::WriteBufferFullSnafu {
+            usage: self.memory_usage(),
+            limit,
+            region,
+        }
+        .fail()
+    }
+
+    /// Flushes the region with the largest mutable memtables in background if memtables
+    /// use more than 7/8 of the write buffer.
+    ///
+    /// Inspired by the write buffer manager of RocksDB, if more than half of the memory is
+    /// being flushed, flushing more regions may not help, so we wait for them instead.
+    fn maybe_flush(&self) {
+        let region = {
+            let mut usage = self.usage.lock().unwrap();
+            gauge!(WRITE_BUFFER_BYTES, usage.total_bytes as f64);
+            gauge!(WRITE_BUFFER_MUTABLE_BYTES, usage.mutable_bytes as f64);
+
+            let Some(limit) = self.limit else { return };
+            if usage.total_bytes < limit / 8 * 7 || usage.mutable_bytes < usage.total_bytes / 2 {
+                return;
+            }
+            usage.pick_region_to_flush()
+        };
+        let Some(region) = region else { return };
+
+        increment_counter!(WRITE_BUFFER_FLUSH_TOTAL);
+        logging::info!(
+            "Flush region {} as the write buffer is almost full, limit: {:?}",
+            region.name(),
+            self.limit
+        );
+        common_runtime::spawn_bg(async move {
+            if let Err(e) = region.schedule_flush().await {
+                logging::error!(e; "Failed to flush region {}", region.name());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use common_error::prelude::*;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockRegion {
+        flushes: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl BufferedRegion for MockRegion {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn schedule_flush(&self) -> Result<()> {
+            self.flushes.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    fn register_region(manager: &WriteBufferManager, region_id: RegionId) -> Arc<MockRegion> {
+        let region = Arc::new(MockRegion::default());
+        let weak = Arc::downgrade(&region);
+        manager.register(region_id, weak);
+        region
+    }
+
+    async fn wait_flushes(region: &MockRegion, expect: usize) {
+        for _ in 0..100 {
+            if region.flushes.load(Ordering::Relaxed) >= expect {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(expect, region.flushes.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_flush_largest_region() {
+        let manager = WriteBufferManager::new(Some(800), Duration::ZERO);
+        let region1 = register_region(&manager, 1);
+        let region2 = register_region(&manager, 2);
+
+        manager.update(1, 300, 300);
+        manager.update(2, 200, 200);
+        manager.update(3, 1000, 1000);
+        // Unregistered regions are ignored.
+        assert_eq!(500, manager.memory_usage());
+
+        // Reaches the flush limit.
+        manager.update(2, 400, 400);
+        wait_flushes(&region2, 1).await;
+        // Region 2 is being flushed, and region 1 is too small to flush.
+        manager.update(1, 310, 310);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(0, region1.flushes.load(Ordering::Relaxed));
+        assert_eq!(1, region2.flushes.load(Ordering::Relaxed));
+
+        // Flush of region 2 releases memory.
+        manager.update(2, 0, 0);
+        manager.unregister(1);
+        assert_eq!(0, manager.memory_usage());
+    }
+
+    #[tokio::test]
+    async fn test_reject_write() {
+        let manager = WriteBufferManager::new(Some(100), Duration::ZERO);
+        let _region = register_region(&manager, 1);
+        manager.wait_for_room("r1").await.unwrap();
+
+        manager.update(1, 0, 100);
+        assert!(manager.is_full());
+        let err = manager.wait_for_room("r1").await.unwrap_err();
+        assert_eq!(StatusCode::StorageBusy, err.status_code());
+        assert!(err.status_code().is_retryable());
+
+        // Writes are never rejected without limit.
+        let manager = WriteBufferManager::new(None, Duration::ZERO);
+        let _region = register_region(&manager, 1);
+        manager.update(1, 0, 100);
+        manager.wait_for_room("r1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stall_write() {
+        let manager = Arc::new(WriteBufferManager::new(Some(100), Duration::from_secs(10)));
+        let region = register_region(&manager, 1);
+        manager.update(1, 100, 100);
+        wait_flushes(&region, 1).await;
+
+        let waiter = {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.wait_for_room("r1").await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        // The flush releases memory.
+        manager.update(1, 0, 0);
+        waiter.await.unwrap().unwrap();
+
+        // Stalled writes are rejected after the timeout.
+        let manager = WriteBufferManager::new(Some(100), Duration::from_millis(10));
+        let _region = register_region(&manager, 1);
+        manager.update(1, 0, 100);
+        assert!(manager.wait_for_room("r1").await.is_err());
+    }
+}
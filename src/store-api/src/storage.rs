@@ -32,11 +32,15 @@ pub use datatypes::schema::{
 
 pub use self::chunk::{Chunk, ChunkReader};
 pub use self::descriptors::*;
-pub use self::engine::{CreateOptions, EngineContext, OpenOptions, StorageEngine};
+pub use self::engine::{
+    CompactionStrategy, CreateOptions, EngineContext, OpenOptions, SstCompression, StorageEngine,
+    DEFAULT_TWCS_MAX_FILES_IN_WINDOW,
+};
 pub use self::metadata::RegionMeta;
-pub use self::region::{FlushContext, Region, WriteContext};
+pub use self::region::{CompactContext, FlushContext, Region, WriteContext};
 pub use self::requests::{
-    AddColumn, AlterOperation, AlterRequest, GetRequest, ScanRequest, WriteRequest,
+    AddColumn, AlterOperation, AlterOptions, AlterRequest, DeleteRange, GetRequest, ModifyColumn,
+    ScanRequest, WriteRequest,
 };
 pub use self::responses::{GetResponse, ScanResponse, WriteResponse};
 pub use self::snapshot::{ReadContext, Snapshot};
@@ -22,6 +22,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
+use serde::{Deserialize, Serialize};
 
 use crate::storage::descriptors::RegionDescriptor;
 use crate::storage::region::Region;
@@ -89,6 +90,12 @@ pub struct CreateOptions {
     /// Region SST files TTL
     pub ttl: Option<Duration>,
     pub compaction_time_window: Option<i64>,
+    /// Strategy to compact SST files of the region
+    pub compaction_strategy: CompactionStrategy,
+    /// Compression codec of SST files of the region
+    pub sst_compression: SstCompression,
+    /// Skip deduplicating rows by key on read, the region only accepts puts
+    pub append_mode: bool,
 }
 
 /// Options to open a region.
@@ -101,4 +108,37 @@ pub struct OpenOptions {
     /// Region SST files TTL
     pub ttl: Option<Duration>,
     pub compaction_time_window: Option<i64>,
+    /// Strategy to compact SST files of the region
+    pub compaction_strategy: CompactionStrategy,
+    /// Compression codec of SST files of the region
+    pub sst_compression: SstCompression,
+    /// Skip deduplicating rows by key on read, the region only accepts puts
+    pub append_mode: bool,
+    /// Open the region read-only at this named snapshot
+    pub snapshot: Option<String>,
+}
+
+/// Default max number of level 1 files in a time window before compacting them.
+pub const DEFAULT_TWCS_MAX_FILES_IN_WINDOW: usize = 4;
+
+/// Strategy to compact SST files of a region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompactionStrategy {
+    /// Only compacts level 0 files into level 1 files partitioned by time windows.
+    #[default]
+    Simple,
+    /// Time-window compaction, which also compacts level 1 files in the same time window
+    /// once there are more than `max_files_in_window` files in that window.
+    Twcs { max_files_in_window: usize },
+}
+
+/// Compression codec of SST files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SstCompression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    #[default]
+    Zstd,
 }
@@ -37,7 +37,7 @@ use common_error::ext::ErrorExt;
 
 use crate::storage::engine::OpenOptions;
 use crate::storage::metadata::RegionMeta;
-use crate::storage::requests::{AlterRequest, WriteRequest};
+use crate::storage::requests::{AlterOptions, AlterRequest, WriteRequest};
 use crate::storage::responses::WriteResponse;
 use crate::storage::snapshot::{ReadContext, Snapshot};
 use crate::storage::RegionId;
@@ -73,12 +73,29 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
 
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
 
+    /// Replaces options of the region, the new options take effect on subsequent
+    /// flushes and compactions.
+    async fn alter_options(&self, options: AlterOptions) -> Result<(), Self::Error>;
+
     async fn close(&self) -> Result<(), Self::Error>;
 
     fn disk_usage_bytes(&self) -> u64;
 
     /// Flush memtable of the region to disk.
     async fn flush(&self, ctx: &FlushContext) -> Result<(), Self::Error>;
+
+    /// Compact SST files of the region.
+    async fn compact(&self, ctx: &CompactContext) -> Result<(), Self::Error>;
+
+    /// Removes all rows of the region, including rows in memtables, SST files and the WAL.
+    async fn truncate(&self) -> Result<(), Self::Error>;
+
+    /// Saves the flushed data of the region as a named snapshot, whose SST files are kept
+    /// until the snapshot is dropped.
+    async fn save_snapshot(&self, name: &str) -> Result<(), Self::Error>;
+
+    /// Drops the named snapshot. Returns false if the snapshot doesn't exist.
+    async fn drop_snapshot(&self, name: &str) -> Result<bool, Self::Error>;
 }
 
 /// Context for write operations.
@@ -104,3 +121,17 @@ impl Default for FlushContext {
         FlushContext { wait: true }
     }
 }
+
+/// Context for compaction operations.
+#[derive(Debug, Clone)]
+pub struct CompactContext {
+    /// If true, the compaction will wait until the compaction is done.
+    /// Default: true
+    pub wait: bool,
+}
+
+impl Default for CompactContext {
+    fn default() -> CompactContext {
+        CompactContext { wait: true }
+    }
+}
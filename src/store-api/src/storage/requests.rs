@@ -13,12 +13,19 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use common_error::ext::ErrorExt;
 use common_query::logical_plan::Expr;
+use common_time::Timestamp;
+use datatypes::value::Value;
 use datatypes::vectors::VectorRef;
+use serde::{Deserialize, Serialize};
 
-use crate::storage::{ColumnDescriptor, RegionDescriptor, SequenceNumber};
+use crate::storage::{
+    ColumnDefaultConstraint, ColumnDescriptor, ColumnDescriptorBuilder, CompactionStrategy,
+    ConcreteDataType, RegionDescriptor, SequenceNumber, SstCompression,
+};
 
 /// Write request holds a collection of updates to apply to a region.
 ///
@@ -36,6 +43,36 @@ pub trait WriteRequest: Send {
     ///
     /// `keys` are the row keys, in columnar format, of the rows to delete.
     fn delete(&mut self, keys: HashMap<String, VectorRef>) -> Result<(), Self::Error>;
+
+    /// Delete all rows in the `range`, without knowing their row keys.
+    fn delete_range(&mut self, range: DeleteRange) -> Result<(), Self::Error>;
+}
+
+/// Rows to delete by a range tombstone.
+///
+/// A row is in the range if its row key columns equal to all `keys` and its timestamp
+/// is in `[start, end)`. Empty `keys` match rows of all series and a bound that is `None`
+/// is unbounded, so the default range contains all rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteRange {
+    /// Values of row key columns (excluding the timestamp) by column name.
+    pub keys: Vec<(String, Value)>,
+    /// Inclusive start of the timestamp.
+    pub start: Option<Timestamp>,
+    /// Exclusive end of the timestamp.
+    pub end: Option<Timestamp>,
+}
+
+impl DeleteRange {
+    /// Returns true if `ts` is in the time range.
+    pub fn contains_timestamp(&self, ts: &Timestamp) -> bool {
+        self.start.map_or(true, |start| start <= *ts) && self.end.map_or(true, |end| *ts < end)
+    }
+
+    /// Returns true if the time range overlaps the inclusive range `[start, end]`.
+    pub fn overlaps(&self, start: &Timestamp, end: &Timestamp) -> bool {
+        self.start.map_or(true, |s| s <= *end) && self.end.map_or(true, |e| *start < e)
+    }
 }
 
 #[derive(Default)]
@@ -45,14 +82,30 @@ pub struct ScanRequest {
     /// Default is None. Only returns data whose sequence number is less than or
     /// equal to the `sequence`.
     pub sequence: Option<SequenceNumber>,
+    /// Only returns data committed at or before this time, None for latest data.
+    ///
+    /// Commit times are tracked in seconds, and the smaller sequence wins if
+    /// `sequence` is also set. The region only knows commit times of sequences
+    /// written or replayed from the WAL since it was opened, and forgets the oldest
+    /// ones after about one day, reading at an unknown time fails.
+    pub commit_time: Option<Timestamp>,
     /// Indices of columns to read, `None` to read all columns.
     pub projection: Option<Vec<usize>>,
     /// Filters pushed down
     pub filters: Vec<Expr>,
 }
 
-#[derive(Debug)]
-pub struct GetRequest {}
+/// Request to get a row by its row key.
+#[derive(Debug, Default)]
+pub struct GetRequest {
+    /// Max sequence number to read, None for latest sequence.
+    pub sequence: Option<SequenceNumber>,
+    /// Indices of columns to read, `None` to read all columns.
+    pub projection: Option<Vec<usize>>,
+    /// Values of all row key columns, including the timestamp, in the same order as
+    /// the row key of the region. Types of the values should be the same as the columns.
+    pub row_key: Vec<Value>,
+}
 
 /// Operation to add a column.
 #[derive(Debug, Clone)]
@@ -63,6 +116,20 @@ pub struct AddColumn {
     pub is_key: bool,
 }
 
+/// Operation to modify a column.
+#[derive(Debug, Clone)]
+pub struct ModifyColumn {
+    /// Name of the column to modify.
+    pub name: String,
+    /// New data type of the column, values of the old type should be able to
+    /// widen to this type.
+    pub data_type: ConcreteDataType,
+    /// Is the column nullable after modification.
+    pub is_nullable: bool,
+    /// New default constraint of the column.
+    pub default_constraint: Option<ColumnDefaultConstraint>,
+}
+
 /// Operation to alter a region.
 #[derive(Debug, Clone)]
 pub enum AlterOperation {
@@ -76,6 +143,12 @@ pub enum AlterOperation {
         /// Name of columns to drop.
         names: Vec<String>,
     },
+    /// Modify the type or default value of columns, only value columns are allowed
+    /// to modify.
+    ModifyColumns {
+        /// Columns to modify.
+        columns: Vec<ModifyColumn>,
+    },
 }
 
 impl AlterOperation {
@@ -88,6 +161,9 @@ impl AlterOperation {
             AlterOperation::DropColumns { names } => {
                 Self::apply_drop(names, descriptor);
             }
+            AlterOperation::ModifyColumns { columns } => {
+                Self::apply_modify(columns, descriptor);
+            }
         }
     }
 
@@ -119,6 +195,30 @@ impl AlterOperation {
             cf.columns.retain(|col| !name_set.contains(&col.name));
         }
     }
+
+    /// Modify value columns in the [RegionDescriptor], the id and comment of the column
+    /// are kept.
+    ///
+    /// Non-value columns in `columns` would be ignored.
+    ///
+    /// # Panics
+    /// Panics if the default constraint is invalid for the new column, which should have
+    /// been validated by the region.
+    fn apply_modify(columns: &[ModifyColumn], descriptor: &mut RegionDescriptor) {
+        let cfs = std::iter::once(&mut descriptor.default_cf).chain(&mut descriptor.extra_cfs);
+        for cf in cfs {
+            for col in &mut cf.columns {
+                let Some(modify) = columns.iter().find(|modify| modify.name == col.name) else { continue };
+
+                *col = ColumnDescriptorBuilder::new(col.id, &col.name, modify.data_type.clone())
+                    .is_nullable(modify.is_nullable)
+                    .default_constraint(modify.default_constraint.clone())
+                    .comment(&col.comment)
+                    .build()
+                    .unwrap();
+            }
+        }
+    }
 }
 
 /// Alter region request.
@@ -130,6 +230,21 @@ pub struct AlterRequest {
     pub version: u32,
 }
 
+/// Options of a region that could be altered at runtime.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlterOptions {
+    /// Region memtable max size in bytes, the engine's default size is used if `None`.
+    pub write_buffer_size: Option<usize>,
+    /// Region SST files TTL, SST files never expire if `None`.
+    pub ttl: Option<Duration>,
+    /// Time window for compaction, it's inferred from SST files if `None`.
+    pub compaction_time_window: Option<i64>,
+    /// Strategy to compact SST files.
+    pub compaction_strategy: CompactionStrategy,
+    /// Compression codec of SST files written afterwards.
+    pub sst_compression: SstCompression,
+}
+
 #[cfg(test)]
 mod tests {
     use datatypes::prelude::*;
@@ -207,4 +322,66 @@ mod tests {
         assert_eq!(1, desc.row_key.columns.len());
         assert_eq!(1, desc.default_cf.columns.len());
     }
+
+    #[test]
+    fn test_alter_modify_columns() {
+        let mut desc = new_region_descriptor();
+
+        let op = AlterOperation::ModifyColumns {
+            columns: vec![
+                ModifyColumn {
+                    name: String::from("2"),
+                    data_type: ConcreteDataType::float64_datatype(),
+                    is_nullable: true,
+                    default_constraint: Some(ColumnDefaultConstraint::Value(1.0f64.into())),
+                },
+                // Key columns are ignored.
+                ModifyColumn {
+                    name: String::from("1"),
+                    data_type: ConcreteDataType::float64_datatype(),
+                    is_nullable: true,
+                    default_constraint: None,
+                },
+            ],
+        };
+        op.apply(&mut desc);
+
+        assert_eq!(
+            ConcreteDataType::int64_datatype(),
+            desc.row_key.timestamp.data_type
+        );
+        assert_eq!(1, desc.default_cf.columns.len());
+        let col = &desc.default_cf.columns[0];
+        assert_eq!(2, col.id);
+        assert_eq!("2", col.name);
+        assert_eq!(ConcreteDataType::float64_datatype(), col.data_type);
+        assert!(col.is_nullable());
+        assert_eq!(
+            Some(&ColumnDefaultConstraint::Value(1.0f64.into())),
+            col.default_constraint()
+        );
+    }
+
+    #[test]
+    fn test_delete_range_time() {
+        let range = DeleteRange {
+            keys: vec![],
+            start: Some(Timestamp::new_millisecond(1000)),
+            end: Some(Timestamp::new_millisecond(2000)),
+        };
+        assert!(range.contains_timestamp(&Timestamp::new_millisecond(1000)));
+        assert!(range.contains_timestamp(&Timestamp::new_second(1)));
+        assert!(!range.contains_timestamp(&Timestamp::new_millisecond(2000)));
+        assert!(!range.contains_timestamp(&Timestamp::new_millisecond(999)));
+
+        let ts = |v| Timestamp::new_millisecond(v);
+        assert!(range.overlaps(&ts(0), &ts(1000)));
+        assert!(range.overlaps(&ts(1999), &ts(3000)));
+        assert!(!range.overlaps(&ts(0), &ts(999)));
+        assert!(!range.overlaps(&ts(2000), &ts(3000)));
+
+        let all = DeleteRange::default();
+        assert!(all.contains_timestamp(&ts(i64::MIN)));
+        assert!(all.overlaps(&ts(i64::MIN), &ts(i64::MAX)));
+    }
 }
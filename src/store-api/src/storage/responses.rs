@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::storage::chunk::Chunk;
+
 #[derive(Debug)]
 pub struct WriteResponse {}
 
@@ -22,4 +24,8 @@ pub struct ScanResponse<R> {
 }
 
 #[derive(Debug)]
-pub struct GetResponse {}
+pub struct GetResponse {
+    /// The row found, in a chunk with only one row. `None` if the row doesn't
+    /// exist or is deleted.
+    pub chunk: Option<Chunk>,
+}
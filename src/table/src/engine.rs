@@ -20,7 +20,10 @@ use store_api::storage::RegionId;
 
 use crate::error::Result;
 use crate::metadata::TableId;
-use crate::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
+use crate::requests::{
+    AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest, RestoreTableRequest,
+    TruncateTableRequest,
+};
 use crate::TableRef;
 pub mod manager;
 
@@ -104,6 +107,21 @@ pub trait TableEngine: Send + Sync {
     /// Drops the given table. Return true if the table is dropped, or false if the table doesn't exist.
     async fn drop_table(&self, ctx: &EngineContext, request: DropTableRequest) -> Result<bool>;
 
+    /// Removes all rows of the given table but keeps the table. Return true if the table is
+    /// truncated, or false if the table doesn't exist.
+    async fn truncate_table(
+        &self,
+        ctx: &EngineContext,
+        request: TruncateTableRequest,
+    ) -> Result<bool>;
+
+    /// Opens a read-only table at the snapshot of the source table in the request.
+    async fn restore_table(
+        &self,
+        ctx: &EngineContext,
+        request: RestoreTableRequest,
+    ) -> Result<TableRef>;
+
     /// Close the table.
     async fn close(&self) -> Result<()>;
 }
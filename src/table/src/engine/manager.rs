@@ -45,6 +45,17 @@ impl MemoryTableEngineManager {
         MemoryTableEngineManager::alias(engine.name().to_string(), engine)
     }
 
+    /// Creates a manager with all `engines`, using their names as keys.
+    pub fn with(engines: Vec<TableEngineRef>) -> Self {
+        let engines = engines
+            .into_iter()
+            .map(|engine| (engine.name().to_string(), engine))
+            .collect::<HashMap<_, _>>();
+        let engines = RwLock::new(engines);
+
+        MemoryTableEngineManager { engines }
+    }
+
     pub fn alias(name: String, engine: TableEngineRef) -> Self {
         let mut engines = HashMap::new();
         engines.insert(name, engine);
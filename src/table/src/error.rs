@@ -94,6 +94,30 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display(
+        "Not allowed to modify index column {} in table {}",
+        column_name,
+        table_name
+    ))]
+    ModifyColumnInIndex {
+        column_name: String,
+        table_name: String,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Failed to modify column {} in table {}, reason: {}",
+        column_name,
+        table_name,
+        reason
+    ))]
+    InvalidModifyColumn {
+        column_name: String,
+        table_name: String,
+        reason: String,
+        location: Location,
+    },
+
     #[snafu(display(
         "Failed to build column descriptor for table: {}, column: {}, source: {}",
         table_name,
@@ -130,9 +154,10 @@ impl ErrorExt for Error {
             | Error::PollStream { .. }
             | Error::SchemaConversion { .. }
             | Error::TableProjection { .. } => StatusCode::EngineExecuteQuery,
-            Error::RemoveColumnInIndex { .. } | Error::BuildColumnDescriptor { .. } => {
-                StatusCode::InvalidArguments
-            }
+            Error::RemoveColumnInIndex { .. }
+            | Error::ModifyColumnInIndex { .. }
+            | Error::InvalidModifyColumn { .. }
+            | Error::BuildColumnDescriptor { .. } => StatusCode::InvalidArguments,
             Error::TablesRecordBatch { .. } => StatusCode::Unexpected,
             Error::ColumnExists { .. } => StatusCode::TableColumnExists,
             Error::SchemaBuild { source, .. } => source.status_code(),
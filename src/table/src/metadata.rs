@@ -22,11 +22,11 @@ pub use datatypes::error::{Error as ConvertError, Result as ConvertResult};
 use datatypes::schema::{ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{ColumnDescriptor, ColumnDescriptorBuilder, ColumnId};
 
 use crate::error::{self, Result};
-use crate::requests::{AddColumnRequest, AlterKind, TableOptions};
+use crate::requests::{AddColumnRequest, AlterKind, ModifyColumnRequest, TableOptions};
 
 pub type TableId = u32;
 pub type TableVersion = u64;
@@ -161,6 +161,19 @@ impl TableMeta {
         match alter_kind {
             AlterKind::AddColumns { columns } => self.add_columns(table_name, columns),
             AlterKind::DropColumns { names } => self.remove_columns(table_name, names),
+            AlterKind::ModifyColumns { columns } => self.modify_columns(table_name, columns),
+            AlterKind::SetTableOptions { options } => {
+                let mut new_options = HashMap::from(&self.options);
+                new_options.extend(options.iter().map(|(k, v)| (k.clone(), v.clone())));
+                self.alter_options(&new_options)
+            }
+            AlterKind::UnsetTableOptions { keys } => {
+                let mut new_options = HashMap::from(&self.options);
+                for key in keys {
+                    new_options.remove(key);
+                }
+                self.alter_options(&new_options)
+            }
             // No need to rebuild table meta when renaming tables.
             AlterKind::RenameTable { .. } => Ok(TableMetaBuilder::default()),
         }
@@ -340,6 +353,99 @@ impl TableMeta {
 
         Ok(meta_builder)
     }
+
+    fn alter_options(&self, options: &HashMap<String, String>) -> Result<TableMetaBuilder> {
+        let options = TableOptions::try_from(options)?;
+        // Rows written in append mode may have duplicate keys, so the mode is fixed once
+        // the table is created.
+        ensure!(
+            options.append_mode == self.options.append_mode,
+            error::UnsupportedSnafu {
+                operation: "altering append_mode of a table",
+            }
+        );
+
+        let mut meta_builder = self.new_meta_builder();
+        meta_builder
+            .schema(self.schema.clone())
+            .primary_key_indices(self.primary_key_indices.clone())
+            .options(options);
+
+        Ok(meta_builder)
+    }
+
+    fn modify_columns(
+        &self,
+        table_name: &str,
+        requests: &[ModifyColumnRequest],
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let mut meta_builder = self.new_meta_builder();
+        let mut columns = table_schema.column_schemas().to_vec();
+
+        let timestamp_index = table_schema.timestamp_index();
+        for request in requests {
+            let new_column = &request.column_schema;
+            let column_name = &new_column.name;
+            let index = table_schema
+                .column_index_by_name(column_name)
+                .with_context(|| error::ColumnNotExistsSnafu {
+                    column_name,
+                    table_name,
+                })?;
+            // Only field columns are allowed to modify, as the storage sorts rows by key columns.
+            ensure!(
+                !self.primary_key_indices.contains(&index) && Some(index) != timestamp_index,
+                error::ModifyColumnInIndexSnafu {
+                    column_name,
+                    table_name,
+                }
+            );
+
+            let old_column = &columns[index];
+            ensure!(
+                old_column.data_type.can_widen_to(&new_column.data_type),
+                error::InvalidModifyColumnSnafu {
+                    column_name,
+                    table_name,
+                    reason: format!(
+                        "could not change type from {:?} to {:?}",
+                        old_column.data_type, new_column.data_type
+                    ),
+                }
+            );
+            ensure!(
+                new_column.is_nullable() || !old_column.is_nullable(),
+                error::InvalidModifyColumnSnafu {
+                    column_name,
+                    table_name,
+                    reason: "could not change a nullable column to not null",
+                }
+            );
+
+            columns[index] = new_column.clone();
+        }
+
+        let mut builder = SchemaBuilder::try_from_columns(columns)
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!("Failed to convert column schemas into schema for table {table_name}"),
+            })?
+            // Also bump the schema version.
+            .version(table_schema.version() + 1);
+        for (k, v) in table_schema.metadata().iter() {
+            builder = builder.add_metadata(k, v);
+        }
+        let new_schema = builder.build().with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {table_name} cannot modify columns"),
+        })?;
+
+        // Columns are modified in place, so indices of primary key columns are unchanged.
+        meta_builder
+            .schema(Arc::new(new_schema))
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        Ok(meta_builder)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Builder)]
@@ -494,9 +600,13 @@ impl TryFrom<RawTableInfo> for TableInfo {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use common_base::readable_size::ReadableSize;
     use common_error::prelude::*;
     use datatypes::data_type::ConcreteDataType;
-    use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
+    use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, Schema, SchemaBuilder};
+    use datatypes::value::Value;
 
     use super::*;
 
@@ -681,6 +791,172 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_modify_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema.clone())
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let column_schema = ColumnSchema::new("col2", ConcreteDataType::int64_datatype(), true)
+            .with_default_constraint(Some(ColumnDefaultConstraint::Value(Value::Int64(1))))
+            .unwrap();
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_schema: column_schema.clone(),
+            }],
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(meta.region_numbers, new_meta.region_numbers);
+        assert_eq!(schema.version() + 1, new_meta.schema.version());
+        assert_eq!(
+            &column_schema,
+            new_meta.schema.column_schema_by_name("col2").unwrap()
+        );
+        assert_eq!(&[0], &new_meta.primary_key_indices[..]);
+        assert_eq!(&[1, 2], &new_meta.value_indices[..]);
+        assert_eq!(
+            schema.timestamp_column(),
+            new_meta.schema.timestamp_column()
+        );
+    }
+
+    #[test]
+    fn test_modify_invalid_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let modify_column = |column_schema| {
+            let alter_kind = AlterKind::ModifyColumns {
+                columns: vec![ModifyColumnRequest { column_schema }],
+            };
+            meta.builder_with_alter_kind("my_table", &alter_kind)
+                .err()
+                .unwrap()
+        };
+
+        // Modify unknown column.
+        let err = modify_column(ColumnSchema::new(
+            "unknown",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+
+        // Modify column in primary key.
+        let err = modify_column(ColumnSchema::new(
+            "col1",
+            ConcreteDataType::int64_datatype(),
+            true,
+        ));
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Modify timestamp column.
+        let err = modify_column(ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_second_datatype(),
+            false,
+        ));
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Narrow the type.
+        let err = modify_column(ColumnSchema::new(
+            "col2",
+            ConcreteDataType::int16_datatype(),
+            true,
+        ));
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        // Change a nullable column to not null.
+        let err = modify_column(ColumnSchema::new(
+            "col2",
+            ConcreteDataType::int64_datatype(),
+            false,
+        ));
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
+    #[test]
+    fn test_alter_table_options() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([
+                ("ttl".to_string(), "7d".to_string()),
+                ("write_buffer_size".to_string(), "1MB".to_string()),
+            ]),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(meta.schema, new_meta.schema);
+        assert_eq!(meta.primary_key_indices, new_meta.primary_key_indices);
+        assert_eq!(
+            Some(Duration::from_secs(7 * 24 * 60 * 60)),
+            new_meta.options.ttl
+        );
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+
+        let alter_kind = AlterKind::UnsetTableOptions {
+            keys: vec!["ttl".to_string()],
+        };
+        let new_meta = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(None, new_meta.options.ttl);
+        assert_eq!(
+            Some(ReadableSize::mb(1)),
+            new_meta.options.write_buffer_size
+        );
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), "a week".to_string())]),
+        };
+        let err = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("append_mode".to_string(), "true".to_string())]),
+        };
+        let err = new_meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::Unsupported, err.status_code());
+    }
+
     #[test]
     fn test_add_existing_column() {
         let schema = Arc::new(new_test_schema());
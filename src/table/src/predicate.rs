@@ -16,8 +16,10 @@ use common_query::logical_plan::{DfExpr, Expr};
 use common_telemetry::{error, warn};
 use common_time::range::TimestampRange;
 use common_time::Timestamp;
-use datafusion::parquet::file::metadata::RowGroupMetaData;
+use datafusion::parquet::arrow::arrow_reader::{RowSelection, RowSelector};
+use datafusion::parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
 use datafusion::physical_optimizer::pruning::PruningPredicate;
+pub use datafusion::physical_optimizer::pruning::PruningStatistics;
 use datafusion_common::ToDFSchema;
 use datafusion_expr::{Between, BinaryExpr, Operator};
 use datafusion_physical_expr::create_physical_expr;
@@ -25,7 +27,7 @@ use datafusion_physical_expr::execution_props::ExecutionProps;
 use datatypes::schema::SchemaRef;
 use datatypes::value::scalar_value_to_timestamp;
 
-use crate::predicate::stats::RowGroupPruningStatistics;
+use crate::predicate::stats::{PagePruningStatistics, RowGroupPruningStatistics};
 
 mod stats;
 
@@ -43,18 +45,50 @@ impl Predicate {
         Self { exprs: vec![] }
     }
 
+    /// Returns the filter expressions of the predicate.
+    pub fn exprs(&self) -> &[Expr] {
+        &self.exprs
+    }
+
+    /// Returns a predicate that only keeps expressions whose columns are all accepted by
+    /// `keep_column`.
+    pub fn retain_columns(&self, keep_column: impl Fn(&str) -> bool) -> Predicate {
+        let exprs = self
+            .exprs
+            .iter()
+            .filter(|expr| {
+                expr.df_expr()
+                    .to_columns()
+                    .map(|columns| columns.iter().all(|column| keep_column(&column.name)))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+        Predicate { exprs }
+    }
+
     pub fn prune_row_groups(
         &self,
         schema: SchemaRef,
         row_groups: &[RowGroupMetaData],
     ) -> Vec<bool> {
-        let mut res = vec![true; row_groups.len()];
-        let arrow_schema = (*schema.arrow_schema()).clone();
-        let df_schema = arrow_schema.clone().to_dfschema_ref();
-        let df_schema = match df_schema {
+        let stats = RowGroupPruningStatistics::new(row_groups, &schema);
+        self.prune_with_stats(&schema, &stats)
+    }
+
+    /// Prunes containers, such as row groups or files, whose column statistics are provided
+    /// by `stats`. Returns whether each container may contain rows matching the predicate.
+    pub fn prune_with_stats<S: PruningStatistics>(
+        &self,
+        schema: &SchemaRef,
+        stats: &S,
+    ) -> Vec<bool> {
+        let mut res = vec![true; stats.num_containers()];
+        let arrow_schema = schema.arrow_schema().clone();
+        let df_schema = match arrow_schema.clone().to_dfschema_ref() {
             Ok(x) => x,
             Err(e) => {
-                warn!("Failed to create Datafusion schema when trying to prune row groups, error: {e}");
+                warn!("Failed to create Datafusion schema when trying to prune, error: {e}");
                 return res;
             }
         };
@@ -69,19 +103,16 @@ impl Predicate {
             )
             .and_then(|expr| PruningPredicate::try_new(expr, arrow_schema.clone()))
             {
-                Ok(p) => {
-                    let stat = RowGroupPruningStatistics::new(row_groups, &schema);
-                    match p.prune(&stat) {
-                        Ok(r) => {
-                            for (curr_val, res) in r.into_iter().zip(res.iter_mut()) {
-                                *res &= curr_val
-                            }
-                        }
-                        Err(e) => {
-                            warn!("Failed to prune row groups, error: {:?}", e);
+                Ok(p) => match p.prune(stats) {
+                    Ok(r) => {
+                        for (curr_val, res) in r.into_iter().zip(res.iter_mut()) {
+                            *res &= curr_val
                         }
                     }
-                }
+                    Err(e) => {
+                        warn!("Failed to prune, error: {:?}", e);
+                    }
+                },
                 Err(e) => {
                     error!("Failed to create predicate for expr, error: {:?}", e);
                 }
@@ -89,6 +120,75 @@ impl Predicate {
         }
         res
     }
+
+    /// Selects rows of `row_groups` to read by the page index of a parquet file, returns
+    /// `None` if the file has no page index or no expression can prune pages.
+    ///
+    /// Only expressions on a single column are used since the page boundaries of different
+    /// columns are not aligned.
+    pub fn prune_pages(
+        &self,
+        schema: &SchemaRef,
+        metadata: &ParquetMetaData,
+        row_groups: &[usize],
+    ) -> Option<RowSelection> {
+        let (Some(column_index), Some(offset_index)) =
+            (metadata.page_indexes(), metadata.offset_indexes()) else { return None };
+        let arrow_schema = schema.arrow_schema().clone();
+        let df_schema = arrow_schema.clone().to_dfschema_ref().ok()?;
+
+        let execution_props = &ExecutionProps::new();
+        let mut selection: Option<RowSelection> = None;
+        for expr in &self.exprs {
+            let Ok(columns) = expr.df_expr().to_columns() else { continue };
+            let mut columns = columns.into_iter();
+            let (Some(column), None) = (columns.next(), columns.next()) else { continue };
+            let Some(column_idx) = schema.column_index_by_name(&column.name) else { continue };
+            let Ok(predicate) = create_physical_expr(
+                expr.df_expr(),
+                df_schema.as_ref(),
+                arrow_schema.as_ref(),
+                execution_props,
+            )
+            .and_then(|expr| PruningPredicate::try_new(expr, arrow_schema.clone())) else { continue };
+
+            let data_type = arrow_schema.field(column_idx).data_type();
+            let mut selectors = Vec::new();
+            for row_group_idx in row_groups {
+                let num_rows = metadata.row_group(*row_group_idx).num_rows() as usize;
+                let pages = &offset_index[*row_group_idx][column_idx];
+                let stats = PagePruningStatistics::new(
+                    &column.name,
+                    &column_index[*row_group_idx][column_idx],
+                    data_type,
+                );
+                match predicate.prune(&stats) {
+                    Ok(values) if values.len() == pages.len() => {
+                        for (i, (page, matched)) in pages.iter().zip(values).enumerate() {
+                            let end = pages
+                                .get(i + 1)
+                                .map(|next| next.first_row_index as usize)
+                                .unwrap_or(num_rows);
+                            let page_rows = end - page.first_row_index as usize;
+                            selectors.push(if matched {
+                                RowSelector::select(page_rows)
+                            } else {
+                                RowSelector::skip(page_rows)
+                            });
+                        }
+                    }
+                    _ => selectors.push(RowSelector::select(num_rows)),
+                }
+            }
+
+            let column_selection = RowSelection::from(selectors);
+            selection = Some(match selection {
+                Some(selection) => selection.intersection(&column_selection),
+                None => column_selection,
+            });
+        }
+        selection
+    }
 }
 
 // tests for `TimeRangePredicateBuilder` locates in src/query/tests/time_range_filter_test.rs
@@ -285,6 +385,8 @@ mod tests {
     use datatypes::arrow::datatypes::{DataType, Field, Schema};
     use datatypes::arrow::record_batch::RecordBatch;
     use datatypes::arrow_array::StringArray;
+    use futures::TryStreamExt;
+    use parquet::arrow::arrow_reader::ArrowReaderOptions;
     use parquet::arrow::ParquetRecordBatchStreamBuilder;
     use parquet::file::properties::WriterProperties;
 
@@ -426,4 +528,76 @@ mod tests {
         let p = Predicate::new(vec![e.into()]);
         assert_prune(40, p, vec![true, true, false, true]).await;
     }
+
+    #[tokio::test]
+    async fn test_prune_pages() {
+        let dir = create_temp_dir("prune_pages");
+        let path = dir
+            .path()
+            .join("test-prune-pages.parquet")
+            .to_string_lossy()
+            .to_string();
+        let schema = Arc::new(Schema::new(vec![Field::new("cnt", DataType::Int32, true)]));
+        let file = std::fs::File::create(&path).unwrap();
+        // One row group with 10 pages.
+        let write_props = WriterProperties::builder()
+            .set_write_batch_size(10)
+            .set_data_page_row_count_limit(10)
+            .build();
+        let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(write_props)).unwrap();
+        let rb = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from((0..100).collect::<Vec<_>>()))],
+        )
+        .unwrap();
+        writer.write(&rb).unwrap();
+        writer.close().unwrap();
+
+        let schema = Arc::new(datatypes::schema::Schema::try_from(schema).unwrap());
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let builder = ParquetRecordBatchStreamBuilder::new_with_options(
+            tokio::fs::File::open(&path).await.unwrap(),
+            options,
+        )
+        .await
+        .unwrap();
+        let metadata = builder.metadata().clone();
+
+        assert!(Predicate::empty()
+            .prune_pages(&schema, &metadata, &[0])
+            .is_none());
+
+        let selection = gen_predicate(75, Operator::Gt)
+            .prune_pages(&schema, &metadata, &[0])
+            .unwrap();
+        let stream = builder.with_row_selection(selection).build().unwrap();
+        let batches: Vec<_> = stream.try_collect().await.unwrap();
+        let values: Vec<i32> = batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap();
+                array.values().to_vec()
+            })
+            .collect();
+        // Only the last pages are read.
+        assert!(values.len() < 100);
+        assert!((76..100).all(|v| values.contains(&v)));
+    }
+
+    #[test]
+    fn test_retain_columns() {
+        let predicate = gen_predicate(3, Operator::Gt);
+        assert_eq!(
+            1,
+            predicate.retain_columns(|name| name == "cnt").exprs().len()
+        );
+        assert!(predicate
+            .retain_columns(|name| name == "name")
+            .exprs()
+            .is_empty());
+    }
 }
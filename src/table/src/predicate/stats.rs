@@ -14,11 +14,14 @@
 
 use std::sync::Arc;
 
+use datafusion::parquet::data_type::ByteArray;
 use datafusion::parquet::file::metadata::RowGroupMetaData;
+use datafusion::parquet::file::page_index::index::Index;
 use datafusion::parquet::file::statistics::Statistics as ParquetStats;
 use datafusion::physical_optimizer::pruning::PruningStatistics;
 use datafusion_common::{Column, ScalarValue};
 use datatypes::arrow::array::{ArrayRef, UInt64Array};
+use datatypes::arrow::compute::cast;
 use datatypes::arrow::datatypes::DataType;
 use paste::paste;
 
@@ -87,7 +90,8 @@ macro_rules! impl_min_max_values {
             .map(|maybe_scalar| maybe_scalar.unwrap_or_else(|| null_scalar.clone()))
             .collect::<Vec<ScalarValue>>();
         debug_assert_eq!(scalar_values.len(), $self.meta_data.len());
-        ScalarValue::iter_to_array(scalar_values).ok()
+        let values = ScalarValue::iter_to_array(scalar_values).ok()?;
+        cast_to_type(values, data_type)
     }};
 }
 
@@ -116,3 +120,95 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
         Some(Arc::new(UInt64Array::from(values)))
     }
 }
+
+/// Statistics of pages of a column in a row group, each page is a container to prune.
+pub struct PagePruningStatistics<'a> {
+    column: &'a str,
+    index: &'a Index,
+    data_type: &'a DataType,
+}
+
+impl<'a> PagePruningStatistics<'a> {
+    pub fn new(column: &'a str, index: &'a Index, data_type: &'a DataType) -> Self {
+        Self {
+            column,
+            index,
+            data_type,
+        }
+    }
+
+    fn page_values(&self, column: &Column, min: bool) -> Option<ArrayRef> {
+        if column.name != self.column {
+            return None;
+        }
+
+        macro_rules! collect_values {
+            ($index: ident, $to_scalar: expr) => {
+                $index
+                    .indexes
+                    .iter()
+                    .map(|page| $to_scalar(if min { &page.min } else { &page.max }))
+                    .collect::<Vec<ScalarValue>>()
+            };
+        }
+
+        let values = match self.index {
+            Index::INT32(index) => collect_values!(index, |v: &Option<i32>| ScalarValue::Int32(*v)),
+            Index::INT64(index) => collect_values!(index, |v: &Option<i64>| ScalarValue::Int64(*v)),
+            Index::FLOAT(index) => {
+                collect_values!(index, |v: &Option<f32>| ScalarValue::Float32(*v))
+            }
+            Index::DOUBLE(index) => {
+                collect_values!(index, |v: &Option<f64>| ScalarValue::Float64(*v))
+            }
+            Index::BYTE_ARRAY(index) => collect_values!(index, |v: &Option<_>| {
+                let s = v
+                    .as_ref()
+                    .and_then(|v: &ByteArray| String::from_utf8(v.data().to_vec()).ok());
+                ScalarValue::Utf8(s)
+            }),
+            _ => return None,
+        };
+        let values = ScalarValue::iter_to_array(values).ok()?;
+        cast_to_type(values, self.data_type)
+    }
+
+    fn num_pages(&self) -> usize {
+        match self.index {
+            Index::INT32(index) => index.indexes.len(),
+            Index::INT64(index) => index.indexes.len(),
+            Index::FLOAT(index) => index.indexes.len(),
+            Index::DOUBLE(index) => index.indexes.len(),
+            Index::BYTE_ARRAY(index) => index.indexes.len(),
+            _ => 0,
+        }
+    }
+}
+
+impl<'a> PruningStatistics for PagePruningStatistics<'a> {
+    fn min_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.page_values(column, true)
+    }
+
+    fn max_values(&self, column: &Column) -> Option<ArrayRef> {
+        self.page_values(column, false)
+    }
+
+    fn num_containers(&self) -> usize {
+        self.num_pages()
+    }
+
+    fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+}
+
+/// Casts statistics to the type of the column, as parquet only stores physical values, e.g.
+/// timestamps are stored as int64. Values that can't be casted become nulls, which never
+/// prune the container.
+fn cast_to_type(values: ArrayRef, data_type: &DataType) -> Option<ArrayRef> {
+    if values.data_type() == data_type {
+        return Some(values);
+    }
+    cast(&values, data_type).ok()
+}
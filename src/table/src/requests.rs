@@ -19,10 +19,14 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use common_base::readable_size::ReadableSize;
+use common_time::Timestamp;
 use datatypes::prelude::VectorRef;
 use datatypes::schema::{ColumnSchema, RawSchema};
+use datatypes::value::Value;
 use serde::{Deserialize, Serialize};
-use store_api::storage::RegionNumber;
+use store_api::storage::{
+    CompactionStrategy, RegionNumber, SstCompression, DEFAULT_TWCS_MAX_FILES_IN_WINDOW,
+};
 
 use crate::error;
 use crate::error::ParseTableOptionSnafu;
@@ -62,11 +66,31 @@ pub struct TableOptions {
     pub extra_options: HashMap<String, String>,
     /// Time window for compaction
     pub compaction_time_window: Option<i64>,
+    /// Strategy to compact SST files.
+    pub compaction_strategy: CompactionStrategy,
+    /// Compression codec of SST files.
+    pub sst_compression: SstCompression,
+    /// Whether rows are only appended to the table. Rows with the same key are not
+    /// deduplicated on read, so the table can't update or delete rows.
+    pub append_mode: bool,
 }
 
 pub const WRITE_BUFFER_SIZE_KEY: &str = "write_buffer_size";
 pub const TTL_KEY: &str = "ttl";
 pub const COMPACTION_TIME_WINDOW_KEY: &str = "compaction_time_window";
+pub const COMPACTION_STRATEGY_KEY: &str = "compaction_strategy";
+pub const TWCS_MAX_FILES_IN_WINDOW_KEY: &str = "twcs_max_files_in_window";
+pub const SST_COMPRESSION_KEY: &str = "sst_compression";
+pub const APPEND_MODE_KEY: &str = "append_mode";
+
+const COMPACTION_STRATEGY_SIMPLE: &str = "simple";
+const COMPACTION_STRATEGY_TWCS: &str = "twcs";
+
+const SST_COMPRESSION_UNCOMPRESSED: &str = "uncompressed";
+const SST_COMPRESSION_SNAPPY: &str = "snappy";
+const SST_COMPRESSION_GZIP: &str = "gzip";
+const SST_COMPRESSION_LZ4: &str = "lz4";
+const SST_COMPRESSION_ZSTD: &str = "zstd";
 
 impl TryFrom<&HashMap<String, String>> for TableOptions {
     type Error = error::Error;
@@ -109,8 +133,55 @@ impl TryFrom<&HashMap<String, String>> for TableOptions {
                 }
             };
         }
+        if let Some(compaction_strategy) = value.get(COMPACTION_STRATEGY_KEY) {
+            options.compaction_strategy = match compaction_strategy.to_lowercase().as_str() {
+                COMPACTION_STRATEGY_SIMPLE => CompactionStrategy::Simple,
+                COMPACTION_STRATEGY_TWCS => CompactionStrategy::Twcs {
+                    max_files_in_window: parse_twcs_max_files_in_window(value)?,
+                },
+                _ => {
+                    return ParseTableOptionSnafu {
+                        key: COMPACTION_STRATEGY_KEY,
+                        value: compaction_strategy,
+                    }
+                    .fail()
+                }
+            };
+        }
+        if let Some(sst_compression) = value.get(SST_COMPRESSION_KEY) {
+            options.sst_compression = match sst_compression.to_lowercase().as_str() {
+                SST_COMPRESSION_UNCOMPRESSED => SstCompression::Uncompressed,
+                SST_COMPRESSION_SNAPPY => SstCompression::Snappy,
+                SST_COMPRESSION_GZIP => SstCompression::Gzip,
+                SST_COMPRESSION_LZ4 => SstCompression::Lz4,
+                SST_COMPRESSION_ZSTD => SstCompression::Zstd,
+                _ => {
+                    return ParseTableOptionSnafu {
+                        key: SST_COMPRESSION_KEY,
+                        value: sst_compression,
+                    }
+                    .fail()
+                }
+            };
+        }
+        if let Some(append_mode) = value.get(APPEND_MODE_KEY) {
+            options.append_mode = append_mode.to_lowercase().parse::<bool>().map_err(|_| {
+                ParseTableOptionSnafu {
+                    key: APPEND_MODE_KEY,
+                    value: append_mode,
+                }
+                .build()
+            })?;
+        }
         options.extra_options = HashMap::from_iter(value.iter().filter_map(|(k, v)| {
-            if k != WRITE_BUFFER_SIZE_KEY && k != TTL_KEY && k != COMPACTION_TIME_WINDOW_KEY {
+            if k != WRITE_BUFFER_SIZE_KEY
+                && k != TTL_KEY
+                && k != COMPACTION_TIME_WINDOW_KEY
+                && k != COMPACTION_STRATEGY_KEY
+                && k != TWCS_MAX_FILES_IN_WINDOW_KEY
+                && k != SST_COMPRESSION_KEY
+                && k != APPEND_MODE_KEY
+            {
                 Some((k.clone(), v.clone()))
             } else {
                 None
@@ -139,6 +210,35 @@ impl From<&TableOptions> for HashMap<String, String> {
                 compaction_time_window.to_string(),
             );
         }
+        match opts.compaction_strategy {
+            CompactionStrategy::Simple => (),
+            CompactionStrategy::Twcs {
+                max_files_in_window,
+            } => {
+                res.insert(
+                    COMPACTION_STRATEGY_KEY.to_string(),
+                    COMPACTION_STRATEGY_TWCS.to_string(),
+                );
+                res.insert(
+                    TWCS_MAX_FILES_IN_WINDOW_KEY.to_string(),
+                    max_files_in_window.to_string(),
+                );
+            }
+        }
+        let sst_compression = match opts.sst_compression {
+            SstCompression::Uncompressed => Some(SST_COMPRESSION_UNCOMPRESSED),
+            SstCompression::Snappy => Some(SST_COMPRESSION_SNAPPY),
+            SstCompression::Gzip => Some(SST_COMPRESSION_GZIP),
+            SstCompression::Lz4 => Some(SST_COMPRESSION_LZ4),
+            // The default codec is omitted.
+            SstCompression::Zstd => None,
+        };
+        if let Some(sst_compression) = sst_compression {
+            res.insert(SST_COMPRESSION_KEY.to_string(), sst_compression.to_string());
+        }
+        if opts.append_mode {
+            res.insert(APPEND_MODE_KEY.to_string(), opts.append_mode.to_string());
+        }
         res.extend(
             opts.extra_options
                 .iter()
@@ -148,6 +248,24 @@ impl From<&TableOptions> for HashMap<String, String> {
     }
 }
 
+/// Parses the max number of files in a time window of the twcs strategy, which should
+/// be positive.
+fn parse_twcs_max_files_in_window(
+    options: &HashMap<String, String>,
+) -> Result<usize, error::Error> {
+    let Some(value) = options.get(TWCS_MAX_FILES_IN_WINDOW_KEY) else {
+        return Ok(DEFAULT_TWCS_MAX_FILES_IN_WINDOW);
+    };
+    match value.parse::<usize>() {
+        Ok(max_files) if max_files > 0 => Ok(max_files),
+        _ => ParseTableOptionSnafu {
+            key: TWCS_MAX_FILES_IN_WINDOW_KEY,
+            value,
+        }
+        .fail(),
+    }
+}
+
 /// Open table request
 #[derive(Debug, Clone)]
 pub struct OpenTableRequest {
@@ -179,11 +297,36 @@ pub struct AddColumnRequest {
     pub is_key: bool,
 }
 
+/// Modify column request, the column to modify is located by the name
+/// of `column_schema`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifyColumnRequest {
+    /// Schema of the column after modification.
+    pub column_schema: ColumnSchema,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AlterKind {
-    AddColumns { columns: Vec<AddColumnRequest> },
-    DropColumns { names: Vec<String> },
-    RenameTable { new_table_name: String },
+    AddColumns {
+        columns: Vec<AddColumnRequest>,
+    },
+    DropColumns {
+        names: Vec<String>,
+    },
+    ModifyColumns {
+        columns: Vec<ModifyColumnRequest>,
+    },
+    RenameTable {
+        new_table_name: String,
+    },
+    /// Sets table options, the options not present are unchanged.
+    SetTableOptions {
+        options: HashMap<String, String>,
+    },
+    /// Resets table options with `keys` to their defaults.
+    UnsetTableOptions {
+        keys: Vec<String>,
+    },
 }
 
 /// Drop table request
@@ -194,6 +337,57 @@ pub struct DropTableRequest {
     pub table_name: String,
 }
 
+/// Truncate table request
+#[derive(Debug, Clone)]
+pub struct TruncateTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+}
+
+/// Create snapshot request
+#[derive(Debug, Clone)]
+pub struct CreateSnapshotRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub snapshot_name: String,
+}
+
+/// Drop snapshot request
+#[derive(Debug, Clone)]
+pub struct DropSnapshotRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub snapshot_name: String,
+}
+
+/// Restore table request, which opens a read-only table at a snapshot of the source table
+/// in the same schema.
+#[derive(Debug, Clone)]
+pub struct RestoreTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    /// Name of the restored table.
+    pub table_name: String,
+    pub source_table_name: String,
+    pub snapshot_name: String,
+}
+
+/// Version of the table to read, rows committed after it are invisible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsOf {
+    /// Reads rows committed at or before the sequence, sequences are assigned by
+    /// each region, so only tables with one region support it.
+    Sequence(u64),
+    /// Reads rows committed at or before the time.
+    ///
+    /// Commit times are only kept in memory for rows written since the region was
+    /// opened, about one day at most. Reading at an earlier time fails.
+    CommitTime(Timestamp),
+}
+
 #[derive(Debug)]
 pub struct InsertRequest {
     pub catalog_name: String,
@@ -212,6 +406,17 @@ pub struct DeleteRequest {
     pub key_column_values: HashMap<String, VectorRef>,
 }
 
+/// Delete rows in a time range without enumerating their keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteRangeRequest {
+    /// Values of primary key columns by column name, empty to delete rows of all series.
+    pub key_values: Vec<(String, Value)>,
+    /// Inclusive start of the time index, unbounded if `None`.
+    pub start: Option<Timestamp>,
+    /// Exclusive end of the time index, unbounded if `None`.
+    pub end: Option<Timestamp>,
+}
+
 #[derive(Debug)]
 pub enum CopyDirection {
     Export,
@@ -225,11 +430,26 @@ pub struct CopyTableRequest {
     pub schema_name: String,
     pub table_name: String,
     pub location: String,
+    /// Options of the file format, such as `FORMAT` and `DELIMITER`, keys are in upper case.
+    pub with: HashMap<String, String>,
     pub connection: HashMap<String, String>,
     pub pattern: Option<String>,
     pub direction: CopyDirection,
 }
 
+/// Copy database request, tables in the database are copied to (or from) sub directories
+/// of the location.
+#[derive(Debug)]
+pub struct CopyDatabaseRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub location: String,
+    /// Options of the file format, keys are in upper case.
+    pub with: HashMap<String, String>,
+    pub connection: HashMap<String, String>,
+    pub direction: CopyDirection,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct FlushTableRequest {
     pub catalog_name: String,
@@ -240,6 +460,16 @@ pub struct FlushTableRequest {
     pub wait: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct CompactTableRequest {
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub region_number: Option<RegionNumber>,
+    /// Wait until the compaction is done.
+    pub wait: Option<bool>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,6 +481,11 @@ mod tests {
             ttl: Some(Duration::from_secs(1000)),
             extra_options: HashMap::new(),
             compaction_time_window: Some(1677652502),
+            compaction_strategy: CompactionStrategy::Twcs {
+                max_files_in_window: 8,
+            },
+            sst_compression: SstCompression::Lz4,
+            append_mode: true,
         };
         let serialized = serde_json::to_string(&options).unwrap();
         let deserialized: TableOptions = serde_json::from_str(&serialized).unwrap();
@@ -264,6 +499,11 @@ mod tests {
             ttl: Some(Duration::from_secs(1000)),
             extra_options: HashMap::new(),
             compaction_time_window: Some(1677652502),
+            compaction_strategy: CompactionStrategy::Twcs {
+                max_files_in_window: 8,
+            },
+            sst_compression: SstCompression::Lz4,
+            append_mode: true,
         };
         let serialized_map = HashMap::from(&options);
         let serialized = TableOptions::try_from(&serialized_map).unwrap();
@@ -274,6 +514,9 @@ mod tests {
             ttl: None,
             extra_options: HashMap::new(),
             compaction_time_window: None,
+            compaction_strategy: CompactionStrategy::Simple,
+            sst_compression: SstCompression::Zstd,
+            append_mode: false,
         };
         let serialized_map = HashMap::from(&options);
         let serialized = TableOptions::try_from(&serialized_map).unwrap();
@@ -284,9 +527,81 @@ mod tests {
             ttl: Some(Duration::from_secs(1000)),
             extra_options: HashMap::from([("a".to_string(), "A".to_string())]),
             compaction_time_window: Some(1677652502),
+            compaction_strategy: CompactionStrategy::Simple,
+            sst_compression: SstCompression::Zstd,
+            append_mode: false,
         };
         let serialized_map = HashMap::from(&options);
         let serialized = TableOptions::try_from(&serialized_map).unwrap();
         assert_eq!(options, serialized);
     }
+
+    #[test]
+    fn test_parse_compaction_strategy() {
+        let options = TableOptions::try_from(&HashMap::from([(
+            COMPACTION_STRATEGY_KEY.to_string(),
+            "TWCS".to_string(),
+        )]))
+        .unwrap();
+        assert_eq!(
+            CompactionStrategy::Twcs {
+                max_files_in_window: DEFAULT_TWCS_MAX_FILES_IN_WINDOW,
+            },
+            options.compaction_strategy
+        );
+        assert!(options.extra_options.is_empty());
+
+        let options = TableOptions::try_from(&HashMap::from([(
+            COMPACTION_STRATEGY_KEY.to_string(),
+            "simple".to_string(),
+        )]))
+        .unwrap();
+        assert_eq!(CompactionStrategy::Simple, options.compaction_strategy);
+
+        for (strategy, max_files) in [("lcs", "4"), ("twcs", "0"), ("twcs", "many")] {
+            let map = HashMap::from([
+                (COMPACTION_STRATEGY_KEY.to_string(), strategy.to_string()),
+                (
+                    TWCS_MAX_FILES_IN_WINDOW_KEY.to_string(),
+                    max_files.to_string(),
+                ),
+            ]);
+            assert!(TableOptions::try_from(&map).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_sst_compression() {
+        let options = TableOptions::try_from(&HashMap::new()).unwrap();
+        assert_eq!(SstCompression::Zstd, options.sst_compression);
+
+        let options = TableOptions::try_from(&HashMap::from([(
+            SST_COMPRESSION_KEY.to_string(),
+            "Snappy".to_string(),
+        )]))
+        .unwrap();
+        assert_eq!(SstCompression::Snappy, options.sst_compression);
+        assert!(options.extra_options.is_empty());
+
+        let map = HashMap::from([(SST_COMPRESSION_KEY.to_string(), "brotli".to_string())]);
+        assert!(TableOptions::try_from(&map).is_err());
+    }
+
+    #[test]
+    fn test_parse_append_mode() {
+        let options = TableOptions::try_from(&HashMap::new()).unwrap();
+        assert!(!options.append_mode);
+        assert!(!HashMap::from(&options).contains_key(APPEND_MODE_KEY));
+
+        let options = TableOptions::try_from(&HashMap::from([(
+            APPEND_MODE_KEY.to_string(),
+            "TRUE".to_string(),
+        )]))
+        .unwrap();
+        assert!(options.append_mode);
+        assert!(options.extra_options.is_empty());
+
+        let map = HashMap::from([(APPEND_MODE_KEY.to_string(), "yes".to_string())]);
+        assert!(TableOptions::try_from(&map).is_err());
+    }
 }
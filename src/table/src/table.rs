@@ -22,12 +22,14 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
+use common_recordbatch::RecordBatch;
 use datatypes::schema::SchemaRef;
+use datatypes::value::Value;
 use store_api::storage::RegionNumber;
 
 use crate::error::{Result, UnsupportedSnafu};
 use crate::metadata::{FilterPushDownType, TableId, TableInfoRef, TableType};
-use crate::requests::{AlterTableRequest, DeleteRequest, InsertRequest};
+use crate::requests::{AlterTableRequest, AsOf, DeleteRangeRequest, DeleteRequest, InsertRequest};
 
 pub type AlterContext = anymap::Map<dyn Any + Send + Sync>;
 
@@ -71,6 +73,30 @@ pub trait Table: Send + Sync {
         limit: Option<usize>,
     ) -> Result<PhysicalPlanRef>;
 
+    /// Scan the table as it was at `as_of`, rows committed later are invisible.
+    async fn scan_as_of(
+        &self,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+        _as_of: AsOf,
+    ) -> Result<PhysicalPlanRef> {
+        UnsupportedSnafu { operation: "AS OF" }.fail()?
+    }
+
+    /// Get the row by its row key, which contains values of the primary key columns
+    /// (except the time index) and then the time index.
+    ///
+    /// Returns a [RecordBatch] with only one row, or `None` if the row doesn't exist. Tables
+    /// in append mode may have multiple rows with the same key, only the latest one is returned.
+    async fn get(
+        &self,
+        _projection: Option<&Vec<usize>>,
+        _row_key: &[Value],
+    ) -> Result<Option<RecordBatch>> {
+        UnsupportedSnafu { operation: "GET" }.fail()?
+    }
+
     /// Tests whether the table provider can make use of any or all filter expressions
     /// to optimise data retrieval.
     fn supports_filters_pushdown(&self, filters: &[&Expr]) -> Result<Vec<FilterPushDownType>> {
@@ -95,6 +121,14 @@ pub trait Table: Send + Sync {
         .fail()?
     }
 
+    /// Delete all rows in the range of the request without scanning them.
+    async fn delete_range(&self, _request: DeleteRangeRequest) -> Result<()> {
+        UnsupportedSnafu {
+            operation: "DELETE by range",
+        }
+        .fail()?
+    }
+
     /// Flush table.
     ///
     /// Options:
@@ -105,6 +139,36 @@ pub trait Table: Send + Sync {
         UnsupportedSnafu { operation: "FLUSH" }.fail()?
     }
 
+    /// Compact SST files of the table.
+    ///
+    /// Options:
+    /// - region_number: specify region to compact.
+    /// - wait: Whether to wait until compaction is done.
+    async fn compact(&self, region_number: Option<RegionNumber>, wait: Option<bool>) -> Result<()> {
+        let _ = (region_number, wait);
+        UnsupportedSnafu {
+            operation: "COMPACT",
+        }
+        .fail()?
+    }
+
+    /// Saves the table as a named snapshot without copying data. The snapshot contains rows
+    /// written before and keeps their files until it's dropped.
+    async fn create_snapshot(&self, _name: &str) -> Result<()> {
+        UnsupportedSnafu {
+            operation: "CREATE SNAPSHOT",
+        }
+        .fail()?
+    }
+
+    /// Drops the named snapshot and deletes files only kept by it.
+    async fn drop_snapshot(&self, _name: &str) -> Result<()> {
+        UnsupportedSnafu {
+            operation: "DROP SNAPSHOT",
+        }
+        .fail()?
+    }
+
     /// Close the table.
     async fn close(&self) -> Result<()> {
         Ok(())
@@ -31,16 +31,25 @@ use snafu::prelude::*;
 
 use crate::error::{self, Result};
 use crate::metadata::TableInfoRef;
+use crate::requests::AsOf;
 use crate::table::{FilterPushDownType, Table, TableRef, TableType};
 
 /// Greptime Table ->  datafusion TableProvider
 pub struct DfTableProviderAdapter {
     table: TableRef,
+    /// Version of the table to scan, `None` to scan the latest version.
+    as_of: Option<AsOf>,
 }
 
 impl DfTableProviderAdapter {
     pub fn new(table: TableRef) -> Self {
-        Self { table }
+        Self { table, as_of: None }
+    }
+
+    /// Scans the table at `as_of` instead of the latest version.
+    pub fn with_as_of(mut self, as_of: Option<AsOf>) -> Self {
+        self.as_of = as_of;
+        self
     }
 
     pub fn table(&self) -> TableRef {
@@ -74,7 +83,14 @@ impl TableProvider for DfTableProviderAdapter {
         limit: Option<usize>,
     ) -> DfResult<Arc<dyn DfPhysicalPlan>> {
         let filters: Vec<Expr> = filters.iter().map(Clone::clone).map(Into::into).collect();
-        let inner = self.table.scan(projection, &filters, limit).await?;
+        let inner = match self.as_of {
+            Some(as_of) => {
+                self.table
+                    .scan_as_of(projection, &filters, limit, as_of)
+                    .await?
+            }
+            None => self.table.scan(projection, &filters, limit).await?,
+        };
         Ok(Arc::new(DfPhysicalPlanAdapter(inner)))
     }
 
@@ -19,7 +19,10 @@ use async_trait::async_trait;
 use tokio::sync::Mutex;
 
 use crate::engine::{EngineContext, TableEngine, TableReference};
-use crate::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
+use crate::requests::{
+    AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest, RestoreTableRequest,
+    TruncateTableRequest,
+};
 use crate::test_util::EmptyTable;
 use crate::{Result, TableRef};
 
@@ -97,6 +100,22 @@ impl TableEngine for MockTableEngine {
         unimplemented!()
     }
 
+    async fn truncate_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: TruncateTableRequest,
+    ) -> Result<bool> {
+        unimplemented!()
+    }
+
+    async fn restore_table(
+        &self,
+        _ctx: &EngineContext,
+        _request: RestoreTableRequest,
+    ) -> Result<TableRef> {
+        unimplemented!()
+    }
+
     async fn close(&self) -> Result<()> {
         Ok(())
     }